@@ -131,10 +131,12 @@ contract LiquidityOrchestrator is
         address[] vaultsEpoch;
         /// @notice Prices of assets in the current epoch [priceAdapterDecimals]
         mapping(address => uint256) pricesEpoch;
-        /// @notice Active volume fee coefficient for current epoch
-        uint16 activeVFeeCoefficient;
-        /// @notice Active revenue share fee coefficient for current epoch
-        uint16 activeRsFeeCoefficient;
+        /// @notice Deprecated storage: the protocol volume fee coefficient, now resolved per vault
+        /// @dev Never read or written; kept so the fields after it keep their slots.
+        uint16 deprecatedVFeeCoefficient;
+        /// @notice Deprecated storage: the protocol revenue share fee coefficient, now resolved per vault
+        /// @dev Never read or written; kept so the fields after it keep their slots.
+        uint16 deprecatedRsFeeCoefficient;
         /// @notice Active fee model for each vault in current epoch
         mapping(address => IOrionVault.FeeModel) feeModel;
         /// @notice Epoch state commitment
@@ -162,6 +164,34 @@ contract LiquidityOrchestrator is
     /// @notice Buffer snapshot at BuyingLeg entry (after bufferIncrease apply) [assets]
     uint256 public buyingLegEntryBuffer;
 
+    /// @notice Fulfilled redemptions awaiting claim, by vault and controller [assets]
+    mapping(address => mapping(address => uint256)) public claimableRedemptions;
+
+    /// @notice Start time of the current or last epoch
+    uint256 public epochStartTime;
+
+    /// @notice Active volume fee coefficient for each vault in current epoch
+    mapping(address => uint16) private _vaultVFeeCoefficientEpoch;
+
+    /// @notice Active revenue share fee coefficient for each vault in current epoch
+    mapping(address => uint16) private _vaultRsFeeCoefficientEpoch;
+
+    /// @notice Expired deposit requests awaiting claim, by vault and controller [assets]
+    mapping(address => mapping(address => uint256)) public claimableDepositRefunds;
+
+    /// @notice Active fee settlement for each vault in current epoch
+    mapping(address => IOrionVault.FeeSettlement) private _vaultFeeSettlementEpoch;
+
+    /// @notice Active management fee schedule for each vault in current epoch
+    mapping(address => IOrionVault.ManagementFeeSchedule) private _vaultManagementFeeScheduleEpoch;
+
+    /// @notice Active performance fee hurdle for each vault in current epoch
+    mapping(address => IOrionVault.Hurdle) private _vaultHurdleEpoch;
+
+    /// @notice Deposit and redemption volume fulfilled for each vault in its last processed epoch [assets]
+    /// @dev Zero for vaults that have not been processed yet.
+    mapping(address => uint256) public vaultEpochVolume;
+
     /* -------------------------------------------------------------------------- */
     /*                                MODIFIERS                                   */
     /* -------------------------------------------------------------------------- */
@@ -361,17 +391,32 @@ contract LiquidityOrchestrator is
     /// @inheritdoc ILiquidityOrchestrator
     function getEpochState() external view returns (EpochStateView memory) {
         // Build vault fee models array
-        IOrionVault.FeeModel[] memory vaultFeeModels = new IOrionVault.FeeModel[](_currentEpoch.vaultsEpoch.length);
-        for (uint16 i = 0; i < _currentEpoch.vaultsEpoch.length; ++i) {
+        uint256 vaultCount = _currentEpoch.vaultsEpoch.length;
+        IOrionVault.FeeModel[] memory vaultFeeModels = new IOrionVault.FeeModel[](vaultCount);
+        IOrionVault.ManagementFeeSchedule[]
+            memory vaultManagementFeeSchedules = new IOrionVault.ManagementFeeSchedule[](vaultCount);
+        IOrionVault.FeeSettlement[] memory vaultFeeSettlements = new IOrionVault.FeeSettlement[](vaultCount);
+        IOrionVault.Hurdle[] memory vaultHurdles = new IOrionVault.Hurdle[](vaultCount);
+        uint16[] memory vaultVFeeCoefficients = new uint16[](vaultCount);
+        uint16[] memory vaultRsFeeCoefficients = new uint16[](vaultCount);
+        for (uint16 i = 0; i < vaultCount; ++i) {
             vaultFeeModels[i] = _currentEpoch.feeModel[_currentEpoch.vaultsEpoch[i]];
+            vaultManagementFeeSchedules[i] = _vaultManagementFeeScheduleEpoch[_currentEpoch.vaultsEpoch[i]];
+            vaultFeeSettlements[i] = _vaultFeeSettlementEpoch[_currentEpoch.vaultsEpoch[i]];
+            vaultHurdles[i] = _vaultHurdleEpoch[_currentEpoch.vaultsEpoch[i]];
+            vaultVFeeCoefficients[i] = _vaultVFeeCoefficientEpoch[_currentEpoch.vaultsEpoch[i]];
+            vaultRsFeeCoefficients[i] = _vaultRsFeeCoefficientEpoch[_currentEpoch.vaultsEpoch[i]];
         }
 
         return
             EpochStateView({
                 vaultsEpoch: _currentEpoch.vaultsEpoch,
-                activeVFeeCoefficient: _currentEpoch.activeVFeeCoefficient,
-                activeRsFeeCoefficient: _currentEpoch.activeRsFeeCoefficient,
                 vaultFeeModels: vaultFeeModels,
+                vaultManagementFeeSchedules: vaultManagementFeeSchedules,
+                vaultFeeSettlements: vaultFeeSettlements,
+                vaultHurdles: vaultHurdles,
+                vaultVFeeCoefficients: vaultVFeeCoefficients,
+                vaultRsFeeCoefficients: vaultRsFeeCoefficients,
                 epochStateCommitment: _currentEpoch.epochStateCommitment
             });
     }
@@ -420,28 +465,73 @@ contract LiquidityOrchestrator is
     }
 
     /// @inheritdoc ILiquidityOrchestrator
-    function transferVaultFees(uint256 amount) external {
+    function transferVaultFees(address recipient, uint256 amount) external {
         address vault = msg.sender;
 
         if (!config.isOrionVault(vault) && !config.isDecommissionedVault(vault)) revert ErrorsLib.NotAuthorized();
         if (amount == 0) revert ErrorsLib.AmountMustBeGreaterThanZero(underlyingAsset);
 
-        // Transfer underlying assets to the manager
-        address manager = IOrionVault(vault).manager();
-        IERC20(underlyingAsset).safeTransfer(manager, amount);
+        // Transfer underlying assets to the fee recipient
+        IERC20(underlyingAsset).safeTransfer(recipient, amount);
     }
 
     /// @inheritdoc ILiquidityOrchestrator
-    function transferRedemptionFunds(address user, uint256 amount) external {
+    function creditRedemptionFunds(address user, uint256 amount) external {
         // Verify the caller is a registered or decommissioned vault
         if (!config.isOrionVault(msg.sender) && !config.isDecommissionedVault(msg.sender)) {
             revert ErrorsLib.NotAuthorized();
         }
 
+        // No transfer: settlement must not depend on whether the user can receive the underlying asset.
+        claimableRedemptions[msg.sender][user] += amount;
+        vaultEpochVolume[msg.sender] += amount;
+        emit EventsLib.RedemptionFundsCredited(msg.sender, user, amount);
+    }
+
+    /// @inheritdoc ILiquidityOrchestrator
+    function transferRedemptionFunds(address user, address receiver, uint256 amount) external nonReentrant {
+        // Verify the caller is a registered or decommissioned vault
+        if (!config.isOrionVault(msg.sender) && !config.isDecommissionedVault(msg.sender)) {
+            revert ErrorsLib.NotAuthorized();
+        }
+
+        uint256 claimable = claimableRedemptions[msg.sender][user];
+        if (amount > claimable) revert ErrorsLib.InsufficientAmount();
+        claimableRedemptions[msg.sender][user] = claimable - amount;
+
         if (amount > 0) {
-            // Transfer underlying assets to the user
-            IERC20(underlyingAsset).safeTransfer(user, amount);
+            // Transfer underlying assets to the receiver
+            IERC20(underlyingAsset).safeTransfer(receiver, amount);
         }
+        emit EventsLib.RedemptionFundsClaimed(msg.sender, user, receiver, amount);
+    }
+
+    /// @inheritdoc ILiquidityOrchestrator
+    function creditDepositRefund(address user, uint256 amount) external {
+        // Verify the caller is a registered or decommissioned vault
+        if (!config.isOrionVault(msg.sender) && !config.isDecommissionedVault(msg.sender)) {
+            revert ErrorsLib.NotAuthorized();
+        }
+
+        // No transfer: settlement must not depend on whether the user can receive the underlying asset.
+        claimableDepositRefunds[msg.sender][user] += amount;
+        emit EventsLib.DepositRefundCredited(msg.sender, user, amount);
+    }
+
+    /// @inheritdoc ILiquidityOrchestrator
+    function transferDepositRefund(address user, address receiver, uint256 amount) external nonReentrant {
+        // Verify the caller is a registered or decommissioned vault
+        if (!config.isOrionVault(msg.sender) && !config.isDecommissionedVault(msg.sender)) {
+            revert ErrorsLib.NotAuthorized();
+        }
+
+        uint256 claimable = claimableDepositRefunds[msg.sender][user];
+        if (amount > claimable) revert ErrorsLib.InsufficientAmount();
+        claimableDepositRefunds[msg.sender][user] = claimable - amount;
+
+        // Transfer underlying assets to the receiver
+        IERC20(underlyingAsset).safeTransfer(receiver, amount);
+        emit EventsLib.DepositRefundClaimed(msg.sender, user, receiver, amount);
     }
 
     /// @inheritdoc ILiquidityOrchestrator
@@ -529,6 +619,7 @@ contract LiquidityOrchestrator is
 
         // Freeze deterministic proof-input anchor at epoch start.
         initialEpochBufferAmount = bufferAmount;
+        epochStartTime = block.timestamp;
         buyingLegEntryBuffer = 0;
 
         // Reset incremental commitment state for the new epoch
@@ -538,13 +629,18 @@ contract LiquidityOrchestrator is
 
         currentPhase = LiquidityUpkeepPhase.StateCommitment;
 
-        // Snapshot protocol fees at epoch start to ensure consistency throughout the epoch
-        (_currentEpoch.activeVFeeCoefficient, _currentEpoch.activeRsFeeCoefficient) = config.activeProtocolFees();
-
-        // Snapshot vault fee types at epoch start to ensure consistency throughout the epoch
+        // Snapshot vault fee models and per-vault protocol fees at epoch start for consistency throughout the epoch.
+        // The volume is zero in a vault's first epoch and after an epoch without flows, which no tier reaches.
         for (uint16 i = 0; i < _currentEpoch.vaultsEpoch.length; ++i) {
-            IOrionVault.FeeModel memory feeModel = IOrionVault(_currentEpoch.vaultsEpoch[i]).activeFeeModel();
-            _currentEpoch.feeModel[_currentEpoch.vaultsEpoch[i]] = feeModel;
+            address vault = _currentEpoch.vaultsEpoch[i];
+            _currentEpoch.feeModel[vault] = IOrionVault(vault).activeFeeModel();
+            _vaultManagementFeeScheduleEpoch[vault] = IOrionVault(vault).activeManagementFeeSchedule();
+            _vaultFeeSettlementEpoch[vault] = IOrionVault(vault).activeFeeSettlement();
+            _vaultHurdleEpoch[vault] = IOrionVault(vault).activeHurdle();
+            (_vaultVFeeCoefficientEpoch[vault], _vaultRsFeeCoefficientEpoch[vault]) = config.activeVaultProtocolFees(
+                vault,
+                vaultEpochVolume[vault]
+            );
         }
 
         address[] memory assets = config.getAllWhitelistedAssets();
@@ -581,6 +677,9 @@ contract LiquidityOrchestrator is
         for (uint16 i = i0; i < i1; ++i) {
             IOrionTransparentVault vault = IOrionTransparentVault(_currentEpoch.vaultsEpoch[i]);
             IOrionVault.FeeModel memory feeModel = _currentEpoch.feeModel[_currentEpoch.vaultsEpoch[i]];
+            IOrionVault.ManagementFeeSchedule memory schedule = _vaultManagementFeeScheduleEpoch[address(vault)];
+            IOrionVault.FeeSettlement memory settlement = _vaultFeeSettlementEpoch[address(vault)];
+            IOrionVault.Hurdle memory hurdle = _vaultHurdleEpoch[address(vault)];
 
             (address[] memory portfolioTokens, uint256[] memory portfolioShares) = vault.getPortfolio();
             (address[] memory intentTokens, uint32[] memory intentWeights) = vault.getIntent();
@@ -588,15 +687,34 @@ contract LiquidityOrchestrator is
             bytes32 portfolioHash = keccak256(abi.encode(portfolioTokens, portfolioShares));
             bytes32 intentHash = keccak256(abi.encode(intentTokens, intentWeights));
 
+            // Binds the per-request split, including a partially filled last request, not just the total.
+            (address[] memory redeemUsers, uint256[] memory redeemShares) = vault.pendingRedeemBatch(
+                maxFulfillBatchSize
+            );
+
             bytes32 vaultLeaf = keccak256(
                 abi.encode(
                     _currentEpoch.vaultsEpoch[i],
                     uint8(feeModel.feeType),
                     feeModel.performanceFee,
                     feeModel.managementFee,
+                    schedule.breakpoints,
+                    schedule.tierRates,
+                    schedule.minimumFee,
                     feeModel.highWaterMark,
+                    settlement.crystallizationPeriod,
+                    settlement.mintFeeShares,
+                    settlement.lastCrystallization,
+                    settlement.crystallizationPrice,
+                    uint8(hurdle.hurdleType),
+                    hurdle.rate,
+                    hurdle.benchmark,
+                    hurdle.benchmarkPrice,
+                    _vaultVFeeCoefficientEpoch[address(vault)],
+                    _vaultRsFeeCoefficientEpoch[address(vault)],
                     vault.pendingRedeem(maxFulfillBatchSize),
                     vault.pendingDeposit(maxFulfillBatchSize),
+                    keccak256(abi.encode(redeemUsers, redeemShares)),
                     vault.totalSupply(),
                     vault.totalAssets(),
                     portfolioHash,
@@ -637,8 +755,6 @@ contract LiquidityOrchestrator is
         return
             keccak256(
                 abi.encode(
-                    _currentEpoch.activeVFeeCoefficient,
-                    _currentEpoch.activeRsFeeCoefficient,
                     config.maxFulfillBatchSize(),
                     targetBufferRatio,
                     config.priceAdapterDecimals(),
@@ -652,7 +768,8 @@ contract LiquidityOrchestrator is
                     initialEpochBufferAmount,
                     buyingLegEntryBuffer,
                     bufferAmount,
-                    IERC20(underlyingAsset).balanceOf(address(this))
+                    IERC20(underlyingAsset).balanceOf(address(this)),
+                    epochStartTime
                 )
             );
     }
@@ -913,12 +1030,9 @@ contract LiquidityOrchestrator is
         ++currentMinibatchIndex;
 
         // slither-disable-next-line incorrect-equality
-        if (i1 > vaultsEpoch.length || i1 == vaultsEpoch.length) {
+        bool lastMinibatch = i1 > vaultsEpoch.length || i1 == vaultsEpoch.length;
+        if (lastMinibatch) {
             i1 = uint16(vaultsEpoch.length);
-            currentPhase = LiquidityUpkeepPhase.Idle;
-            currentMinibatchIndex = 0;
-            completedInCurrentMinibatch = 0;
-            _nextUpdateTime = block.timestamp + epochDuration;
         }
 
         for (uint16 i = i0; i < i1; ++i) {
@@ -937,6 +1051,14 @@ contract LiquidityOrchestrator is
                 vaultState.shares
             );
         }
+
+        // Vaults still see the epoch running while their operations are processed, e.g. for request expiry.
+        if (lastMinibatch) {
+            currentPhase = LiquidityUpkeepPhase.Idle;
+            currentMinibatchIndex = 0;
+            completedInCurrentMinibatch = 0;
+            _nextUpdateTime = block.timestamp + epochDuration;
+        }
     }
 
     /// @notice Processes deposit and redeem operations for a single vault
@@ -945,8 +1067,8 @@ contract LiquidityOrchestrator is
     /// @param totalAssetsForDeposit The total assets for deposit operations
     /// @param totalAssetsForRedeem The total assets for redeem operations
     /// @param finalTotalAssets The final total assets for the vault
-    /// @param managementFee The management fee to accrue
-    /// @param performanceFee The performance fee to accrue
+    /// @param managementFee The management fee to accrue, in shares when the epoch's fee model mints fee shares
+    /// @param performanceFee The performance fee to accrue, in the same unit as managementFee
     /// @param tokens The portfolio token addresses
     /// @param shares The portfolio token number of shares
     function _processSingleVaultOperations(
@@ -962,19 +1084,29 @@ contract LiquidityOrchestrator is
     ) internal {
         IOrionTransparentVault vaultContract = IOrionTransparentVault(vaultAddress);
 
+        // Fee shares are minted first so requests are fulfilled at the post-fee share price.
+        vaultContract.accrueVaultFees(
+            managementFee,
+            performanceFee,
+            _vaultFeeSettlementEpoch[vaultAddress].mintFeeShares
+        );
+
         uint256 maxFulfillBatchSize = config.maxFulfillBatchSize();
         uint256 pendingRedeem = vaultContract.pendingRedeem(maxFulfillBatchSize);
-        uint256 pendingDeposit = vaultContract.pendingDeposit(maxFulfillBatchSize);
 
-        if (processRedeem && pendingRedeem > 0) {
+        // Redemptions credited by fulfillRedeem add to the epoch volume.
+        vaultEpochVolume[vaultAddress] = 0;
+
+        // Also called when every request in the batch has expired, to clear them from the queue.
+        if ((processRedeem || pendingRedeem == 0) && vaultContract.pendingRedeemCount() > 0) {
             vaultContract.fulfillRedeem(totalAssetsForRedeem);
         }
 
-        if (pendingDeposit > 0) {
+        if (vaultContract.pendingDepositCount() > 0) {
+            vaultEpochVolume[vaultAddress] += vaultContract.pendingDeposit(maxFulfillBatchSize);
             vaultContract.fulfillDeposit(totalAssetsForDeposit);
         }
 
-        IOrionVault(vaultAddress).accrueVaultFees(managementFee, performanceFee);
         vaultContract.updateVaultState(tokens, shares, finalTotalAssets);
 
         if (config.isDecommissioningVault(vaultAddress)) {
@@ -1018,5 +1150,5 @@ contract LiquidityOrchestrator is
     }
 
     /// @dev Storage gap to allow for future upgrades
-    uint256[45] private __gap;
+    uint256[36] private __gap;
 }
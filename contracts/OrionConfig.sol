@@ -92,6 +92,23 @@ contract OrionConfig is Initializable, Ownable2StepUpgradeable, UUPSUpgradeable,
     /// @notice Address of the upgrade timelock that must authorise all implementation upgrades
     address public upgradeTimelock;
 
+    /// @notice Maximum number of protocol fee tiers
+    uint256 public constant MAX_PROTOCOL_FEE_TIERS = 8;
+
+    /// @notice Protocol fee tiers, by ascending vault volume
+    ProtocolFeeTier[] private _protocolFeeTiers;
+    /// @notice Old protocol fee tiers (used during cooldown period)
+    ProtocolFeeTier[] private _oldProtocolFeeTiers;
+    /// @notice Timestamp when new protocol fee tiers become effective
+    uint256 public newProtocolFeeTiersTimestamp;
+
+    /// @notice Protocol fee override, by vault
+    mapping(address => ProtocolFeeOverride) private _protocolFeeOverrides;
+    /// @notice Old protocol fee override (used during cooldown period), by vault
+    mapping(address => ProtocolFeeOverride) private _oldProtocolFeeOverrides;
+    /// @notice Timestamp when the new protocol fee override becomes effective, by vault
+    mapping(address => uint256) public newProtocolFeeOverrideTimestamp;
+
     modifier onlyFactories() {
         if (msg.sender != transparentVaultFactory) revert ErrorsLib.NotAuthorized();
         _;
@@ -141,9 +158,7 @@ contract OrionConfig is Initializable, Ownable2StepUpgradeable, UUPSUpgradeable,
 
     /// @inheritdoc IOrionConfig
     function updateProtocolFees(uint16 _vFeeCoefficient, uint16 _rsFeeCoefficient) external onlyOwner {
-        /// Maximum volume fee: 0.5%
-        /// Maximum revenue share fee: 20%
-        if (_vFeeCoefficient > 50 || _rsFeeCoefficient > 2_000) revert ErrorsLib.InvalidArguments();
+        _validateProtocolFees(_vFeeCoefficient, _rsFeeCoefficient);
         if (!isSystemIdle()) revert ErrorsLib.SystemNotIdle();
 
         // Store current active fees as old;
@@ -174,6 +189,102 @@ contract OrionConfig is Initializable, Ownable2StepUpgradeable, UUPSUpgradeable,
         return (vFeeCoefficient, rsFeeCoefficient);
     }
 
+    /// @inheritdoc IOrionConfig
+    function updateProtocolFeeTiers(ProtocolFeeTier[] calldata tiers) external onlyOwner {
+        if (tiers.length > MAX_PROTOCOL_FEE_TIERS) revert ErrorsLib.InvalidArguments();
+        if (!isSystemIdle()) revert ErrorsLib.SystemNotIdle();
+
+        uint256 previousMinVolume = 0;
+        for (uint256 i = 0; i < tiers.length; ++i) {
+            if (tiers[i].minVolume <= previousMinVolume) revert ErrorsLib.InvalidArguments();
+            _validateProtocolFees(tiers[i].vFeeCoefficient, tiers[i].rsFeeCoefficient);
+            previousMinVolume = tiers[i].minVolume;
+        }
+
+        // Store current active tiers as old; a prior schedule still in cooldown is cancelled
+        ProtocolFeeTier[] memory currentTiers = activeProtocolFeeTiers();
+        delete _oldProtocolFeeTiers;
+        for (uint256 i = 0; i < currentTiers.length; ++i) {
+            _oldProtocolFeeTiers.push(currentTiers[i]);
+        }
+
+        delete _protocolFeeTiers;
+        for (uint256 i = 0; i < tiers.length; ++i) {
+            _protocolFeeTiers.push(tiers[i]);
+        }
+        newProtocolFeeTiersTimestamp = block.timestamp + feeChangeCooldownDuration;
+
+        emit ProtocolFeeTiersChangeScheduled(tiers, newProtocolFeeTiersTimestamp);
+    }
+
+    /// @inheritdoc IOrionConfig
+    function activeProtocolFeeTiers() public view returns (ProtocolFeeTier[] memory) {
+        // If we're still in cooldown period, return old tiers
+        if (newProtocolFeeTiersTimestamp > block.timestamp) {
+            return _oldProtocolFeeTiers;
+        }
+        return _protocolFeeTiers;
+    }
+
+    /// @inheritdoc IOrionConfig
+    function updateVaultProtocolFeeOverride(
+        address vault,
+        ProtocolFeeOverride calldata feeOverride
+    ) external onlyOwner {
+        if (!encryptedVaults.contains(vault) && !transparentVaults.contains(vault)) revert ErrorsLib.InvalidAddress();
+        if (feeOverride.enabled) {
+            _validateProtocolFees(feeOverride.vFeeCoefficient, feeOverride.rsFeeCoefficient);
+        } else if (feeOverride.vFeeCoefficient != 0 || feeOverride.rsFeeCoefficient != 0) {
+            revert ErrorsLib.InvalidArguments();
+        }
+        if (!isSystemIdle()) revert ErrorsLib.SystemNotIdle();
+
+        // Store current active override as old; a prior schedule still in cooldown is cancelled
+        _oldProtocolFeeOverrides[vault] = activeVaultProtocolFeeOverride(vault);
+        _protocolFeeOverrides[vault] = feeOverride;
+        newProtocolFeeOverrideTimestamp[vault] = block.timestamp + feeChangeCooldownDuration;
+
+        emit VaultProtocolFeeOverrideChangeScheduled(vault, feeOverride, newProtocolFeeOverrideTimestamp[vault]);
+    }
+
+    /// @inheritdoc IOrionConfig
+    function activeVaultProtocolFeeOverride(address vault) public view returns (ProtocolFeeOverride memory) {
+        // If we're still in cooldown period, return old override
+        if (newProtocolFeeOverrideTimestamp[vault] > block.timestamp) {
+            return _oldProtocolFeeOverrides[vault];
+        }
+        return _protocolFeeOverrides[vault];
+    }
+
+    /// @inheritdoc IOrionConfig
+    function activeVaultProtocolFees(
+        address vault,
+        uint256 volume
+    ) external view returns (uint16 vFee, uint16 rsFee) {
+        ProtocolFeeOverride memory feeOverride = activeVaultProtocolFeeOverride(vault);
+        if (feeOverride.enabled) {
+            return (feeOverride.vFeeCoefficient, feeOverride.rsFeeCoefficient);
+        }
+
+        // Highest tier reached by the vault volume
+        ProtocolFeeTier[] memory tiers = activeProtocolFeeTiers();
+        for (uint256 i = tiers.length; i > 0; --i) {
+            if (volume >= tiers[i - 1].minVolume) {
+                return (tiers[i - 1].vFeeCoefficient, tiers[i - 1].rsFeeCoefficient);
+            }
+        }
+        return activeProtocolFees();
+    }
+
+    /// @notice Validates protocol fee coefficients against the protocol maximums
+    /// @param _vFeeCoefficient The volume fee coefficient
+    /// @param _rsFeeCoefficient The revenue share fee coefficient
+    function _validateProtocolFees(uint16 _vFeeCoefficient, uint16 _rsFeeCoefficient) private pure {
+        /// Maximum volume fee: 0.5%
+        /// Maximum revenue share fee: 20%
+        if (_vFeeCoefficient > 50 || _rsFeeCoefficient > 2_000) revert ErrorsLib.InvalidArguments();
+    }
+
     /// @inheritdoc IOrionConfig
     function setLiquidityOrchestrator(address orchestrator) external onlyOwner {
         if (orchestrator == address(0)) revert ErrorsLib.ZeroAddress();
@@ -562,5 +673,5 @@ contract OrionConfig is Initializable, Ownable2StepUpgradeable, UUPSUpgradeable,
     }
 
     /// @dev Storage gap to allow for future upgrades
-    uint256[49] private __gap;
+    uint256[43] private __gap;
 }
@@ -0,0 +1,230 @@
+// SPDX-License-Identifier: MIT
+pragma solidity 0.8.34;
+
+import { IOrionAccessControl } from "../interfaces/IOrionAccessControl.sol";
+import { IOrionVault } from "../interfaces/IOrionVault.sol";
+import { AccessDataLib } from "../libraries/AccessDataLib.sol";
+import { ErrorsLib } from "../libraries/ErrorsLib.sol";
+import { ERC165 } from "@openzeppelin/contracts/utils/introspection/ERC165.sol";
+import { ERC165Checker } from "@openzeppelin/contracts/utils/introspection/ERC165Checker.sol";
+
+/**
+ * @title CompositeAccessControl
+ * @notice Implementation of IOrionAccessControl combining child controllers in a boolean AND/OR tree
+ * @author Orion Finance
+ * @dev The policy is a list of nodes with the root at index 0. A node is either a leaf, which asks a child
+ *      controller, or an AND/OR of other nodes. Every node but the root has exactly one parent, which comes
+ *      before it in the list, so every policy is a tree and evaluation visits each node at most once;
+ *      AND and OR short-circuit in child order.
+ *      Proofs for the children are appended as one segment per node, see AccessDataLib, so that children
+ *      with different payload formats can be combined.
+ *      The policy is managed by the manager of the vault it is bound to.
+ * @custom:security-contact security@orionfinance.ai
+ */
+contract CompositeAccessControl is IOrionAccessControl, ERC165 {
+    /// @notice Maximum number of nodes in a policy
+    uint256 public constant MAX_NODES = 32;
+
+    /// @notice Node type
+    enum NodeType {
+        LEAF,
+        AND,
+        OR
+    }
+
+    /// @notice Policy node
+    struct Node {
+        /// @notice Node type
+        NodeType nodeType;
+        /// @notice Child controller (leaves only)
+        address controller;
+        /// @notice Indices of the child nodes, all greater than the node's own index and not shared (AND/OR only)
+        uint256[] children;
+    }
+
+    /// @notice Access control hook being evaluated
+    enum Hook {
+        DEPOSIT,
+        REDEEM,
+        TRANSFER,
+        BLOCKED
+    }
+
+    /// @notice Vault whose manager manages the policy
+    IOrionVault public immutable VAULT;
+
+    /// @notice Policy nodes, root first
+    Node[] internal _nodes;
+
+    /// @notice Emitted for every node of a new policy
+    /// @param index The index of the node
+    /// @param nodeType The node type
+    /// @param controller The child controller (address(0) for AND/OR nodes)
+    /// @param children The indices of the child nodes
+    event PolicyNodeSet(uint256 indexed index, NodeType nodeType, address controller, uint256[] children);
+
+    /// @notice Emitted when a new policy has been set, after its nodes
+    /// @param nodeCount The number of nodes in the policy
+    event PolicySet(uint256 nodeCount);
+
+    /// @notice Constructor
+    /// @param vault_ The vault whose manager manages the policy
+    /// @param nodes_ The initial policy
+    constructor(IOrionVault vault_, Node[] memory nodes_) {
+        if (address(vault_) == address(0)) revert ErrorsLib.ZeroAddress();
+        VAULT = vault_;
+        _setPolicy(nodes_);
+    }
+
+    /// @inheritdoc ERC165
+    function supportsInterface(bytes4 interfaceId) public view virtual override returns (bool) {
+        return interfaceId == type(IOrionAccessControl).interfaceId || super.supportsInterface(interfaceId);
+    }
+
+    /// @inheritdoc IOrionAccessControl
+    function canRequestDeposit(address account, bytes calldata data) external view override returns (bool) {
+        return _evaluate(0, Hook.DEPOSIT, account, address(0), 0, data, _segments(data));
+    }
+
+    /// @inheritdoc IOrionAccessControl
+    function canRequestRedeem(address account, bytes calldata data) external view override returns (bool) {
+        return _evaluate(0, Hook.REDEEM, account, address(0), 0, data, _segments(data));
+    }
+
+    /// @inheritdoc IOrionAccessControl
+    /// @dev Children see this contract as the calling vault, so shares leaving the vault's escrow are
+    ///      forwarded with this contract as `from`.
+    function canTransferShares(address from, address to, uint256 amount) external view override returns (bool) {
+        if (from == address(VAULT)) from = address(this);
+        return _evaluate(0, Hook.TRANSFER, from, to, amount, msg.data[0:0], new bytes[](0));
+    }
+
+    /// @inheritdoc IOrionAccessControl
+    /// @dev The policy is evaluated on the accounts children do not block, so an account is blocked
+    ///      when a child of an AND blocks it, or when every child of an OR does.
+    function isBlocked(address account) external view override returns (bool) {
+        return !_evaluate(0, Hook.BLOCKED, account, address(0), 0, msg.data[0:0], new bytes[](0));
+    }
+
+    /**
+     * @notice Replace the policy
+     * @param nodes The new policy, root first
+     * @dev Only callable by the vault manager
+     */
+    function setPolicy(Node[] memory nodes) external {
+        if (msg.sender != VAULT.manager()) revert ErrorsLib.NotAuthorized();
+        _setPolicy(nodes);
+    }
+
+    /**
+     * @notice Returns the policy
+     * @return The policy nodes, root first
+     */
+    function getPolicy() external view returns (Node[] memory) {
+        return _nodes;
+    }
+
+    /**
+     * @notice Validates and stores a policy
+     * @param nodes The policy, root first
+     */
+    function _setPolicy(Node[] memory nodes) internal {
+        if (nodes.length == 0 || nodes.length > MAX_NODES) revert ErrorsLib.InvalidArguments();
+
+        delete _nodes;
+        bool[] memory hasParent = new bool[](nodes.length);
+        for (uint256 i = 0; i < nodes.length; ++i) {
+            // Parents come first, so a node without one by now is unreachable from the root
+            if (i != 0 && !hasParent[i]) revert ErrorsLib.InvalidArguments();
+            Node memory node = nodes[i];
+            if (node.nodeType == NodeType.LEAF) {
+                if (node.controller == address(0)) revert ErrorsLib.ZeroAddress();
+                if (node.children.length != 0) revert ErrorsLib.InvalidArguments();
+            } else {
+                if (node.controller != address(0) || node.children.length == 0) revert ErrorsLib.InvalidArguments();
+                for (uint256 j = 0; j < node.children.length; ++j) {
+                    uint256 child = node.children[j];
+                    if (child <= i || child >= nodes.length || hasParent[child]) revert ErrorsLib.InvalidArguments();
+                    hasParent[child] = true;
+                }
+            }
+
+            Node storage stored = _nodes.push();
+            stored.nodeType = node.nodeType;
+            stored.controller = node.controller;
+            stored.children = node.children;
+
+            emit PolicyNodeSet(i, node.nodeType, node.controller, node.children);
+        }
+
+        emit PolicySet(nodes.length);
+    }
+
+    /**
+     * @notice Evaluates a node of the policy
+     * @param index The node index
+     * @param hook The hook being evaluated
+     * @param account The account checked, or the sender of a transfer
+     * @param to The recipient of a transfer
+     * @param amount The amount of a transfer
+     * @param data The calldata of the vault call (deposits and redemptions)
+     * @param segments The payload segments of the policy nodes, by node index
+     * @return True if the node allows the operation, or does not block the account for Hook.BLOCKED
+     */
+    function _evaluate(
+        uint256 index,
+        Hook hook,
+        address account,
+        address to,
+        uint256 amount,
+        bytes calldata data,
+        bytes[] memory segments
+    ) internal view returns (bool) {
+        Node storage node = _nodes[index];
+
+        if (node.nodeType == NodeType.LEAF) {
+            IOrionAccessControl controller = IOrionAccessControl(node.controller);
+            if (hook == Hook.DEPOSIT) return controller.canRequestDeposit(account, _childData(data, segments, index));
+            // Like vaults, children predating the redemption, transfer and blocking hooks allow everything else
+            if (!ERC165Checker.supportsERC165InterfaceUnchecked(node.controller, type(IOrionAccessControl).interfaceId))
+                return true;
+            if (hook == Hook.REDEEM) return controller.canRequestRedeem(account, _childData(data, segments, index));
+            if (hook == Hook.BLOCKED) return !controller.isBlocked(account);
+            return controller.canTransferShares(account, to, amount);
+        }
+
+        // AND fails on the first false child, OR succeeds on the first true one
+        bool isAnd = node.nodeType == NodeType.AND;
+        uint256[] storage children = node.children;
+        for (uint256 i = 0; i < children.length; ++i) {
+            if (_evaluate(children[i], hook, account, to, amount, data, segments) != isAnd) return !isAnd;
+        }
+        return isAnd;
+    }
+
+    /**
+     * @notice Splits the payload appended to a vault call into the segments of the policy nodes
+     * @param data The calldata of the vault call
+     * @return The segments by node index, empty if no payload was appended
+     */
+    function _segments(bytes calldata data) internal pure returns (bytes[] memory) {
+        bytes calldata payload = AccessDataLib.appendedPayload(data);
+        if (payload.length == 0) return new bytes[](0);
+        return abi.decode(payload, (bytes[]));
+    }
+
+    /**
+     * @notice The vault call as seen by the controller of a leaf, with the leaf's segment appended
+     * @param data The calldata of the vault call
+     * @param segments The payload segments of the policy nodes, by node index
+     * @param index The index of the leaf
+     * @return The calldata followed by the leaf's segment, empty if there is none
+     */
+    function _childData(
+        bytes calldata data,
+        bytes[] memory segments,
+        uint256 index
+    ) internal pure returns (bytes memory) {
+        return AccessDataLib.withPayload(data, index < segments.length ? segments[index] : bytes(""));
+    }
+}
@@ -0,0 +1,87 @@
+// SPDX-License-Identifier: MIT
+pragma solidity 0.8.34;
+
+import { IOrionAccessControl } from "../interfaces/IOrionAccessControl.sol";
+import { AccessDataLib } from "../libraries/AccessDataLib.sol";
+import "@openzeppelin/contracts/access/Ownable2Step.sol";
+import { MerkleProof } from "@openzeppelin/contracts/utils/cryptography/MerkleProof.sol";
+import { ERC165 } from "@openzeppelin/contracts/utils/introspection/ERC165.sol";
+
+/**
+ * @title MerkleAccessControl
+ * @notice Implementation of IOrionAccessControl checking a Merkle proof against an owner-set root
+ * @author Orion Finance
+ * @dev Leaves are `keccak256(bytes.concat(keccak256(abi.encode(account))))`, as built by OpenZeppelin's
+ *      StandardMerkleTree for the `address` type. The proof is appended to the vault call as
+ *      `abi.encode(bytes32[] proof)`, see AccessDataLib.
+ *      Plain share transfers carry no proof, so only shares leaving the vault's escrow can move.
+ * @custom:security-contact security@orionfinance.ai
+ */
+contract MerkleAccessControl is IOrionAccessControl, ERC165, Ownable2Step {
+    /// @notice Root of the tree of allowed addresses
+    bytes32 public merkleRoot;
+
+    /// @notice Emitted when the Merkle root is updated
+    /// @param merkleRoot The new Merkle root
+    event MerkleRootUpdated(bytes32 indexed merkleRoot);
+
+    /// @notice Constructor
+    /// @param initialOwner_ The address of the initial owner
+    /// @param merkleRoot_ The initial Merkle root
+    constructor(address initialOwner_, bytes32 merkleRoot_) Ownable(initialOwner_) {
+        merkleRoot = merkleRoot_;
+        emit MerkleRootUpdated(merkleRoot_);
+    }
+
+    /// @inheritdoc ERC165
+    function supportsInterface(bytes4 interfaceId) public view virtual override returns (bool) {
+        return interfaceId == type(IOrionAccessControl).interfaceId || super.supportsInterface(interfaceId);
+    }
+
+    /// @inheritdoc IOrionAccessControl
+    function canRequestDeposit(address account, bytes calldata data) external view override returns (bool) {
+        return _isAllowed(account, data);
+    }
+
+    /// @inheritdoc IOrionAccessControl
+    function canRequestRedeem(address account, bytes calldata data) external view override returns (bool) {
+        return _isAllowed(account, data);
+    }
+
+    /// @inheritdoc IOrionAccessControl
+    /// @dev Only transfers out of the calling vault's escrow are allowed.
+    function canTransferShares(address from, address, uint256) external view override returns (bool) {
+        return from == msg.sender;
+    }
+
+    /// @inheritdoc IOrionAccessControl
+    /// @dev Access is proven per call, so no account is blocked from having its requests fulfilled.
+    function isBlocked(address) external pure override returns (bool) {
+        return false;
+    }
+
+    /**
+     * @notice Set the Merkle root
+     * @param newMerkleRoot The new Merkle root
+     * @dev Only callable by owner
+     */
+    function setMerkleRoot(bytes32 newMerkleRoot) external onlyOwner {
+        merkleRoot = newMerkleRoot;
+        emit MerkleRootUpdated(newMerkleRoot);
+    }
+
+    /**
+     * @notice Verifies the proof appended to `data` for `account`
+     * @param account The account to check
+     * @param data The calldata of the vault call
+     * @return True if the proof is valid for the current root
+     */
+    function _isAllowed(address account, bytes calldata data) internal view returns (bool) {
+        bytes calldata payload = AccessDataLib.appendedPayload(data);
+        if (payload.length == 0) return false;
+
+        bytes32[] memory proof = abi.decode(payload, (bytes32[]));
+        bytes32 leaf = keccak256(bytes.concat(keccak256(abi.encode(account))));
+        return MerkleProof.verify(proof, merkleRoot, leaf);
+    }
+}
@@ -0,0 +1,53 @@
+// SPDX-License-Identifier: MIT
+pragma solidity 0.8.34;
+
+import { IOrionAccessControl } from "../interfaces/IOrionAccessControl.sol";
+import { ISanctionsList } from "../interfaces/ISanctionsList.sol";
+import { ErrorsLib } from "../libraries/ErrorsLib.sol";
+import { ERC165 } from "@openzeppelin/contracts/utils/introspection/ERC165.sol";
+
+/**
+ * @title SanctionsAccessControl
+ * @notice Implementation of IOrionAccessControl blocking addresses flagged by a sanctions oracle
+ * @author Orion Finance
+ * @dev Checks new requests and transfers, and blocks flagged addresses: vaults freeze requests made before
+ *      an address was flagged when they come up for fulfillment. Combine with an allowlist through
+ *      CompositeAccessControl to also require KYC.
+ * @custom:security-contact security@orionfinance.ai
+ */
+contract SanctionsAccessControl is IOrionAccessControl, ERC165 {
+    /// @notice Sanctions oracle
+    ISanctionsList public immutable SANCTIONS_LIST;
+
+    /// @notice Constructor
+    /// @param sanctionsList_ The sanctions oracle
+    constructor(ISanctionsList sanctionsList_) {
+        if (address(sanctionsList_) == address(0)) revert ErrorsLib.ZeroAddress();
+        SANCTIONS_LIST = sanctionsList_;
+    }
+
+    /// @inheritdoc ERC165
+    function supportsInterface(bytes4 interfaceId) public view virtual override returns (bool) {
+        return interfaceId == type(IOrionAccessControl).interfaceId || super.supportsInterface(interfaceId);
+    }
+
+    /// @inheritdoc IOrionAccessControl
+    function canRequestDeposit(address account, bytes calldata) external view override returns (bool) {
+        return !SANCTIONS_LIST.isSanctioned(account);
+    }
+
+    /// @inheritdoc IOrionAccessControl
+    function canRequestRedeem(address account, bytes calldata) external view override returns (bool) {
+        return !SANCTIONS_LIST.isSanctioned(account);
+    }
+
+    /// @inheritdoc IOrionAccessControl
+    function canTransferShares(address from, address to, uint256) external view override returns (bool) {
+        return !SANCTIONS_LIST.isSanctioned(from) && !SANCTIONS_LIST.isSanctioned(to);
+    }
+
+    /// @inheritdoc IOrionAccessControl
+    function isBlocked(address account) external view override returns (bool) {
+        return SANCTIONS_LIST.isSanctioned(account);
+    }
+}
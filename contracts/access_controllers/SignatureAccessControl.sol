@@ -0,0 +1,126 @@
+// SPDX-License-Identifier: MIT
+pragma solidity 0.8.34;
+
+import { IOrionAccessControl } from "../interfaces/IOrionAccessControl.sol";
+import { AccessDataLib } from "../libraries/AccessDataLib.sol";
+import "@openzeppelin/contracts/access/Ownable2Step.sol";
+import { EIP712 } from "@openzeppelin/contracts/utils/cryptography/EIP712.sol";
+import { SignatureChecker } from "@openzeppelin/contracts/utils/cryptography/SignatureChecker.sol";
+import { ERC165 } from "@openzeppelin/contracts/utils/introspection/ERC165.sol";
+
+/**
+ * @title SignatureAccessControl
+ * @notice Implementation of IOrionAccessControl checking EIP-712 attestations of a compliance signer
+ * @author Orion Finance
+ * @dev The attestation is appended to the vault call as `abi.encode(uint256 nonce, uint256 expiry, bytes signature)`,
+ *      see AccessDataLib. An attestation is valid until its expiry, and only for the account's current nonce:
+ *      revoking an account bumps its nonce, which invalidates every attestation issued before.
+ *      The signer may be a contract (ERC-1271).
+ *      Plain share transfers carry no attestation, so only shares leaving the vault's escrow can move.
+ * @custom:security-contact security@orionfinance.ai
+ */
+contract SignatureAccessControl is IOrionAccessControl, ERC165, Ownable2Step, EIP712 {
+    /// @notice EIP-712 type hash of an attestation
+    bytes32 public constant ATTESTATION_TYPEHASH =
+        keccak256("Attestation(address account,uint256 nonce,uint256 expiry)");
+
+    /// @notice Compliance signer issuing attestations
+    address public signer;
+
+    /// @notice Current attestation nonce of each account
+    mapping(address => uint256) public nonces;
+
+    /// @notice Emitted when the compliance signer is updated
+    /// @param signer The new signer
+    event SignerUpdated(address indexed signer);
+
+    /// @notice Emitted when the attestations of an account are revoked
+    /// @param account The account
+    /// @param nonce The new nonce of the account
+    event AttestationsRevoked(address indexed account, uint256 nonce);
+
+    /// @notice Constructor
+    /// @param initialOwner_ The address of the initial owner
+    /// @param signer_ The compliance signer
+    constructor(address initialOwner_, address signer_) Ownable(initialOwner_) EIP712("OrionAccessControl", "1") {
+        signer = signer_;
+        emit SignerUpdated(signer_);
+    }
+
+    /// @inheritdoc ERC165
+    function supportsInterface(bytes4 interfaceId) public view virtual override returns (bool) {
+        return interfaceId == type(IOrionAccessControl).interfaceId || super.supportsInterface(interfaceId);
+    }
+
+    /// @inheritdoc IOrionAccessControl
+    function canRequestDeposit(address account, bytes calldata data) external view override returns (bool) {
+        return _isAllowed(account, data);
+    }
+
+    /// @inheritdoc IOrionAccessControl
+    function canRequestRedeem(address account, bytes calldata data) external view override returns (bool) {
+        return _isAllowed(account, data);
+    }
+
+    /// @inheritdoc IOrionAccessControl
+    /// @dev Only transfers out of the calling vault's escrow are allowed.
+    function canTransferShares(address from, address, uint256) external view override returns (bool) {
+        return from == msg.sender;
+    }
+
+    /// @inheritdoc IOrionAccessControl
+    /// @dev Access is proven per call, so no account is blocked from having its requests fulfilled.
+    function isBlocked(address) external pure override returns (bool) {
+        return false;
+    }
+
+    /**
+     * @notice Set the compliance signer
+     * @param newSigner The new signer
+     * @dev Only callable by owner. Attestations of the previous signer stop being accepted.
+     */
+    function setSigner(address newSigner) external onlyOwner {
+        signer = newSigner;
+        emit SignerUpdated(newSigner);
+    }
+
+    /**
+     * @notice Revoke all outstanding attestations of accounts
+     * @param accounts Array of accounts to revoke
+     * @dev Only callable by owner
+     */
+    function revoke(address[] calldata accounts) external onlyOwner {
+        for (uint256 i = 0; i < accounts.length; ++i) {
+            uint256 nonce = ++nonces[accounts[i]];
+            emit AttestationsRevoked(accounts[i], nonce);
+        }
+    }
+
+    /**
+     * @notice EIP-712 digest of an attestation
+     * @param account The attested account
+     * @param nonce The nonce of the account
+     * @param expiry The timestamp from which the attestation is expired
+     * @return The digest to sign
+     */
+    function attestationDigest(address account, uint256 nonce, uint256 expiry) public view returns (bytes32) {
+        return _hashTypedDataV4(keccak256(abi.encode(ATTESTATION_TYPEHASH, account, nonce, expiry)));
+    }
+
+    /**
+     * @notice Verifies the attestation appended to `data` for `account`
+     * @param account The account to check
+     * @param data The calldata of the vault call
+     * @return True if the attestation is signed by the signer, unexpired and for the current nonce
+     */
+    function _isAllowed(address account, bytes calldata data) internal view returns (bool) {
+        bytes calldata payload = AccessDataLib.appendedPayload(data);
+        if (payload.length == 0) return false;
+
+        (uint256 nonce, uint256 expiry, bytes memory signature) = abi.decode(payload, (uint256, uint256, bytes));
+        // slither-disable-next-line timestamp
+        if (expiry <= block.timestamp || nonce != nonces[account]) return false;
+
+        return SignatureChecker.isValidSignatureNow(signer, attestationDigest(account, nonce, expiry), signature);
+    }
+}
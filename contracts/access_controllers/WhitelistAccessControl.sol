@@ -3,6 +3,7 @@ pragma solidity 0.8.34;
 
 import { IOrionAccessControl } from "../interfaces/IOrionAccessControl.sol";
 import "@openzeppelin/contracts/access/Ownable2Step.sol";
+import { ERC165 } from "@openzeppelin/contracts/utils/introspection/ERC165.sol";
 
 /**
  * @title WhitelistAccessControl
@@ -10,8 +11,8 @@ import "@openzeppelin/contracts/access/Ownable2Step.sol";
  * @author Orion Finance
  * @custom:security-contact security@orionfinance.ai
  */
-contract WhitelistAccessControl is IOrionAccessControl, Ownable2Step {
-    /// @notice Mapping of addresses allowed to deposit
+contract WhitelistAccessControl is IOrionAccessControl, ERC165, Ownable2Step {
+    /// @notice Mapping of addresses allowed to deposit, redeem and receive shares
     mapping(address => bool) public whitelist;
 
     /// @notice Emitted when an address is added to the whitelist
@@ -26,9 +27,31 @@ contract WhitelistAccessControl is IOrionAccessControl, Ownable2Step {
     /// @param initialOwner_ The address of the initial owner
     constructor(address initialOwner_) Ownable(initialOwner_) {}
 
+    /// @inheritdoc ERC165
+    function supportsInterface(bytes4 interfaceId) public view virtual override returns (bool) {
+        return interfaceId == type(IOrionAccessControl).interfaceId || super.supportsInterface(interfaceId);
+    }
+
+    /// @inheritdoc IOrionAccessControl
+    function canRequestDeposit(address account, bytes calldata) external view override returns (bool) {
+        return whitelist[account];
+    }
+
+    /// @inheritdoc IOrionAccessControl
+    function canRequestRedeem(address account, bytes calldata) external view override returns (bool) {
+        return whitelist[account];
+    }
+
+    /// @inheritdoc IOrionAccessControl
+    /// @dev Only the recipient must be whitelisted, so removed holders can still move their shares out.
+    function canTransferShares(address, address to, uint256) external view override returns (bool) {
+        return whitelist[to];
+    }
+
     /// @inheritdoc IOrionAccessControl
-    function canRequestDeposit(address sender, bytes calldata) external view override returns (bool) {
-        return whitelist[sender];
+    /// @dev Removal from the whitelist only stops new requests; pending ones are still fulfilled.
+    function isBlocked(address) external pure override returns (bool) {
+        return false;
     }
 
     /**
@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: BUSL-1.1
+pragma solidity ^0.8.34;
+
+/// @title IERC7540Operator
+/// @notice ERC-7540 operator approvals (ERC-165 interface id 0xe3bc4e65)
+/// @author Orion Finance
+/// @dev https://eips.ethereum.org/EIPS/eip-7540
+/// @custom:security-contact security@orionfinance.ai
+interface IERC7540Operator {
+    /// @notice An operator has been approved or revoked by a controller.
+    /// @param controller The controller granting or revoking the approval.
+    /// @param operator The operator.
+    /// @param approved Whether the operator is approved.
+    event OperatorSet(address indexed controller, address indexed operator, bool approved);
+
+    /// @notice Grant or revoke permission for `operator` to manage requests on behalf of msg.sender
+    /// @param operator The operator
+    /// @param approved Whether the operator is approved
+    /// @return Whether the call was executed successfully
+    function setOperator(address operator, bool approved) external returns (bool);
+
+    /// @notice Whether `operator` is approved to manage requests on behalf of `controller`
+    /// @param controller The controller
+    /// @param operator The operator
+    /// @return status The approval status
+    function isOperator(address controller, address operator) external view returns (bool status);
+}
+
+/// @title IERC7540Deposit
+/// @notice ERC-7540 asynchronous deposit requests (ERC-165 interface id 0xce3bbe50)
+/// @author Orion Finance
+/// @dev https://eips.ethereum.org/EIPS/eip-7540
+/// @custom:security-contact security@orionfinance.ai
+interface IERC7540Deposit {
+    /// @notice A deposit request has been made.
+    /// @param controller The address controlling the request.
+    /// @param owner The address whose assets were transferred.
+    /// @param requestId The request id.
+    /// @param sender The caller.
+    /// @param assets The amount of assets requested.
+    event DepositRequest(
+        address indexed controller,
+        address indexed owner,
+        uint256 indexed requestId,
+        address sender,
+        uint256 assets
+    );
+
+    /// @notice Transfer `assets` from `owner` and submit a request for asynchronous deposit
+    /// @param assets The amount of assets to deposit
+    /// @param controller The controller of the request
+    /// @param owner The source of the assets
+    /// @return requestId The request id
+    function requestDeposit(uint256 assets, address controller, address owner) external returns (uint256 requestId);
+
+    /// @notice Amount of requested assets in Pending state
+    /// @param requestId The request id
+    /// @param controller The controller of the request
+    /// @return pendingAssets The pending assets
+    function pendingDepositRequest(uint256 requestId, address controller) external view returns (uint256 pendingAssets);
+
+    /// @notice Amount of requested assets in Claimable state
+    /// @param requestId The request id
+    /// @param controller The controller of the request
+    /// @return claimableAssets The claimable assets
+    function claimableDepositRequest(
+        uint256 requestId,
+        address controller
+    ) external view returns (uint256 claimableAssets);
+
+    /// @notice Claim the shares of `assets` worth of fulfilled deposit requests
+    /// @param assets The amount of claimable assets
+    /// @param receiver The receiver of the shares
+    /// @param controller The controller of the request
+    /// @return shares The shares transferred to `receiver`
+    function deposit(uint256 assets, address receiver, address controller) external returns (uint256 shares);
+
+    /// @notice Claim exactly `shares` from fulfilled deposit requests
+    /// @param shares The amount of claimable shares
+    /// @param receiver The receiver of the shares
+    /// @param controller The controller of the request
+    /// @return assets The claimable assets consumed
+    function mint(uint256 shares, address receiver, address controller) external returns (uint256 assets);
+}
+
+/// @title IERC7540Redeem
+/// @notice ERC-7540 asynchronous redemption requests (ERC-165 interface id 0x620ee8e4)
+/// @author Orion Finance
+/// @dev https://eips.ethereum.org/EIPS/eip-7540
+///      Claims go through the ERC-4626 redeem and withdraw functions, with the controller as third argument.
+/// @custom:security-contact security@orionfinance.ai
+interface IERC7540Redeem {
+    /// @notice A redemption request has been made.
+    /// @param controller The address controlling the request.
+    /// @param owner The address whose shares were transferred.
+    /// @param requestId The request id.
+    /// @param sender The caller.
+    /// @param shares The amount of shares requested.
+    event RedeemRequest(
+        address indexed controller,
+        address indexed owner,
+        uint256 indexed requestId,
+        address sender,
+        uint256 shares
+    );
+
+    /// @notice Transfer `shares` from `owner` and submit a request for asynchronous redemption
+    /// @param shares The amount of shares to redeem
+    /// @param controller The controller of the request
+    /// @param owner The source of the shares
+    /// @return requestId The request id
+    function requestRedeem(uint256 shares, address controller, address owner) external returns (uint256 requestId);
+
+    /// @notice Amount of requested shares in Pending state
+    /// @param requestId The request id
+    /// @param controller The controller of the request
+    /// @return pendingShares The pending shares
+    function pendingRedeemRequest(uint256 requestId, address controller) external view returns (uint256 pendingShares);
+
+    /// @notice Amount of requested shares in Claimable state
+    /// @param requestId The request id
+    /// @param controller The controller of the request
+    /// @return claimableShares The claimable shares
+    function claimableRedeemRequest(
+        uint256 requestId,
+        address controller
+    ) external view returns (uint256 claimableShares);
+}
@@ -39,7 +39,9 @@ interface ILiquidityOrchestrator {
         uint256 totalAssetsForRedeem;
         uint256 totalAssetsForDeposit;
         uint256 finalTotalAssets;
+        /// @notice Management fee [assets], or [shares] when the vault's fee model mints fee shares
         uint256 managementFee;
+        /// @notice Performance fee, in the same unit as managementFee
         uint256 performanceFee;
         address[] tokens;
         uint256[] shares;
@@ -81,6 +83,18 @@ interface ILiquidityOrchestrator {
     /// @return The initial epoch buffer amount
     function initialEpochBufferAmount() external view returns (uint256);
 
+    /// @notice Returns the start time of the current or last epoch
+    /// @dev Vaults check request expiry and fee crystallization against it while an epoch runs.
+    /// @return The epoch start timestamp
+    function epochStartTime() external view returns (uint256);
+
+    /// @notice Returns the deposit and redemption volume fulfilled for a vault in its last processed epoch
+    /// @dev Selects the vault's protocol fee tier at the next epoch start. Zero before the vault's first epoch
+    ///      and after an epoch without flows, so those epochs get the protocol fees.
+    /// @param vault The vault address
+    /// @return The fulfilled deposit and redemption volume [assets]
+    function vaultEpochVolume(address vault) external view returns (uint256);
+
     /// @notice Returns the BuyingLeg-entry buffer snapshot (after sell→buy bufferIncrease apply)
     /// @return The BuyingLeg entry buffer amount
     function buyingLegEntryBuffer() external view returns (uint256);
@@ -102,12 +116,18 @@ interface ILiquidityOrchestrator {
     struct EpochStateView {
         /// @notice Transparent vaults associated to the current epoch
         address[] vaultsEpoch;
-        /// @notice Active volume fee coefficient for current epoch
-        uint16 activeVFeeCoefficient;
-        /// @notice Active revenue share fee coefficient for current epoch
-        uint16 activeRsFeeCoefficient;
         /// @notice Active fee models for vaults in current epoch
         IOrionVault.FeeModel[] vaultFeeModels;
+        /// @notice Active management fee schedules for vaults in current epoch
+        IOrionVault.ManagementFeeSchedule[] vaultManagementFeeSchedules;
+        /// @notice Active fee settlements for vaults in current epoch
+        IOrionVault.FeeSettlement[] vaultFeeSettlements;
+        /// @notice Active performance fee hurdles for vaults in current epoch
+        IOrionVault.Hurdle[] vaultHurdles;
+        /// @notice Active volume fee coefficients for vaults in current epoch, overrides and tiers applied
+        uint16[] vaultVFeeCoefficients;
+        /// @notice Active revenue share fee coefficients for vaults in current epoch, overrides and tiers applied
+        uint16[] vaultRsFeeCoefficients;
         /// @notice Epoch state commitment
         bytes32 epochStateCommitment;
     }
@@ -185,16 +205,53 @@ interface ILiquidityOrchestrator {
     /// @param amount The amount to return
     function returnDepositFunds(address user, uint256 amount) external;
 
-    /// @notice Transfer pending fees to manager
-    /// @dev Called by vault contracts when managers claim their fees
+    /// @notice Transfer pending fees to a fee recipient
+    /// @dev Called by vault contracts when the manager or a fee split recipient claims their fees
+    /// @param recipient The fee recipient
     /// @param amount The amount of fees to transfer
-    function transferVaultFees(uint256 amount) external;
-
-    /// @notice Transfer redemption funds to a user after shares are burned
-    /// @dev Called by vault contracts when processing redemption requests
-    /// @param user The user to transfer funds to
+    function transferVaultFees(address recipient, uint256 amount) external;
+
+    /// @notice Credit the assets of a fulfilled redemption to a user's claimable balance
+    /// @dev Called by vault contracts in fulfillRedeem. Nothing is transferred, so a recipient that cannot
+    ///      receive the underlying asset does not make the epoch revert.
+    /// @param user The controller of the redemption request
+    /// @param amount The amount of underlying assets credited
+    function creditRedemptionFunds(address user, uint256 amount) external;
+
+    /// @notice Pay out claimable redemption funds
+    /// @dev Called by vault contracts when a fulfilled redemption request is claimed.
+    ///      Reverts if `amount` exceeds the claimable balance the calling vault credited to `user`.
+    /// @param user The controller of the redemption request
+    /// @param receiver The receiver of the underlying assets
     /// @param amount The amount of underlying assets to transfer
-    function transferRedemptionFunds(address user, uint256 amount) external;
+    function transferRedemptionFunds(address user, address receiver, uint256 amount) external;
+
+    /// @notice Claimable redemption funds of a vault's controller
+    /// @param vault The vault address
+    /// @param user The controller of the redemption requests
+    /// @return The fulfilled, unclaimed redemption assets
+    function claimableRedemptions(address vault, address user) external view returns (uint256);
+
+    /// @notice Credit the assets of an expired deposit request to a user's claimable refund balance
+    /// @dev Called by vault contracts in fulfillDeposit. Nothing is transferred, so a controller that cannot
+    ///      receive the underlying asset does not make the epoch revert.
+    /// @param user The controller of the deposit request
+    /// @param amount The amount of underlying assets credited
+    function creditDepositRefund(address user, uint256 amount) external;
+
+    /// @notice Pay out a claimable deposit refund
+    /// @dev Called by vault contracts when an expired deposit request's refund is claimed.
+    ///      Reverts if `amount` exceeds the claimable balance the calling vault credited to `user`.
+    /// @param user The controller of the deposit request
+    /// @param receiver The receiver of the underlying assets
+    /// @param amount The amount of underlying assets to transfer
+    function transferDepositRefund(address user, address receiver, uint256 amount) external;
+
+    /// @notice Claimable deposit refunds of a vault's controller
+    /// @param vault The vault address
+    /// @param user The controller of the expired deposit requests
+    /// @return The refunded, unclaimed deposit assets
+    function claimableDepositRefunds(address vault, address user) external view returns (uint256);
 
     /// @notice Deposits underlying assets to the liquidity orchestrator buffer
     /// @dev Increases the buffer amount by the deposited amount.
@@ -3,17 +3,53 @@ pragma solidity ^0.8.34;
 
 /**
  * @title IOrionAccessControl
- * @notice Interface for deposit access control in Orion vaults
+ * @notice Interface for deposit, redemption and share transfer access control in Orion vaults
  * @author Orion Finance
- * @dev Used to implement KYC, AML, and other compliance requirements
+ * @dev Used to implement KYC, AML, and other compliance requirements.
+ *      Implementations that need a proof from the caller read it from `data`, see AccessDataLib.
+ *      Implementations report this interface through ERC-165. Controllers that do not are taken to predate the
+ *      redemption, transfer and blocking hooks: they only gate deposit requests, redemptions and transfers
+ *      stay open and no controller is blocked.
  * @custom:security-contact security@orionfinance.ai
  */
 interface IOrionAccessControl {
     /**
      * @notice Check if a deposit request is allowed
-     * @param sender The address of the sender of the deposit request
-     * @param data The data of the deposit request
+     * @dev Vaults pass the controller of the request, which receives the shares, rather than the caller,
+     *      so operators and routers cannot deposit on behalf of accounts that are not allowed.
+     * @param account The controller of the deposit request
+     * @param data The calldata of the deposit request
      * @return True if the deposit request is allowed, false otherwise
      */
-    function canRequestDeposit(address sender, bytes calldata data) external view returns (bool);
+    function canRequestDeposit(address account, bytes calldata data) external view returns (bool);
+
+    /**
+     * @notice Check if a redemption is allowed
+     * @dev Called for redemption requests with their controller, which claims the assets,
+     *      and for synchronous redemptions of a decommissioned vault with the owner of the shares.
+     * @param account The controller of the redemption request, or the owner of the redeemed shares
+     * @param data The calldata of the redemption
+     * @return True if the redemption is allowed, false otherwise
+     */
+    function canRequestRedeem(address account, bytes calldata data) external view returns (bool);
+
+    /**
+     * @notice Check if a share transfer is allowed
+     * @dev Not called for mints, burns and shares escrowed by the vault for redemption requests.
+     *      Shares leaving the escrow, e.g. claimed deposits, are checked with the vault as `from`.
+     * @param from The sender of the shares
+     * @param to The recipient of the shares
+     * @param amount The amount of shares
+     * @return True if the transfer is allowed, false otherwise
+     */
+    function canTransferShares(address from, address to, uint256 amount) external view returns (bool);
+
+    /**
+     * @notice Check if an account is blocked, e.g. sanctioned after requesting
+     * @dev Vaults freeze the pending requests of blocked controllers instead of fulfilling them,
+     *      so a blocked controller never holds up the requests queued behind it.
+     * @param account The controller of a pending request
+     * @return True if the account's pending requests must not be fulfilled, false otherwise
+     */
+    function isBlocked(address account) external view returns (bool);
 }
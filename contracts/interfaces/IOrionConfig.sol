@@ -9,6 +9,42 @@ import "../libraries/EventsLib.sol";
 /// @author Orion Finance
 /// @custom:security-contact security@orionfinance.ai
 interface IOrionConfig {
+    /// @notice Protocol fee rates applied to vaults from a volume threshold
+    struct ProtocolFeeTier {
+        /// @notice Minimum vault deposit and redemption volume over its last epoch for the tier to apply
+        ///         [underlying asset units]
+        uint256 minVolume;
+        /// @notice Volume fee coefficient
+        uint16 vFeeCoefficient;
+        /// @notice Revenue share fee coefficient
+        uint16 rsFeeCoefficient;
+    }
+
+    /// @notice Protocol fee rates negotiated for a single vault
+    struct ProtocolFeeOverride {
+        /// @notice Whether the override replaces the protocol fee tiers and rates
+        bool enabled;
+        /// @notice Volume fee coefficient
+        uint16 vFeeCoefficient;
+        /// @notice Revenue share fee coefficient
+        uint16 rsFeeCoefficient;
+    }
+
+    /// @notice A protocol fee tiers change has been scheduled.
+    /// @param tiers The new protocol fee tiers.
+    /// @param newProtocolFeeTiersTimestamp The timestamp when the new protocol fee tiers become effective.
+    event ProtocolFeeTiersChangeScheduled(ProtocolFeeTier[] tiers, uint256 newProtocolFeeTiersTimestamp);
+
+    /// @notice A vault protocol fee override change has been scheduled.
+    /// @param vault The vault the override applies to.
+    /// @param feeOverride The new protocol fee override.
+    /// @param newProtocolFeeOverrideTimestamp The timestamp when the new override becomes effective.
+    event VaultProtocolFeeOverrideChangeScheduled(
+        address indexed vault,
+        ProtocolFeeOverride feeOverride,
+        uint256 newProtocolFeeOverrideTimestamp
+    );
+
     /// @notice Returns the address of the liquidity orchestrator contract
     /// @dev This orchestrator manages liquidity operations and coordination
     /// @return The address of the liquidity orchestrator
@@ -55,6 +91,42 @@ interface IOrionConfig {
     /// @return rsFee The active revenue share fee coefficient
     function activeProtocolFees() external view returns (uint16 vFee, uint16 rsFee);
 
+    /// @notice Updates the protocol fee tiers
+    /// @dev Tiers must have strictly ascending, non-zero volume thresholds. A vault pays the rates of the highest
+    ///      tier its deposit and redemption volume over its last epoch reaches, or the protocol fees below the first
+    ///      tier. Follows the same cooldown and cancellation semantics as updateProtocolFees.
+    /// @param tiers The new protocol fee tiers, empty to disable tiering
+    function updateProtocolFeeTiers(ProtocolFeeTier[] calldata tiers) external;
+
+    /// @notice Returns the active protocol fee tiers (old during cooldown, new after)
+    /// @return The active protocol fee tiers
+    function activeProtocolFeeTiers() external view returns (ProtocolFeeTier[] memory);
+
+    /// @notice Updates the protocol fee override of a vault
+    /// @dev An enabled override takes precedence over the protocol fee tiers and rates. Follows the same cooldown
+    ///      and cancellation semantics as updateProtocolFees, tracked per vault.
+    /// @param vault The Orion vault address
+    /// @param feeOverride The new protocol fee override, disabled to fall back to the protocol fees
+    function updateVaultProtocolFeeOverride(address vault, ProtocolFeeOverride calldata feeOverride) external;
+
+    /// @notice Returns the active protocol fee override of a vault (old during cooldown, new after)
+    /// @param vault The Orion vault address
+    /// @return The active protocol fee override
+    function activeVaultProtocolFeeOverride(address vault) external view returns (ProtocolFeeOverride memory);
+
+    /// @notice Returns the active protocol fees applied to a vault
+    /// @dev Resolved from the vault override, then the protocol fee tiers, then the protocol fees.
+    ///      Tiers start above zero volume, so a vault's first epoch, which follows no volume, gets the protocol
+    ///      fees unless overridden.
+    /// @param vault The Orion vault address
+    /// @param volume The vault deposit and redemption volume used to select the fee tier
+    /// @return vFee The active volume fee coefficient
+    /// @return rsFee The active revenue share fee coefficient
+    function activeVaultProtocolFees(
+        address vault,
+        uint256 volume
+    ) external view returns (uint16 vFee, uint16 rsFee);
+
     /// @notice Sets the liquidity orchestrator for the protocol
     /// @dev Can only be called by the contract owner
     /// @param orchestrator The address of the liquidity orchestrator
@@ -2,13 +2,18 @@
 pragma solidity ^0.8.34;
 
 import "@openzeppelin/contracts/interfaces/IERC4626.sol";
+import "@openzeppelin/contracts/utils/introspection/IERC165.sol";
+import "./IERC7540.sol";
 import "./IOrionConfig.sol";
 
 /// @title IOrionVault
 /// @notice Interface for Orion vaults
 /// @author Orion Finance
+/// @dev ERC-7540 vault: requests are aggregated per controller under request id 0.
+///      The ERC-4626 deposit, mint, redeem and withdraw functions claim fulfilled requests of msg.sender,
+///      and the preview functions revert.
 /// @custom:security-contact security@orionfinance.ai
-interface IOrionVault is IERC4626 {
+interface IOrionVault is IERC4626, IERC165, IERC7540Operator, IERC7540Deposit, IERC7540Redeem {
     // --------- ERRORS ---------
 
     /// @notice External synchronous calls are disabled in the current context.
@@ -16,26 +21,38 @@ interface IOrionVault is IERC4626 {
 
     // --------- EVENTS ---------
 
-    /// @notice A deposit request has been made by a user.
-    /// @param sender The address of the user making the deposit request.
-    /// @param assets The amount of assets being deposited.
-    event DepositRequest(address indexed sender, uint256 indexed assets);
-
     /// @notice A deposit request has been cancelled.
     /// @param user The address of the user whose deposit request was cancelled.
     /// @param amount The amount of assets that were requested for deposit.
     event DepositRequestCancelled(address indexed user, uint256 indexed amount);
 
-    /// @notice A redemption request has been made by a user.
-    /// @param sender The address of the user making the redemption request.
-    /// @param shares The number of shares being redeemed.
-    event RedeemRequest(address indexed sender, uint256 indexed shares);
-
     /// @notice A redemption request has been cancelled.
     /// @param user The address of the user whose redemption request was cancelled.
     /// @param shares The number of shares that were requested for redemption.
     event RedeemRequestCancelled(address indexed user, uint256 indexed shares);
 
+    /// @notice An expired deposit request has been refunded.
+    /// @param user The controller of the request, credited with a claimable refund.
+    /// @param amount The amount of assets refunded.
+    event DepositRequestExpired(address indexed user, uint256 amount);
+
+    /// @notice An expired redemption request has been dropped.
+    /// @param user The controller of the request, who received the shares back.
+    /// @param shares The number of shares returned.
+    event RedeemRequestExpired(address indexed user, uint256 shares);
+
+    /// @notice The pending requests of a controller have been frozen.
+    /// @param controller The controller of the requests.
+    /// @param assets The pending deposit assets frozen.
+    /// @param shares The pending redemption shares frozen.
+    event RequestsFrozen(address indexed controller, uint256 assets, uint256 shares);
+
+    /// @notice The frozen requests of a controller have been queued again.
+    /// @param controller The controller of the requests.
+    /// @param assets The deposit assets queued again.
+    /// @param shares The redemption shares queued again.
+    event RequestsUnfrozen(address indexed controller, uint256 assets, uint256 shares);
+
     /// @notice The strategist has been updated.
     /// @param newStrategist The new strategist address.
     event StrategistUpdated(address indexed newStrategist);
@@ -46,26 +63,56 @@ interface IOrionVault is IERC4626 {
     /// @param managementFee The new management fee in basis points.
     event VaultFeeModelUpdated(uint8 indexed mode, uint16 indexed performanceFee, uint16 indexed managementFee);
 
-    /// @notice A redemption request has been fulfilled.
-    /// @param user The address of the user whose redemption was fulfilled.
-    /// @param redeemAmount The amount of assets redeemed by the user.
-    /// @param sharesBurned The number of shares burned for the user.
-    event Redeem(address indexed user, uint256 indexed redeemAmount, uint256 indexed sharesBurned);
+    /// @notice A deposit request has been fulfilled and its shares can be claimed.
+    /// @param controller The controller of the request.
+    /// @param requestId The request id.
+    /// @param assets The amount of assets fulfilled.
+    /// @param shares The number of shares minted to the vault for the controller to claim.
+    event DepositClaimable(address indexed controller, uint256 indexed requestId, uint256 assets, uint256 shares);
+
+    /// @notice A redemption request has been fulfilled and its assets can be claimed.
+    /// @param controller The controller of the request.
+    /// @param requestId The request id.
+    /// @param assets The amount of assets the controller can claim.
+    /// @param shares The number of shares burned.
+    event RedeemClaimable(address indexed controller, uint256 indexed requestId, uint256 assets, uint256 shares);
 
     /// @notice Fees have been accrued.
     /// @param managementFee The amount of management fees accrued.
     /// @param performanceFee The amount of performance fees accrued.
     event VaultFeesAccrued(uint256 indexed managementFee, uint256 indexed performanceFee);
 
+    /// @notice Fees have been paid by minting vault shares.
+    /// @param recipient The address the shares were minted to.
+    /// @param managementFeeShares The number of shares minted for the management fee.
+    /// @param performanceFeeShares The number of shares minted for the performance fee.
+    event VaultFeeSharesMinted(address indexed recipient, uint256 managementFeeShares, uint256 performanceFeeShares);
+
     /// @notice Fees have been claimed.
-    /// @param manager The address of the manager who claimed the fees.
+    /// @param recipient The address of the fee recipient who claimed the fees.
     /// @param feeAmount The amount of fees claimed.
-    event VaultFeesClaimed(address indexed manager, uint256 indexed feeAmount);
+    event VaultFeesClaimed(address indexed recipient, uint256 indexed feeAmount);
 
     /// @notice The deposit access control contract has been updated.
     /// @param newDepositAccessControl The new deposit access control contract address (address(0) = permissionless).
     event DepositAccessControlUpdated(address indexed newDepositAccessControl);
 
+    /// @notice The per-epoch fulfillment limits have been updated.
+    /// @param maxDepositPerEpoch The maximum deposits fulfilled per epoch [assets] (0 = unlimited).
+    /// @param maxRedeemPerEpoch The maximum redemptions fulfilled per epoch [shares] (0 = unlimited).
+    event FulfillLimitsUpdated(uint256 indexed maxDepositPerEpoch, uint256 indexed maxRedeemPerEpoch);
+
+    /// @notice A deposit caps change has been scheduled.
+    /// @param vaultCap The new cap on total assets plus pending deposits [assets] (0 = uncapped).
+    /// @param userCap The new cap per controller [assets] (0 = uncapped).
+    /// @param newDepositCapsTimestamp The timestamp when the new caps become effective.
+    event DepositCapsChangeScheduled(uint256 vaultCap, uint256 userCap, uint256 newDepositCapsTimestamp);
+
+    /// @notice A fee splits change has been scheduled.
+    /// @param feeSplits The new fee splits.
+    /// @param newFeeSplitsTimestamp The timestamp when the new fee splits become effective.
+    event FeeSplitsChangeScheduled(FeeSplit[] feeSplits, uint256 newFeeSplitsTimestamp);
+
     // --------- ENUMS AND STRUCTS ---------
 
     /// @notice Fee type
@@ -77,6 +124,13 @@ interface IOrionVault is IERC4626 {
         HURDLE_HWM // Combination of (hard) hurdle rate and HWM
     }
 
+    /// @notice Hurdle of the hurdle fee types
+    enum HurdleType {
+        RISK_FREE_RATE, // Protocol-wide risk-free rate
+        FIXED_RATE, // Vault-specific annual rate
+        BENCHMARK // Price change of a whitelisted benchmark asset
+    }
+
     /// @notice Fee model
     /// @dev This struct is used to define the fee model for the vault
     struct FeeModel {
@@ -84,12 +138,84 @@ interface IOrionVault is IERC4626 {
         FeeType feeType;
         /// @notice Performance fee - charged on the performance of the vault
         uint16 performanceFee;
-        /// @notice Management fee - charged on the total assets of the vault
+        /// @notice Management fee - charged on the total assets of the vault, up to the first breakpoint
         uint16 managementFee;
         /// @notice High watermark for performance fees
         uint256 highWaterMark;
     }
 
+    /// @notice Management fee schedule on top of the FeeModel management fee
+    /// @dev Kept out of FeeModel so that the fee model storage layout is unchanged; shares its cooldown.
+    struct ManagementFeeSchedule {
+        /// @notice Management fee breakpoints [assets], strictly ascending (0 = unused, and so are the following)
+        uint256[3] breakpoints;
+        /// @notice Management fee charged on the part of the total assets above each breakpoint
+        uint16[3] tierRates;
+        /// @notice Minimum annual management fee [assets] (0 = none)
+        uint256 minimumFee;
+    }
+
+    /// @notice Fee settlement
+    /// @dev Kept out of FeeModel so that the fee model storage layout is unchanged; shares its cooldown.
+    struct FeeSettlement {
+        /// @notice Performance fee crystallization period [s] (0 = every epoch)
+        uint32 crystallizationPeriod;
+        /// @notice Whether fees are paid by minting vault shares instead of accruing underlying
+        bool mintFeeShares;
+        /// @notice Epoch start time of the last performance fee crystallization
+        uint64 lastCrystallization;
+        /// @notice Share price at the last crystallization, the performance fee reference of a crystallization period
+        uint256 crystallizationPrice;
+    }
+
+    /// @notice Performance fee hurdle of the hurdle fee types
+    /// @dev Kept out of FeeModel so that the fee model storage layout is unchanged; shares its cooldown.
+    struct Hurdle {
+        /// @notice Hurdle type
+        HurdleType hurdleType;
+        /// @notice Annual hurdle rate [bps], used with HurdleType.FIXED_RATE
+        uint16 rate;
+        /// @notice Benchmark asset, used with HurdleType.BENCHMARK
+        address benchmark;
+        /// @notice Benchmark price at the last crystallization [priceAdapterDecimals] (0 = not recorded yet)
+        uint256 benchmarkPrice;
+    }
+
+    /// @notice Deposit caps
+    /// @dev Checked when deposit requests are made; fulfillment never reverts because of them.
+    struct DepositCaps {
+        /// @notice Cap on total assets plus pending deposits [assets] (0 = uncapped)
+        uint256 vaultCap;
+        /// @notice Cap on a controller's pending, claimable and held position [assets] (0 = uncapped)
+        uint256 userCap;
+    }
+
+    /// @notice Fulfilled deposit requests of a controller, not yet claimed
+    /// @dev Accumulates across epochs; partial claims are priced at the average of the fulfilled requests.
+    struct ClaimableRequest {
+        /// @notice Fulfilled assets [assets]
+        uint256 assets;
+        /// @notice Fulfilled shares [shares]
+        uint256 shares;
+    }
+
+    /// @notice Frozen requests of a controller
+    /// @dev The assets stay in the liquidity orchestrator and the shares in the vault while frozen.
+    struct FrozenRequests {
+        /// @notice Frozen deposit requests [assets]
+        uint256 assets;
+        /// @notice Frozen redemption requests [shares]
+        uint256 shares;
+    }
+
+    /// @notice Share of the vault fees paid to a recipient other than the manager
+    struct FeeSplit {
+        /// @notice Fee recipient
+        address recipient;
+        /// @notice Share of every accrued fee [bps]
+        uint16 share;
+    }
+
     // --------- GETTERS ---------
 
     /// @notice Orion config getter
@@ -112,6 +238,18 @@ interface IOrionVault is IERC4626 {
     /// @return The currently active fee model
     function activeFeeModel() external view returns (FeeModel memory);
 
+    /// @notice Returns the active fee settlement (old during the fee model cooldown, new after)
+    /// @return The currently active fee settlement
+    function activeFeeSettlement() external view returns (FeeSettlement memory);
+
+    /// @notice Returns the active management fee schedule (old during the fee model cooldown, new after)
+    /// @return The currently active management fee schedule
+    function activeManagementFeeSchedule() external view returns (ManagementFeeSchedule memory);
+
+    /// @notice Returns the active hurdle (old during the fee model cooldown, new after)
+    /// @return The currently active hurdle
+    function activeHurdle() external view returns (Hurdle memory);
+
     // --------- CONFIG FUNCTIONS ---------
 
     /// @notice Override intent to 100% underlying asset for decommissioning
@@ -123,29 +261,121 @@ interface IOrionVault is IERC4626 {
     /// @notice Submit an asynchronous deposit request.
     /// @dev No share tokens are minted immediately. The specified amount of underlying tokens
     ///      is transferred to the liquidity orchestrator for centralized liquidity management.
+    ///      Each call joins the back of the deposit queue, including top-ups of an existing request.
+    ///      Shorthand for requestDeposit(assets, msg.sender, msg.sender).
     /// @param assets The amount of the underlying asset to deposit.
     function requestDeposit(uint256 assets) external;
 
+    /// @notice Submit a deposit request, approving the vault with an EIP-2612 permit on the underlying asset.
+    /// @dev Same as requestDeposit(assets) without a prior approve transaction.
+    ///      A failing permit is ignored as long as the vault already has the allowance, so a front-run
+    ///      permit cannot block the request.
+    /// @param assets The amount of the underlying asset to deposit.
+    /// @param deadline The permit deadline.
+    /// @param v The permit signature v.
+    /// @param r The permit signature r.
+    /// @param s The permit signature s.
+    function requestDepositWithPermit(uint256 assets, uint256 deadline, uint8 v, bytes32 r, bytes32 s) external;
+
+    /// @notice Submit a deposit request, transferring the assets with a Permit2 signature transfer.
+    /// @dev Same as requestDeposit(assets), with the assets moved by Permit2 from msg.sender to the
+    ///      liquidity orchestrator. The signed permit must name the vault as spender, the underlying asset
+    ///      and at least `assets`.
+    /// @param assets The amount of the underlying asset to deposit.
+    /// @param nonce The Permit2 nonce.
+    /// @param deadline The Permit2 deadline.
+    /// @param signature The Permit2 signature of msg.sender.
+    function requestDepositWithPermit2(
+        uint256 assets,
+        uint256 nonce,
+        uint256 deadline,
+        bytes calldata signature
+    ) external;
+
+    /// @notice Submit a deposit request that expires if it has not been fulfilled by `expiry`.
+    /// @dev Same as requestDeposit(assets, controller, owner). Once expired, the request is left out of
+    ///      pendingDeposit and refunded to `controller` by the next fulfillDeposit that reaches it.
+    ///      During an epoch, expiry is checked against the epoch start.
+    /// @param assets The amount of the underlying asset to deposit.
+    /// @param controller The controller of the request.
+    /// @param owner The source of the assets.
+    /// @param expiry Timestamp from which the request is expired, in the future.
+    /// @return requestId The request id, always 0.
+    function requestDepositWithExpiry(
+        uint256 assets,
+        address controller,
+        address owner,
+        uint64 expiry
+    ) external returns (uint256 requestId);
+
     /// @notice Cancel a previously submitted deposit request.
     /// @dev Allows LPs to withdraw their funds before any share tokens are minted.
     ///      The request must still have enough balance remaining to cover the cancellation.
+    ///      The most recent requests are cancelled first, so older ones keep their place in the queue.
     ///      Funds are returned from the liquidity orchestrator to the LP.
+    ///      Shorthand for cancelDepositRequest(amount, msg.sender, msg.sender).
     /// @param amount The amount of funds to withdraw.
     function cancelDepositRequest(uint256 amount) external;
 
+    /// @notice Cancel deposit requests of a controller.
+    /// @dev Callable by the controller and its operators. Funds are returned to `receiver`.
+    /// @param amount The amount of funds to withdraw.
+    /// @param controller The controller of the requests.
+    /// @param receiver The receiver of the funds.
+    function cancelDepositRequest(uint256 amount, address controller, address receiver) external;
+
     /// @notice Submit a redemption request.
     /// @dev No share tokens are burned immediately. The specified amount of share tokens
-    ///      is transferred to the vault.
+    ///      is transferred to the vault. Each call joins the back of the redemption queue.
+    ///      Shorthand for requestRedeem(shares, msg.sender, msg.sender).
     /// @param shares The amount of the share tokens to withdraw.
     function requestRedeem(uint256 shares) external;
 
+    /// @notice Submit a redemption request that expires if it has not been fulfilled by `expiry`.
+    /// @dev Same as requestRedeem(shares, controller, owner). Once expired, the request is left out of
+    ///      pendingRedeem and its shares are returned to `controller` by the next fulfillRedeem that reaches it.
+    ///      During an epoch, expiry is checked against the epoch start.
+    /// @param shares The amount of the share tokens to redeem.
+    /// @param controller The controller of the request.
+    /// @param owner The source of the shares.
+    /// @param expiry Timestamp from which the request is expired, in the future.
+    /// @return requestId The request id, always 0.
+    function requestRedeemWithExpiry(
+        uint256 shares,
+        address controller,
+        address owner,
+        uint64 expiry
+    ) external returns (uint256 requestId);
+
     /// @notice Cancel a previously submitted redemption request.
     /// @dev Allows LPs to recover their share tokens before any burning occurs.
     ///      The request must still have enough shares remaining to cover the cancellation.
+    ///      The most recent requests are cancelled first, so older ones keep their place in the queue.
     ///      Share tokens are returned from the vault.
+    ///      Shorthand for cancelRedeemRequest(shares, msg.sender, msg.sender).
     /// @param shares The amount of share tokens to recover.
     function cancelRedeemRequest(uint256 shares) external;
 
+    /// @notice Cancel redemption requests of a controller.
+    /// @dev Callable by the controller and its operators. Share tokens are returned to `receiver`.
+    /// @param shares The amount of share tokens to recover.
+    /// @param controller The controller of the requests.
+    /// @param receiver The receiver of the share tokens.
+    function cancelRedeemRequest(uint256 shares, address controller, address receiver) external;
+
+    /// @notice Claim all of msg.sender's fulfilled redemption requests.
+    /// @dev Pays out msg.sender's claimable balance in the liquidity orchestrator.
+    ///      Equivalent to withdraw(maxWithdraw(msg.sender), receiver, msg.sender).
+    /// @param receiver The receiver of the underlying assets.
+    /// @return assets The amount of underlying assets paid.
+    function claimRedemption(address receiver) external returns (uint256 assets);
+
+    /// @notice Claim the refunds of all of msg.sender's expired deposit requests.
+    /// @dev Pays out msg.sender's claimable refund balance in the liquidity orchestrator.
+    /// @param receiver The receiver of the underlying assets.
+    /// @return assets The amount of underlying assets paid.
+    function claimDepositRefund(address receiver) external returns (uint256 assets);
+
     // --------- MANAGER AND STRATEGIST FUNCTIONS ---------
 
     /// @notice Update the strategist address
@@ -162,59 +392,176 @@ interface IOrionVault is IERC4626 {
     /// @param managementFee The management fee
     function updateFeeModel(uint8 mode, uint16 performanceFee, uint16 managementFee) external;
 
-    /// @notice Claim accrued vault fees
+    /// @notice Update how vault fees are crystallized and paid
+    /// @dev Takes effect after the same cooldown as updateFeeModel.
+    /// @param crystallizationPeriod Minimum time between performance fee crystallizations [s] (0 = every epoch)
+    /// @param mintFeeShares Whether fees are paid by minting vault shares to the manager instead of in underlying
+    function updateFeeSettlement(uint32 crystallizationPeriod, bool mintFeeShares) external;
+
+    /// @notice Update the management fee schedule
+    /// @dev Takes effect after the same cooldown as updateFeeModel. Tiers are marginal: `managementFee` applies to
+    ///      the total assets up to the first breakpoint and each tier rate to the part between its breakpoint and
+    ///      the next. The annual fee is then raised to `minimumManagementFee` if below it.
+    /// @param breakpoints Strictly ascending breakpoints [assets], zero-padded (all zero = flat `managementFee`)
+    /// @param tierRates Management fee above each breakpoint, zero for unused breakpoints
+    /// @param minimumManagementFee Minimum annual management fee [assets] (0 = none)
+    function updateManagementFeeSchedule(
+        uint256[3] calldata breakpoints,
+        uint16[3] calldata tierRates,
+        uint256 minimumManagementFee
+    ) external;
+
+    /// @notice Update the performance fee hurdle
+    /// @dev Takes effect after the same cooldown as updateFeeModel. Only used by the hurdle fee types.
+    ///      A benchmark hurdle scales the reference share price by the benchmark's price change since the last
+    ///      crystallization; the first crystallization after switching benchmark only records its price.
+    /// @param hurdleType The hurdle type (see HurdleType)
+    /// @param hurdleRate The annual hurdle rate [bps], zero unless hurdleType is FIXED_RATE
+    /// @param hurdleBenchmark The whitelisted benchmark asset, address(0) unless hurdleType is BENCHMARK
+    function updateHurdle(uint8 hurdleType, uint16 hurdleRate, address hurdleBenchmark) external;
+
+    /// @notice Claim the caller's accrued vault fees
+    /// @dev Callable by the manager and by every fee split recipient, up to their own claimable balance.
     /// @param amount The amount of vault fees to claim
     function claimVaultFees(uint256 amount) external;
 
+    /// @notice Vault fees accrued to a recipient and not yet claimed [assets]
+    /// @param recipient The fee recipient
+    /// @return The claimable amount
+    function claimableVaultFees(address recipient) external view returns (uint256);
+
+    /// @notice Update the fee splits with cooldown protection
+    /// @param feeSplits The recipients and their share of every accrued fee; the manager receives the remainder
+    /// @dev Only callable by vault manager while the system is idle.
+    ///      New splits take effect after `feeChangeCooldownDuration`, like fee model changes.
+    ///      Recipients must be non-zero with a non-zero share, and the shares may not add up to more than 100%.
+    function updateFeeSplits(FeeSplit[] calldata feeSplits) external;
+
+    /// @notice Returns the active fee splits (old during cooldown, new after)
+    /// @return The currently active fee splits
+    function activeFeeSplits() external view returns (FeeSplit[] memory);
+
     /// @notice Set deposit access control contract
     /// @param newDepositAccessControl Address of the new access control contract (address(0) = permissionless)
     /// @dev Only callable by vault manager
+    ///      The contract gates deposit requests, redemptions and share transfers.
     ///      It is the FULL responsibility of the vault manager
     ///      to ensure the deposit access control is capable of performing its duties.
     function setDepositAccessControl(address newDepositAccessControl) external;
 
+    /// @notice Set the maximum amounts fulfilled per epoch
+    /// @param maxDepositPerEpoch_ The maximum deposits fulfilled per epoch [assets] (0 = unlimited)
+    /// @param maxRedeemPerEpoch_ The maximum redemptions fulfilled per epoch [shares] (0 = unlimited)
+    /// @dev Only callable by vault manager while the system is idle.
+    ///      The request that crosses a limit is partially fulfilled and keeps its place at the head of the queue,
+    ///      so a request waits for at most (amount queued ahead of it + its amount) / limit epochs.
+    function setFulfillLimits(uint256 maxDepositPerEpoch_, uint256 maxRedeemPerEpoch_) external;
+
+    /// @notice Update the deposit caps with cooldown protection
+    /// @param vaultCap The cap on total assets plus pending deposits [assets] (0 = uncapped)
+    /// @param userCap The cap per controller [assets] (0 = uncapped)
+    /// @dev Only callable by vault manager while the system is idle.
+    ///      New caps take effect after `feeChangeCooldownDuration`, like fee model changes.
+    ///      Lowering a cap below the current usage only blocks new requests.
+    function updateDepositCaps(uint256 vaultCap, uint256 userCap) external;
+
+    /// @notice Returns the active deposit caps (old during cooldown, new after)
+    /// @return The currently active deposit caps
+    function activeDepositCaps() external view returns (DepositCaps memory);
+
+    /// @notice Maximum amount of assets a deposit request for `controller` can currently be made for
+    /// @dev The smallest headroom under the active vault and per-controller caps, type(uint256).max when uncapped,
+    ///      and 0 once the vault is decommissioning. Caps only apply to requests: maxDeposit and maxMint report
+    ///      the full claimable amounts, per ERC-7540, so fulfilled deposits can always be claimed.
+    /// @param controller The controller of the request
+    /// @return The maximum requestable assets
+    function maxDepositRequest(address controller) external view returns (uint256);
+
+    /// @notice Freeze the pending deposit and redemption requests of a controller
+    /// @dev Only callable by vault manager while the system is idle. Requests of controllers the access control
+    ///      blocks are frozen at fulfillment without it; this is the manual override for any other controller.
+    ///      The requests leave the queues, so fulfillDeposit and fulfillRedeem skip them and
+    ///      the controller can no longer cancel them.
+    /// @param controller The controller whose requests are frozen
+    function freezeRequests(address controller) external;
+
+    /// @notice Queue the frozen requests of a controller again
+    /// @dev Only callable by vault manager while the system is idle. The requests join the back of the queues,
+    ///      and are frozen again at fulfillment if the access control still blocks the controller.
+    /// @param controller The controller whose requests are unfrozen
+    function unfreezeRequests(address controller) external;
+
     // --------- LIQUIDITY ORCHESTRATOR FUNCTIONS ---------
 
-    /// @notice Get total pending deposit amount across all users
+    /// @notice Get the deposit amount fulfilled next epoch
+    /// @dev Sums the oldest `fulfillBatchSize` requests, capped at `maxDepositPerEpoch`.
+    ///      Expired requests and requests of blocked controllers count towards `fulfillBatchSize`
+    ///      but not towards the sum.
     /// @param fulfillBatchSize The maximum number of requests to process per fulfill call
     /// @return Total pending deposits denominated in underlying asset units (e.g., USDC, ETH)
     /// @dev This returns asset amounts, not share amounts
     function pendingDeposit(uint256 fulfillBatchSize) external view returns (uint256);
 
-    /// @notice Get total pending redemption shares across all users
+    /// @notice Get the redemption shares fulfilled next epoch
+    /// @dev Sums the oldest `fulfillBatchSize` requests, capped at `maxRedeemPerEpoch`.
+    ///      Expired requests and requests of blocked controllers count towards `fulfillBatchSize`
+    ///      but not towards the sum.
     /// @param fulfillBatchSize The maximum number of requests to process per fulfill call
     /// @return Total pending redemptions denominated in vault share units
     /// @dev This returns share amounts, not underlying asset amounts
     function pendingRedeem(uint256 fulfillBatchSize) external view returns (uint256);
 
     /// @notice Get the number of pending deposit queue entries.
-    /// @return The number of unique users with non-zero pending deposit requests.
+    /// @return The number of queued deposit requests; a user can hold several.
     function pendingDepositCount() external view returns (uint256);
 
     /// @notice Get the number of pending redeem queue entries.
-    /// @return The number of unique users with non-zero pending redeem requests.
+    /// @return The number of queued redeem requests; a user can hold several.
     function pendingRedeemCount() external view returns (uint256);
 
+    /// @notice Get a user's total pending deposit
+    /// @param user The user address
+    /// @return The sum of the user's queued deposit requests [assets]
+    function pendingDepositOf(address user) external view returns (uint256);
+
+    /// @notice Get a user's total pending redemption
+    /// @param user The user address
+    /// @return The sum of the user's queued redeem requests [shares]
+    function pendingRedeemOf(address user) external view returns (uint256);
+
     /// @notice Get the list of pending redeem entries (users and shares) for the next fulfill batch
     /// @param fulfillBatchSize The maximum number of requests to consider
-    /// @return users Addresses with pending redeem requests in batch order
-    /// @return shares Share amounts per user (same index as users)
+    /// @return users Requesting addresses in queue order, once per request that is neither expired nor blocked
+    /// @return shares Shares fulfilled per request (same index as users); the last one may be partial
     /// @dev This function enables per-request conversion,
     ///      ensuring exact rounding behaviour for state transition.
     function pendingRedeemBatch(
         uint256 fulfillBatchSize
     ) external view returns (address[] memory users, uint256[] memory shares);
 
-    /// @notice Process all pending deposit requests and mint shares to depositors
+    /// @notice Process the next batch of deposit requests and make their shares claimable
+    /// @dev Fulfills exactly the requests summed by `pendingDeposit(maxFulfillBatchSize)`.
+    ///      Expired requests visited on the way are removed and their assets credited to each controller's
+    ///      claimable refund balance in the liquidity orchestrator, until claimDepositRefund is called.
+    ///      Requests of controllers blocked by the access control are frozen instead, see freezeRequests.
+    ///      Shares are minted to the vault and transferred to the controller's receiver on deposit or mint.
     /// @param depositTotalAssets The total assets associated with the deposit requests
     function fulfillDeposit(uint256 depositTotalAssets) external;
 
-    /// @notice Process all pending redemption requests and burn shares from redeemers
+    /// @notice Process the next batch of redemption requests and make their assets claimable
+    /// @dev Fulfills exactly the requests returned by `pendingRedeemBatch(maxFulfillBatchSize)`.
+    ///      Expired requests visited on the way are removed and their shares returned to their controllers.
+    ///      Requests of controllers blocked by the access control are frozen instead, see freezeRequests.
+    ///      Shares are burned and the assets credited to each controller's claimable balance in the liquidity
+    ///      orchestrator, without transferring anything, until redeem, withdraw or claimRedemption is called.
     /// @param redeemTotalAssets The total assets associated with the redemption requests
     function fulfillRedeem(uint256 redeemTotalAssets) external;
 
     /// @notice Accrue vault fees for a specific epoch
-    /// @param managementFee The amount of management fees to accrue in underlying asset units
-    /// @param performanceFee The amount of performance fees to accrue in underlying asset units
-    function accrueVaultFees(uint256 managementFee, uint256 performanceFee) external;
+    /// @dev Called before fulfillRedeem and fulfillDeposit, so minted fee shares dilute the epoch's share price.
+    ///      Each fee is split across the active fee splits, the manager receiving the remainder.
+    /// @param managementFee The management fee, in underlying asset units or in shares to mint
+    /// @param performanceFee The performance fee, in underlying asset units or in shares to mint
+    /// @param inShares Whether the fees are shares to mint instead of underlying to accrue
+    function accrueVaultFees(uint256 managementFee, uint256 performanceFee, bool inShares) external;
 }
@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: BUSL-1.1
+pragma solidity ^0.8.34;
+
+/// @title ISignatureTransfer
+/// @notice Subset of Uniswap Permit2 used for signature-based transfers
+/// @author Orion Finance
+/// @dev https://github.com/Uniswap/permit2/blob/main/src/interfaces/ISignatureTransfer.sol
+/// @custom:security-contact security@orionfinance.ai
+interface ISignatureTransfer {
+    /// @notice The token and amount details for a transfer signed in the permit transfer signature
+    struct TokenPermissions {
+        /// @notice ERC20 token address
+        address token;
+        /// @notice The maximum amount that can be spent
+        uint256 amount;
+    }
+
+    /// @notice The signed permit message for a single token transfer
+    struct PermitTransferFrom {
+        /// @notice The token and maximum amount
+        TokenPermissions permitted;
+        /// @notice A unique value for every token owner's signature to prevent signature replays
+        uint256 nonce;
+        /// @notice Deadline on the permit signature
+        uint256 deadline;
+    }
+
+    /// @notice Specifies the recipient address and amount for batched transfers.
+    struct SignatureTransferDetails {
+        /// @notice Recipient address
+        address to;
+        /// @notice Spender requested amount
+        uint256 requestedAmount;
+    }
+
+    /// @notice Transfers a token using a signed permit message
+    /// @dev The spender of the permit is msg.sender
+    /// @param permit The permit data signed over by the owner
+    /// @param transferDetails The spender's requested transfer details for the permitted token
+    /// @param owner The owner of the tokens to transfer
+    /// @param signature The signature to verify
+    function permitTransferFrom(
+        PermitTransferFrom memory permit,
+        SignatureTransferDetails calldata transferDetails,
+        address owner,
+        bytes calldata signature
+    ) external;
+}
@@ -0,0 +1,17 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.34;
+
+/**
+ * @title ISanctionsList
+ * @notice Sanctions oracle interface, as exposed by the Chainalysis sanctions screening oracle
+ * @author Orion Finance
+ * @custom:security-contact security@orionfinance.ai
+ */
+interface ISanctionsList {
+    /**
+     * @notice Check if an address is sanctioned
+     * @param addr The address to check
+     * @return True if the address is sanctioned
+     */
+    function isSanctioned(address addr) external view returns (bool);
+}
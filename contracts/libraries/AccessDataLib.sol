@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.34;
+
+/**
+ * @title AccessDataLib
+ * @notice Reads access-control payloads appended to vault calls
+ * @author Orion Finance
+ * @dev Vaults hand their full calldata to the access control. Callers that need to prove eligibility
+ *      append a payload followed by its length as a 32-byte word:
+ *      `bytes.concat(callData, payload, abi.encode(payload.length))`.
+ *      The ABI decoder of the vault ignores the extra bytes, whatever the called function.
+ *      A CompositeAccessControl payload is `abi.encode(bytes[] segments)`, with the payload of each policy node
+ *      at its index (empty for nodes that need none); each child sees the call with its own segment appended.
+ * @custom:security-contact security@orionfinance.ai
+ */
+library AccessDataLib {
+    /**
+     * @notice Returns the payload appended to `data`
+     * @param data The calldata of the vault call
+     * @return payload The appended payload, empty if the trailing length word does not fit
+     */
+    function appendedPayload(bytes calldata data) internal pure returns (bytes calldata payload) {
+        if (data.length < 32) return data[0:0];
+
+        uint256 end = data.length - 32;
+        uint256 length = uint256(bytes32(data[end:]));
+        if (length > end) return data[0:0];
+
+        return data[end - length:end];
+    }
+
+    /**
+     * @notice Returns `data` with its appended payload, if any, replaced by `payload`
+     * @param data The calldata of the vault call
+     * @param payload The payload to append instead
+     * @return The calldata followed by `payload` and its length
+     */
+    function withPayload(bytes calldata data, bytes memory payload) internal pure returns (bytes memory) {
+        uint256 callLength = data.length;
+        uint256 appended = appendedPayload(data).length;
+        if (appended != 0) callLength -= appended + 32;
+        return bytes.concat(data[0:callLength], payload, abi.encode(payload.length));
+    }
+}
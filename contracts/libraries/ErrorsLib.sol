@@ -75,6 +75,24 @@ library ErrorsLib {
     /// @notice Deposit not allowed due to access control restrictions.
     error DepositNotAllowed();
 
+    /// @notice Redemption not allowed due to access control restrictions.
+    error RedeemNotAllowed();
+
+    /// @notice Share transfer not allowed due to access control restrictions.
+    error TransferNotAllowed();
+
+    /// @notice The deposit request exceeds the vault or per-address deposit cap.
+    /// @param amount The amount that was requested.
+    /// @param available The amount that can still be requested.
+    error DepositCapExceeded(uint256 amount, uint256 available);
+
+    /// @notice Requests made before the request queue upgrade have not all been moved to the queues yet.
+    error RequestQueuesNotMigrated();
+
+    /// @notice The request expiry is not in the future.
+    /// @param expiry The requested expiry timestamp.
+    error InvalidRequestExpiry(uint256 expiry);
+
     /// @notice Slippage exceeds the configured tolerance.
     /// @param asset The asset address where slippage was detected.
     /// @param actual The actual value observed.
@@ -58,6 +58,40 @@ library EventsLib {
         uint256 newFeeRatesTimestamp
     );
 
+    /// @notice A vault fee settlement change has been scheduled.
+    /// @param crystallizationPeriod The new performance fee crystallization period.
+    /// @param mintFeeShares Whether fees will be paid in vault shares.
+    /// @param newFeeRatesTimestamp The timestamp when the change becomes effective.
+    event VaultFeeSettlementChangeScheduled(
+        uint32 crystallizationPeriod,
+        bool mintFeeShares,
+        uint256 newFeeRatesTimestamp
+    );
+
+    /// @notice A vault management fee schedule change has been scheduled.
+    /// @param breakpoints The new management fee breakpoints.
+    /// @param tierRates The new management fee above each breakpoint.
+    /// @param minimumManagementFee The new minimum annual management fee.
+    /// @param newFeeRatesTimestamp The timestamp when the change becomes effective.
+    event VaultManagementFeeScheduleChangeScheduled(
+        uint256[3] breakpoints,
+        uint16[3] tierRates,
+        uint256 minimumManagementFee,
+        uint256 newFeeRatesTimestamp
+    );
+
+    /// @notice A vault hurdle change has been scheduled.
+    /// @param hurdleType The new hurdle type.
+    /// @param hurdleRate The new annual hurdle rate.
+    /// @param hurdleBenchmark The new benchmark asset.
+    /// @param newFeeRatesTimestamp The timestamp when the change becomes effective.
+    event VaultHurdleChangeScheduled(
+        uint8 hurdleType,
+        uint16 hurdleRate,
+        address hurdleBenchmark,
+        uint256 newFeeRatesTimestamp
+    );
+
     /// @notice A protocol fee change has been scheduled.
     /// @param vFeeCoefficient The new volume fee coefficient.
     /// @param rsFeeCoefficient The new revenue share fee coefficient.
@@ -202,6 +236,32 @@ library EventsLib {
     /// @param amount The amount of liquidity withdrawn.
     event LiquidityWithdrawn(address indexed withdrawer, uint256 indexed amount);
 
+    /// @notice A fulfilled redemption has been credited to a user's claimable balance.
+    /// @param vault The address of the vault.
+    /// @param user The controller of the redemption request.
+    /// @param amount The amount of underlying assets credited.
+    event RedemptionFundsCredited(address indexed vault, address indexed user, uint256 amount);
+
+    /// @notice Claimable redemption funds have been paid out.
+    /// @param vault The address of the vault.
+    /// @param user The controller of the redemption request.
+    /// @param receiver The receiver of the underlying assets.
+    /// @param amount The amount of underlying assets paid.
+    event RedemptionFundsClaimed(address indexed vault, address indexed user, address indexed receiver, uint256 amount);
+
+    /// @notice The assets of an expired deposit request have been credited to a claimable refund balance.
+    /// @param vault The address of the vault.
+    /// @param user The controller of the deposit request.
+    /// @param amount The amount of underlying assets credited.
+    event DepositRefundCredited(address indexed vault, address indexed user, uint256 amount);
+
+    /// @notice A claimable deposit refund has been paid out.
+    /// @param vault The address of the vault.
+    /// @param user The controller of the deposit request.
+    /// @param receiver The receiver of the underlying assets.
+    /// @param amount The amount of underlying assets paid.
+    event DepositRefundClaimed(address indexed vault, address indexed user, address indexed receiver, uint256 amount);
+
     /// @notice Enumeration of available vault types.
     enum VaultType {
         Transparent,
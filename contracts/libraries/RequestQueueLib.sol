@@ -0,0 +1,302 @@
+// SPDX-License-Identifier: BUSL-1.1
+pragma solidity ^0.8.34;
+
+import { IOrionAccessControl } from "../interfaces/IOrionAccessControl.sol";
+
+/**
+ * @title RequestQueueLib
+ * @notice First-in first-out queue of deposit or redemption requests
+ * @author Orion Finance
+ * @dev
+ * Requests form a doubly linked list in arrival order, so removing one never moves another.
+ * Every call to requestDeposit/requestRedeem appends a new entry, even for a user that already has one:
+ * topping up a request does not let the added amount skip ahead of later requesters.
+ * Each user's requests also form a doubly linked list, so cancellations take from the user's most recent
+ * entries first and only ever visit that user's queued requests, however many were removed before.
+ * The head entry can be partially consumed, which keeps its place at the front of the queue.
+ * Requests can carry an expiry. Expired requests are skipped by nextBatch and handed back separately,
+ * so that the fulfiller removes them together with the batch; they still count towards the batch size.
+ * Requests of users blocked by the access control, e.g. sanctioned after requesting, are handed back the same way.
+ * @custom:security-contact security@orionfinance.ai
+ */
+library RequestQueueLib {
+    /// @notice A queued request
+    struct Request {
+        /// @notice Requesting user
+        address user;
+        /// @notice Next request in the queue (0 = tail)
+        uint64 next;
+        /// @notice Previous request in the queue (0 = head)
+        uint64 previous;
+        /// @notice Previous queued request of the same user (0 = oldest)
+        uint64 userPrevious;
+        /// @notice Next queued request of the same user (0 = most recent)
+        uint64 userNext;
+        /// @notice Timestamp from which the request is expired (0 = never)
+        uint64 expiry;
+        /// @notice Amount still pending [assets for deposits, shares for redemptions]
+        uint256 amount;
+    }
+
+    /// @notice Why a visited request is left out of a batch
+    enum Skip {
+        NONE,
+        EXPIRED,
+        BLOCKED
+    }
+
+    /// @notice Per-user aggregate of queued requests
+    struct Account {
+        /// @notice Sum of the user's pending amounts
+        uint256 total;
+        /// @notice The user's most recent queued request (0 = none)
+        uint64 latest;
+    }
+
+    /// @notice The queue
+    struct Queue {
+        /// @notice Oldest request (0 = empty)
+        uint64 head;
+        /// @notice Newest request (0 = empty)
+        uint64 tail;
+        /// @notice Last request id handed out; ids start at 1 and are never reused
+        uint64 lastId;
+        /// @notice Number of queued requests
+        uint64 length;
+        /// @notice Requests by id
+        mapping(uint64 => Request) requests;
+        /// @notice Aggregates by user
+        mapping(address => Account) accounts;
+    }
+
+    /// @notice Appends a request to the tail of the queue
+    /// @param queue The queue
+    /// @param user The requesting user
+    /// @param amount The requested amount
+    /// @param expiry Timestamp from which the request is expired (0 = never)
+    function push(Queue storage queue, address user, uint256 amount, uint64 expiry) internal {
+        uint64 id = ++queue.lastId;
+        uint64 tail = queue.tail;
+        Account storage account = queue.accounts[user];
+
+        uint64 latest = account.latest;
+        queue.requests[id] = Request({
+            user: user,
+            next: 0,
+            previous: tail,
+            userPrevious: latest,
+            userNext: 0,
+            expiry: expiry,
+            amount: amount
+        });
+        if (latest != 0) {
+            queue.requests[latest].userNext = id;
+        }
+        if (tail == 0) {
+            queue.head = id;
+        } else {
+            queue.requests[tail].next = id;
+        }
+        queue.tail = id;
+        ++queue.length;
+
+        account.total += amount;
+        account.latest = id;
+    }
+
+    /// @notice Removes `amount` from a user's requests, newest first
+    /// @dev The caller checks that `amount` does not exceed `pendingOf(queue, user)`.
+    /// @param queue The queue
+    /// @param user The user cancelling
+    /// @param amount The amount to cancel
+    function cancel(Queue storage queue, address user, uint256 amount) internal {
+        Account storage account = queue.accounts[user];
+        account.total -= amount;
+
+        uint64 id = account.latest;
+        while (amount > 0) {
+            Request storage request = queue.requests[id];
+            uint64 userPrevious = request.userPrevious;
+            uint256 requested = request.amount;
+            if (requested > amount) {
+                request.amount = requested - amount;
+                break;
+            }
+            amount -= requested;
+            _unlink(queue, id);
+            id = userPrevious;
+        }
+        if (account.total == 0) {
+            delete queue.accounts[user];
+        }
+    }
+
+    /// @notice Consumes part or all of a request, removing it once nothing is left
+    /// @param queue The queue
+    /// @param id The request id
+    /// @param amount The amount fulfilled, at most the request amount
+    function consume(Queue storage queue, uint64 id, uint256 amount) internal {
+        Request storage request = queue.requests[id];
+        address user = request.user;
+        uint256 remaining = request.amount - amount;
+
+        Account storage account = queue.accounts[user];
+        account.total -= amount;
+        if (remaining == 0) {
+            _unlink(queue, id);
+        } else {
+            request.amount = remaining;
+        }
+        if (account.total == 0) {
+            delete queue.accounts[user];
+        }
+    }
+
+    /// @notice Removes a whole request
+    /// @param queue The queue
+    /// @param id The request id
+    /// @return user The requesting user
+    /// @return amount The amount that was still pending
+    function remove(Queue storage queue, uint64 id) internal returns (address user, uint256 amount) {
+        Request storage request = queue.requests[id];
+        user = request.user;
+        amount = request.amount;
+        consume(queue, id, amount);
+    }
+
+    /// @notice Total amount a user has queued
+    /// @param queue The queue
+    /// @param user The user
+    /// @return The pending amount
+    function pendingOf(Queue storage queue, address user) internal view returns (uint256) {
+        return queue.accounts[user].total;
+    }
+
+    /// @notice The requests fulfilled next, in queue order
+    /// @dev Visits at most `batchSize` requests and takes at most `limit` in total, the last one partially if needed.
+    ///      Requests of users blocked by `accessControl` and requests expired at `timestamp` are visited but only
+    ///      returned in `blockedIds` and `expiredIds`; a blocked user's expired request counts as blocked.
+    /// @param queue The queue
+    /// @param batchSize The maximum number of requests visited
+    /// @param limit The maximum total amount (0 = unlimited)
+    /// @param timestamp The time against which expiry is checked
+    /// @param accessControl The access control whose blocked users are skipped (address(0) = none)
+    /// @return ids The request ids
+    /// @return users The requesting users
+    /// @return amounts The amounts fulfilled per request
+    /// @return expiredIds The ids of the expired requests visited
+    /// @return blockedIds The ids of the blocked users' requests visited
+    function nextBatch(
+        Queue storage queue,
+        uint256 batchSize,
+        uint256 limit,
+        uint256 timestamp,
+        IOrionAccessControl accessControl
+    )
+        internal
+        view
+        returns (
+            uint64[] memory ids,
+            address[] memory users,
+            uint256[] memory amounts,
+            uint64[] memory expiredIds,
+            uint64[] memory blockedIds
+        )
+    {
+        uint256 cap = limit == 0 ? type(uint256).max : limit;
+
+        // First pass sizes the arrays: the limit can run out before batchSize requests.
+        // Each request is classified once, so the access control is asked at most once per visited request.
+        uint256 maxVisited = batchSize < queue.length ? batchSize : queue.length;
+        Skip[] memory skips = new Skip[](maxVisited);
+        uint256 size = 0;
+        uint256 expiredCount = 0;
+        uint256 blockedCount = 0;
+        uint256 visited = 0;
+        uint256 remaining = cap;
+        uint64 id = queue.head;
+        while (id != 0 && visited < batchSize && remaining > 0) {
+            Request storage request = queue.requests[id];
+            if (address(accessControl) != address(0) && accessControl.isBlocked(request.user)) {
+                skips[visited] = Skip.BLOCKED;
+                ++blockedCount;
+            } else if (_isExpired(request, timestamp)) {
+                skips[visited] = Skip.EXPIRED;
+                ++expiredCount;
+            } else {
+                remaining -= request.amount < remaining ? request.amount : remaining;
+                ++size;
+            }
+            ++visited;
+            id = request.next;
+        }
+
+        ids = new uint64[](size);
+        users = new address[](size);
+        amounts = new uint256[](size);
+        expiredIds = new uint64[](expiredCount);
+        blockedIds = new uint64[](blockedCount);
+
+        remaining = cap;
+        id = queue.head;
+        uint256 i = 0;
+        uint256 e = 0;
+        uint256 b = 0;
+        for (uint256 k = 0; k < visited; ++k) {
+            Request storage request = queue.requests[id];
+            if (skips[k] == Skip.BLOCKED) {
+                blockedIds[b++] = id;
+            } else if (skips[k] == Skip.EXPIRED) {
+                expiredIds[e++] = id;
+            } else {
+                uint256 amount = request.amount < remaining ? request.amount : remaining;
+                ids[i] = id;
+                users[i] = request.user;
+                amounts[i] = amount;
+                remaining -= amount;
+                ++i;
+            }
+            id = request.next;
+        }
+    }
+
+    /// @notice Whether a request is expired
+    /// @param request The request
+    /// @param timestamp The time against which expiry is checked
+    /// @return True if the request has an expiry at or before `timestamp`
+    function _isExpired(Request storage request, uint256 timestamp) private view returns (bool) {
+        return request.expiry != 0 && request.expiry <= timestamp;
+    }
+
+    /// @notice Removes a request from the queue and from its user's requests
+    /// @param queue The queue
+    /// @param id The request id
+    function _unlink(Queue storage queue, uint64 id) private {
+        Request storage request = queue.requests[id];
+        uint64 next = request.next;
+        uint64 previous = request.previous;
+        uint64 userPrevious = request.userPrevious;
+        uint64 userNext = request.userNext;
+
+        if (previous == 0) {
+            queue.head = next;
+        } else {
+            queue.requests[previous].next = next;
+        }
+        if (next == 0) {
+            queue.tail = previous;
+        } else {
+            queue.requests[next].previous = previous;
+        }
+        if (userPrevious != 0) {
+            queue.requests[userPrevious].userNext = userNext;
+        }
+        if (userNext == 0) {
+            queue.accounts[request.user].latest = userPrevious;
+        } else {
+            queue.requests[userNext].userPrevious = userPrevious;
+        }
+        --queue.length;
+        delete queue.requests[id];
+    }
+}
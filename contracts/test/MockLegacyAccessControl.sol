@@ -0,0 +1,16 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.34;
+
+/// @dev Access control deployed against the original IOrionAccessControl, which only had the deposit hook.
+///      No ERC-165 support and no redemption or transfer hooks, like the first WhitelistAccessControl release.
+contract MockLegacyAccessControl {
+    mapping(address => bool) public whitelist;
+
+    function addToWhitelist(address account) external {
+        whitelist[account] = true;
+    }
+
+    function canRequestDeposit(address sender, bytes calldata) external view returns (bool) {
+        return whitelist[sender];
+    }
+}
@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: BUSL-1.1
+pragma solidity ^0.8.34;
+
+import { IERC20 } from "@openzeppelin/contracts/token/ERC20/IERC20.sol";
+import { SafeERC20 } from "@openzeppelin/contracts/token/ERC20/utils/SafeERC20.sol";
+import { ISignatureTransfer } from "../interfaces/IPermit2.sol";
+
+/// @title Permit2 mock
+/// @notice Signature transfers without signature verification: owners approve this contract directly.
+/// @dev Deployed code is copied to the canonical Permit2 address in tests.
+contract MockPermit2 is ISignatureTransfer {
+    using SafeERC20 for IERC20;
+
+    /// @notice Used nonces, by owner
+    mapping(address => mapping(uint256 => bool)) public usedNonces;
+
+    error SignatureExpired(uint256 deadline);
+    error InvalidNonce();
+    error InvalidAmount(uint256 maxAmount);
+
+    /// @inheritdoc ISignatureTransfer
+    function permitTransferFrom(
+        PermitTransferFrom memory permit,
+        SignatureTransferDetails calldata transferDetails,
+        address owner,
+        bytes calldata
+    ) external {
+        if (block.timestamp > permit.deadline) revert SignatureExpired(permit.deadline);
+        if (transferDetails.requestedAmount > permit.permitted.amount) revert InvalidAmount(permit.permitted.amount);
+        if (usedNonces[owner][permit.nonce]) revert InvalidNonce();
+
+        usedNonces[owner][permit.nonce] = true;
+        IERC20(permit.permitted.token).safeTransferFrom(owner, transferDetails.to, transferDetails.requestedAmount);
+    }
+}
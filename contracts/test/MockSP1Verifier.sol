@@ -0,0 +1,31 @@
+// SPDX-License-Identifier: BUSL-1.1
+pragma solidity ^0.8.34;
+
+import { ISP1Verifier } from "../interfaces/ISP1Verifier.sol";
+
+/// @title SP1 verifier mock
+/// @notice Accepts natively executed (unproven) state transitions for a configurable program key.
+/// @dev Pair with `orion-keeper --mock-prover` or the `orion-state-orchestrator` binary. Like Succinct's
+///      SP1MockVerifier, only empty proofs are accepted so mock payloads never pass a real verifier.
+contract MockSP1Verifier is ISP1Verifier {
+    /// @notice Program verification key this verifier accepts
+    bytes32 public acceptedVKey;
+
+    /// @notice Deploys the mock for a given program key
+    /// @param vKey The verification key to accept (use the LiquidityOrchestrator `vKey`)
+    constructor(bytes32 vKey) {
+        acceptedVKey = vKey;
+    }
+
+    /// @notice Changes the accepted program key
+    /// @param vKey The new verification key
+    function setAcceptedVKey(bytes32 vKey) external {
+        acceptedVKey = vKey;
+    }
+
+    /// @inheritdoc ISP1Verifier
+    function verifyProof(bytes32 programVKey, bytes calldata, bytes calldata proofBytes) external view {
+        require(programVKey == acceptedVKey, "MockSP1Verifier: unexpected vKey");
+        require(proofBytes.length == 0, "MockSP1Verifier: proof must be empty");
+    }
+}
@@ -0,0 +1,23 @@
+// SPDX-License-Identifier: BUSL-1.1
+pragma solidity ^0.8.34;
+
+import { ISanctionsList } from "../interfaces/ISanctionsList.sol";
+
+/// @title Sanctions oracle mock
+/// @notice Sanctions list whose entries anyone can set.
+contract MockSanctionsList is ISanctionsList {
+    /// @notice Sanctioned addresses
+    mapping(address => bool) public sanctioned;
+
+    /// @notice Flags or clears an address
+    /// @param addr The address
+    /// @param isSanctioned_ Whether the address is sanctioned
+    function setSanctioned(address addr, bool isSanctioned_) external {
+        sanctioned[addr] = isSanctioned_;
+    }
+
+    /// @inheritdoc ISanctionsList
+    function isSanctioned(address addr) external view returns (bool) {
+        return sanctioned[addr];
+    }
+}
@@ -2,11 +2,12 @@
 pragma solidity ^0.8.34;
 
 import "@openzeppelin/contracts/token/ERC20/ERC20.sol";
+import "@openzeppelin/contracts/token/ERC20/extensions/ERC20Permit.sol";
 
-contract MockUnderlyingAsset is ERC20 {
+contract MockUnderlyingAsset is ERC20, ERC20Permit {
     uint8 private _decimals;
 
-    constructor(uint8 decimals_) ERC20("USD Coin", "USDC") {
+    constructor(uint8 decimals_) ERC20("USD Coin", "USDC") ERC20Permit("USD Coin") {
         _decimals = decimals_;
     }
 
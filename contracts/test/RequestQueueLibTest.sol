@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: BUSL-1.1
+pragma solidity ^0.8.34;
+
+import "../libraries/RequestQueueLib.sol";
+
+contract RequestQueueLibTest {
+    using RequestQueueLib for RequestQueueLib.Queue;
+
+    RequestQueueLib.Queue private _queue;
+
+    function push(address user, uint256 amount) external {
+        _queue.push(user, amount, 0);
+    }
+
+    function cancel(address user, uint256 amount) external {
+        _queue.cancel(user, amount);
+    }
+
+    function remove(uint64 id) external {
+        _queue.remove(id);
+    }
+
+    function pendingOf(address user) external view returns (uint256) {
+        return _queue.pendingOf(user);
+    }
+
+    function queued() external view returns (address[] memory users, uint256[] memory amounts) {
+        (, users, amounts, , ) = _queue.nextBatch(
+            type(uint256).max,
+            0,
+            block.timestamp,
+            IOrionAccessControl(address(0))
+        );
+    }
+
+    function length() external view returns (uint64) {
+        return _queue.length;
+    }
+}
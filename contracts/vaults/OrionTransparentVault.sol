@@ -161,13 +161,7 @@ contract OrionTransparentVault is OrionVault, IOrionTransparentVault {
 
         uint256 currentSharePrice = convertToAssets(10 ** decimals());
 
-        // Advance both HWMs to prevent double-charging during fee cooldown
-        if (currentSharePrice > feeModel.highWaterMark) {
-            feeModel.highWaterMark = currentSharePrice;
-        }
-        if (currentSharePrice > oldFeeModel.highWaterMark) {
-            oldFeeModel.highWaterMark = currentSharePrice;
-        }
+        _crystallizeFeeModels(currentSharePrice);
 
         emit EventsLib.VaultStateUpdated(
             newTotalAssets,
@@ -11,10 +11,14 @@ import "../interfaces/IOrionVault.sol";
 import "../interfaces/ILiquidityOrchestrator.sol";
 import "../interfaces/IOrionAccessControl.sol";
 import "../interfaces/IOrionStrategist.sol";
+import "../interfaces/IPermit2.sol";
 import "@openzeppelin/contracts/utils/introspection/IERC165.sol";
+import { ERC165Checker } from "@openzeppelin/contracts/utils/introspection/ERC165Checker.sol";
 import { ErrorsLib } from "../libraries/ErrorsLib.sol";
+import { RequestQueueLib } from "../libraries/RequestQueueLib.sol";
 import { Math } from "@openzeppelin/contracts/utils/math/Math.sol";
 import "@openzeppelin/contracts/token/ERC20/utils/SafeERC20.sol";
+import "@openzeppelin/contracts/token/ERC20/extensions/IERC20Permit.sol";
 
 /**
  * @title OrionVault
@@ -22,7 +26,7 @@ import "@openzeppelin/contracts/token/ERC20/utils/SafeERC20.sol";
  * @author Orion Finance
  * @dev
  * Abstract base contract providing common functionality for transparent and encrypted vaults.
- * Implements an asynchronous deposit and redemption pattern inspired by ERC-7540 with custom enhancements:
+ * Implements ERC-7540 asynchronous deposits and redemptions with custom enhancements:
  * - https://eips.ethereum.org/EIPS/eip-4626
  * - https://eips.ethereum.org/EIPS/eip-7540
  * - https://eips.ethereum.org/EIPS/eip-7887
@@ -34,15 +38,22 @@ import "@openzeppelin/contracts/token/ERC20/utils/SafeERC20.sol";
  *
  * Key vault states:
  * 1. Total Assets (_totalAssets) [assets] – total assets under management
- * 2. Deposit Requests (_depositRequests) [assets] – pending deposits, denominated in underlying tokens
- * 3. Redemption Requests (_redeemRequests) [shares] – pending redemptions, denominated in vault shares
- * 4. Portfolio Weights (w_0) [shares] – current allocation in share units for stateless TVL estimation
- * 5. Strategist Intent (w_1) [%] – target allocation in percentage of total supply
+ * 2. Deposit Requests (_depositRequests) [assets] – FIFO queue of pending deposits, in underlying tokens
+ * 3. Redemption Requests (_redeemRequests) [shares] – FIFO queue of pending redemptions, in vault shares
+ * 4. Claimable Requests (_claimableDeposits, _claimableRedeemShares) – fulfilled requests awaiting claim
+ * 5. Portfolio Weights (w_0) [shares] – current allocation in share units for stateless TVL estimation
+ * 6. Strategist Intent (w_1) [%] – target allocation in percentage of total supply
+ *
+ * ERC-7540 requests are aggregated per controller under request id 0. Fulfilled deposits mint shares to the vault,
+ * which the controller claims with deposit or mint; fulfilled redemptions burn the escrowed shares and credit the
+ * assets to the controller's claimable balance in the liquidity orchestrator, paid out on redeem, withdraw or
+ * claimRedemption.
  */
 abstract contract OrionVault is Initializable, ERC4626Upgradeable, ReentrancyGuardTransient, IOrionVault {
     using Math for uint256;
     using SafeERC20 for IERC20;
     using EnumerableMap for EnumerableMap.AddressToUintMap;
+    using RequestQueueLib for RequestQueueLib.Queue;
 
     /// @notice Vault manager
     address public manager;
@@ -52,17 +63,17 @@ abstract contract OrionVault is Initializable, ERC4626Upgradeable, ReentrancyGua
     IOrionConfig public config;
     /// @notice Liquidity orchestrator
     ILiquidityOrchestrator public liquidityOrchestrator;
-    /// @notice Deposit access control contract (address(0) = permissionless)
+    /// @notice Access control contract for deposits, redemptions and share transfers (address(0) = permissionless)
     address public depositAccessControl;
 
     /// @notice Total assets under management (t_0) - denominated in underlying asset units
     uint256 internal _totalAssets;
 
-    /// @notice Deposit requests queue (D) - mapping of user address to requested [assets] amount
-    EnumerableMap.AddressToUintMap private _depositRequests;
+    /// @notice Deposit requests of the per-user map layout, moved to `_depositRequests` by migrateRequestQueues
+    EnumerableMap.AddressToUintMap private _legacyDepositRequests;
 
-    /// @notice Redemption requests queue (R) - mapping of user address to requested [shares] amount
-    EnumerableMap.AddressToUintMap private _redeemRequests;
+    /// @notice Redemption requests of the per-user map layout, moved to `_redeemRequests` by migrateRequestQueues
+    EnumerableMap.AddressToUintMap private _legacyRedeemRequests;
 
     /// @notice Pending vault fees [assets]
     uint256 public pendingVaultFees;
@@ -70,6 +81,12 @@ abstract contract OrionVault is Initializable, ERC4626Upgradeable, ReentrancyGua
     /// @notice Share token decimals
     uint8 public constant SHARE_DECIMALS = 18;
 
+    /// @notice ERC-7540 request id shared by all requests of a controller
+    uint256 public constant REQUEST_ID = 0;
+
+    /// @notice Uniswap Permit2, deployed at the same address on every chain
+    ISignatureTransfer public constant PERMIT2 = ISignatureTransfer(0x000000000022D473030F116dDEE9F6B43aC78BA3);
+
     /* -------------------------------------------------------------------------- */
     /*                               VAULT FEES                                 */
     /* -------------------------------------------------------------------------- */
@@ -78,6 +95,10 @@ abstract contract OrionVault is Initializable, ERC4626Upgradeable, ReentrancyGua
     uint32 public constant YEAR_IN_SECONDS = 365 days;
     /// @notice Basis points factor (100% = 10_000)
     uint16 public constant BASIS_POINTS_FACTOR = 10_000;
+    /// @notice Maximum number of fee split recipients
+    uint8 public constant MAX_FEE_SPLITS = 8;
+    /// @notice Number of management fee breakpoints
+    uint8 public constant MANAGEMENT_FEE_TIERS = 3;
 
     /// @notice Fee model
     FeeModel public feeModel;
@@ -92,6 +113,77 @@ abstract contract OrionVault is Initializable, ERC4626Upgradeable, ReentrancyGua
     /// @dev When true, intent is overridden to 100% underlying asset
     bool public isDecommissioning;
 
+    /// @notice Maximum deposits fulfilled per epoch [assets] (0 = unlimited)
+    uint256 public maxDepositPerEpoch;
+
+    /// @notice Maximum redemptions fulfilled per epoch [shares] (0 = unlimited)
+    uint256 public maxRedeemPerEpoch;
+
+    /// @notice Fulfilled deposit requests awaiting claim, by controller
+    mapping(address => ClaimableRequest) internal _claimableDeposits;
+
+    /// @notice Burned shares of fulfilled redemption requests awaiting claim, by controller
+    /// @dev The matching assets are held in `liquidityOrchestrator.claimableRedemptions`.
+    mapping(address => uint256) internal _claimableRedeemShares;
+
+    /// @notice ERC-7540 operator approvals, by controller and operator
+    mapping(address => mapping(address => bool)) public isOperator;
+
+    /// @notice Deposit caps
+    DepositCaps public depositCaps;
+
+    /// @notice Timestamp when new deposit caps become effective
+    uint256 public newDepositCapsTimestamp;
+
+    /// @notice Previous deposit caps (used during cooldown period)
+    DepositCaps internal oldDepositCaps;
+
+    /// @notice Sum of the pending deposit requests [assets]
+    uint256 internal _pendingDepositTotal;
+
+    /// @notice Frozen requests, by controller
+    mapping(address => FrozenRequests) public frozenRequests;
+
+    /// @notice Fee splits
+    FeeSplit[] internal _feeSplits;
+
+    /// @notice Timestamp when new fee splits become effective
+    uint256 public newFeeSplitsTimestamp;
+
+    /// @notice Previous fee splits (used during cooldown period)
+    FeeSplit[] internal _oldFeeSplits;
+
+    /// @notice Vault fees accrued and not yet claimed, by recipient [assets]
+    mapping(address => uint256) public claimableVaultFees;
+
+    /// @notice Fee settlement
+    FeeSettlement public feeSettlement;
+
+    /// @notice Previous fee settlement (used during the fee model cooldown period)
+    FeeSettlement internal oldFeeSettlement;
+
+    /// @notice Management fee schedule
+    ManagementFeeSchedule internal _managementFeeSchedule;
+
+    /// @notice Previous management fee schedule (used during the fee model cooldown period)
+    ManagementFeeSchedule internal _oldManagementFeeSchedule;
+
+    /// @notice Performance fee hurdle
+    Hurdle public hurdle;
+
+    /// @notice Previous performance fee hurdle (used during the fee model cooldown period)
+    Hurdle internal oldHurdle;
+
+    /// @notice Sum of the claimable vault fees [assets]
+    /// @dev Falls short of pendingVaultFees only by the fees accrued before per-recipient claims.
+    uint256 internal _claimableVaultFeesTotal;
+
+    /// @notice Deposit requests queue (D) - requested [assets] amounts in arrival order
+    RequestQueueLib.Queue private _depositRequests;
+
+    /// @notice Redemption requests queue (R) - requested [shares] amounts in arrival order
+    RequestQueueLib.Queue private _redeemRequests;
+
     /// @dev Restricts function to only vault manager
     modifier onlyManager() {
         if (msg.sender != manager) revert ErrorsLib.NotAuthorized();
@@ -110,6 +202,12 @@ abstract contract OrionVault is Initializable, ERC4626Upgradeable, ReentrancyGua
         _;
     }
 
+    /// @dev Restricts function to `account` itself and the operators it approved
+    modifier onlyOperatorOf(address account) {
+        if (msg.sender != account && !isOperator[account][msg.sender]) revert ErrorsLib.NotAuthorized();
+        _;
+    }
+
     /// @dev Restricts function to only Orion Config contract
     modifier onlyConfig() {
         if (msg.sender != address(config)) revert ErrorsLib.NotAuthorized();
@@ -165,29 +263,83 @@ abstract contract OrionVault is Initializable, ERC4626Upgradeable, ReentrancyGua
         feeModel.managementFee = managementFee_;
 
         feeModel.highWaterMark = 10 ** underlyingDecimals;
+        feeSettlement.crystallizationPrice = 10 ** underlyingDecimals;
+        feeSettlement.lastCrystallization = uint64(block.timestamp);
 
         oldFeeModel = feeModel;
+        oldFeeSettlement = feeSettlement;
         newFeeRatesTimestamp = block.timestamp;
     }
 
     /// @inheritdoc IERC4626
-    function deposit(uint256, address) public pure override(ERC4626Upgradeable, IERC4626) returns (uint256) {
-        revert SynchronousCallDisabled();
+    /// @dev Claims `assets` worth of msg.sender's fulfilled deposit requests.
+    function deposit(
+        uint256 assets,
+        address receiver
+    ) public override(ERC4626Upgradeable, IERC4626) nonReentrant returns (uint256) {
+        return _claimDeposit(assets, receiver, msg.sender);
+    }
+
+    /// @inheritdoc IERC7540Deposit
+    function deposit(
+        uint256 assets,
+        address receiver,
+        address controller
+    ) external nonReentrant onlyOperatorOf(controller) returns (uint256) {
+        return _claimDeposit(assets, receiver, controller);
     }
 
     /// @inheritdoc IERC4626
-    function mint(uint256, address) public pure override(ERC4626Upgradeable, IERC4626) returns (uint256) {
-        revert SynchronousCallDisabled();
+    /// @dev Claims `shares` from msg.sender's fulfilled deposit requests.
+    function mint(
+        uint256 shares,
+        address receiver
+    ) public override(ERC4626Upgradeable, IERC4626) nonReentrant returns (uint256) {
+        return _claimMint(shares, receiver, msg.sender);
+    }
+
+    /// @inheritdoc IERC7540Deposit
+    function mint(
+        uint256 shares,
+        address receiver,
+        address controller
+    ) external nonReentrant onlyOperatorOf(controller) returns (uint256) {
+        return _claimMint(shares, receiver, controller);
     }
 
     /// @inheritdoc IERC4626
+    /// @dev Claims `shares` of the controller's fulfilled redemption requests.
+    ///      Once the vault is decommissioned, a controller without claimable redemptions
+    ///      redeems its shares synchronously instead, with the third argument acting as the ERC-4626 owner.
     function redeem(
         uint256 shares,
         address receiver,
-        address owner
+        address controller
     ) public override(ERC4626Upgradeable, IERC4626) nonReentrant returns (uint256) {
-        // Only allow synchronous redemption for decommissioned vaults
-        if (!config.isDecommissionedVault(address(this))) revert SynchronousCallDisabled();
+        if (_claimableRedeemShares[controller] == 0 && config.isDecommissionedVault(address(this))) {
+            return _redeemDecommissioned(shares, receiver, controller);
+        }
+        if (msg.sender != controller && !isOperator[controller][msg.sender]) revert ErrorsLib.NotAuthorized();
+        return _claimRedeem(shares, receiver, controller);
+    }
+
+    /// @inheritdoc IERC4626
+    /// @dev Claims `assets` of the controller's fulfilled redemption requests.
+    function withdraw(
+        uint256 assets,
+        address receiver,
+        address controller
+    ) public override(ERC4626Upgradeable, IERC4626) nonReentrant onlyOperatorOf(controller) returns (uint256) {
+        return _claimWithdraw(assets, receiver, controller);
+    }
+
+    /// @notice Synchronous redemption of a decommissioned vault
+    /// @param shares The shares to redeem
+    /// @param receiver The receiver of the assets
+    /// @param owner The owner of the shares
+    /// @return assets The assets sent to `receiver`
+    function _redeemDecommissioned(uint256 shares, address receiver, address owner) internal returns (uint256 assets) {
+        _checkCanRedeem(owner);
 
         uint256 maxShares = maxRedeem(owner);
         if (shares > maxShares) {
@@ -199,7 +351,7 @@ abstract contract OrionVault is Initializable, ERC4626Upgradeable, ReentrancyGua
             _spendAllowance(owner, msg.sender, shares);
         }
 
-        uint256 assets = previewRedeem(shares);
+        assets = previewRedeem(shares);
         // Update total assets accounting
         _totalAssets -= assets;
 
@@ -208,52 +360,72 @@ abstract contract OrionVault is Initializable, ERC4626Upgradeable, ReentrancyGua
         emit Withdraw(msg.sender, receiver, owner, assets, shares);
 
         liquidityOrchestrator.withdraw(assets, receiver);
+    }
 
-        return assets;
+    /// @inheritdoc IERC4626
+    function totalAssets() public view override(ERC4626Upgradeable, IERC4626) returns (uint256) {
+        return _totalAssets;
     }
 
     /// @inheritdoc IERC4626
-    function withdraw(uint256, address, address) public pure override(ERC4626Upgradeable, IERC4626) returns (uint256) {
-        revert SynchronousCallDisabled();
+    /// @dev Claimable assets of the controller's fulfilled deposit requests.
+    function maxDeposit(address controller) public view override(ERC4626Upgradeable, IERC4626) returns (uint256) {
+        return _claimableDeposits[controller].assets;
     }
 
     /// @inheritdoc IERC4626
-    function totalAssets() public view override(ERC4626Upgradeable, IERC4626) returns (uint256) {
-        return _totalAssets;
+    /// @dev Claimable shares of the controller's fulfilled deposit requests.
+    function maxMint(address controller) public view override(ERC4626Upgradeable, IERC4626) returns (uint256) {
+        return _claimableDeposits[controller].shares;
     }
 
     /// @inheritdoc IERC4626
-    function maxDeposit(address receiver) public view override(ERC4626Upgradeable, IERC4626) returns (uint256) {
-        if (!config.isSystemIdle()) return 0;
-        if (isDecommissioning || config.isDecommissionedVault(address(this))) return 0;
-        if (depositAccessControl != address(0)) {
-            if (!IOrionAccessControl(depositAccessControl).canRequestDeposit(receiver, "")) return 0;
-        }
-        return type(uint256).max;
+    /// @dev Claimable shares of the controller's fulfilled redemption requests,
+    ///      or its share balance in a decommissioned vault once nothing is left to claim.
+    function maxRedeem(address controller) public view override(ERC4626Upgradeable, IERC4626) returns (uint256) {
+        uint256 claimableShares = _claimableRedeemShares[controller];
+        if (claimableShares == 0 && config.isDecommissionedVault(address(this))) return balanceOf(controller);
+        return claimableShares;
+    }
+
+    /// @inheritdoc IERC4626
+    /// @dev Claimable assets of the controller's fulfilled redemption requests.
+    function maxWithdraw(address controller) public view override(ERC4626Upgradeable, IERC4626) returns (uint256) {
+        return liquidityOrchestrator.claimableRedemptions(address(this), controller);
     }
 
     /// @inheritdoc IERC4626
-    function maxMint(address receiver) public view override(ERC4626Upgradeable, IERC4626) returns (uint256) {
-        uint256 maxAssets = maxDeposit(receiver);
-        if (maxAssets == 0) return 0;
-        return type(uint256).max;
+    /// @dev Reverts: deposits are asynchronous.
+    function previewDeposit(uint256) public pure override(ERC4626Upgradeable, IERC4626) returns (uint256) {
+        revert SynchronousCallDisabled();
     }
 
     /// @inheritdoc IERC4626
-    function maxRedeem(address owner) public view override(ERC4626Upgradeable, IERC4626) returns (uint256) {
-        if (config.isDecommissionedVault(address(this))) return balanceOf(owner);
+    /// @dev Reverts: deposits are asynchronous.
+    function previewMint(uint256) public pure override(ERC4626Upgradeable, IERC4626) returns (uint256) {
+        revert SynchronousCallDisabled();
+    }
 
-        if (!config.isSystemIdle()) return 0;
-        uint256 shares = balanceOf(owner);
-        if (shares < config.minRedeemAmount()) return 0;
-        return shares;
+    /// @inheritdoc IERC4626
+    /// @dev Reverts unless the vault is decommissioned: redemptions are asynchronous.
+    function previewRedeem(uint256 shares) public view override(ERC4626Upgradeable, IERC4626) returns (uint256) {
+        if (!config.isDecommissionedVault(address(this))) revert SynchronousCallDisabled();
+        return _convertToAssets(shares, Math.Rounding.Floor);
     }
 
     /// @inheritdoc IERC4626
-    function maxWithdraw(address owner) public view override(ERC4626Upgradeable, IERC4626) returns (uint256) {
-        uint256 maxShares = maxRedeem(owner);
-        if (maxShares == 0) return 0;
-        return convertToAssets(maxShares);
+    /// @dev Reverts: redemptions are asynchronous.
+    function previewWithdraw(uint256) public pure override(ERC4626Upgradeable, IERC4626) returns (uint256) {
+        revert SynchronousCallDisabled();
+    }
+
+    /// @inheritdoc IERC165
+    function supportsInterface(bytes4 interfaceId) public view virtual returns (bool) {
+        return
+            interfaceId == type(IERC165).interfaceId ||
+            interfaceId == type(IERC7540Operator).interfaceId ||
+            interfaceId == type(IERC7540Deposit).interfaceId ||
+            interfaceId == type(IERC7540Redeem).interfaceId;
     }
 
     /// @notice Override ERC4626 decimals to always use SHARE_DECIMALS regardless of underlying asset decimals
@@ -313,112 +485,475 @@ abstract contract OrionVault is Initializable, ERC4626Upgradeable, ReentrancyGua
 
     /// --------- LP FUNCTIONS ---------
 
+    /// @inheritdoc IERC7540Operator
+    function setOperator(address operator, bool approved) external returns (bool) {
+        if (operator == msg.sender) revert ErrorsLib.InvalidAddress();
+
+        isOperator[msg.sender][operator] = approved;
+        emit OperatorSet(msg.sender, operator, approved);
+        return true;
+    }
+
     /// @inheritdoc IOrionVault
     function requestDeposit(uint256 assets) external nonReentrant {
+        _requestDeposit(assets, msg.sender, msg.sender, 0);
+    }
+
+    /// @inheritdoc IERC7540Deposit
+    function requestDeposit(
+        uint256 assets,
+        address controller,
+        address owner
+    ) external nonReentrant onlyOperatorOf(owner) returns (uint256) {
+        _requestDeposit(assets, controller, owner, 0);
+        return REQUEST_ID;
+    }
+
+    /// @inheritdoc IOrionVault
+    function requestDepositWithExpiry(
+        uint256 assets,
+        address controller,
+        address owner,
+        uint64 expiry
+    ) external nonReentrant onlyOperatorOf(owner) returns (uint256) {
+        _validateExpiry(expiry);
+        _requestDeposit(assets, controller, owner, expiry);
+        return REQUEST_ID;
+    }
+
+    /// @inheritdoc IOrionVault
+    function requestDepositWithPermit(
+        uint256 assets,
+        uint256 deadline,
+        uint8 v,
+        bytes32 r,
+        bytes32 s
+    ) external nonReentrant {
+        // A front-run permit consumes the nonce but still sets the allowance, so only fail on the transfer
+        try IERC20Permit(asset()).permit(msg.sender, address(this), assets, deadline, v, r, s) {} catch {}
+        _requestDeposit(assets, msg.sender, msg.sender, 0);
+    }
+
+    /// @inheritdoc IOrionVault
+    function requestDepositWithPermit2(
+        uint256 assets,
+        uint256 nonce,
+        uint256 deadline,
+        bytes calldata signature
+    ) external nonReentrant {
+        _validateDepositRequest(assets, msg.sender, msg.sender);
+
+        PERMIT2.permitTransferFrom(
+            ISignatureTransfer.PermitTransferFrom({
+                permitted: ISignatureTransfer.TokenPermissions({ token: asset(), amount: assets }),
+                nonce: nonce,
+                deadline: deadline
+            }),
+            ISignatureTransfer.SignatureTransferDetails({
+                to: address(liquidityOrchestrator),
+                requestedAmount: assets
+            }),
+            msg.sender,
+            signature
+        );
+
+        _queueDepositRequest(assets, msg.sender, msg.sender, 0);
+    }
+
+    /// @notice Queues a deposit request, taking the assets from `owner`
+    /// @param assets The amount of assets to deposit
+    /// @param controller The controller of the request
+    /// @param owner The source of the assets, authorized by the caller
+    /// @param expiry Timestamp from which the request is expired (0 = never)
+    function _requestDeposit(uint256 assets, address controller, address owner, uint64 expiry) internal {
+        _validateDepositRequest(assets, controller, owner);
+
+        IERC20(asset()).safeTransferFrom(owner, address(liquidityOrchestrator), assets);
+
+        _queueDepositRequest(assets, controller, owner, expiry);
+    }
+
+    /// @notice Checks that a deposit request can be made
+    /// @param assets The amount of assets to deposit
+    /// @param controller The controller of the request
+    /// @param owner The source of the assets
+    function _validateDepositRequest(uint256 assets, address controller, address owner) internal view {
+        // The controller receives the shares, so it is the one the access control checks
         if (depositAccessControl != address(0)) {
-            if (!IOrionAccessControl(depositAccessControl).canRequestDeposit(msg.sender, msg.data))
+            if (!IOrionAccessControl(depositAccessControl).canRequestDeposit(controller, msg.data))
                 revert ErrorsLib.DepositNotAllowed();
         }
 
         if (!config.isSystemIdle()) revert ErrorsLib.SystemNotIdle();
         if (isDecommissioning || config.isDecommissionedVault(address(this))) revert ErrorsLib.VaultDecommissioned();
+        if (controller == address(0)) revert ErrorsLib.ZeroAddress();
         if (assets == 0) revert ErrorsLib.AmountMustBeGreaterThanZero(asset());
 
         uint256 minDeposit = config.minDepositAmount();
         if (assets < minDeposit) revert ErrorsLib.BelowMinimumDeposit(assets, minDeposit);
 
-        uint256 senderBalance = IERC20(asset()).balanceOf(msg.sender);
-        if (assets > senderBalance) revert ErrorsLib.InsufficientAmount();
+        uint256 available = _depositCapHeadroom(controller);
+        if (assets > available) revert ErrorsLib.DepositCapExceeded(assets, available);
 
-        IERC20(asset()).safeTransferFrom(msg.sender, address(liquidityOrchestrator), assets);
+        uint256 ownerBalance = IERC20(asset()).balanceOf(owner);
+        if (assets > ownerBalance) revert ErrorsLib.InsufficientAmount();
+    }
 
-        // slither-disable-next-line unused-return
-        (, uint256 currentAmount) = _depositRequests.tryGet(msg.sender);
-        // slither-disable-next-line unused-return
-        _depositRequests.set(msg.sender, currentAmount + assets);
+    /// @notice Assets that can still be requested for `controller` under the active deposit caps
+    /// @param controller The controller of the request
+    /// @return available The smallest headroom, type(uint256).max when uncapped
+    function _depositCapHeadroom(address controller) internal view returns (uint256 available) {
+        DepositCaps memory caps = activeDepositCaps();
+        available = type(uint256).max;
 
-        emit DepositRequest(msg.sender, assets);
+        if (caps.vaultCap != 0) {
+            uint256 vaultUsage = _totalAssets + _pendingDepositTotal;
+            available = caps.vaultCap > vaultUsage ? caps.vaultCap - vaultUsage : 0;
+        }
+        if (caps.userCap != 0) {
+            uint256 userUsage = _depositRequests.pendingOf(controller) +
+                _claimableDeposits[controller].assets +
+                _convertToAssets(balanceOf(controller), Math.Rounding.Floor);
+            available = Math.min(available, caps.userCap > userUsage ? caps.userCap - userUsage : 0);
+        }
+    }
+
+    /// @inheritdoc IOrionVault
+    function maxDepositRequest(address controller) external view returns (uint256) {
+        if (isDecommissioning || config.isDecommissionedVault(address(this))) return 0;
+        return _depositCapHeadroom(controller);
+    }
+
+    /// @notice Records a deposit request whose assets reached the liquidity orchestrator
+    /// @param assets The amount of assets deposited
+    /// @param controller The controller of the request
+    /// @param owner The source of the assets
+    /// @param expiry Timestamp from which the request is expired (0 = never)
+    function _queueDepositRequest(uint256 assets, address controller, address owner, uint64 expiry) internal {
+        _checkRequestQueuesMigrated();
+        _depositRequests.push(controller, assets, expiry);
+        _pendingDepositTotal += assets;
+
+        emit DepositRequest(controller, owner, REQUEST_ID, msg.sender, assets);
     }
 
     /// @inheritdoc IOrionVault
     function cancelDepositRequest(uint256 amount) external nonReentrant {
+        _cancelDepositRequest(amount, msg.sender, msg.sender);
+    }
+
+    /// @inheritdoc IOrionVault
+    function cancelDepositRequest(
+        uint256 amount,
+        address controller,
+        address receiver
+    ) external nonReentrant onlyOperatorOf(controller) {
+        _cancelDepositRequest(amount, controller, receiver);
+    }
+
+    /// @notice Cancels the most recent deposit requests of `controller`
+    /// @param amount The amount of assets to cancel
+    /// @param controller The controller of the requests, authorized by the caller
+    /// @param receiver The receiver of the refunded assets
+    function _cancelDepositRequest(uint256 amount, address controller, address receiver) internal {
         if (!config.isSystemIdle()) revert ErrorsLib.SystemNotIdle();
         if (amount == 0) revert ErrorsLib.AmountMustBeGreaterThanZero(asset());
 
-        // slither-disable-next-line unused-return
-        (, uint256 currentAmount) = _depositRequests.tryGet(msg.sender);
+        uint256 currentAmount = _depositRequests.pendingOf(controller);
         if (currentAmount < amount) revert ErrorsLib.InsufficientAmount();
 
         // Update internal state
         uint256 newAmount = currentAmount - amount;
 
-        if (newAmount == 0) {
-            // slither-disable-next-line unused-return
-            _depositRequests.remove(msg.sender);
-        } else {
+        if (newAmount != 0) {
             // Avoid dust deposit requests by rejecting cancellations with small reminders.
             uint256 minDeposit = config.minDepositAmount();
             if (newAmount < minDeposit) revert ErrorsLib.BelowMinimumDeposit(newAmount, minDeposit);
-
-            // slither-disable-next-line unused-return
-            _depositRequests.set(msg.sender, newAmount);
         }
+        _depositRequests.cancel(controller, amount);
+        _pendingDepositTotal -= amount;
 
         // Request funds from liquidity orchestrator
-        liquidityOrchestrator.returnDepositFunds(msg.sender, amount);
+        liquidityOrchestrator.returnDepositFunds(receiver, amount);
 
-        emit DepositRequestCancelled(msg.sender, amount);
+        emit DepositRequestCancelled(controller, amount);
     }
 
     /// @inheritdoc IOrionVault
     function requestRedeem(uint256 shares) external nonReentrant {
+        _requestRedeem(shares, msg.sender, msg.sender, 0);
+    }
+
+    /// @inheritdoc IERC7540Redeem
+    /// @dev Callers other than the owner and its operators spend the owner's share allowance.
+    function requestRedeem(uint256 shares, address controller, address owner) external nonReentrant returns (uint256) {
+        if (msg.sender != owner && !isOperator[owner][msg.sender]) {
+            _spendAllowance(owner, msg.sender, shares);
+        }
+        _requestRedeem(shares, controller, owner, 0);
+        return REQUEST_ID;
+    }
+
+    /// @inheritdoc IOrionVault
+    function requestRedeemWithExpiry(
+        uint256 shares,
+        address controller,
+        address owner,
+        uint64 expiry
+    ) external nonReentrant returns (uint256) {
+        if (msg.sender != owner && !isOperator[owner][msg.sender]) {
+            _spendAllowance(owner, msg.sender, shares);
+        }
+        _validateExpiry(expiry);
+        _requestRedeem(shares, controller, owner, expiry);
+        return REQUEST_ID;
+    }
+
+    /// @notice Checks that a request expiry lies in the future
+    /// @param expiry The requested expiry timestamp
+    function _validateExpiry(uint64 expiry) internal view {
+        // slither-disable-next-line timestamp
+        if (expiry <= block.timestamp) revert ErrorsLib.InvalidRequestExpiry(expiry);
+    }
+
+    /// @notice Queues a redemption request, escrowing the shares of `owner` in the vault
+    /// @param shares The amount of shares to redeem
+    /// @param controller The controller of the request
+    /// @param owner The source of the shares, authorized by the caller
+    /// @param expiry Timestamp from which the request is expired (0 = never)
+    function _requestRedeem(uint256 shares, address controller, address owner, uint64 expiry) internal {
+        // The controller claims the assets, so it is the one the access control checks
+        _checkCanRedeem(controller);
+
         if (!config.isSystemIdle()) revert ErrorsLib.SystemNotIdle();
         if (config.isDecommissionedVault(address(this))) revert ErrorsLib.VaultDecommissioned();
+        if (controller == address(0)) revert ErrorsLib.ZeroAddress();
         if (shares == 0) revert ErrorsLib.AmountMustBeGreaterThanZero(address(this));
 
         uint256 minRedeem = config.minRedeemAmount();
         if (shares < minRedeem) revert ErrorsLib.BelowMinimumRedeem(shares, minRedeem);
 
-        uint256 senderBalance = balanceOf(msg.sender);
-        if (shares > senderBalance) revert ErrorsLib.InsufficientAmount();
+        uint256 ownerBalance = balanceOf(owner);
+        if (shares > ownerBalance) revert ErrorsLib.InsufficientAmount();
 
-        IERC20(address(this)).safeTransferFrom(msg.sender, address(this), shares);
+        _transfer(owner, address(this), shares);
 
-        // slither-disable-next-line unused-return
-        (, uint256 currentShares) = _redeemRequests.tryGet(msg.sender);
-        // slither-disable-next-line unused-return
-        _redeemRequests.set(msg.sender, currentShares + shares);
+        _checkRequestQueuesMigrated();
+        _redeemRequests.push(controller, shares, expiry);
+
+        emit RedeemRequest(controller, owner, REQUEST_ID, msg.sender, shares);
+    }
 
-        emit RedeemRequest(msg.sender, shares);
+    /// @notice Reverts if the access control does not allow `account` to redeem
+    /// @param account The controller of the request, or the owner of the redeemed shares
+    function _checkCanRedeem(address account) internal view {
+        if (_hasAccessControlHooks()) {
+            if (!IOrionAccessControl(depositAccessControl).canRequestRedeem(account, msg.data))
+                revert ErrorsLib.RedeemNotAllowed();
+        }
+    }
+
+    /// @notice Share transfers are checked by the access control
+    /// @dev Mints, burns and transfers into the vault's own escrow are not checked.
+    /// @param from The sender
+    /// @param to The recipient
+    /// @param value The amount of shares
+    function _update(address from, address to, uint256 value) internal virtual override {
+        if (
+            from != address(0) &&
+            to != address(0) &&
+            to != address(this) &&
+            _hasAccessControlHooks() &&
+            !IOrionAccessControl(depositAccessControl).canTransferShares(from, to, value)
+        ) revert ErrorsLib.TransferNotAllowed();
+
+        super._update(from, to, value);
+    }
+
+    /// @notice Whether the access control implements the redemption and transfer hooks
+    /// @dev Access controls predating the hooks do not report the current IOrionAccessControl interface
+    ///      and only gate deposit requests, so vaults upgraded with one keep redemptions and transfers open.
+    /// @return True if the redemption and transfer hooks are called
+    function _hasAccessControlHooks() internal view returns (bool) {
+        return
+            depositAccessControl != address(0) &&
+            ERC165Checker.supportsERC165InterfaceUnchecked(depositAccessControl, type(IOrionAccessControl).interfaceId);
     }
 
     /// @inheritdoc IOrionVault
     function cancelRedeemRequest(uint256 shares) external nonReentrant {
+        _cancelRedeemRequest(shares, msg.sender, msg.sender);
+    }
+
+    /// @inheritdoc IOrionVault
+    function cancelRedeemRequest(
+        uint256 shares,
+        address controller,
+        address receiver
+    ) external nonReentrant onlyOperatorOf(controller) {
+        _cancelRedeemRequest(shares, controller, receiver);
+    }
+
+    /// @notice Cancels the most recent redemption requests of `controller`
+    /// @param shares The amount of shares to cancel
+    /// @param controller The controller of the requests, authorized by the caller
+    /// @param receiver The receiver of the returned shares
+    function _cancelRedeemRequest(uint256 shares, address controller, address receiver) internal {
         if (!config.isSystemIdle()) revert ErrorsLib.SystemNotIdle();
         if (shares == 0) revert ErrorsLib.AmountMustBeGreaterThanZero(address(this));
 
-        // slither-disable-next-line unused-return
-        (, uint256 currentShares) = _redeemRequests.tryGet(msg.sender);
+        uint256 currentShares = _redeemRequests.pendingOf(controller);
         if (currentShares < shares) revert ErrorsLib.InsufficientAmount();
 
         // Effects - update internal state
         uint256 newShares = currentShares - shares;
-        if (newShares == 0) {
-            // slither-disable-next-line unused-return
-            _redeemRequests.remove(msg.sender);
-        } else {
+        if (newShares != 0) {
             // Avoid dust redeem requests by rejecting cancellations with small reminders.
             uint256 minRedeem = config.minRedeemAmount();
             if (newShares < minRedeem) revert ErrorsLib.BelowMinimumRedeem(newShares, minRedeem);
-
-            // slither-disable-next-line unused-return
-            _redeemRequests.set(msg.sender, newShares);
         }
+        _redeemRequests.cancel(controller, shares);
 
         // Interactions - return shares to LP.
-        IERC20(address(this)).safeTransfer(msg.sender, shares);
+        IERC20(address(this)).safeTransfer(receiver, shares);
+
+        emit RedeemRequestCancelled(controller, shares);
+    }
+
+    /// @inheritdoc IERC7540Deposit
+    function pendingDepositRequest(uint256, address controller) external view returns (uint256) {
+        return _depositRequests.pendingOf(controller);
+    }
+
+    /// @inheritdoc IERC7540Deposit
+    function claimableDepositRequest(uint256, address controller) external view returns (uint256) {
+        return _claimableDeposits[controller].assets;
+    }
+
+    /// @inheritdoc IERC7540Redeem
+    function pendingRedeemRequest(uint256, address controller) external view returns (uint256) {
+        return _redeemRequests.pendingOf(controller);
+    }
+
+    /// @inheritdoc IERC7540Redeem
+    function claimableRedeemRequest(uint256, address controller) external view returns (uint256) {
+        return _claimableRedeemShares[controller];
+    }
+
+    /// @inheritdoc IOrionVault
+    function claimRedemption(address receiver) external nonReentrant returns (uint256 assets) {
+        assets = liquidityOrchestrator.claimableRedemptions(address(this), msg.sender);
+        if (assets == 0) revert ErrorsLib.AmountMustBeGreaterThanZero(asset());
+
+        _payClaimedAssets(assets, _claimableRedeemShares[msg.sender], receiver, msg.sender);
+    }
+
+    /// @inheritdoc IOrionVault
+    function claimDepositRefund(address receiver) external nonReentrant returns (uint256 assets) {
+        assets = liquidityOrchestrator.claimableDepositRefunds(address(this), msg.sender);
+        if (assets == 0) revert ErrorsLib.AmountMustBeGreaterThanZero(asset());
+
+        liquidityOrchestrator.transferDepositRefund(msg.sender, receiver, assets);
+    }
+
+    /// @notice Transfers the shares of `assets` worth of fulfilled deposit requests
+    /// @param assets The claimable assets to consume
+    /// @param receiver The receiver of the shares
+    /// @param controller The controller of the requests
+    /// @return shares The shares transferred, at the average price of the fulfilled requests
+    function _claimDeposit(uint256 assets, address receiver, address controller) internal returns (uint256 shares) {
+        ClaimableRequest storage claimable = _claimableDeposits[controller];
+        if (assets == 0) revert ErrorsLib.AmountMustBeGreaterThanZero(asset());
+        if (assets > claimable.assets) revert ERC4626ExceededMaxDeposit(controller, assets, claimable.assets);
+
+        shares = assets == claimable.assets
+            ? claimable.shares
+            : assets.mulDiv(claimable.shares, claimable.assets, Math.Rounding.Floor);
+        _transferClaimedShares(claimable, assets, shares, receiver, controller);
+    }
+
+    /// @notice Transfers `shares` from fulfilled deposit requests
+    /// @param shares The claimable shares to transfer
+    /// @param receiver The receiver of the shares
+    /// @param controller The controller of the requests
+    /// @return assets The claimable assets consumed, at the average price of the fulfilled requests
+    function _claimMint(uint256 shares, address receiver, address controller) internal returns (uint256 assets) {
+        ClaimableRequest storage claimable = _claimableDeposits[controller];
+        if (shares == 0) revert ErrorsLib.AmountMustBeGreaterThanZero(address(this));
+        if (shares > claimable.shares) revert ERC4626ExceededMaxMint(controller, shares, claimable.shares);
 
-        emit RedeemRequestCancelled(msg.sender, shares);
+        assets = shares == claimable.shares
+            ? claimable.assets
+            : shares.mulDiv(claimable.assets, claimable.shares, Math.Rounding.Ceil);
+        _transferClaimedShares(claimable, assets, shares, receiver, controller);
+    }
+
+    /// @notice Settles a deposit claim
+    /// @param claimable The controller's claimable deposits
+    /// @param assets The claimable assets consumed
+    /// @param shares The shares transferred out of the vault
+    /// @param receiver The receiver of the shares
+    /// @param controller The controller of the requests
+    function _transferClaimedShares(
+        ClaimableRequest storage claimable,
+        uint256 assets,
+        uint256 shares,
+        address receiver,
+        address controller
+    ) internal {
+        claimable.assets -= assets;
+        claimable.shares -= shares;
+
+        _transfer(address(this), receiver, shares);
+
+        emit Deposit(controller, receiver, assets, shares);
+    }
+
+    /// @notice Pays out the assets of `shares` from fulfilled redemption requests
+    /// @param shares The claimable shares to consume
+    /// @param receiver The receiver of the assets
+    /// @param controller The controller of the requests
+    /// @return assets The assets paid, at the average price of the fulfilled requests
+    function _claimRedeem(uint256 shares, address receiver, address controller) internal returns (uint256 assets) {
+        uint256 claimableShares = _claimableRedeemShares[controller];
+        if (shares == 0) revert ErrorsLib.AmountMustBeGreaterThanZero(address(this));
+        if (shares > claimableShares) revert ERC4626ExceededMaxRedeem(controller, shares, claimableShares);
+
+        uint256 claimableAssets = liquidityOrchestrator.claimableRedemptions(address(this), controller);
+        assets = shares == claimableShares
+            ? claimableAssets
+            : shares.mulDiv(claimableAssets, claimableShares, Math.Rounding.Floor);
+        _payClaimedAssets(assets, shares, receiver, controller);
+    }
+
+    /// @notice Pays out `assets` from fulfilled redemption requests
+    /// @param assets The claimable assets to pay
+    /// @param receiver The receiver of the assets
+    /// @param controller The controller of the requests
+    /// @return shares The claimable shares consumed, at the average price of the fulfilled requests
+    function _claimWithdraw(uint256 assets, address receiver, address controller) internal returns (uint256 shares) {
+        uint256 claimableAssets = liquidityOrchestrator.claimableRedemptions(address(this), controller);
+        if (assets == 0) revert ErrorsLib.AmountMustBeGreaterThanZero(asset());
+        if (assets > claimableAssets) revert ERC4626ExceededMaxWithdraw(controller, assets, claimableAssets);
+
+        uint256 claimableShares = _claimableRedeemShares[controller];
+        shares = assets == claimableAssets
+            ? claimableShares
+            : assets.mulDiv(claimableShares, claimableAssets, Math.Rounding.Ceil);
+        _payClaimedAssets(assets, shares, receiver, controller);
+    }
+
+    /// @notice Settles a redemption claim
+    /// @param assets The assets paid out of the controller's balance in the liquidity orchestrator
+    /// @param shares The claimable shares consumed
+    /// @param receiver The receiver of the assets
+    /// @param controller The controller of the requests
+    function _payClaimedAssets(uint256 assets, uint256 shares, address receiver, address controller) internal {
+        _claimableRedeemShares[controller] -= shares;
+
+        emit Withdraw(msg.sender, receiver, controller, assets, shares);
+
+        liquidityOrchestrator.transferRedemptionFunds(controller, receiver, assets);
     }
 
     /// --------- MANAGER AND STRATEGIST FUNCTIONS ---------
@@ -447,6 +982,118 @@ abstract contract OrionVault is Initializable, ERC4626Upgradeable, ReentrancyGua
         emit DepositAccessControlUpdated(newDepositAccessControl);
     }
 
+    /// @inheritdoc IOrionVault
+    function setFulfillLimits(uint256 maxDepositPerEpoch_, uint256 maxRedeemPerEpoch_) external onlyManager {
+        // The orchestrator reads the limits both when committing to the epoch and when fulfilling it.
+        if (!config.isSystemIdle()) revert ErrorsLib.SystemNotIdle();
+
+        maxDepositPerEpoch = maxDepositPerEpoch_;
+        maxRedeemPerEpoch = maxRedeemPerEpoch_;
+        emit FulfillLimitsUpdated(maxDepositPerEpoch_, maxRedeemPerEpoch_);
+    }
+
+    /// @inheritdoc IOrionVault
+    function updateDepositCaps(uint256 vaultCap, uint256 userCap) external onlyManager {
+        if (!config.isSystemIdle()) revert ErrorsLib.SystemNotIdle();
+
+        // Store old caps for cooldown period
+        oldDepositCaps = activeDepositCaps();
+
+        depositCaps = DepositCaps({ vaultCap: vaultCap, userCap: userCap });
+        newDepositCapsTimestamp = block.timestamp + config.feeChangeCooldownDuration();
+
+        emit DepositCapsChangeScheduled(vaultCap, userCap, newDepositCapsTimestamp);
+    }
+
+    /// @inheritdoc IOrionVault
+    function activeDepositCaps() public view returns (DepositCaps memory) {
+        // If we're still in cooldown period, return old caps
+        if (newDepositCapsTimestamp > block.timestamp) {
+            return oldDepositCaps;
+        }
+        return depositCaps;
+    }
+
+    /// @inheritdoc IOrionVault
+    function updateFeeSplits(FeeSplit[] calldata feeSplits) external onlyManager {
+        if (!config.isSystemIdle()) revert ErrorsLib.SystemNotIdle();
+        if (feeSplits.length > MAX_FEE_SPLITS) revert ErrorsLib.InvalidArguments();
+
+        uint256 totalShare = 0;
+        for (uint256 i = 0; i < feeSplits.length; ++i) {
+            if (feeSplits[i].recipient == address(0)) revert ErrorsLib.ZeroAddress();
+            if (feeSplits[i].share == 0) revert ErrorsLib.InvalidArguments();
+            totalShare += feeSplits[i].share;
+        }
+        if (totalShare > BASIS_POINTS_FACTOR) revert ErrorsLib.InvalidArguments();
+
+        // Store old splits for cooldown period
+        FeeSplit[] memory currentSplits = activeFeeSplits();
+        delete _oldFeeSplits;
+        for (uint256 i = 0; i < currentSplits.length; ++i) {
+            _oldFeeSplits.push(currentSplits[i]);
+        }
+
+        delete _feeSplits;
+        for (uint256 i = 0; i < feeSplits.length; ++i) {
+            _feeSplits.push(feeSplits[i]);
+        }
+        newFeeSplitsTimestamp = block.timestamp + config.feeChangeCooldownDuration();
+
+        emit FeeSplitsChangeScheduled(feeSplits, newFeeSplitsTimestamp);
+    }
+
+    /// @inheritdoc IOrionVault
+    function activeFeeSplits() public view returns (FeeSplit[] memory) {
+        // If we're still in cooldown period, return old splits
+        if (newFeeSplitsTimestamp > block.timestamp) {
+            return _oldFeeSplits;
+        }
+        return _feeSplits;
+    }
+
+    /// @inheritdoc IOrionVault
+    function freezeRequests(address controller) external onlyManager {
+        if (!config.isSystemIdle()) revert ErrorsLib.SystemNotIdle();
+
+        uint256 assets = _depositRequests.pendingOf(controller);
+        uint256 shares = _redeemRequests.pendingOf(controller);
+        if (assets == 0 && shares == 0) revert ErrorsLib.InsufficientAmount();
+
+        if (assets != 0) {
+            _depositRequests.cancel(controller, assets);
+            _pendingDepositTotal -= assets;
+        }
+        if (shares != 0) {
+            _redeemRequests.cancel(controller, shares);
+        }
+
+        FrozenRequests storage frozen = frozenRequests[controller];
+        frozen.assets += assets;
+        frozen.shares += shares;
+
+        emit RequestsFrozen(controller, assets, shares);
+    }
+
+    /// @inheritdoc IOrionVault
+    function unfreezeRequests(address controller) external onlyManager {
+        if (!config.isSystemIdle()) revert ErrorsLib.SystemNotIdle();
+
+        FrozenRequests memory frozen = frozenRequests[controller];
+        if (frozen.assets == 0 && frozen.shares == 0) revert ErrorsLib.InsufficientAmount();
+        delete frozenRequests[controller];
+
+        if (frozen.assets != 0) {
+            _depositRequests.push(controller, frozen.assets, 0);
+            _pendingDepositTotal += frozen.assets;
+        }
+        if (frozen.shares != 0) {
+            _redeemRequests.push(controller, frozen.shares, 0);
+        }
+
+        emit RequestsUnfrozen(controller, frozen.assets, frozen.shares);
+    }
+
     /// @notice Update the fee model parameters with cooldown protection
     /// @param feeType The fee type (0=ABSOLUTE, 1=HURDLE, 2=HIGH_WATER_MARK, 3=HURDLE_HWM)
     /// @param performanceFee The performance fee
@@ -461,7 +1108,7 @@ abstract contract OrionVault is Initializable, ERC4626Upgradeable, ReentrancyGua
         if (feeType > uint8(FeeType.HURDLE_HWM)) revert ErrorsLib.InvalidArguments();
 
         // Store old fee model for cooldown period
-        oldFeeModel = activeFeeModel();
+        _snapshotActiveFees();
 
         // Update to new fee model immediately in storage
         feeModel.feeType = FeeType(feeType);
@@ -474,6 +1121,86 @@ abstract contract OrionVault is Initializable, ERC4626Upgradeable, ReentrancyGua
         emit EventsLib.VaultFeeChangeScheduled(feeType, performanceFee, managementFee, newFeeRatesTimestamp);
     }
 
+    /// @inheritdoc IOrionVault
+    function updateFeeSettlement(uint32 crystallizationPeriod, bool mintFeeShares) external onlyManager {
+        if (!config.isSystemIdle()) revert ErrorsLib.SystemNotIdle();
+        if (crystallizationPeriod > YEAR_IN_SECONDS) revert ErrorsLib.InvalidArguments();
+
+        _snapshotActiveFees();
+
+        feeSettlement.crystallizationPeriod = crystallizationPeriod;
+        feeSettlement.mintFeeShares = mintFeeShares;
+
+        newFeeRatesTimestamp = block.timestamp + config.feeChangeCooldownDuration();
+
+        emit EventsLib.VaultFeeSettlementChangeScheduled(crystallizationPeriod, mintFeeShares, newFeeRatesTimestamp);
+    }
+
+    /// @inheritdoc IOrionVault
+    function updateManagementFeeSchedule(
+        uint256[3] calldata breakpoints,
+        uint16[3] calldata tierRates,
+        uint256 minimumManagementFee
+    ) external onlyManager {
+        if (!config.isSystemIdle()) revert ErrorsLib.SystemNotIdle();
+
+        // Used breakpoints come first, strictly ascending; unused ones carry no rate.
+        uint256 previousBreakpoint = 0;
+        for (uint256 i = 0; i < MANAGEMENT_FEE_TIERS; ++i) {
+            if (breakpoints[i] == 0) {
+                if (tierRates[i] != 0) revert ErrorsLib.InvalidArguments();
+                previousBreakpoint = type(uint256).max;
+            } else if (breakpoints[i] <= previousBreakpoint) {
+                revert ErrorsLib.InvalidArguments();
+            } else {
+                previousBreakpoint = breakpoints[i];
+            }
+        }
+
+        _snapshotActiveFees();
+
+        _managementFeeSchedule.breakpoints = breakpoints;
+        _managementFeeSchedule.tierRates = tierRates;
+        _managementFeeSchedule.minimumFee = minimumManagementFee;
+
+        newFeeRatesTimestamp = block.timestamp + config.feeChangeCooldownDuration();
+
+        emit EventsLib.VaultManagementFeeScheduleChangeScheduled(
+            breakpoints,
+            tierRates,
+            minimumManagementFee,
+            newFeeRatesTimestamp
+        );
+    }
+
+    /// @inheritdoc IOrionVault
+    function updateHurdle(uint8 hurdleType, uint16 hurdleRate, address hurdleBenchmark) external onlyManager {
+        if (!config.isSystemIdle()) revert ErrorsLib.SystemNotIdle();
+
+        // Validate input
+        if (hurdleType > uint8(HurdleType.BENCHMARK)) revert ErrorsLib.InvalidArguments();
+        if (hurdleType != uint8(HurdleType.FIXED_RATE) && hurdleRate != 0) revert ErrorsLib.InvalidArguments();
+        if (hurdleType == uint8(HurdleType.BENCHMARK)) {
+            if (!config.isWhitelisted(hurdleBenchmark)) revert ErrorsLib.TokenNotWhitelisted(hurdleBenchmark);
+        } else if (hurdleBenchmark != address(0)) {
+            revert ErrorsLib.InvalidArguments();
+        }
+
+        _snapshotActiveFees();
+
+        // A new benchmark has no reference price until its first crystallization
+        if (hurdleBenchmark != hurdle.benchmark) {
+            hurdle.benchmarkPrice = 0;
+        }
+        hurdle.hurdleType = HurdleType(hurdleType);
+        hurdle.rate = hurdleRate;
+        hurdle.benchmark = hurdleBenchmark;
+
+        newFeeRatesTimestamp = block.timestamp + config.feeChangeCooldownDuration();
+
+        emit EventsLib.VaultHurdleChangeScheduled(hurdleType, hurdleRate, hurdleBenchmark, newFeeRatesTimestamp);
+    }
+
     /// @inheritdoc IOrionVault
     function activeFeeModel() public view returns (FeeModel memory) {
         // If we're still in cooldown period, return old rates
@@ -484,6 +1211,39 @@ abstract contract OrionVault is Initializable, ERC4626Upgradeable, ReentrancyGua
         return feeModel;
     }
 
+    /// @inheritdoc IOrionVault
+    function activeFeeSettlement() public view returns (FeeSettlement memory) {
+        if (newFeeRatesTimestamp > block.timestamp) {
+            return oldFeeSettlement;
+        }
+        return feeSettlement;
+    }
+
+    /// @inheritdoc IOrionVault
+    function activeManagementFeeSchedule() public view returns (ManagementFeeSchedule memory) {
+        if (newFeeRatesTimestamp > block.timestamp) {
+            return _oldManagementFeeSchedule;
+        }
+        return _managementFeeSchedule;
+    }
+
+    /// @inheritdoc IOrionVault
+    function activeHurdle() public view returns (Hurdle memory) {
+        if (newFeeRatesTimestamp > block.timestamp) {
+            return oldHurdle;
+        }
+        return hurdle;
+    }
+
+    /// @notice Keeps the active fee settings for the cooldown of a fee change
+    /// @dev Every fee setting shares newFeeRatesTimestamp, so a change to one of them snapshots all of them.
+    function _snapshotActiveFees() internal {
+        oldFeeModel = activeFeeModel();
+        oldFeeSettlement = activeFeeSettlement();
+        _oldManagementFeeSchedule = activeManagementFeeSchedule();
+        oldHurdle = activeHurdle();
+    }
+
     /// @notice Validate that all assets in an intent are whitelisted
     /// @param assets Array of asset addresses to validate
     function _validateIntentAssets(address[] memory assets) internal view {
@@ -492,13 +1252,70 @@ abstract contract OrionVault is Initializable, ERC4626Upgradeable, ReentrancyGua
         }
     }
 
+    /// @notice Moves requests made before the upgrade to the request queues
+    /// @dev Requests move in the order the per-user maps fulfilled them, ahead of any later request:
+    ///      new requests are rejected until both maps are empty. Bounded by `maxRequests` per map
+    ///      so that long queues can be moved over several calls; anyone can call it.
+    /// @param maxRequests The maximum number of requests moved from each map
+    function migrateRequestQueues(uint256 maxRequests) external nonReentrant {
+        uint256 count = Math.min(_legacyDepositRequests.length(), maxRequests);
+        address[] memory users = new address[](count);
+        for (uint256 i = 0; i < count; ++i) {
+            (address user, uint256 assets) = _legacyDepositRequests.at(i);
+            _depositRequests.push(user, assets, 0);
+            _pendingDepositTotal += assets;
+            users[i] = user;
+        }
+        for (uint256 i = 0; i < count; ++i) {
+            // slither-disable-next-line unused-return
+            _legacyDepositRequests.remove(users[i]);
+        }
+
+        count = Math.min(_legacyRedeemRequests.length(), maxRequests);
+        users = new address[](count);
+        for (uint256 i = 0; i < count; ++i) {
+            (address user, uint256 shares) = _legacyRedeemRequests.at(i);
+            _redeemRequests.push(user, shares, 0);
+            users[i] = user;
+        }
+        for (uint256 i = 0; i < count; ++i) {
+            // slither-disable-next-line unused-return
+            _legacyRedeemRequests.remove(users[i]);
+        }
+    }
+
+    /// @notice Reverts while requests made before the upgrade are still to be moved to the request queues
+    function _checkRequestQueuesMigrated() internal view {
+        if (_legacyDepositRequests.length() != 0 || _legacyRedeemRequests.length() != 0) {
+            revert ErrorsLib.RequestQueuesNotMigrated();
+        }
+    }
+
+    /// @notice Credits the vault fees accrued before per-recipient claims to the manager
+    /// @dev The first claim after the beacon upgrade does the same, so those fees are claimable right away;
+    ///      calling this only makes them show in claimableVaultFees before then.
+    function migrateVaultFeeClaims() external onlyManager {
+        _migrateVaultFeeClaims();
+    }
+
+    /// @notice Credits the vault fees accrued before per-recipient claims to the manager, once
+    /// @dev Only credits what no recipient was credited, so it is a no-op on vaults deployed after the upgrade.
+    function _migrateVaultFeeClaims() internal reinitializer(2) {
+        uint256 unclaimable = pendingVaultFees - _claimableVaultFeesTotal;
+        claimableVaultFees[manager] += unclaimable;
+        _claimableVaultFeesTotal += unclaimable;
+    }
+
     /// @inheritdoc IOrionVault
-    function claimVaultFees(uint256 amount) external onlyManager {
+    function claimVaultFees(uint256 amount) external {
         if (amount == 0) revert ErrorsLib.AmountMustBeGreaterThanZero(asset());
-        if (amount > pendingVaultFees) revert ErrorsLib.InsufficientAmount();
+        if (_getInitializedVersion() < 2) _migrateVaultFeeClaims();
+        if (amount > claimableVaultFees[msg.sender]) revert ErrorsLib.InsufficientAmount();
 
+        claimableVaultFees[msg.sender] -= amount;
+        _claimableVaultFeesTotal -= amount;
         pendingVaultFees -= amount;
-        liquidityOrchestrator.transferVaultFees(amount);
+        liquidityOrchestrator.transferVaultFees(msg.sender, amount);
 
         emit VaultFeesClaimed(msg.sender, amount);
     }
@@ -507,103 +1324,225 @@ abstract contract OrionVault is Initializable, ERC4626Upgradeable, ReentrancyGua
 
     /// @inheritdoc IOrionVault
     function pendingDeposit(uint256 fulfillBatchSize) external view returns (uint256) {
-        uint256 length = _depositRequests.length();
-        if (length == 0) {
-            return 0;
-        }
-
-        uint256 batchSize = Math.min(length, fulfillBatchSize);
-        uint256 processableAmount = 0;
-
-        for (uint16 i = 0; i < batchSize; ++i) {
-            // slither-disable-next-line unused-return
-            (, uint256 amount) = _depositRequests.at(i);
-            processableAmount += amount;
-        }
-
-        return processableAmount;
+        // slither-disable-next-line unused-return
+        (, , uint256[] memory amounts, , ) = _depositRequests.nextBatch(
+            fulfillBatchSize,
+            maxDepositPerEpoch,
+            _expiryReferenceTime(),
+            _blockingAccessControl()
+        );
+        return _sum(amounts);
     }
 
     /// @inheritdoc IOrionVault
     function pendingRedeem(uint256 fulfillBatchSize) external view returns (uint256) {
-        uint256 length = _redeemRequests.length();
-        if (length == 0) {
-            return 0;
-        }
-
-        uint256 batchSize = Math.min(length, fulfillBatchSize);
-        uint256 processableShares = 0;
-
-        for (uint16 i = 0; i < batchSize; ++i) {
-            // slither-disable-next-line unused-return
-            (, uint256 shares) = _redeemRequests.at(i);
-            processableShares += shares;
-        }
-
-        return processableShares;
+        // slither-disable-next-line unused-return
+        (, , uint256[] memory shares, , ) = _redeemRequests.nextBatch(
+            fulfillBatchSize,
+            maxRedeemPerEpoch,
+            _expiryReferenceTime(),
+            _blockingAccessControl()
+        );
+        return _sum(shares);
     }
 
     /// @inheritdoc IOrionVault
     function pendingDepositCount() external view returns (uint256) {
-        return _depositRequests.length();
+        return _depositRequests.length;
     }
 
     /// @inheritdoc IOrionVault
     function pendingRedeemCount() external view returns (uint256) {
-        return _redeemRequests.length();
+        return _redeemRequests.length;
+    }
+
+    /// @inheritdoc IOrionVault
+    function pendingDepositOf(address user) external view returns (uint256) {
+        return _depositRequests.pendingOf(user);
+    }
+
+    /// @inheritdoc IOrionVault
+    function pendingRedeemOf(address user) external view returns (uint256) {
+        return _redeemRequests.pendingOf(user);
     }
 
     /// @inheritdoc IOrionVault
     function pendingRedeemBatch(uint256 fulfillBatchSize) external view returns (address[] memory, uint256[] memory) {
-        uint256 length = _redeemRequests.length();
-        if (length == 0) {
-            return (new address[](0), new uint256[](0));
-        }
-        uint256 batchSize = Math.min(length, fulfillBatchSize);
-        address[] memory users = new address[](batchSize);
-        uint256[] memory shares = new uint256[](batchSize);
-        for (uint256 i = 0; i < batchSize; ++i) {
-            (users[i], shares[i]) = _redeemRequests.at(i);
-        }
+        // slither-disable-next-line unused-return
+        (, address[] memory users, uint256[] memory shares, , ) = _redeemRequests.nextBatch(
+            fulfillBatchSize,
+            maxRedeemPerEpoch,
+            _expiryReferenceTime(),
+            _blockingAccessControl()
+        );
         return (users, shares);
     }
 
+    /// @notice Time against which request expiry is checked
+    /// @dev The epoch start while an epoch runs, so the commitment and the fulfillment skip the same requests.
+    /// @return The reference timestamp
+    function _expiryReferenceTime() internal view returns (uint256) {
+        return config.isSystemIdle() ? block.timestamp : liquidityOrchestrator.epochStartTime();
+    }
+
+    /// @notice Access control whose blocked controllers are skipped when fulfilling requests
+    /// @return The access control, or address(0) when there is none or it predates the blocking hook
+    function _blockingAccessControl() internal view returns (IOrionAccessControl) {
+        return IOrionAccessControl(_hasAccessControlHooks() ? depositAccessControl : address(0));
+    }
+
+    /// @notice Sums an array of amounts
+    /// @param amounts The amounts
+    /// @return total The sum
+    function _sum(uint256[] memory amounts) internal pure returns (uint256 total) {
+        for (uint256 i = 0; i < amounts.length; ++i) {
+            total += amounts[i];
+        }
+    }
+
     /// @inheritdoc IOrionVault
-    function accrueVaultFees(uint256 managementFee, uint256 performanceFee) external onlyLiquidityOrchestrator {
+    function accrueVaultFees(
+        uint256 managementFee,
+        uint256 performanceFee,
+        bool inShares
+    ) external onlyLiquidityOrchestrator {
         if (managementFee == 0 && performanceFee == 0) return;
 
-        uint256 totalFee = managementFee + performanceFee;
-        pendingVaultFees += totalFee;
+        FeeSplit[] memory splits = activeFeeSplits();
+        uint256 managementFeeLeft = managementFee;
+        uint256 performanceFeeLeft = performanceFee;
+        for (uint256 i = 0; i < splits.length; ++i) {
+            uint256 managementFeeShare = managementFee.mulDiv(splits[i].share, BASIS_POINTS_FACTOR);
+            uint256 performanceFeeShare = performanceFee.mulDiv(splits[i].share, BASIS_POINTS_FACTOR);
+            managementFeeLeft -= managementFeeShare;
+            performanceFeeLeft -= performanceFeeShare;
+            _payVaultFees(splits[i].recipient, managementFeeShare, performanceFeeShare, inShares);
+        }
+        // Rounding dust goes to the manager with the remainder.
+        _payVaultFees(manager, managementFeeLeft, performanceFeeLeft, inShares);
+
+        if (inShares) return;
+
+        pendingVaultFees += managementFee + performanceFee;
 
         emit VaultFeesAccrued(managementFee, performanceFee);
     }
 
+    /// @notice Pays a recipient's part of the accrued vault fees
+    /// @param recipient The fee recipient
+    /// @param managementFee The recipient's management fee, in underlying asset units or in shares to mint
+    /// @param performanceFee The recipient's performance fee, in underlying asset units or in shares to mint
+    /// @param inShares Whether the fees are shares to mint instead of underlying to credit
+    function _payVaultFees(address recipient, uint256 managementFee, uint256 performanceFee, bool inShares) private {
+        uint256 totalFee = managementFee + performanceFee;
+        if (totalFee == 0) return;
+
+        if (inShares) {
+            _mint(recipient, totalFee);
+            emit VaultFeeSharesMinted(recipient, managementFee, performanceFee);
+        } else {
+            claimableVaultFees[recipient] += totalFee;
+            _claimableVaultFeesTotal += totalFee;
+        }
+    }
+
+    /// @notice Advances the performance fee reference of both fee models after a state update
+    /// @dev Each model crystallizes on its own period, counted from the epoch start time, so the one snapshotted
+    ///      for the epoch moves exactly when the state orchestrator charged its performance fee. Both move to
+    ///      prevent double-charging during fee cooldown.
+    /// @param sharePrice The share price after the update
+    function _crystallizeFeeModels(uint256 sharePrice) internal {
+        uint256 epochStart = liquidityOrchestrator.epochStartTime();
+        _crystallize(feeModel, feeSettlement, hurdle, sharePrice, epochStart);
+        _crystallize(oldFeeModel, oldFeeSettlement, oldHurdle, sharePrice, epochStart);
+    }
+
+    /// @notice Crystallizes a fee model if its period has elapsed
+    /// @param model The fee model
+    /// @param settlement The fee settlement that goes with the fee model
+    /// @param modelHurdle The hurdle that goes with the fee model
+    /// @param sharePrice The share price after the update
+    /// @param epochStart The start time of the epoch being processed
+    function _crystallize(
+        FeeModel storage model,
+        FeeSettlement storage settlement,
+        Hurdle storage modelHurdle,
+        uint256 sharePrice,
+        uint256 epochStart
+    ) private {
+        uint256 period = settlement.crystallizationPeriod;
+        if (period != 0 && epochStart < settlement.lastCrystallization + period) return;
+
+        if (sharePrice > model.highWaterMark) {
+            model.highWaterMark = sharePrice;
+        }
+        settlement.crystallizationPrice = sharePrice;
+        settlement.lastCrystallization = uint64(epochStart);
+
+        if (modelHurdle.hurdleType == HurdleType.BENCHMARK) {
+            uint256 benchmarkPrice = _hurdleBenchmarkPrice(modelHurdle.benchmark);
+            if (benchmarkPrice != 0) {
+                modelHurdle.benchmarkPrice = benchmarkPrice;
+            }
+        }
+    }
+
+    /// @notice Epoch price of a hurdle benchmark asset
+    /// @dev Zero when the asset is no longer whitelisted, as it is then not priced for the epoch.
+    /// @param benchmark The benchmark asset
+    /// @return The benchmark price [priceAdapterDecimals]
+    function _hurdleBenchmarkPrice(address benchmark) private view returns (uint256) {
+        if (!config.isWhitelisted(benchmark)) return 0;
+
+        address[] memory assets = new address[](1);
+        assets[0] = benchmark;
+        return liquidityOrchestrator.getAssetPrices(assets)[0];
+    }
+
     /// @inheritdoc IOrionVault
     function fulfillDeposit(uint256 depositTotalAssets) external onlyLiquidityOrchestrator nonReentrant {
-        uint256 length = _depositRequests.length();
-        if (length == 0) {
-            return;
+        (
+            uint64[] memory ids,
+            address[] memory users,
+            uint256[] memory amounts,
+            uint64[] memory expiredIds,
+            uint64[] memory blockedIds
+        ) = _depositRequests.nextBatch(
+            config.maxFulfillBatchSize(),
+            maxDepositPerEpoch,
+            _expiryReferenceTime(),
+            _blockingAccessControl()
+        );
+
+        // Requests of blocked controllers are frozen, their assets stay in the liquidity orchestrator
+        for (uint256 i = 0; i < blockedIds.length; ++i) {
+            (address controller, uint256 amount) = _depositRequests.remove(blockedIds[i]);
+            _pendingDepositTotal -= amount;
+            frozenRequests[controller].assets += amount;
+
+            emit RequestsFrozen(controller, amount, 0);
         }
 
-        uint256 batchSize = Math.min(length, config.maxFulfillBatchSize());
+        // Expired requests are credited back to their controllers, who claim the refund themselves
+        for (uint256 i = 0; i < expiredIds.length; ++i) {
+            (address controller, uint256 amount) = _depositRequests.remove(expiredIds[i]);
+            _pendingDepositTotal -= amount;
+            liquidityOrchestrator.creditDepositRefund(controller, amount);
+
+            emit DepositRequestExpired(controller, amount);
+        }
 
         // Capture totalSupply snapshot to ensure consistent pricing for all users in this batch
         uint256 snapshotTotalSupply = totalSupply();
 
-        address[] memory users = new address[](batchSize);
-        uint256[] memory amounts = new uint256[](batchSize);
-        for (uint256 i = 0; i < batchSize; ++i) {
-            (users[i], amounts[i]) = _depositRequests.at(i);
-        }
-
-        // Process requests in batch
-        uint256 processedAmount = 0;
-        for (uint256 i = 0; i < batchSize; ++i) {
-            address user = users[i];
+        // Process requests in queue order; only the last one can be partially filled
+        uint256 mintedShares = 0;
+        for (uint256 i = 0; i < ids.length; ++i) {
+            address controller = users[i];
             uint256 amount = amounts[i];
 
-            // slither-disable-next-line unused-return
-            _depositRequests.remove(user);
+            _depositRequests.consume(ids[i], amount);
+            _pendingDepositTotal -= amount;
 
             uint256 shares = _convertToSharesWithPITTotalAssets(
                 amount,
@@ -611,40 +1550,63 @@ abstract contract OrionVault is Initializable, ERC4626Upgradeable, ReentrancyGua
                 snapshotTotalSupply,
                 Math.Rounding.Floor
             );
-            _mint(user, shares);
-            processedAmount += amount;
+            mintedShares += shares;
+
+            ClaimableRequest storage claimable = _claimableDeposits[controller];
+            claimable.assets += amount;
+            claimable.shares += shares;
 
-            emit Deposit(user, user, amount, shares);
+            emit DepositClaimable(controller, REQUEST_ID, amount, shares);
         }
+        // Shares stay in the vault until their controllers claim them
+        _mint(address(this), mintedShares);
     }
 
     /// @inheritdoc IOrionVault
     function fulfillRedeem(uint256 redeemTotalAssets) external onlyLiquidityOrchestrator nonReentrant {
-        uint256 length = _redeemRequests.length();
-        if (length == 0) {
-            return;
+        (
+            uint64[] memory ids,
+            address[] memory users,
+            uint256[] memory shares,
+            uint64[] memory expiredIds,
+            uint64[] memory blockedIds
+        ) = _redeemRequests.nextBatch(
+            config.maxFulfillBatchSize(),
+            maxRedeemPerEpoch,
+            _expiryReferenceTime(),
+            _blockingAccessControl()
+        );
+
+        // Requests of blocked controllers are frozen, their shares stay in escrow
+        for (uint256 i = 0; i < blockedIds.length; ++i) {
+            (address controller, uint256 blockedShares) = _redeemRequests.remove(blockedIds[i]);
+            frozenRequests[controller].shares += blockedShares;
+
+            emit RequestsFrozen(controller, 0, blockedShares);
         }
 
-        uint256 batchSize = Math.min(length, config.maxFulfillBatchSize());
+        // Expired requests are dropped and their shares returned to the controllers
+        for (uint256 i = 0; i < expiredIds.length; ++i) {
+            (address controller, uint256 expiredShares) = _redeemRequests.remove(expiredIds[i]);
+            // Bypasses the transfer check: a controller removed from the access control must not block the epoch
+            super._update(address(this), controller, expiredShares);
+
+            emit RedeemRequestExpired(controller, expiredShares);
+        }
+        if (ids.length == 0) {
+            return;
+        }
 
         // Capture totalSupply snapshot to ensure consistent pricing for all users in this batch
         uint256 snapshotTotalSupply = totalSupply();
 
-        // Collect all keys to process first to avoid swap-and-pop reordering issues
-        address[] memory users = new address[](batchSize);
-        uint256[] memory shares = new uint256[](batchSize);
-        for (uint256 i = 0; i < batchSize; ++i) {
-            (users[i], shares[i]) = _redeemRequests.at(i);
-        }
-
-        // Process requests in batch
+        // Process requests in queue order; only the last one can be partially filled
         uint256 processedShares = 0;
-        for (uint256 i = 0; i < batchSize; ++i) {
-            address user = users[i];
+        for (uint256 i = 0; i < ids.length; ++i) {
+            address controller = users[i];
             uint256 userShares = shares[i];
 
-            // slither-disable-next-line unused-return
-            _redeemRequests.remove(user);
+            _redeemRequests.consume(ids[i], userShares);
 
             uint256 underlyingAmount = _convertToAssetsWithPITTotalAssets(
                 userShares,
@@ -654,12 +1616,15 @@ abstract contract OrionVault is Initializable, ERC4626Upgradeable, ReentrancyGua
             );
             processedShares += userShares;
 
-            liquidityOrchestrator.transferRedemptionFunds(user, underlyingAmount);
-            emit Redeem(user, underlyingAmount, userShares);
+            // Credited to the controller in the liquidity orchestrator, paid out when claimed
+            _claimableRedeemShares[controller] += userShares;
+            liquidityOrchestrator.creditRedemptionFunds(controller, underlyingAmount);
+
+            emit RedeemClaimable(controller, REQUEST_ID, underlyingAmount, userShares);
         }
         _burn(address(this), processedShares);
     }
 
     /// @dev Storage gap to allow for future upgrades
-    uint256[50] private __gap;
+    uint256[9] private __gap;
 }
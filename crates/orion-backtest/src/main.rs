@@ -0,0 +1,68 @@
+//! `orion-backtest` replays a scenario through the protocol's epoch model and prints the series.
+//!
+//! ```text
+//! orion-backtest scenario.json
+//! orion-backtest scenario.json --fee-type 0,3,4 --k 1,2,3
+//! ```
+//!
+//! Prints one JSON run per combination of `--fee-type` and `--k`, or a single run of the
+//! scenario as written when neither is given.
+
+use std::{fs, path::PathBuf};
+
+use anyhow::Context;
+use clap::Parser;
+use orion_backtest::{simulate, Report, Scenario, Variant};
+use serde::Serialize;
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Backtest Orion vault strategies and fee models over a price and flow scenario")]
+struct Cli {
+    /// Path to the scenario JSON (`-` for stdin).
+    scenario: PathBuf,
+
+    /// Fee types (`IOrionVault.FeeType` as a number) to apply to every vault, one run each.
+    #[arg(long, value_delimiter = ',')]
+    fee_type: Vec<u8>,
+
+    /// `k` values to apply to every `kBestApy` strategist, one run each.
+    #[arg(long, value_delimiter = ',')]
+    k: Vec<u16>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Run {
+    variant: Variant,
+    report: Report,
+}
+
+fn options<T: Copy>(values: &[T]) -> Vec<Option<T>> {
+    if values.is_empty() {
+        vec![None]
+    } else {
+        values.iter().copied().map(Some).collect()
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let raw = if cli.scenario.as_os_str() == "-" {
+        std::io::read_to_string(std::io::stdin()).context("reading scenario from stdin")?
+    } else {
+        fs::read_to_string(&cli.scenario).with_context(|| format!("reading {}", cli.scenario.display()))?
+    };
+    let scenario = Scenario::from_json(&raw)?;
+
+    let mut runs = Vec::new();
+    for fee_type in options(&cli.fee_type) {
+        for k in options(&cli.k) {
+            let variant = Variant { fee_type, k };
+            let report = simulate(&scenario.with_variant(variant)).with_context(|| format!("running {variant:?}"))?;
+            runs.push(Run { variant, report });
+        }
+    }
+    println!("{}", serde_json::to_string_pretty(&runs)?);
+    Ok(())
+}
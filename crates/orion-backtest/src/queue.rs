@@ -0,0 +1,107 @@
+//! Pending deposit and redeem requests in `RequestQueueLib` order.
+
+use std::collections::VecDeque;
+
+use alloy_primitives::{Address, U256};
+
+/// Mirror of the vault's `RequestQueueLib.Queue` request queues.
+///
+/// Requests are fulfilled oldest first, at most `maxFulfillBatchSize` of them and at most
+/// `limit` in total per epoch. The request crossing the limit is partially filled and keeps its
+/// place at the head; top-ups join the tail as separate requests.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RequestQueue {
+    requests: VecDeque<(Address, U256)>,
+    limit: U256,
+}
+
+impl RequestQueue {
+    /// An empty queue fulfilling at most `limit` per epoch (`maxDepositPerEpoch` /
+    /// `maxRedeemPerEpoch`, zero meaning unlimited).
+    pub fn with_limit(limit: U256) -> Self {
+        Self { requests: VecDeque::new(), limit }
+    }
+
+    /// Appends a request for `amount` to the tail.
+    pub fn add(&mut self, user: Address, amount: U256) {
+        self.requests.push_back((user, amount));
+    }
+
+    /// Number of queued requests.
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    /// Whether the queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// The requests fulfilled next, with the amount filled for each (`nextBatch`).
+    pub fn batch(&self, batch_size: U256) -> Vec<(Address, U256)> {
+        let size = batch_size.saturating_to::<usize>();
+        let mut remaining = if self.limit.is_zero() { U256::MAX } else { self.limit };
+        let mut batch = Vec::new();
+        for &(user, amount) in self.requests.iter().take(size) {
+            if remaining.is_zero() {
+                break;
+            }
+            let filled = amount.min(remaining);
+            remaining -= filled;
+            batch.push((user, filled));
+        }
+        batch
+    }
+
+    /// `pendingDeposit(batchSize)` / `pendingRedeem(batchSize)`
+    pub fn pending(&self, batch_size: U256) -> U256 {
+        self.batch(batch_size).iter().map(|&(_, amount)| amount).sum()
+    }
+
+    /// Consumes [`Self::batch`] as `fulfillDeposit` / `fulfillRedeem` do.
+    pub fn fulfill(&mut self, batch_size: U256) -> Vec<(Address, U256)> {
+        let batch = self.batch(batch_size);
+        for &(_, filled) in &batch {
+            let head = self.requests.front_mut().expect("batch entries are queued");
+            head.1 -= filled;
+            if head.1.is_zero() {
+                self.requests.pop_front();
+            }
+        }
+        batch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(n: u8) -> Address {
+        Address::with_last_byte(n)
+    }
+
+    #[test]
+    fn repeated_requests_queue_behind_later_ones() {
+        let mut queue = RequestQueue::default();
+        queue.add(user(1), U256::from(10));
+        queue.add(user(2), U256::from(20));
+        queue.add(user(1), U256::from(5));
+        assert_eq!(
+            queue.batch(U256::from(10)),
+            vec![(user(1), U256::from(10)), (user(2), U256::from(20)), (user(1), U256::from(5))]
+        );
+        assert_eq!(queue.pending(U256::from(1)), U256::from(10));
+    }
+
+    #[test]
+    fn limit_partially_fills_the_head_across_epochs() {
+        let mut queue = RequestQueue::with_limit(U256::from(25));
+        queue.add(user(1), U256::from(60));
+        queue.add(user(2), U256::from(10));
+
+        assert_eq!(queue.fulfill(U256::MAX), vec![(user(1), U256::from(25))]);
+        assert_eq!(queue.fulfill(U256::MAX), vec![(user(1), U256::from(25))]);
+        assert_eq!(queue.fulfill(U256::MAX), vec![(user(1), U256::from(10)), (user(2), U256::from(10))]);
+        assert!(queue.is_empty());
+    }
+}
@@ -0,0 +1,89 @@
+//! Output series of a backtest.
+
+use alloy_primitives::{Address, I256, U256};
+use orion_commitment::{IntentSnapshot, PortfolioSnapshot};
+use serde::Serialize;
+
+use crate::scenario::FeeModelParams;
+
+/// Everything a run produced.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Report {
+    /// Protocol-level series, one point per epoch.
+    pub epochs: Vec<EpochPoint>,
+    /// Per-vault series, in scenario order.
+    pub vaults: Vec<VaultReport>,
+    /// Sum of `epochProtocolFees` over the run [assets].
+    pub total_protocol_fees: U256,
+    /// `bufferAmount` after the last epoch [assets].
+    pub final_buffer: U256,
+}
+
+/// Protocol state after one epoch.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochPoint {
+    /// `epochCounter`
+    pub epoch: u64,
+    /// Time the epoch started [s since the start of the run].
+    pub timestamp: u64,
+    /// `bufferIncrease` taken from the vaults.
+    pub buffer_increase: U256,
+    /// `epochProtocolFees`
+    pub protocol_fees: U256,
+    /// `nettedRebalanceVolumeUnderlying`
+    pub netted_volume: U256,
+    /// Slippage the buffer absorbed across both legs; negative when execution beat the estimates.
+    pub execution_slippage: I256,
+    /// `bufferAmount` at the end of the epoch.
+    pub buffer: U256,
+}
+
+/// Series for one vault.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultReport {
+    /// Vault address.
+    pub address: Address,
+    /// Fee model the vault ran with.
+    pub fee_model: FeeModelParams,
+    /// Sum of management fees [assets], or [shares] with `mintFeeShares`.
+    pub total_management_fee: U256,
+    /// Sum of performance fees [assets], or [shares] with `mintFeeShares`.
+    pub total_performance_fee: U256,
+    /// One point per epoch.
+    pub points: Vec<VaultPoint>,
+}
+
+/// Vault state after `updateVaultState`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultPoint {
+    /// `epochCounter`
+    pub epoch: u64,
+    /// `totalAssets()` [assets]
+    pub total_assets: U256,
+    /// `totalSupply()` [shares]
+    pub total_supply: U256,
+    /// `convertToAssets(10 ** decimals())` [assets]
+    pub share_price: U256,
+    /// High water mark after the update [assets].
+    pub high_water_mark: U256,
+    /// Management fee accrued this epoch [assets], or minted [shares] with `mintFeeShares`.
+    pub management_fee: U256,
+    /// Performance fee accrued this epoch [assets], or minted [shares] with `mintFeeShares`.
+    pub performance_fee: U256,
+    /// Underlying of the fulfilled deposit requests [assets].
+    pub deposited_assets: U256,
+    /// Shares minted for them.
+    pub minted_shares: U256,
+    /// Underlying paid out to fulfilled redeem requests [assets].
+    pub redeemed_assets: U256,
+    /// Shares burned for them.
+    pub burned_shares: U256,
+    /// Intent the strategist submitted before the epoch.
+    pub intent: IntentSnapshot,
+    /// Portfolio after rebalancing.
+    pub portfolio: PortfolioSnapshot,
+}
@@ -0,0 +1,289 @@
+//! JSON description of a backtest: protocol parameters, vaults and one entry per epoch.
+//!
+//! Field names follow the Solidity setters and getters they stand in for, like the snapshots in
+//! `orion-commitment`, so parameters can be copied from a deployment.
+
+use std::collections::BTreeMap;
+
+use alloy_primitives::{Address, U256};
+use orion_commitment::MANAGEMENT_FEE_TIERS;
+use serde::{Deserialize, Serialize};
+
+use crate::SimulationError;
+
+/// A complete backtest.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Scenario {
+    /// Protocol-wide parameters, constant over the run.
+    pub protocol: ProtocolParams,
+    /// Simulated vaults, in `getAllOrionVaults(Transparent)` order.
+    pub vaults: Vec<VaultParams>,
+    /// Market data and LP flows, one entry per epoch.
+    pub epochs: Vec<EpochScenario>,
+}
+
+/// Protocol parameters read from `OrionConfig` and the `LiquidityOrchestrator`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolParams {
+    /// `vFeeCoefficient` [bps]
+    pub v_fee_coefficient: u16,
+    /// `rsFeeCoefficient` [bps]
+    pub rs_fee_coefficient: u16,
+    /// `activeProtocolFeeTiers()`, by strictly ascending, non-zero volume threshold
+    #[serde(default)]
+    pub protocol_fee_tiers: Vec<ProtocolFeeTierParams>,
+    /// `maxFulfillBatchSize`
+    pub max_fulfill_batch_size: U256,
+    /// `targetBufferRatio` [bps]
+    pub target_buffer_ratio: U256,
+    /// `slippageTolerance` [bps]
+    pub slippage_tolerance: u16,
+    /// `priceAdapterDecimals`
+    pub price_adapter_decimals: u8,
+    /// `strategistIntentDecimals`
+    pub strategist_intent_decimals: u8,
+    /// `epochDuration` [s]
+    pub epoch_duration: u32,
+    /// `riskFreeRate` [bps]
+    pub risk_free_rate: u16,
+    /// Whitelisted assets in `getAllWhitelistedAssets()` order; the first one is the underlying.
+    pub assets: Vec<AssetParams>,
+    /// Liquidity deposited into the buffer with `depositLiquidity` before the first epoch [assets].
+    #[serde(default)]
+    pub initial_buffer: U256,
+}
+
+/// `IOrionConfig.ProtocolFeeTier`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolFeeTierParams {
+    /// Minimum vault deposit and redemption volume over its last epoch for the tier to apply [assets]
+    pub min_volume: U256,
+    /// Volume fee coefficient [bps]
+    pub v_fee_coefficient: u16,
+    /// Revenue share fee coefficient [bps]
+    pub rs_fee_coefficient: u16,
+}
+
+/// Enabled `IOrionConfig.ProtocolFeeOverride` of a vault.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolFeeOverrideParams {
+    /// Volume fee coefficient [bps]
+    pub v_fee_coefficient: u16,
+    /// Revenue share fee coefficient [bps]
+    pub rs_fee_coefficient: u16,
+}
+
+/// One whitelisted asset.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetParams {
+    /// Token address.
+    pub address: Address,
+    /// Token decimals.
+    pub decimals: u8,
+}
+
+/// One vault and the strategist driving its intent.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultParams {
+    /// Vault address; only used to label the output and route flows.
+    pub address: Address,
+    /// Fee model; the high water mark starts at one underlying unit, as in `OrionVault.initialize`.
+    pub fee_model: FeeModelParams,
+    /// How the intent is produced each epoch.
+    pub strategist: StrategistParams,
+    /// `maxDepositPerEpoch` [assets], zero meaning unlimited.
+    #[serde(default)]
+    pub max_deposit_per_epoch: U256,
+    /// `maxRedeemPerEpoch` [shares], zero meaning unlimited.
+    #[serde(default)]
+    pub max_redeem_per_epoch: U256,
+    /// `activeVaultProtocolFeeOverride(vault)` when enabled, taking precedence over the protocol fee tiers.
+    #[serde(default)]
+    pub protocol_fee_override: Option<ProtocolFeeOverrideParams>,
+}
+
+/// `IOrionVault.FeeModel` without the high water mark, crystallization and benchmark state, which the simulation
+/// tracks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeModelParams {
+    /// `uint8(feeType)`
+    pub fee_type: u8,
+    /// Performance fee [bps]
+    pub performance_fee: u16,
+    /// Management fee up to the first breakpoint [bps]
+    pub management_fee: u16,
+    /// Management fee breakpoints [assets], strictly ascending and zero-padded
+    #[serde(default)]
+    pub management_fee_breakpoints: [U256; MANAGEMENT_FEE_TIERS],
+    /// Management fee above each breakpoint [bps]
+    #[serde(default)]
+    pub management_fee_tier_rates: [u16; MANAGEMENT_FEE_TIERS],
+    /// Minimum annual management fee [assets]
+    #[serde(default)]
+    pub minimum_management_fee: U256,
+    /// Performance fee crystallization period [s], zero meaning every epoch
+    #[serde(default)]
+    pub crystallization_period: u32,
+    /// Whether fees are paid by minting vault shares instead of in underlying
+    #[serde(default)]
+    pub mint_fee_shares: bool,
+    /// `uint8(hurdleType)`
+    #[serde(default)]
+    pub hurdle_type: u8,
+    /// Annual hurdle rate [bps], used with the fixed-rate hurdle
+    #[serde(default)]
+    pub hurdle_rate: u16,
+    /// Benchmark asset, used with the benchmark hurdle
+    #[serde(default)]
+    pub hurdle_benchmark: Address,
+}
+
+/// Strategist implementations the simulator can replay.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum StrategistParams {
+    /// A passive strategist that submits the same intent every epoch.
+    #[serde(rename_all = "camelCase")]
+    Fixed {
+        /// Intent tokens.
+        tokens: Vec<Address>,
+        /// Weights with `strategistIntentDecimals` decimals, parallel to `tokens`.
+        weights: Vec<u32>,
+    },
+    /// `KBestApyStrategist`, calling `submitIntent` right before every epoch starts.
+    #[serde(rename_all = "camelCase")]
+    KBestApy {
+        /// `k`
+        k: u16,
+        /// `WEIGHTING_MODE`
+        weighting: WeightingMode,
+    },
+}
+
+/// Mirror of `KBestApyStrategist.WeightingMode`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WeightingMode {
+    /// Equal split among the top K.
+    EqualWeighted,
+    /// Weights proportional to APY, equal when every APY is zero.
+    ApyWeighted,
+}
+
+/// Market data for one epoch and the LP requests placed while the system was idle before it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochScenario {
+    /// `getPrice` per asset, parallel to `protocol.assets`; the underlying is `10 ** priceAdapterDecimals`.
+    pub prices: Vec<U256>,
+    /// Execution slippage per asset against the adapter estimate [bps]; positive is worse than
+    /// estimated (less underlying on sells, more on buys). Missing assets execute at the estimate.
+    #[serde(default)]
+    pub slippage: BTreeMap<Address, i32>,
+    /// Requests submitted before the epoch, in order.
+    #[serde(default)]
+    pub flows: Vec<Flow>,
+}
+
+/// An LP request.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Flow {
+    /// `requestDeposit(assets)`
+    #[serde(rename_all = "camelCase")]
+    Deposit {
+        /// Vault the request is sent to.
+        vault: Address,
+        /// Requesting LP.
+        user: Address,
+        /// Underlying deposited [assets].
+        assets: U256,
+    },
+    /// `requestRedeem(shares)`
+    #[serde(rename_all = "camelCase")]
+    Redeem {
+        /// Vault the request is sent to.
+        vault: Address,
+        /// Requesting LP.
+        user: Address,
+        /// Shares to redeem; the LP's whole balance when omitted.
+        #[serde(default)]
+        shares: Option<U256>,
+    },
+}
+
+/// Parameters to vary between runs of the same scenario.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Variant {
+    /// Fee type applied to every vault.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_type: Option<u8>,
+    /// `k` applied to every `KBestApy` strategist.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub k: Option<u16>,
+}
+
+impl Scenario {
+    /// Parses a scenario from its JSON representation.
+    pub fn from_json(json: &str) -> Result<Self, SimulationError> {
+        let scenario: Self = serde_json::from_str(json)?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    /// Copy of this scenario with `variant` applied.
+    pub fn with_variant(&self, variant: Variant) -> Self {
+        let mut scenario = self.clone();
+        for vault in &mut scenario.vaults {
+            if let Some(fee_type) = variant.fee_type {
+                vault.fee_model.fee_type = fee_type;
+            }
+            if let (Some(new_k), StrategistParams::KBestApy { k, .. }) = (variant.k, &mut vault.strategist) {
+                *k = new_k;
+            }
+        }
+        scenario
+    }
+
+    /// Checks the invariants the contracts enforce at configuration time.
+    pub fn validate(&self) -> Result<(), SimulationError> {
+        let invalid = |reason: String| Err(SimulationError::InvalidScenario(reason));
+        let assets = self.protocol.assets.len();
+        if assets == 0 {
+            return invalid("protocol.assets is empty".into());
+        }
+        let thresholds = self.protocol.protocol_fee_tiers.iter().map(|tier| tier.min_volume);
+        if std::iter::once(U256::ZERO).chain(thresholds.clone()).zip(thresholds).any(|(a, b)| a >= b) {
+            return invalid("protocol.protocolFeeTiers must be strictly ascending from above zero".into());
+        }
+        for (i, vault) in self.vaults.iter().enumerate() {
+            if self.vaults[..i].iter().any(|other| other.address == vault.address) {
+                return invalid(format!("vault {} is listed twice", vault.address));
+            }
+            match &vault.strategist {
+                StrategistParams::Fixed { tokens, weights } if tokens.len() != weights.len() => {
+                    return invalid(format!("vaults[{i}].strategist.weights must be parallel to tokens"));
+                }
+                StrategistParams::KBestApy { k: 0, .. } => return invalid(format!("vaults[{i}].strategist.k is zero")),
+                _ => {}
+            }
+        }
+        for (i, epoch) in self.epochs.iter().enumerate() {
+            if epoch.prices.len() != assets {
+                return invalid(format!("epochs[{i}].prices has {} entries, expected {assets}", epoch.prices.len()));
+            }
+            if epoch.slippage.values().any(|&bps| bps.unsigned_abs() > 10_000) {
+                return invalid(format!("epochs[{i}].slippage exceeds 10000 bps"));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,625 @@
+//! The epoch loop: requests, intents, the state transition, leg execution and vault settlement.
+
+use std::collections::BTreeMap;
+
+use alloy_primitives::{Address, I256, U256};
+use orion_commitment::{
+    hash, EpochSnapshot, FeeModelSnapshot, IntentSnapshot, PortfolioSnapshot, ProtocolSnapshot, VaultSnapshot,
+};
+use orion_state_orchestrator::fees::{crystallizes, HurdleType, ShareMath, BASIS_POINTS_FACTOR};
+use orion_state_orchestrator::math::{mul_div, pow10, Rounding};
+use orion_state_orchestrator::{execute, EpochInputs, RedeemBatch, VaultState};
+
+use crate::queue::RequestQueue;
+use crate::report::{EpochPoint, Report, VaultPoint, VaultReport};
+use crate::scenario::{EpochScenario, Flow, ProtocolParams, Scenario, VaultParams};
+use crate::strategist::Strategist;
+use crate::SimulationError;
+
+/// Off-chain state of one vault.
+#[derive(Clone, Debug)]
+struct SimulatedVault {
+    params: VaultParams,
+    strategist: Strategist,
+    intent: IntentSnapshot,
+    portfolio: PortfolioSnapshot,
+    total_assets: U256,
+    total_supply: U256,
+    epoch_volume: U256,
+    high_water_mark: U256,
+    last_crystallization: u64,
+    crystallization_price: U256,
+    hurdle_benchmark_price: U256,
+    balances: BTreeMap<Address, U256>,
+    deposits: RequestQueue,
+    redeems: RequestQueue,
+    report: VaultReport,
+}
+
+/// Replays a scenario one epoch at a time.
+#[derive(Clone, Debug)]
+pub struct Simulator {
+    protocol: ProtocolParams,
+    share_math: ShareMath,
+    vaults: Vec<SimulatedVault>,
+    epoch: u64,
+    buffer: U256,
+    underlying_balance: U256,
+    report: Report,
+}
+
+impl Simulator {
+    /// Deploys the scenario's vaults and seeds the buffer.
+    pub fn new(scenario: &Scenario) -> Self {
+        let protocol = scenario.protocol.clone();
+        let underlying_decimals = protocol.assets[0].decimals;
+        let vaults = scenario
+            .vaults
+            .iter()
+            .map(|params| SimulatedVault {
+                params: params.clone(),
+                strategist: Strategist::from(&params.strategist),
+                intent: IntentSnapshot::default(),
+                portfolio: PortfolioSnapshot::default(),
+                total_assets: U256::ZERO,
+                total_supply: U256::ZERO,
+                epoch_volume: U256::ZERO,
+                high_water_mark: pow10(underlying_decimals),
+                last_crystallization: 0,
+                crystallization_price: pow10(underlying_decimals),
+                hurdle_benchmark_price: U256::ZERO,
+                balances: BTreeMap::new(),
+                deposits: RequestQueue::with_limit(params.max_deposit_per_epoch),
+                redeems: RequestQueue::with_limit(params.max_redeem_per_epoch),
+                report: VaultReport {
+                    address: params.address,
+                    fee_model: params.fee_model,
+                    total_management_fee: U256::ZERO,
+                    total_performance_fee: U256::ZERO,
+                    points: Vec::new(),
+                },
+            })
+            .collect();
+        Self {
+            share_math: ShareMath::new(underlying_decimals),
+            buffer: protocol.initial_buffer,
+            underlying_balance: protocol.initial_buffer,
+            protocol,
+            vaults,
+            epoch: 0,
+            report: Report::default(),
+        }
+    }
+
+    /// Shares `user` holds in `vault`, excluding shares queued for redemption.
+    pub fn balance_of(&self, vault: Address, user: Address) -> U256 {
+        self.vaults
+            .iter()
+            .find(|v| v.params.address == vault)
+            .and_then(|v| v.balances.get(&user).copied())
+            .unwrap_or_default()
+    }
+
+    /// Runs one full epoch, from the Idle-phase requests to `EpochEnd`.
+    pub fn step(&mut self, epoch: &EpochScenario) -> Result<(), SimulationError> {
+        let index = self.epoch;
+        self.run_epoch(epoch).map_err(|err| SimulationError::Epoch { epoch: index, source: Box::new(err) })?;
+        self.epoch += 1;
+        Ok(())
+    }
+
+    /// The accumulated series.
+    pub fn finish(mut self) -> Report {
+        self.report.final_buffer = self.buffer;
+        self.report.vaults = self.vaults.into_iter().map(|vault| vault.report).collect();
+        self.report
+    }
+
+    fn run_epoch(&mut self, epoch: &EpochScenario) -> Result<(), SimulationError> {
+        // Idle: requests and intents land before `_handleStart` snapshots anything.
+        let timestamp = (self.epoch + 1) * u64::from(self.protocol.epoch_duration);
+        for flow in &epoch.flows {
+            self.request(flow)?;
+        }
+        let assets: Vec<Address> = self.protocol.assets.iter().map(|asset| asset.address).collect();
+        for vault in &mut self.vaults {
+            vault.intent = vault.strategist.submit_intent(
+                &assets,
+                &epoch.prices,
+                timestamp,
+                self.protocol.strategist_intent_decimals,
+            )?;
+        }
+
+        let states = execute(&self.inputs(&epoch.prices, timestamp))?.states;
+        let mut point = EpochPoint {
+            epoch: self.epoch,
+            timestamp,
+            buffer_increase: states.bufferIncrease,
+            protocol_fees: states.epochProtocolFees,
+            netted_volume: states.nettedRebalanceVolumeUnderlying,
+            ..Default::default()
+        };
+
+        // SellingLeg, then the buffer top-up and protocol fees, then BuyingLeg.
+        let sell = &states.sellLeg;
+        for ((&asset, &amount), &estimate) in
+            sell.sellingTokens.iter().zip(&sell.sellingAmounts).zip(&sell.sellingEstimatedUnderlyingAmounts)
+        {
+            if !amount.is_zero() {
+                let slippage = epoch.slippage.get(&asset).copied().unwrap_or_default();
+                point.execution_slippage += self.execute_sell(asset, estimate, slippage)?;
+            }
+        }
+        self.buffer += states.bufferIncrease;
+        let buy = &states.buyLeg;
+        for ((&asset, &amount), &estimate) in
+            buy.buyingTokens.iter().zip(&buy.buyingAmounts).zip(&buy.buyingEstimatedUnderlyingAmounts)
+        {
+            if !amount.is_zero() {
+                let slippage = epoch.slippage.get(&asset).copied().unwrap_or_default();
+                point.execution_slippage += self.execute_buy(asset, estimate, slippage)?;
+            }
+        }
+
+        // ProcessVaultOperations
+        let batch_size = self.protocol.max_fulfill_batch_size;
+        for (vault, state) in self.vaults.iter_mut().zip(&states.vaults) {
+            let benchmark = vault.params.fee_model.hurdle_benchmark;
+            let benchmark_price =
+                assets.iter().position(|&asset| asset == benchmark).map(|i| epoch.prices[i]).unwrap_or_default();
+            let paid = vault.settle(&self.share_math, state, batch_size, self.epoch, timestamp, benchmark_price)?;
+            self.underlying_balance = self.underlying_balance.saturating_sub(paid);
+        }
+
+        point.buffer = self.buffer;
+        self.report.total_protocol_fees += states.epochProtocolFees;
+        self.report.epochs.push(point);
+        Ok(())
+    }
+
+    fn request(&mut self, flow: &Flow) -> Result<(), SimulationError> {
+        let (Flow::Deposit { vault: address, user, .. } | Flow::Redeem { vault: address, user, .. }) = *flow;
+        let vault = self
+            .vaults
+            .iter_mut()
+            .find(|vault| vault.params.address == address)
+            .ok_or(SimulationError::UnknownVault(address))?;
+        let reject = |reason| SimulationError::InvalidRequest { vault: address, user, reason };
+
+        match *flow {
+            Flow::Deposit { assets, .. } => {
+                if assets.is_zero() {
+                    return Err(reject("zero assets"));
+                }
+                vault.deposits.add(user, assets);
+                self.underlying_balance += assets;
+            }
+            Flow::Redeem { shares, .. } => {
+                let balance = vault.balances.entry(user).or_default();
+                let shares = shares.unwrap_or(*balance);
+                if shares.is_zero() {
+                    return Err(reject("zero shares"));
+                }
+                if shares > *balance {
+                    return Err(reject("shares exceed balance"));
+                }
+                // requestRedeem escrows the shares in the vault until they are burned.
+                *balance -= shares;
+                vault.redeems.add(user, shares);
+            }
+        }
+        Ok(())
+    }
+
+    /// The guest inputs `_handleStart` and the StateCommitment phase would snapshot.
+    fn inputs(&self, prices: &[U256], epoch_start_time: u64) -> EpochInputs {
+        let protocol = &self.protocol;
+        let batch_size = protocol.max_fulfill_batch_size;
+        let redeem_batches: Vec<RedeemBatch> = self
+            .vaults
+            .iter()
+            .map(|vault| {
+                let (users, shares) = vault.redeems.batch(batch_size).into_iter().unzip();
+                RedeemBatch { users, shares }
+            })
+            .collect();
+        let snapshot = EpochSnapshot {
+            protocol: ProtocolSnapshot {
+                max_fulfill_batch_size: batch_size,
+                target_buffer_ratio: protocol.target_buffer_ratio,
+                price_adapter_decimals: protocol.price_adapter_decimals,
+                strategist_intent_decimals: protocol.strategist_intent_decimals,
+                epoch_duration: protocol.epoch_duration,
+                whitelisted_assets: protocol.assets.iter().map(|asset| asset.address).collect(),
+                token_decimals: protocol.assets.iter().map(|asset| asset.decimals).collect(),
+                risk_free_rate: protocol.risk_free_rate,
+                decommissioning_assets: Vec::new(),
+                failed_epoch_tokens: Vec::new(),
+                initial_epoch_buffer_amount: self.buffer,
+                buying_leg_entry_buffer: U256::ZERO,
+                buffer_amount: self.buffer,
+                underlying_balance: self.underlying_balance,
+                epoch_start_time: U256::from(epoch_start_time),
+            },
+            asset_prices: prices.to_vec(),
+            vaults: self
+                .vaults
+                .iter()
+                .zip(&redeem_batches)
+                .map(|(vault, batch)| {
+                    let (v_fee_coefficient, rs_fee_coefficient) = vault.protocol_fees(protocol);
+                    VaultSnapshot {
+                        address: vault.params.address,
+                        fee_model: vault.fee_model(),
+                        pending_redeem: vault.redeems.pending(batch_size),
+                        pending_deposit: vault.deposits.pending(batch_size),
+                        redeem_batch_hash: hash::redeem_batch_hash(&batch.users, &batch.shares),
+                        total_supply: vault.total_supply,
+                        total_assets: vault.total_assets,
+                        v_fee_coefficient,
+                        rs_fee_coefficient,
+                        portfolio: vault.portfolio.clone(),
+                        intent: vault.intent.clone(),
+                    }
+                })
+                .collect(),
+        };
+        EpochInputs { snapshot, redeem_batches }
+    }
+
+    /// `_executeSell`: returns the slippage absorbed by the buffer.
+    fn execute_sell(&mut self, asset: Address, estimate: U256, slippage_bps: i32) -> Result<I256, SimulationError> {
+        let bps = U256::from(BASIS_POINTS_FACTOR);
+        let executed = mul_div(estimate, scaled_bps(-slippage_bps), bps, Rounding::Floor)?;
+        let limit = mul_div(estimate, bps - U256::from(self.protocol.slippage_tolerance), bps, Rounding::Floor)?;
+        if executed < limit {
+            return Err(SimulationError::SlippageExceeded { side: "sell", asset, executed, limit });
+        }
+        self.underlying_balance += executed;
+        self.absorb(asset, executed, estimate)
+    }
+
+    /// `_executeBuy`: the adapter's approval is capped at the tolerance, so a costlier buy reverts.
+    fn execute_buy(&mut self, asset: Address, estimate: U256, slippage_bps: i32) -> Result<I256, SimulationError> {
+        let bps = U256::from(BASIS_POINTS_FACTOR);
+        let executed = mul_div(estimate, scaled_bps(slippage_bps), bps, Rounding::Ceil)?;
+        let limit = mul_div(estimate, bps + U256::from(self.protocol.slippage_tolerance), bps, Rounding::Floor)?;
+        if executed > limit {
+            return Err(SimulationError::SlippageExceeded { side: "buy", asset, executed, limit });
+        }
+        self.underlying_balance = self.underlying_balance.saturating_sub(executed);
+        self.absorb(asset, estimate, executed)
+    }
+
+    /// `_updateBufferAmount(credit - debit)`; reverts on underflow like the checked subtraction.
+    fn absorb(&mut self, asset: Address, credit: U256, debit: U256) -> Result<I256, SimulationError> {
+        if credit >= debit {
+            self.buffer += credit - debit;
+        } else {
+            let shortfall = debit - credit;
+            self.buffer = self
+                .buffer
+                .checked_sub(shortfall)
+                .ok_or(SimulationError::BufferExhausted { asset, shortfall: shortfall - self.buffer })?;
+        }
+        Ok(I256::from_raw(debit) - I256::from_raw(credit))
+    }
+}
+
+/// `BASIS_POINTS_FACTOR + bps` for a signed `bps` already bounded by `Scenario::validate`.
+fn scaled_bps(bps: i32) -> U256 {
+    U256::from(BASIS_POINTS_FACTOR.saturating_add_signed(i64::from(bps)))
+}
+
+impl SimulatedVault {
+    /// `activeFeeModel()`, as snapshotted by `_handleStart`.
+    fn fee_model(&self) -> FeeModelSnapshot {
+        let params = self.params.fee_model;
+        FeeModelSnapshot {
+            fee_type: params.fee_type,
+            performance_fee: params.performance_fee,
+            management_fee: params.management_fee,
+            management_fee_breakpoints: params.management_fee_breakpoints,
+            management_fee_tier_rates: params.management_fee_tier_rates,
+            minimum_management_fee: params.minimum_management_fee,
+            crystallization_period: params.crystallization_period,
+            mint_fee_shares: params.mint_fee_shares,
+            last_crystallization: self.last_crystallization,
+            high_water_mark: self.high_water_mark,
+            crystallization_price: self.crystallization_price,
+            hurdle_type: params.hurdle_type,
+            hurdle_rate: params.hurdle_rate,
+            hurdle_benchmark: params.hurdle_benchmark,
+            hurdle_benchmark_price: self.hurdle_benchmark_price,
+        }
+    }
+
+    /// `activeVaultProtocolFees(vault, vaultEpochVolume(vault))`: the override, else the highest tier reached, else
+    /// the protocol fees.
+    fn protocol_fees(&self, protocol: &ProtocolParams) -> (u16, u16) {
+        if let Some(fees) = self.params.protocol_fee_override {
+            return (fees.v_fee_coefficient, fees.rs_fee_coefficient);
+        }
+        protocol
+            .protocol_fee_tiers
+            .iter()
+            .rev()
+            .find(|tier| self.epoch_volume >= tier.min_volume)
+            .map_or((protocol.v_fee_coefficient, protocol.rs_fee_coefficient), |tier| {
+                (tier.v_fee_coefficient, tier.rs_fee_coefficient)
+            })
+    }
+
+    /// `_processSingleVaultOperations`; returns the underlying paid to redeemers.
+    fn settle(
+        &mut self,
+        share_math: &ShareMath,
+        state: &VaultState,
+        batch_size: U256,
+        epoch: u64,
+        epoch_start_time: u64,
+        benchmark_price: U256,
+    ) -> Result<U256, SimulationError> {
+        let mut point = VaultPoint { epoch, intent: self.intent.clone(), ..Default::default() };
+
+        // accrueVaultFees mints fee shares before any request is fulfilled.
+        if self.params.fee_model.mint_fee_shares {
+            self.total_supply += state.managementFee + state.performanceFee;
+        }
+
+        if state.processRedeem && !self.redeems.pending(batch_size).is_zero() {
+            // fulfillRedeem prices the whole batch against the pre-burn supply.
+            let supply = self.total_supply;
+            for (_, shares) in self.redeems.fulfill(batch_size) {
+                point.redeemed_assets +=
+                    share_math.to_assets(shares, state.totalAssetsForRedeem, supply, Rounding::Floor)?;
+                point.burned_shares += shares;
+            }
+            self.total_supply -= point.burned_shares;
+        }
+
+        if !self.deposits.pending(batch_size).is_zero() {
+            let supply = self.total_supply;
+            for (user, assets) in self.deposits.fulfill(batch_size) {
+                let shares = share_math.to_shares(assets, state.totalAssetsForDeposit, supply, Rounding::Floor)?;
+                *self.balances.entry(user).or_default() += shares;
+                point.deposited_assets += assets;
+                point.minted_shares += shares;
+            }
+            self.total_supply += point.minted_shares;
+        }
+
+        // vaultEpochVolume: redemptions credited plus deposits fulfilled.
+        self.epoch_volume = point.redeemed_assets + point.deposited_assets;
+
+        point.management_fee = state.managementFee;
+        point.performance_fee = state.performanceFee;
+        self.report.total_management_fee += state.managementFee;
+        self.report.total_performance_fee += state.performanceFee;
+
+        // updateVaultState
+        self.portfolio = PortfolioSnapshot { tokens: state.tokens.clone(), shares: state.shares.clone() };
+        self.total_assets = state.finalTotalAssets;
+        let share_price = share_math.share_price(self.total_assets, self.total_supply)?;
+        if crystallizes(&self.fee_model(), epoch_start_time) {
+            self.high_water_mark = self.high_water_mark.max(share_price);
+            self.crystallization_price = share_price;
+            self.last_crystallization = epoch_start_time;
+            let benchmark_hurdle =
+                matches!(HurdleType::try_from(self.params.fee_model.hurdle_type), Ok(HurdleType::Benchmark));
+            if benchmark_hurdle && !benchmark_price.is_zero() {
+                self.hurdle_benchmark_price = benchmark_price;
+            }
+        }
+
+        point.total_assets = self.total_assets;
+        point.total_supply = self.total_supply;
+        point.share_price = share_price;
+        point.high_water_mark = self.high_water_mark;
+        point.portfolio = self.portfolio.clone();
+        let paid = point.redeemed_assets;
+        self.report.points.push(point);
+        Ok(paid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::{
+        AssetParams, FeeModelParams, ProtocolFeeOverrideParams, ProtocolFeeTierParams, StrategistParams,
+    };
+    use crate::simulate;
+    use orion_commitment::MANAGEMENT_FEE_TIERS;
+
+    const USDC: Address = Address::with_last_byte(0xa1);
+    const TOKEN: Address = Address::with_last_byte(0xa2);
+    const VAULT: Address = Address::with_last_byte(0xf1);
+    const ALICE: Address = Address::with_last_byte(0xe1);
+    const BOB: Address = Address::with_last_byte(0xe2);
+
+    /// One 50/50 vault with a 20% performance fee and no other fees; `token_prices` in cents.
+    fn scenario(fee_type: u8, token_prices: &[u64]) -> Scenario {
+        Scenario {
+            protocol: ProtocolParams {
+                v_fee_coefficient: 0,
+                rs_fee_coefficient: 0,
+                protocol_fee_tiers: Vec::new(),
+                max_fulfill_batch_size: U256::from(150),
+                target_buffer_ratio: U256::ZERO,
+                slippage_tolerance: 100,
+                price_adapter_decimals: 14,
+                strategist_intent_decimals: 9,
+                epoch_duration: 86_400,
+                risk_free_rate: 0,
+                assets: vec![AssetParams { address: USDC, decimals: 6 }, AssetParams { address: TOKEN, decimals: 18 }],
+                initial_buffer: U256::from(1_000_000),
+            },
+            vaults: vec![VaultParams {
+                address: VAULT,
+                fee_model: FeeModelParams {
+                    fee_type,
+                    performance_fee: 2_000,
+                    management_fee: 0,
+                    management_fee_breakpoints: [U256::ZERO; MANAGEMENT_FEE_TIERS],
+                    management_fee_tier_rates: [0; MANAGEMENT_FEE_TIERS],
+                    minimum_management_fee: U256::ZERO,
+                    crystallization_period: 0,
+                    mint_fee_shares: false,
+                    hurdle_type: 0,
+                    hurdle_rate: 0,
+                    hurdle_benchmark: Address::ZERO,
+                },
+                strategist: StrategistParams::Fixed { tokens: vec![USDC, TOKEN], weights: vec![500_000_000; 2] },
+                max_deposit_per_epoch: U256::ZERO,
+                max_redeem_per_epoch: U256::ZERO,
+                protocol_fee_override: None,
+            }],
+            epochs: token_prices
+                .iter()
+                .map(|&cents| EpochScenario {
+                    prices: vec![pow10(14), U256::from(cents) * pow10(12)],
+                    ..Default::default()
+                })
+                .collect(),
+        }
+    }
+
+    fn deposit(user: Address, assets: u64) -> Flow {
+        Flow::Deposit { vault: VAULT, user, assets: U256::from(assets) }
+    }
+
+    #[test]
+    fn deposits_mint_at_par_and_gains_move_the_high_water_mark() {
+        let mut scenario = scenario(3, &[100, 110]);
+        scenario.epochs[0].flows.push(deposit(ALICE, 100_000_000));
+        let report = simulate(&scenario).unwrap();
+        let points = &report.vaults[0].points;
+
+        assert_eq!(points[0].minted_shares, U256::from(100_000_000) * pow10(12));
+        assert_eq!(points[0].share_price, U256::from(1_000_000));
+        assert_eq!(points[0].portfolio.tokens, vec![USDC, TOKEN]);
+
+        // 50 USDC + 50 USDC of token up 10%: 5 USDC gain, 20% of it charged (less virtual-share rounding).
+        let fee = points[1].performance_fee;
+        assert!(fee > U256::from(999_900) && fee <= U256::from(1_000_000));
+        assert_eq!(points[1].total_assets + fee, U256::from(105_000_000));
+        assert_eq!(points[1].high_water_mark, points[1].share_price);
+        assert!(points[1].share_price > U256::from(1_039_000));
+    }
+
+    #[test]
+    fn high_water_mark_charges_less_than_absolute_on_a_recovery() {
+        let prices = [100, 110, 90, 110];
+        let run = |fee_type| {
+            let mut scenario = scenario(fee_type, &prices);
+            scenario.epochs[0].flows.push(deposit(ALICE, 100_000_000));
+            simulate(&scenario).unwrap().vaults.remove(0)
+        };
+        let (absolute, high_water_mark) = (run(0), run(3));
+        assert_eq!(absolute.points[1].performance_fee, high_water_mark.points[1].performance_fee);
+        assert_eq!(high_water_mark.points[2].performance_fee, U256::ZERO);
+        assert!(high_water_mark.points[3].performance_fee < absolute.points[3].performance_fee);
+        assert!(high_water_mark.total_performance_fee < absolute.total_performance_fee);
+    }
+
+    #[test]
+    fn redemptions_pay_out_at_point_in_time_assets() {
+        let mut scenario = scenario(3, &[100, 100]);
+        scenario.epochs[0].flows.extend([deposit(ALICE, 50_000_000), deposit(BOB, 50_000_000)]);
+        scenario.epochs[1].flows.push(Flow::Redeem { vault: VAULT, user: ALICE, shares: None });
+
+        let mut simulator = Simulator::new(&scenario);
+        simulator.step(&scenario.epochs[0]).unwrap();
+        let alice = simulator.balance_of(VAULT, ALICE);
+        assert_eq!(alice, U256::from(50_000_000) * pow10(12));
+        simulator.step(&scenario.epochs[1]).unwrap();
+        assert_eq!(simulator.balance_of(VAULT, ALICE), U256::ZERO);
+
+        let point = &simulator.finish().vaults[0].points[1];
+        assert_eq!(point.burned_shares, alice);
+        assert_eq!(point.redeemed_assets, U256::from(50_000_000));
+        assert_eq!(point.total_supply, alice);
+        assert_eq!(point.total_assets, U256::from(50_000_000));
+    }
+
+    #[test]
+    fn buffer_absorbs_slippage_without_touching_vault_accounting() {
+        let mut clean = scenario(3, &[100, 120]);
+        clean.epochs[0].flows.push(deposit(ALICE, 100_000_000));
+        let mut slipped = clean.clone();
+        slipped.epochs[0].slippage.insert(TOKEN, 50);
+
+        let (clean, slipped) = (simulate(&clean).unwrap(), simulate(&slipped).unwrap());
+        assert_eq!(clean.vaults, slipped.vaults);
+        // Buying 50 USDC of token at 0.5% worse than estimated.
+        assert_eq!(slipped.epochs[0].execution_slippage, I256::try_from(250_000).unwrap());
+        assert_eq!(clean.final_buffer - slipped.final_buffer, U256::from(250_000));
+    }
+
+    #[test]
+    fn slippage_beyond_tolerance_fails_the_epoch() {
+        let mut scenario = scenario(3, &[100]);
+        scenario.epochs[0].flows.push(deposit(ALICE, 100_000_000));
+        scenario.epochs[0].slippage.insert(TOKEN, 150);
+        let err = simulate(&scenario).unwrap_err();
+        let SimulationError::Epoch { epoch: 0, source } = err else { panic!("{err}") };
+        assert!(matches!(*source, SimulationError::SlippageExceeded { side: "buy", asset: TOKEN, .. }));
+    }
+
+    #[test]
+    fn rejects_redeeming_more_than_the_balance() {
+        let mut scenario = scenario(3, &[100, 100]);
+        scenario.epochs[0].flows.push(deposit(ALICE, 1_000_000));
+        scenario.epochs[1].flows.push(Flow::Redeem { vault: VAULT, user: BOB, shares: Some(U256::from(1)) });
+        let err = simulate(&scenario).unwrap_err();
+        assert_eq!(err.to_string(), format!("epoch 1: request from {BOB} to {VAULT} reverts: shares exceed balance"));
+    }
+
+    #[test]
+    fn vault_override_takes_precedence_over_the_protocol_fee_tiers() {
+        let mut scenario = scenario(3, &[100]);
+        scenario.protocol.v_fee_coefficient = 10;
+        scenario.protocol.rs_fee_coefficient = 1_000;
+        scenario.protocol.protocol_fee_tiers = vec![
+            ProtocolFeeTierParams { min_volume: U256::from(1_000), v_fee_coefficient: 8, rs_fee_coefficient: 800 },
+            ProtocolFeeTierParams { min_volume: U256::from(5_000), v_fee_coefficient: 5, rs_fee_coefficient: 500 },
+        ];
+        let mut simulator = Simulator::new(&scenario);
+        let vault = &mut simulator.vaults[0];
+
+        assert_eq!(vault.protocol_fees(&scenario.protocol), (10, 1_000));
+        vault.epoch_volume = U256::from(4_999);
+        assert_eq!(vault.protocol_fees(&scenario.protocol), (8, 800));
+        vault.epoch_volume = U256::from(5_000);
+        assert_eq!(vault.protocol_fees(&scenario.protocol), (5, 500));
+
+        vault.params.protocol_fee_override =
+            Some(ProtocolFeeOverrideParams { v_fee_coefficient: 0, rs_fee_coefficient: 0 });
+        assert_eq!(vault.protocol_fees(&scenario.protocol), (0, 0));
+    }
+
+    #[test]
+    fn protocol_fee_tier_follows_the_last_epoch_volume() {
+        let mut scenario = scenario(3, &[100, 100, 100]);
+        scenario.protocol.protocol_fee_tiers = vec![ProtocolFeeTierParams {
+            min_volume: U256::from(1_000),
+            v_fee_coefficient: 8,
+            rs_fee_coefficient: 800,
+        }];
+        scenario.epochs[0].flows.push(deposit(ALICE, 100_000_000));
+        let mut simulator = Simulator::new(&scenario);
+
+        // First epoch: no volume yet, and tiers start above zero, so the protocol fees apply.
+        assert_eq!(simulator.vaults[0].protocol_fees(&scenario.protocol), (0, 0));
+        simulator.step(&scenario.epochs[0]).unwrap();
+        assert_eq!(simulator.vaults[0].epoch_volume, U256::from(100_000_000));
+        assert_eq!(simulator.vaults[0].protocol_fees(&scenario.protocol), (8, 800));
+
+        // No flows: the vault falls back below the first tier.
+        simulator.step(&scenario.epochs[1]).unwrap();
+        assert_eq!(simulator.vaults[0].epoch_volume, U256::ZERO);
+        assert_eq!(simulator.vaults[0].protocol_fees(&scenario.protocol), (0, 0));
+    }
+}
@@ -0,0 +1,208 @@
+//! Off-chain replicas of the strategists that submit vault intents.
+
+use std::collections::BTreeMap;
+
+use alloy_primitives::{Address, U256};
+use orion_commitment::IntentSnapshot;
+use orion_state_orchestrator::math::{mul_div, pow10, Rounding};
+
+use crate::scenario::{StrategistParams, WeightingMode};
+use crate::SimulationError;
+
+/// `KBestApyStrategist.SECONDS_PER_YEAR`
+const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+/// `KBestApyStrategist.MIN_WINDOW`
+const MIN_WINDOW: u64 = 60 * 60;
+
+#[derive(Clone, Copy, Debug)]
+struct Checkpoint {
+    share_price: U256,
+    timestamp: u64,
+}
+
+/// A strategist together with whatever state it keeps between submissions.
+#[derive(Clone, Debug)]
+pub struct Strategist(Kind);
+
+#[derive(Clone, Debug)]
+enum Kind {
+    Fixed(IntentSnapshot),
+    KBestApy {
+        k: u16,
+        weighting: WeightingMode,
+        /// Recorded at deployment, which happens on the first submission, and after each one.
+        checkpoints: Option<BTreeMap<Address, Checkpoint>>,
+    },
+}
+
+impl From<&StrategistParams> for Strategist {
+    fn from(params: &StrategistParams) -> Self {
+        Self(match params {
+            StrategistParams::Fixed { tokens, weights } => {
+                Kind::Fixed(IntentSnapshot { tokens: tokens.clone(), weights: weights.clone() })
+            }
+            StrategistParams::KBestApy { k, weighting } => {
+                Kind::KBestApy { k: *k, weighting: *weighting, checkpoints: None }
+            }
+        })
+    }
+}
+
+impl Strategist {
+    /// Intent submitted at `timestamp`, right before the epoch priced at `prices` starts.
+    ///
+    /// `KBestApyStrategist` ranks assets by the growth of their ERC-4626 share price since the
+    /// last checkpoint. The epoch price stands in for that share price: APYs are ratios, so the
+    /// price scale cancels out, and the underlying, whose price never moves, ranks at zero just
+    /// like on-chain where it has no `convertToAssets`.
+    pub fn submit_intent(
+        &mut self,
+        assets: &[Address],
+        prices: &[U256],
+        timestamp: u64,
+        intent_decimals: u8,
+    ) -> Result<IntentSnapshot, SimulationError> {
+        let (k, weighting, checkpoints) = match &mut self.0 {
+            Kind::Fixed(intent) => return Ok(intent.clone()),
+            Kind::KBestApy { k, weighting, checkpoints } => (*k, *weighting, checkpoints),
+        };
+        let checkpoints = checkpoints.get_or_insert_with(|| {
+            let mut initial = BTreeMap::new();
+            record_checkpoints(&mut initial, assets, prices, timestamp);
+            initial
+        });
+
+        let apys = assets
+            .iter()
+            .zip(prices)
+            .map(|(asset, &price)| asset_apy(checkpoints.get(asset), price, timestamp))
+            .collect::<Result<Vec<_>, _>>()?;
+        let k_actual = usize::from(k).min(assets.len());
+        let (tokens, top_apys) = select_top_k(assets, &apys, k_actual);
+        let weights = build_weights(weighting, &top_apys, intent_decimals)?;
+
+        record_checkpoints(checkpoints, assets, prices, timestamp);
+        Ok(IntentSnapshot { tokens, weights })
+    }
+}
+
+fn record_checkpoints(
+    checkpoints: &mut BTreeMap<Address, Checkpoint>,
+    assets: &[Address],
+    prices: &[U256],
+    timestamp: u64,
+) {
+    for (&asset, &price) in assets.iter().zip(prices) {
+        if let Some(existing) = checkpoints.get(&asset) {
+            if existing.timestamp != 0 && timestamp - existing.timestamp < MIN_WINDOW {
+                continue;
+            }
+        }
+        if price.is_zero() || price > U256::from(u128::MAX) {
+            continue;
+        }
+        checkpoints.insert(asset, Checkpoint { share_price: price, timestamp });
+    }
+}
+
+/// `_getAssetApy` with WAD precision.
+fn asset_apy(checkpoint: Option<&Checkpoint>, price: U256, timestamp: u64) -> Result<U256, SimulationError> {
+    let Some(checkpoint) = checkpoint.filter(|cp| !cp.share_price.is_zero() && cp.timestamp != 0) else {
+        return Ok(U256::ZERO);
+    };
+    let elapsed = timestamp - checkpoint.timestamp;
+    if elapsed < MIN_WINDOW || price.is_zero() || price > U256::from(u128::MAX) || price < checkpoint.share_price {
+        return Ok(U256::ZERO);
+    }
+    let growth = mul_div(price - checkpoint.share_price, pow10(18), checkpoint.share_price, Rounding::Floor)?;
+    Ok(mul_div(growth, U256::from(SECONDS_PER_YEAR), U256::from(elapsed), Rounding::Floor)?)
+}
+
+/// `_selectTopKByApy`: repeated arg-max, ties resolved towards the earlier asset.
+fn select_top_k(assets: &[Address], apys: &[U256], k: usize) -> (Vec<Address>, Vec<U256>) {
+    let mut used = vec![false; assets.len()];
+    let mut tokens = Vec::with_capacity(k);
+    let mut top = Vec::with_capacity(k);
+    for _ in 0..k {
+        let mut best: Option<usize> = None;
+        for j in 0..assets.len() {
+            if !used[j] && best.is_none_or(|b| apys[j] > apys[b]) {
+                best = Some(j);
+            }
+        }
+        let best = best.expect("k never exceeds the number of assets");
+        used[best] = true;
+        tokens.push(assets[best]);
+        top.push(apys[best]);
+    }
+    (tokens, top)
+}
+
+/// `_buildIntent`: rounding dust always goes to the first token.
+fn build_weights(weighting: WeightingMode, apys: &[U256], intent_decimals: u8) -> Result<Vec<u32>, SimulationError> {
+    let scale = pow10(intent_decimals);
+    let total_apy: U256 = apys.iter().copied().sum();
+    let count = U256::from(apys.len());
+    let mut weights = apys
+        .iter()
+        .map(|&apy| {
+            let weight = if weighting == WeightingMode::EqualWeighted || total_apy.is_zero() {
+                scale / count
+            } else {
+                mul_div(apy, scale, total_apy, Rounding::Floor)?
+            };
+            Ok(weight.to::<u32>())
+        })
+        .collect::<Result<Vec<_>, SimulationError>>()?;
+    let sum: u32 = weights.iter().sum();
+    weights[0] += scale.to::<u32>() - sum;
+    Ok(weights)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: u64 = 86_400;
+
+    fn assets() -> Vec<Address> {
+        (1..=3).map(Address::with_last_byte).collect()
+    }
+
+    fn prices(a: u64, b: u64) -> Vec<U256> {
+        vec![U256::from(100), U256::from(a), U256::from(b)]
+    }
+
+    fn k_best(k: u16, weighting: WeightingMode) -> Strategist {
+        Strategist::from(&StrategistParams::KBestApy { k, weighting })
+    }
+
+    #[test]
+    fn first_submission_has_no_history() {
+        let mut strategist = k_best(2, WeightingMode::ApyWeighted);
+        let intent = strategist.submit_intent(&assets(), &prices(100, 100), DAY, 9).unwrap();
+        // Every APY is zero, so the first K assets are picked with equal weights.
+        assert_eq!(intent.tokens, assets()[..2]);
+        assert_eq!(intent.weights, vec![500_000_000, 500_000_000]);
+    }
+
+    #[test]
+    fn ranks_assets_by_growth_since_the_last_checkpoint() {
+        let mut strategist = k_best(2, WeightingMode::ApyWeighted);
+        strategist.submit_intent(&assets(), &prices(100, 100), DAY, 9).unwrap();
+        let intent = strategist.submit_intent(&assets(), &prices(101, 103), 2 * DAY, 9).unwrap();
+        assert_eq!(intent.tokens, vec![assets()[2], assets()[1]]);
+        assert_eq!(intent.weights, vec![750_000_000, 250_000_000]);
+
+        // Checkpoints moved, so a flat day ranks everything at zero again.
+        let intent = strategist.submit_intent(&assets(), &prices(101, 103), 3 * DAY, 9).unwrap();
+        assert_eq!(intent.tokens, assets()[..2]);
+    }
+
+    #[test]
+    fn equal_weighting_gives_rounding_dust_to_the_first_token() {
+        let mut strategist = k_best(3, WeightingMode::EqualWeighted);
+        let intent = strategist.submit_intent(&assets(), &prices(100, 100), DAY, 9).unwrap();
+        assert_eq!(intent.weights, vec![333_333_334, 333_333_333, 333_333_333]);
+    }
+}
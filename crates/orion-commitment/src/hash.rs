@@ -0,0 +1,360 @@
+//! Hash primitives mirroring `LiquidityOrchestrator` one function at a time.
+//!
+//! Every `abi.encode(a, b, ...)` call on the Solidity side is reproduced with
+//! [`SolType::abi_encode_params`] over a tuple of the exact Solidity types, so the preimages
+//! match byte for byte (including head/tail offsets for dynamic arrays).
+
+use alloy_primitives::{keccak256, Address, B256, U256};
+use alloy_sol_types::{
+    sol_data::{Address as SolAddress, Array, Bool, FixedArray, FixedBytes, Uint},
+    SolType,
+};
+
+use crate::snapshot::{
+    EpochSnapshot, IntentSnapshot, PortfolioSnapshot, ProtocolSnapshot, VaultSnapshot, MANAGEMENT_FEE_TIERS,
+};
+
+/// `abi.encode(address[] tokens, uint256[] shares)`
+type PortfolioTuple = (Array<SolAddress>, Array<Uint<256>>);
+
+/// `abi.encode(address[] tokens, uint32[] weights)`
+type IntentTuple = (Array<SolAddress>, Array<Uint<32>>);
+
+/// `abi.encode(address[] users, uint256[] shares)`
+type RedeemBatchTuple = (Array<SolAddress>, Array<Uint<256>>);
+
+/// Leading params of the vault leaf `abi.encode(vault, uint8 feeType, performanceFee, managementFee,
+/// managementFeeBreakpoints, managementFeeTierRates, minimumManagementFee, highWaterMark, crystallizationPeriod,
+/// mintFeeShares, lastCrystallization, crystallizationPrice, uint8 hurdleType, hurdleRate, hurdleBenchmark,
+/// hurdleBenchmarkPrice, vFeeCoefficient, rsFeeCoefficient, ...)`.
+///
+/// The leaf has more params than a single alloy tuple holds. Every param is static, so the encoding is
+/// the concatenation of the head words and splitting it in two tuples yields the same bytes.
+type VaultLeafFeeTuple = (
+    SolAddress,
+    Uint<8>,
+    Uint<16>,
+    Uint<16>,
+    FixedArray<Uint<256>, MANAGEMENT_FEE_TIERS>,
+    FixedArray<Uint<16>, MANAGEMENT_FEE_TIERS>,
+    Uint<256>,
+    Uint<256>,
+    Uint<32>,
+    Bool,
+    Uint<64>,
+    Uint<256>,
+    Uint<8>,
+    Uint<16>,
+    SolAddress,
+    Uint<256>,
+    Uint<16>,
+    Uint<16>,
+);
+
+/// Trailing params of the vault leaf `abi.encode(..., pendingRedeem, pendingDeposit, redeemBatchHash,
+/// totalSupply, totalAssets, portfolioHash, intentHash)`.
+type VaultLeafStateTuple = (Uint<256>, Uint<256>, FixedBytes<32>, Uint<256>, Uint<256>, FixedBytes<32>, FixedBytes<32>);
+
+/// `abi.encode(...)` inside `_buildProtocolStateHash`.
+type ProtocolStateTuple = (
+    Uint<256>,
+    Uint<256>,
+    Uint<8>,
+    Uint<8>,
+    Uint<32>,
+    Array<SolAddress>,
+    Array<Uint<8>>,
+    Uint<16>,
+    Array<SolAddress>,
+    Array<SolAddress>,
+    Uint<256>,
+    Uint<256>,
+    Uint<256>,
+    Uint<256>,
+    Uint<256>,
+);
+
+/// `abi.encode(address asset, uint256 price)`
+type AssetLeafTuple = (SolAddress, Uint<256>);
+
+/// `abi.encode(bytes32 accumulator, bytes32 leaf)`
+type FoldTuple = (FixedBytes<32>, FixedBytes<32>);
+
+/// `abi.encode(bytes32 protocolStateHash, bytes32 assetsHash, bytes32 vaultsHash)`
+type CommitmentTuple = (FixedBytes<32>, FixedBytes<32>, FixedBytes<32>);
+
+/// `keccak256(abi.encode(portfolioTokens, portfolioShares))`
+pub fn portfolio_hash(portfolio: &PortfolioSnapshot) -> B256 {
+    keccak256(PortfolioTuple::abi_encode_params(&(portfolio.tokens.clone(), portfolio.shares.clone())))
+}
+
+/// `keccak256(abi.encode(intentTokens, intentWeights))`
+pub fn intent_hash(intent: &IntentSnapshot) -> B256 {
+    keccak256(IntentTuple::abi_encode_params(&(intent.tokens.clone(), intent.weights.clone())))
+}
+
+/// `keccak256(abi.encode(redeemUsers, redeemShares))` over `pendingRedeemBatch(maxFulfillBatchSize)`
+pub fn redeem_batch_hash(users: &[Address], shares: &[U256]) -> B256 {
+    keccak256(RedeemBatchTuple::abi_encode_params(&(users.to_vec(), shares.to_vec())))
+}
+
+/// Single vault leaf as built in `_processCommitmentMinibatch`.
+pub fn vault_leaf(vault: &VaultSnapshot) -> B256 {
+    vault_leaf_with_hashes(vault, portfolio_hash(&vault.portfolio), intent_hash(&vault.intent))
+}
+
+/// Vault leaf from precomputed portfolio and intent hashes.
+pub fn vault_leaf_with_hashes(vault: &VaultSnapshot, portfolio_hash: B256, intent_hash: B256) -> B256 {
+    let fee = &vault.fee_model;
+    let mut preimage = VaultLeafFeeTuple::abi_encode_params(&(
+        vault.address,
+        fee.fee_type,
+        fee.performance_fee,
+        fee.management_fee,
+        fee.management_fee_breakpoints,
+        fee.management_fee_tier_rates,
+        fee.minimum_management_fee,
+        fee.high_water_mark,
+        fee.crystallization_period,
+        fee.mint_fee_shares,
+        fee.last_crystallization,
+        fee.crystallization_price,
+        fee.hurdle_type,
+        fee.hurdle_rate,
+        fee.hurdle_benchmark,
+        fee.hurdle_benchmark_price,
+        vault.v_fee_coefficient,
+        vault.rs_fee_coefficient,
+    ));
+    preimage.extend(VaultLeafStateTuple::abi_encode_params(&(
+        vault.pending_redeem,
+        vault.pending_deposit,
+        vault.redeem_batch_hash,
+        vault.total_supply,
+        vault.total_assets,
+        portfolio_hash,
+        intent_hash,
+    )));
+    keccak256(preimage)
+}
+
+/// One step of the sequential fold: `keccak256(abi.encode(accumulator, leaf))`.
+pub fn fold(accumulator: B256, leaf: B256) -> B256 {
+    keccak256(FoldTuple::abi_encode_params(&(accumulator, leaf)))
+}
+
+/// Folds leaves left to right starting from `bytes32(0)`.
+pub fn fold_all<I: IntoIterator<Item = B256>>(leaves: I) -> B256 {
+    leaves.into_iter().fold(B256::ZERO, fold)
+}
+
+/// `LiquidityOrchestrator._buildProtocolStateHash`
+pub fn protocol_state_hash(protocol: &ProtocolSnapshot) -> B256 {
+    keccak256(ProtocolStateTuple::abi_encode_params(&(
+        protocol.max_fulfill_batch_size,
+        protocol.target_buffer_ratio,
+        protocol.price_adapter_decimals,
+        protocol.strategist_intent_decimals,
+        protocol.epoch_duration,
+        protocol.whitelisted_assets.clone(),
+        protocol.token_decimals.clone(),
+        protocol.risk_free_rate,
+        protocol.decommissioning_assets.clone(),
+        protocol.failed_epoch_tokens.clone(),
+        protocol.initial_epoch_buffer_amount,
+        protocol.buying_leg_entry_buffer,
+        protocol.buffer_amount,
+        protocol.underlying_balance,
+        protocol.epoch_start_time,
+    )))
+}
+
+/// `keccak256(abi.encode(asset, price))`
+pub fn asset_leaf(asset: Address, price: U256) -> B256 {
+    keccak256(AssetLeafTuple::abi_encode_params(&(asset, price)))
+}
+
+/// `LiquidityOrchestrator._aggregateAssetLeaves`
+pub fn aggregate_asset_leaves(assets: &[Address], prices: &[U256]) -> B256 {
+    fold_all(assets.iter().zip(prices).map(|(asset, price)| asset_leaf(*asset, *price)))
+}
+
+/// Final `keccak256(abi.encode(protocolStateHash, assetsHash, vaultsHash))`.
+pub fn epoch_state_commitment(protocol_state_hash: B256, assets_hash: B256, vaults_hash: B256) -> B256 {
+    keccak256(CommitmentTuple::abi_encode_params(&(protocol_state_hash, assets_hash, vaults_hash)))
+}
+
+/// Vaults hash over every vault in the snapshot, as sealed at the end of StateCommitment.
+pub fn vaults_hash(snapshot: &EpochSnapshot) -> B256 {
+    fold_all(snapshot.vaults.iter().map(vault_leaf))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::FeeModelSnapshot;
+    use alloy_primitives::{address, b256};
+
+    fn word(value: u64) -> [u8; 32] {
+        U256::from(value).to_be_bytes()
+    }
+
+    fn address_word(address: Address) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        out[12..].copy_from_slice(address.as_slice());
+        out
+    }
+
+    #[test]
+    fn fold_from_zero_matches_manual_preimage() {
+        let leaf = b256!("1111111111111111111111111111111111111111111111111111111111111111");
+        let mut preimage = [0u8; 64];
+        preimage[32..].copy_from_slice(leaf.as_slice());
+        assert_eq!(fold(B256::ZERO, leaf), keccak256(preimage));
+        assert_eq!(fold_all([leaf]), keccak256(preimage));
+    }
+
+    #[test]
+    fn empty_asset_list_aggregates_to_zero() {
+        assert_eq!(aggregate_asset_leaves(&[], &[]), B256::ZERO);
+    }
+
+    #[test]
+    fn asset_leaf_is_two_static_words() {
+        let asset = address!("00000000000000000000000000000000000000aa");
+        let mut preimage = Vec::new();
+        preimage.extend_from_slice(&address_word(asset));
+        preimage.extend_from_slice(&word(100_000_000_000_000));
+        assert_eq!(asset_leaf(asset, U256::from(100_000_000_000_000u64)), keccak256(preimage));
+    }
+
+    #[test]
+    fn vault_leaf_inlines_the_fee_schedules() {
+        let vault = VaultSnapshot {
+            address: address!("00000000000000000000000000000000000000ee"),
+            fee_model: FeeModelSnapshot {
+                fee_type: 3,
+                performance_fee: 2_000,
+                management_fee: 100,
+                management_fee_breakpoints: [U256::from(10), U256::from(50), U256::ZERO],
+                management_fee_tier_rates: [75, 50, 0],
+                minimum_management_fee: U256::from(5),
+                crystallization_period: 0,
+                mint_fee_shares: true,
+                last_crystallization: 0,
+                high_water_mark: U256::from(1_000_000),
+                crystallization_price: U256::from(1_000_000),
+                hurdle_type: 0,
+                hurdle_rate: 0,
+                hurdle_benchmark: Address::ZERO,
+                hurdle_benchmark_price: U256::ZERO,
+            },
+            pending_redeem: U256::ZERO,
+            pending_deposit: U256::ZERO,
+            redeem_batch_hash: B256::ZERO,
+            total_supply: U256::ZERO,
+            total_assets: U256::ZERO,
+            v_fee_coefficient: 10,
+            rs_fee_coefficient: 1_000,
+            portfolio: PortfolioSnapshot::default(),
+            intent: IntentSnapshot::default(),
+        };
+
+        // Fixed-size arrays are static: their elements sit in the head, one word each.
+        let mut preimage = Vec::new();
+        preimage.extend_from_slice(&address_word(vault.address));
+        for value in [3u64, 2_000, 100, 10, 50, 0, 75, 50, 0, 5, 1_000_000, 0, 1, 0, 1_000_000] {
+            preimage.extend_from_slice(&word(value));
+        }
+        for value in [0u64, 0, 0, 0, 10, 1_000, 0, 0, 0, 0, 0, 0, 0] {
+            preimage.extend_from_slice(&word(value));
+        }
+        assert_eq!(vault_leaf_with_hashes(&vault, B256::ZERO, B256::ZERO), keccak256(preimage));
+    }
+
+    #[test]
+    fn portfolio_hash_uses_parameter_encoding_without_outer_offset() {
+        let token = address!("00000000000000000000000000000000000000bb");
+        let portfolio = PortfolioSnapshot { tokens: vec![token], shares: vec![U256::from(7)] };
+
+        // abi.encode(address[], uint256[]): two head offsets followed by length-prefixed tails.
+        let mut preimage = Vec::new();
+        preimage.extend_from_slice(&word(0x40));
+        preimage.extend_from_slice(&word(0x80));
+        preimage.extend_from_slice(&word(1));
+        preimage.extend_from_slice(&address_word(token));
+        preimage.extend_from_slice(&word(1));
+        preimage.extend_from_slice(&word(7));
+        assert_eq!(portfolio_hash(&portfolio), keccak256(preimage));
+    }
+
+    #[test]
+    fn empty_redeem_batch_still_hashes_both_arrays() {
+        let mut preimage = Vec::new();
+        preimage.extend_from_slice(&word(0x40));
+        preimage.extend_from_slice(&word(0x60));
+        preimage.extend_from_slice(&word(0));
+        preimage.extend_from_slice(&word(0));
+        assert_eq!(redeem_batch_hash(&[], &[]), keccak256(preimage));
+    }
+
+    #[test]
+    fn intent_weights_are_padded_to_full_words() {
+        let token = address!("00000000000000000000000000000000000000cc");
+        let intent = IntentSnapshot { tokens: vec![token], weights: vec![1_000_000_000] };
+
+        let mut preimage = Vec::new();
+        preimage.extend_from_slice(&word(0x40));
+        preimage.extend_from_slice(&word(0x80));
+        preimage.extend_from_slice(&word(1));
+        preimage.extend_from_slice(&address_word(token));
+        preimage.extend_from_slice(&word(1));
+        preimage.extend_from_slice(&word(1_000_000_000));
+        assert_eq!(intent_hash(&intent), keccak256(preimage));
+    }
+
+    #[test]
+    fn protocol_state_hash_places_dynamic_arrays_in_the_tail() {
+        let asset = address!("00000000000000000000000000000000000000dd");
+        let protocol = ProtocolSnapshot {
+            max_fulfill_batch_size: U256::from(150),
+            target_buffer_ratio: U256::from(100),
+            price_adapter_decimals: 14,
+            strategist_intent_decimals: 9,
+            epoch_duration: 86_400,
+            whitelisted_assets: vec![asset],
+            token_decimals: vec![6],
+            risk_free_rate: 400,
+            decommissioning_assets: vec![],
+            failed_epoch_tokens: vec![],
+            initial_epoch_buffer_amount: U256::from(1),
+            buying_leg_entry_buffer: U256::ZERO,
+            buffer_amount: U256::from(2),
+            underlying_balance: U256::from(3),
+            epoch_start_time: U256::from(4),
+        };
+
+        // 15 head words; the four dynamic arrays live in the tail in declaration order.
+        let head_size = 15 * 32;
+        let mut preimage = Vec::new();
+        for value in [150u64, 100, 14, 9, 86_400] {
+            preimage.extend_from_slice(&word(value));
+        }
+        preimage.extend_from_slice(&word(head_size)); // whitelistedAssets
+        preimage.extend_from_slice(&word(head_size + 64)); // tokenDecimals
+        preimage.extend_from_slice(&word(400));
+        preimage.extend_from_slice(&word(head_size + 128)); // decommissioningAssets
+        preimage.extend_from_slice(&word(head_size + 160)); // failedEpochTokens
+        for value in [1u64, 0, 2, 3, 4] {
+            preimage.extend_from_slice(&word(value));
+        }
+        preimage.extend_from_slice(&word(1));
+        preimage.extend_from_slice(&address_word(asset));
+        preimage.extend_from_slice(&word(1));
+        preimage.extend_from_slice(&word(6));
+        preimage.extend_from_slice(&word(0));
+        preimage.extend_from_slice(&word(0));
+
+        assert_eq!(protocol_state_hash(&protocol), keccak256(preimage));
+    }
+}
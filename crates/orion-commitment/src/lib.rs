@@ -0,0 +1,40 @@
+//! Off-chain reproduction of the `LiquidityOrchestrator` epoch state commitment.
+//!
+//! The commitment sealed at the end of the StateCommitment phase is
+//!
+//! ```text
+//! keccak256(abi.encode(protocolStateHash, assetsHash, vaultsHash))
+//! ```
+//!
+//! where `vaultsHash` and `assetsHash` are sequential keccak folds over per-vault and per-asset
+//! leaves. This crate recomputes every one of those values from an [`EpochSnapshot`] of the
+//! on-chain reads and keeps the intermediates in a [`CommitmentReport`], so a `CommitmentMismatch`
+//! revert can be traced to the exact input that diverged.
+
+pub mod hash;
+pub mod report;
+pub mod snapshot;
+
+pub use report::{AssetLeafReport, CommitmentReport, VaultLeafReport};
+pub use snapshot::{
+    EpochSnapshot, FeeModelSnapshot, IntentSnapshot, PortfolioSnapshot, ProtocolSnapshot, VaultSnapshot,
+    MANAGEMENT_FEE_TIERS,
+};
+
+/// Errors raised while loading a snapshot.
+#[derive(Debug, thiserror::Error)]
+pub enum CommitmentError {
+    /// The snapshot is not valid JSON or does not match the expected schema.
+    #[error("invalid snapshot: {0}")]
+    Json(#[from] serde_json::Error),
+    /// Two arrays that are parallel on-chain have different lengths.
+    #[error("{field} has {actual} entries, expected {expected}")]
+    LengthMismatch {
+        /// Offending snapshot field.
+        field: String,
+        /// Length of the array it must be parallel to.
+        expected: usize,
+        /// Actual length.
+        actual: usize,
+    },
+}
@@ -0,0 +1,76 @@
+//! `orion-commitment` prints every intermediate hash of an epoch state commitment.
+//!
+//! ```text
+//! orion-commitment snapshot.json [--expected 0x...] [--json]
+//! ```
+
+use std::{fs, path::PathBuf, process::ExitCode};
+
+use alloy_primitives::B256;
+use anyhow::Context;
+use clap::Parser;
+use orion_commitment::{CommitmentReport, EpochSnapshot};
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Recompute the LiquidityOrchestrator epoch state commitment from a JSON snapshot")]
+struct Cli {
+    /// Path to the epoch snapshot JSON (`-` for stdin).
+    snapshot: PathBuf,
+
+    /// On-chain `epochStateCommitment` to compare against; exits non-zero on mismatch.
+    #[arg(long)]
+    expected: Option<B256>,
+
+    /// Print the report as JSON instead of plain text.
+    #[arg(long)]
+    json: bool,
+}
+
+fn main() -> anyhow::Result<ExitCode> {
+    let cli = Cli::parse();
+
+    let raw = if cli.snapshot.as_os_str() == "-" {
+        std::io::read_to_string(std::io::stdin()).context("reading snapshot from stdin")?
+    } else {
+        fs::read_to_string(&cli.snapshot).with_context(|| format!("reading {}", cli.snapshot.display()))?
+    };
+    let snapshot = EpochSnapshot::from_json(&raw)?;
+    let report = CommitmentReport::compute(&snapshot);
+
+    if cli.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+
+    match cli.expected {
+        Some(expected) if expected != report.epoch_state_commitment => {
+            eprintln!("CommitmentMismatch: expected {expected}, computed {}", report.epoch_state_commitment);
+            Ok(ExitCode::FAILURE)
+        }
+        _ => Ok(ExitCode::SUCCESS),
+    }
+}
+
+fn print_report(report: &CommitmentReport) {
+    println!("vault leaves:");
+    for (i, vault) in report.vaults.iter().enumerate() {
+        println!("  [{i}] {}", vault.address);
+        println!("      portfolioHash  {}", vault.portfolio_hash);
+        println!("      intentHash     {}", vault.intent_hash);
+        println!("      leaf           {}", vault.leaf);
+        println!("      accumulator    {}", vault.accumulator);
+    }
+    println!("vaultsHash             {}", report.vaults_hash);
+
+    println!("asset leaves:");
+    for (i, asset) in report.assets.iter().enumerate() {
+        println!("  [{i}] {} @ {}", asset.address, asset.price);
+        println!("      leaf           {}", asset.leaf);
+        println!("      accumulator    {}", asset.accumulator);
+    }
+    println!("assetsHash             {}", report.assets_hash);
+
+    println!("protocolStateHash      {}", report.protocol_state_hash);
+    println!("epochStateCommitment   {}", report.epoch_state_commitment);
+}
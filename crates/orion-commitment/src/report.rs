@@ -0,0 +1,217 @@
+//! Commitment computation that keeps every intermediate hash.
+
+use alloy_primitives::{Address, B256, U256};
+use serde::Serialize;
+
+use crate::hash;
+use crate::snapshot::EpochSnapshot;
+
+/// Intermediate values for one vault leaf.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultLeafReport {
+    /// Vault address.
+    pub address: Address,
+    /// `keccak256(abi.encode(portfolioTokens, portfolioShares))`
+    pub portfolio_hash: B256,
+    /// `keccak256(abi.encode(intentTokens, intentWeights))`
+    pub intent_hash: B256,
+    /// Vault leaf.
+    pub leaf: B256,
+    /// `_partialVaultsHash` after folding this leaf.
+    pub accumulator: B256,
+}
+
+/// Intermediate values for one asset leaf.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetLeafReport {
+    /// Asset address.
+    pub address: Address,
+    /// Epoch price [priceAdapterDecimals].
+    pub price: U256,
+    /// `keccak256(abi.encode(asset, price))`
+    pub leaf: B256,
+    /// Assets hash after folding this leaf.
+    pub accumulator: B256,
+}
+
+/// Full breakdown of an epoch state commitment.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitmentReport {
+    /// Vault leaves in `vaultsEpoch` order.
+    pub vaults: Vec<VaultLeafReport>,
+    /// Final vault fold (`_cachedVaultsHash`).
+    pub vaults_hash: B256,
+    /// Asset leaves in whitelist order.
+    pub assets: Vec<AssetLeafReport>,
+    /// Final asset fold (`_cachedAssetsHash`).
+    pub assets_hash: B256,
+    /// `_buildProtocolStateHash()`
+    pub protocol_state_hash: B256,
+    /// `_currentEpoch.epochStateCommitment`
+    pub epoch_state_commitment: B256,
+}
+
+impl CommitmentReport {
+    /// Recomputes the commitment for `snapshot`, recording each fold step.
+    ///
+    /// The snapshot is expected to have passed [`EpochSnapshot::validate`]; extra prices beyond
+    /// the whitelist length are ignored exactly like the Solidity loop would.
+    pub fn compute(snapshot: &EpochSnapshot) -> Self {
+        let mut accumulator = B256::ZERO;
+        let vaults = snapshot
+            .vaults
+            .iter()
+            .map(|vault| {
+                let portfolio_hash = hash::portfolio_hash(&vault.portfolio);
+                let intent_hash = hash::intent_hash(&vault.intent);
+                let leaf = hash::vault_leaf_with_hashes(vault, portfolio_hash, intent_hash);
+                accumulator = hash::fold(accumulator, leaf);
+                VaultLeafReport { address: vault.address, portfolio_hash, intent_hash, leaf, accumulator }
+            })
+            .collect();
+        let vaults_hash = accumulator;
+
+        let mut accumulator = B256::ZERO;
+        let assets = snapshot
+            .protocol
+            .whitelisted_assets
+            .iter()
+            .zip(&snapshot.asset_prices)
+            .map(|(&address, &price)| {
+                let leaf = hash::asset_leaf(address, price);
+                accumulator = hash::fold(accumulator, leaf);
+                AssetLeafReport { address, price, leaf, accumulator }
+            })
+            .collect();
+        let assets_hash = accumulator;
+
+        let protocol_state_hash = hash::protocol_state_hash(&snapshot.protocol);
+        let epoch_state_commitment = hash::epoch_state_commitment(protocol_state_hash, assets_hash, vaults_hash);
+
+        Self { vaults, vaults_hash, assets, assets_hash, protocol_state_hash, epoch_state_commitment }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::{
+        FeeModelSnapshot, IntentSnapshot, PortfolioSnapshot, ProtocolSnapshot, VaultSnapshot, MANAGEMENT_FEE_TIERS,
+    };
+    use alloy_primitives::address;
+
+    fn snapshot() -> EpochSnapshot {
+        let usdc = address!("00000000000000000000000000000000000000a1");
+        let vault = |address: Address| VaultSnapshot {
+            address,
+            fee_model: FeeModelSnapshot {
+                fee_type: 3,
+                performance_fee: 2_000,
+                management_fee: 100,
+                management_fee_breakpoints: [U256::ZERO; MANAGEMENT_FEE_TIERS],
+                management_fee_tier_rates: [0; MANAGEMENT_FEE_TIERS],
+                minimum_management_fee: U256::ZERO,
+                crystallization_period: 0,
+                mint_fee_shares: false,
+                last_crystallization: 0,
+                high_water_mark: U256::from(1_000_000),
+                crystallization_price: U256::from(1_000_000),
+                hurdle_type: 0,
+                hurdle_rate: 0,
+                hurdle_benchmark: Address::ZERO,
+                hurdle_benchmark_price: U256::ZERO,
+            },
+            pending_redeem: U256::ZERO,
+            pending_deposit: U256::from(5_000_000),
+            redeem_batch_hash: crate::hash::redeem_batch_hash(&[], &[]),
+            total_supply: U256::ZERO,
+            total_assets: U256::ZERO,
+            v_fee_coefficient: 0,
+            rs_fee_coefficient: 0,
+            portfolio: PortfolioSnapshot::default(),
+            intent: IntentSnapshot { tokens: vec![usdc], weights: vec![1_000_000_000] },
+        };
+        EpochSnapshot {
+            protocol: ProtocolSnapshot {
+                max_fulfill_batch_size: U256::from(150),
+                target_buffer_ratio: U256::ZERO,
+                price_adapter_decimals: 14,
+                strategist_intent_decimals: 9,
+                epoch_duration: 86_400,
+                whitelisted_assets: vec![usdc],
+                token_decimals: vec![6],
+                risk_free_rate: 0,
+                decommissioning_assets: vec![],
+                failed_epoch_tokens: vec![],
+                initial_epoch_buffer_amount: U256::ZERO,
+                buying_leg_entry_buffer: U256::ZERO,
+                buffer_amount: U256::ZERO,
+                underlying_balance: U256::from(10_000_000),
+                epoch_start_time: U256::from(86_400),
+            },
+            asset_prices: vec![U256::from(100_000_000_000_000u64)],
+            vaults: vec![
+                vault(address!("00000000000000000000000000000000000000f1")),
+                vault(address!("00000000000000000000000000000000000000f2")),
+            ],
+        }
+    }
+
+    #[test]
+    fn report_matches_standalone_hash_functions() {
+        let snapshot = snapshot();
+        let report = CommitmentReport::compute(&snapshot);
+
+        assert_eq!(report.vaults_hash, hash::vaults_hash(&snapshot));
+        assert_eq!(
+            report.assets_hash,
+            hash::aggregate_asset_leaves(&snapshot.protocol.whitelisted_assets, &snapshot.asset_prices)
+        );
+        assert_eq!(
+            report.epoch_state_commitment,
+            hash::epoch_state_commitment(report.protocol_state_hash, report.assets_hash, report.vaults_hash)
+        );
+        assert_eq!(report.vaults.last().map(|v| v.accumulator), Some(report.vaults_hash));
+    }
+
+    #[test]
+    fn vault_order_changes_the_commitment() {
+        let snapshot = snapshot();
+        let mut reversed = snapshot.clone();
+        reversed.vaults.reverse();
+        assert_ne!(
+            CommitmentReport::compute(&snapshot).epoch_state_commitment,
+            CommitmentReport::compute(&reversed).epoch_state_commitment
+        );
+    }
+
+    #[test]
+    fn json_round_trip_preserves_commitment() {
+        let snapshot = snapshot();
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let parsed = EpochSnapshot::from_json(&json).unwrap();
+        assert_eq!(CommitmentReport::compute(&parsed), CommitmentReport::compute(&snapshot));
+    }
+
+    #[test]
+    fn bundled_fixture_parses() {
+        let snapshot = EpochSnapshot::from_json(include_str!("../fixtures/single-vault.json")).unwrap();
+        let report = CommitmentReport::compute(&snapshot);
+        assert_eq!(report.vaults.len(), 1);
+        assert_eq!(report.assets.len(), 2);
+    }
+
+    #[test]
+    fn mismatched_price_array_is_rejected() {
+        let mut snapshot = snapshot();
+        snapshot.asset_prices.push(U256::from(1));
+        let json = serde_json::to_string(&snapshot).unwrap();
+        assert!(matches!(
+            EpochSnapshot::from_json(&json),
+            Err(crate::CommitmentError::LengthMismatch { expected: 1, actual: 2, .. })
+        ));
+    }
+}
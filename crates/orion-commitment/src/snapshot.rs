@@ -0,0 +1,202 @@
+//! JSON snapshot of the on-chain reads the LiquidityOrchestrator folds into `epochStateCommitment`.
+//!
+//! Field names follow the Solidity getters they are read from, so a snapshot can be produced by
+//! calling the contracts at the block where `EpochStateCommitted` was emitted and dumping the results.
+
+use alloy_primitives::{Address, B256, U256};
+use serde::{Deserialize, Serialize};
+
+use crate::CommitmentError;
+
+/// Everything `_buildProtocolStateHash`, `_aggregateAssetLeaves` and the vault leaf loop read.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochSnapshot {
+    /// Protocol-wide parameters hashed into `protocolStateHash`.
+    pub protocol: ProtocolSnapshot,
+    /// Epoch prices, parallel to `protocol.whitelistedAssets` (`getAssetPrices(getAllWhitelistedAssets())`).
+    pub asset_prices: Vec<U256>,
+    /// Vaults in `vaultsEpoch` order.
+    pub vaults: Vec<VaultSnapshot>,
+}
+
+/// Inputs to `LiquidityOrchestrator._buildProtocolStateHash`, in encoding order.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolSnapshot {
+    /// `config.maxFulfillBatchSize()`
+    pub max_fulfill_batch_size: U256,
+    /// `targetBufferRatio`
+    pub target_buffer_ratio: U256,
+    /// `config.priceAdapterDecimals()`
+    pub price_adapter_decimals: u8,
+    /// `config.strategistIntentDecimals()`
+    pub strategist_intent_decimals: u8,
+    /// `epochDuration`
+    pub epoch_duration: u32,
+    /// `config.getAllWhitelistedAssets()`
+    pub whitelisted_assets: Vec<Address>,
+    /// `config.getAllTokenDecimals()`, parallel to `whitelisted_assets`
+    pub token_decimals: Vec<u8>,
+    /// `config.riskFreeRate()`
+    pub risk_free_rate: u16,
+    /// `config.decommissioningAssets()`
+    pub decommissioning_assets: Vec<Address>,
+    /// `getFailedEpochTokens()`
+    #[serde(default)]
+    pub failed_epoch_tokens: Vec<Address>,
+    /// `initialEpochBufferAmount`
+    pub initial_epoch_buffer_amount: U256,
+    /// `buyingLegEntryBuffer`
+    #[serde(default)]
+    pub buying_leg_entry_buffer: U256,
+    /// `bufferAmount`
+    pub buffer_amount: U256,
+    /// `IERC20(underlyingAsset).balanceOf(liquidityOrchestrator)`
+    pub underlying_balance: U256,
+    /// `epochStartTime`
+    #[serde(default)]
+    pub epoch_start_time: U256,
+}
+
+/// Per-vault reads folded into a single vault leaf.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultSnapshot {
+    /// Vault address as listed in `vaultsEpoch`.
+    pub address: Address,
+    /// Fee model snapshotted at epoch start (`getEpochState().vaultFeeModels[i]`,
+    /// `vaultManagementFeeSchedules[i]`, `vaultFeeSettlements[i]` and `vaultHurdles[i]`).
+    pub fee_model: FeeModelSnapshot,
+    /// `pendingRedeem(maxFulfillBatchSize)` [shares]
+    pub pending_redeem: U256,
+    /// `pendingDeposit(maxFulfillBatchSize)` [assets]
+    pub pending_deposit: U256,
+    /// `keccak256(abi.encode(users, shares))` over `pendingRedeemBatch(maxFulfillBatchSize)`,
+    /// see [`crate::hash::redeem_batch_hash`].
+    pub redeem_batch_hash: B256,
+    /// `totalSupply()` [shares]
+    pub total_supply: U256,
+    /// `totalAssets()` [assets]
+    pub total_assets: U256,
+    /// Volume fee coefficient snapshotted at epoch start (`getEpochState().vaultVFeeCoefficients[i]`).
+    #[serde(default)]
+    pub v_fee_coefficient: u16,
+    /// Revenue share fee coefficient snapshotted at epoch start (`getEpochState().vaultRsFeeCoefficients[i]`).
+    #[serde(default)]
+    pub rs_fee_coefficient: u16,
+    /// `getPortfolio()`
+    pub portfolio: PortfolioSnapshot,
+    /// `getIntent()`
+    pub intent: IntentSnapshot,
+}
+
+/// `OrionVault.MANAGEMENT_FEE_TIERS`
+pub const MANAGEMENT_FEE_TIERS: usize = 3;
+
+/// Mirror of `IOrionVault.FeeModel` together with the `IOrionVault.ManagementFeeSchedule`,
+/// `IOrionVault.FeeSettlement` and `IOrionVault.Hurdle` snapshotted with it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeModelSnapshot {
+    /// `uint8(feeType)`
+    pub fee_type: u8,
+    /// Performance fee [bps]
+    pub performance_fee: u16,
+    /// Management fee up to the first breakpoint [bps]
+    pub management_fee: u16,
+    /// Management fee breakpoints [assets], strictly ascending and zero-padded
+    #[serde(default)]
+    pub management_fee_breakpoints: [U256; MANAGEMENT_FEE_TIERS],
+    /// Management fee above each breakpoint [bps]
+    #[serde(default)]
+    pub management_fee_tier_rates: [u16; MANAGEMENT_FEE_TIERS],
+    /// Minimum annual management fee [assets]
+    #[serde(default)]
+    pub minimum_management_fee: U256,
+    /// Performance fee crystallization period [s], zero meaning every epoch
+    #[serde(default)]
+    pub crystallization_period: u32,
+    /// Whether fees are paid by minting vault shares
+    #[serde(default)]
+    pub mint_fee_shares: bool,
+    /// Epoch start time of the last crystallization [s]
+    #[serde(default)]
+    pub last_crystallization: u64,
+    /// High water mark [assets per share unit]
+    pub high_water_mark: U256,
+    /// Share price at the last crystallization [assets per share unit]
+    #[serde(default)]
+    pub crystallization_price: U256,
+    /// `uint8(hurdleType)`
+    #[serde(default)]
+    pub hurdle_type: u8,
+    /// Annual hurdle rate [bps], used with the fixed-rate hurdle
+    #[serde(default)]
+    pub hurdle_rate: u16,
+    /// Benchmark asset, used with the benchmark hurdle
+    #[serde(default)]
+    pub hurdle_benchmark: Address,
+    /// Benchmark price at the last crystallization [priceAdapterDecimals], zero if not recorded yet
+    #[serde(default)]
+    pub hurdle_benchmark_price: U256,
+}
+
+/// Live portfolio (w_0) as returned by `getPortfolio()`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortfolioSnapshot {
+    /// Portfolio tokens.
+    pub tokens: Vec<Address>,
+    /// Shares held per token, parallel to `tokens`.
+    pub shares: Vec<U256>,
+}
+
+/// Strategist intent (w_1) as returned by `getIntent()`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntentSnapshot {
+    /// Intent tokens.
+    pub tokens: Vec<Address>,
+    /// Target weights with `strategistIntentDecimals` decimals, parallel to `tokens`.
+    pub weights: Vec<u32>,
+}
+
+impl EpochSnapshot {
+    /// Parses a snapshot from its JSON representation.
+    pub fn from_json(json: &str) -> Result<Self, CommitmentError> {
+        let snapshot: Self = serde_json::from_str(json)?;
+        snapshot.validate()?;
+        Ok(snapshot)
+    }
+
+    /// Checks that every pair of parallel arrays has matching lengths.
+    ///
+    /// The Solidity side never produces mismatched arrays, so a mismatch means the snapshot was
+    /// assembled incorrectly and any hash computed from it would be meaningless.
+    pub fn validate(&self) -> Result<(), CommitmentError> {
+        let assets = self.protocol.whitelisted_assets.len();
+        ensure_parallel("protocol.tokenDecimals", assets, self.protocol.token_decimals.len())?;
+        ensure_parallel("assetPrices", assets, self.asset_prices.len())?;
+        for (i, vault) in self.vaults.iter().enumerate() {
+            ensure_parallel(
+                &format!("vaults[{i}].portfolio.shares"),
+                vault.portfolio.tokens.len(),
+                vault.portfolio.shares.len(),
+            )?;
+            ensure_parallel(
+                &format!("vaults[{i}].intent.weights"),
+                vault.intent.tokens.len(),
+                vault.intent.weights.len(),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn ensure_parallel(field: &str, expected: usize, actual: usize) -> Result<(), CommitmentError> {
+    if expected != actual {
+        return Err(CommitmentError::LengthMismatch { field: field.to_owned(), expected, actual });
+    }
+    Ok(())
+}
@@ -0,0 +1,139 @@
+//! Event and getter declarations the indexer decodes against.
+//!
+//! Event signatures mirror `EventsLib` and `IOrionVault` exactly; a renamed parameter is harmless
+//! but a changed type or `indexed` flag silently stops matching, so keep them in sync.
+
+#![allow(missing_docs, clippy::too_many_arguments)]
+
+use alloy::sol;
+
+sol! {
+    #[sol(all_derives)]
+    interface EventsLib {
+        enum VaultType {
+            Transparent,
+            Encrypted
+        }
+
+        event WhitelistedAssetAdded(address indexed asset);
+        event WhitelistedAssetRemoved(address indexed asset);
+        event AssetDecommissioningInitiated(address indexed asset);
+        event OrionVaultAdded(address indexed vault);
+        event RiskFreeRateUpdated(uint16 indexed riskFreeRate);
+        event MinDepositAmountUpdated(uint256 indexed minDepositAmount);
+        event MinRedeemAmountUpdated(uint256 indexed minRedeemAmount);
+        event FeeChangeCooldownDurationUpdated(uint256 indexed newCooldownDuration);
+        event MaxFulfillBatchSizeUpdated(uint256 indexed maxFulfillBatchSize);
+        event VaultFeeChangeScheduled(
+            uint8 indexed feeType,
+            uint16 indexed performanceFee,
+            uint16 indexed managementFee,
+            uint256 newFeeRatesTimestamp
+        );
+        event ProtocolFeeChangeScheduled(
+            uint16 indexed vFeeCoefficient,
+            uint16 indexed rsFeeCoefficient,
+            uint256 indexed newProtocolFeeRatesTimestamp
+        );
+        event GuardianUpdated(address indexed guardian);
+        event ProtocolPaused(address indexed pauser);
+        event ProtocolUnpaused(address indexed unpauser);
+        event ManagerAdded(address indexed manager);
+        event ManagerRemoved(address indexed manager);
+        event OrderSubmitted(address indexed strategist, address[] assets, uint256[] weights);
+        event VaultStateUpdated(
+            uint256 indexed newTotalAssets,
+            uint256 indexed totalSupply,
+            uint256 indexed currentSharePrice,
+            uint256 highWaterMark,
+            address[] tokens,
+            uint256[] shares
+        );
+        event AutomationRegistryUpdated(address indexed newAutomationRegistry);
+        event SP1VerifierUpdated(address indexed newVerifier);
+        event VKeyUpdated(bytes32 indexed vKey);
+        event EpochStart(uint256 indexed epochCounter, address[] assets, uint256[] prices);
+        event EpochStateCommitted(uint256 indexed epochCounter, bytes32 indexed epochStateCommitment);
+        event EpochEnd(uint256 indexed epochCounter, uint256 nettedRebalanceVolumeUnderlying);
+        event EpochSellExecuted(
+            uint256 indexed epochCounter,
+            address indexed asset,
+            uint256 indexed executionUnderlyingAmount,
+            uint256 sharesAmount,
+            uint256 estimatedUnderlyingAmount
+        );
+        event EpochBuyExecuted(
+            uint256 indexed epochCounter,
+            address indexed asset,
+            uint256 indexed executionUnderlyingAmount,
+            uint256 sharesAmount,
+            uint256 estimatedUnderlyingAmount
+        );
+        event PriceAdapterSet(address indexed asset, address indexed adapter);
+        event ExecutionAdapterSet(address indexed asset, address indexed adapter);
+        event ProtocolFeesAccrued(uint256 indexed epochProtocolFees);
+        event ProtocolFeesClaimed(uint256 indexed amount);
+        event LiquidityDeposited(address indexed depositor, uint256 indexed amount);
+        event LiquidityWithdrawn(address indexed withdrawer, uint256 indexed amount);
+        event OrionVaultCreated(
+            address indexed vault,
+            address indexed manager,
+            address indexed strategist,
+            string name,
+            string symbol,
+            uint8 feeType,
+            uint16 performanceFee,
+            uint16 managementFee,
+            address depositAccessControl,
+            VaultType vaultType
+        );
+        event VaultDecommissioningInitiated(address indexed vault);
+        event OrionVaultDecommissioned(address indexed vault);
+        event VaultBeaconUpdated(address indexed newBeacon);
+        event UpgradeTimelockSet(address indexed proxy, address indexed timelock);
+    }
+
+    /// `IOrionVault` events, including the ERC-7540 request and operator events.
+    ///
+    /// Fulfillments are read from `DepositClaimable` / `RedeemClaimable`; the ERC-4626 `Deposit`
+    /// and `Withdraw` events only mark later claims and are not indexed.
+    #[sol(all_derives)]
+    interface IOrionVault {
+        event DepositRequest(
+            address indexed controller,
+            address indexed owner,
+            uint256 indexed requestId,
+            address sender,
+            uint256 assets
+        );
+        event DepositRequestCancelled(address indexed user, uint256 indexed amount);
+        event RedeemRequest(
+            address indexed controller,
+            address indexed owner,
+            uint256 indexed requestId,
+            address sender,
+            uint256 shares
+        );
+        event RedeemRequestCancelled(address indexed user, uint256 indexed shares);
+        event StrategistUpdated(address indexed newStrategist);
+        event VaultFeeModelUpdated(uint8 indexed mode, uint16 indexed performanceFee, uint16 indexed managementFee);
+        event DepositClaimable(address indexed controller, uint256 indexed requestId, uint256 assets, uint256 shares);
+        event RedeemClaimable(address indexed controller, uint256 indexed requestId, uint256 assets, uint256 shares);
+        event OperatorSet(address indexed controller, address indexed operator, bool approved);
+        event VaultFeesAccrued(uint256 indexed managementFee, uint256 indexed performanceFee);
+        event VaultFeesClaimed(address indexed recipient, uint256 indexed feeAmount);
+        event DepositAccessControlUpdated(address indexed newDepositAccessControl);
+    }
+
+    #[sol(rpc)]
+    interface IOrionConfig {
+        function liquidityOrchestrator() external view returns (address);
+        function priceAdapterRegistry() external view returns (address);
+        function transparentVaultFactory() external view returns (address);
+    }
+
+    #[sol(rpc)]
+    interface ILiquidityOrchestrator {
+        function epochCounter() external view returns (uint256);
+    }
+}
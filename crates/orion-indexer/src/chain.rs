@@ -0,0 +1,93 @@
+//! RPC reads: contract discovery, block headers and logs.
+
+use alloy::{
+    eips::{BlockId, BlockNumberOrTag},
+    primitives::Address,
+    providers::{DynProvider, Provider},
+    rpc::types::{Filter, Log},
+};
+use anyhow::Context;
+
+use crate::bindings::{ILiquidityOrchestrator, IOrionConfig};
+use crate::store::BlockRef;
+
+/// Protocol contracts whose logs are always followed; vaults are added as they are registered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Contracts {
+    /// `OrionConfig`
+    pub config: Address,
+    /// `LiquidityOrchestrator`
+    pub orchestrator: Address,
+    /// `PriceAdapterRegistry`
+    pub price_adapter_registry: Address,
+    /// `TransparentVaultFactory`, once set on the config.
+    pub transparent_vault_factory: Option<Address>,
+}
+
+impl Contracts {
+    /// Addresses to filter logs on.
+    pub fn addresses(&self) -> Vec<Address> {
+        [Some(self.config), Some(self.orchestrator), Some(self.price_adapter_registry), self.transparent_vault_factory]
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+}
+
+/// Read-only connection to one deployment.
+#[derive(Clone, Debug)]
+pub struct Chain {
+    provider: DynProvider,
+}
+
+impl Chain {
+    /// Wraps a provider.
+    pub fn new(provider: DynProvider) -> Self {
+        Self { provider }
+    }
+
+    /// Resolves the protocol contracts from `OrionConfig`.
+    pub async fn contracts(&self, config: Address) -> anyhow::Result<Contracts> {
+        let orion_config = IOrionConfig::new(config, self.provider.clone());
+        let factory = orion_config.transparentVaultFactory().call().await?;
+        Ok(Contracts {
+            config,
+            orchestrator: orion_config.liquidityOrchestrator().call().await?,
+            price_adapter_registry: orion_config.priceAdapterRegistry().call().await?,
+            transparent_vault_factory: (!factory.is_zero()).then_some(factory),
+        })
+    }
+
+    /// Latest block number.
+    pub async fn head(&self) -> anyhow::Result<u64> {
+        Ok(self.provider.get_block_number().await?)
+    }
+
+    /// Hash and timestamp of block `number`.
+    pub async fn block(&self, number: u64) -> anyhow::Result<BlockRef> {
+        let block = self
+            .provider
+            .get_block_by_number(BlockNumberOrTag::Number(number))
+            .await?
+            .with_context(|| format!("block {number} not found"))?;
+        Ok(BlockRef { number, hash: block.header.hash, timestamp: block.header.timestamp })
+    }
+
+    /// Logs emitted by `addresses` in `from..=to`.
+    pub async fn logs(&self, addresses: Vec<Address>, from: u64, to: u64) -> anyhow::Result<Vec<Log>> {
+        if addresses.is_empty() {
+            return Ok(Vec::new());
+        }
+        let filter = Filter::new().address(addresses).from_block(from).to_block(to);
+        Ok(self.provider.get_logs(&filter).await?)
+    }
+
+    /// `epochCounter` at the end of block `number`, or 0 before the orchestrator was deployed.
+    pub async fn epoch_counter(&self, orchestrator: Address, number: u64) -> anyhow::Result<u64> {
+        let orchestrator = ILiquidityOrchestrator::new(orchestrator, self.provider.clone());
+        if self.provider.get_code_at(*orchestrator.address()).block_id(BlockId::number(number)).await?.is_empty() {
+            return Ok(0);
+        }
+        Ok(orchestrator.epochCounter().block(BlockId::number(number)).call().await?.saturating_to())
+    }
+}
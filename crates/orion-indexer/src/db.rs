@@ -0,0 +1,331 @@
+//! Minimal SQL backend abstraction over SQLite and Postgres.
+//!
+//! Statements are written once, with `?` placeholders and the portable subset of SQL both engines
+//! accept (`BIGINT`, `TEXT`, `ON CONFLICT DO NOTHING`). Every `uint256` is stored as a decimal
+//! `TEXT` and aggregated in Rust, since neither engine has a native 256-bit integer.
+
+use std::fmt;
+
+use alloy::primitives::{Address, B256, U256};
+use anyhow::{bail, Context};
+
+/// A bound parameter or a returned column.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Value {
+    /// SQL `NULL`; only ever returned, never bound.
+    Null,
+    /// `BIGINT`
+    Int(i64),
+    /// `TEXT`
+    Text(String),
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<u64> for Value {
+    fn from(value: u64) -> Self {
+        // Block numbers and log indices; both fit comfortably in a signed 64-bit column.
+        Self::Int(value as i64)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_owned())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<Address> for Value {
+    /// Lowercase hex, so lookups do not depend on checksum casing.
+    fn from(value: Address) -> Self {
+        Self::Text(format!("{value:#x}"))
+    }
+}
+
+impl From<B256> for Value {
+    fn from(value: B256) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<U256> for Value {
+    fn from(value: U256) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Null => f.write_str("NULL"),
+            Self::Int(value) => write!(f, "{value}"),
+            Self::Text(value) => f.write_str(value),
+        }
+    }
+}
+
+/// One result row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Row(pub Vec<Value>);
+
+impl Row {
+    fn get(&self, index: usize) -> anyhow::Result<&Value> {
+        self.0.get(index).with_context(|| format!("column {index} out of range"))
+    }
+
+    /// Reads a `BIGINT` column.
+    pub fn int(&self, index: usize) -> anyhow::Result<i64> {
+        match self.get(index)? {
+            Value::Int(value) => Ok(*value),
+            other => bail!("column {index} is not an integer: {other:?}"),
+        }
+    }
+
+    /// Reads a nullable `BIGINT` column.
+    pub fn opt_int(&self, index: usize) -> anyhow::Result<Option<i64>> {
+        match self.get(index)? {
+            Value::Null => Ok(None),
+            _ => self.int(index).map(Some),
+        }
+    }
+
+    /// Reads a `TEXT` column.
+    pub fn text(&self, index: usize) -> anyhow::Result<&str> {
+        match self.get(index)? {
+            Value::Text(value) => Ok(value),
+            other => bail!("column {index} is not text: {other:?}"),
+        }
+    }
+
+    /// Reads a `uint256` stored as decimal text.
+    pub fn u256(&self, index: usize) -> anyhow::Result<U256> {
+        let text = self.text(index)?;
+        text.parse().with_context(|| format!("column {index} is not a uint256: {text}"))
+    }
+
+    /// Reads an address stored as hex text.
+    pub fn address(&self, index: usize) -> anyhow::Result<Address> {
+        let text = self.text(index)?;
+        text.parse().with_context(|| format!("column {index} is not an address: {text}"))
+    }
+}
+
+/// A blocking SQL connection.
+pub trait Backend: Send {
+    /// Runs one statement and returns the number of affected rows.
+    fn execute(&mut self, sql: &str, params: &[Value]) -> anyhow::Result<u64>;
+
+    /// Runs one query and collects every row.
+    fn query(&mut self, sql: &str, params: &[Value]) -> anyhow::Result<Vec<Row>>;
+
+    /// Runs several parameterless statements separated by `;`.
+    fn execute_batch(&mut self, sql: &str) -> anyhow::Result<()>;
+}
+
+/// Opens `postgres://` / `postgresql://` URLs with the Postgres backend and anything else
+/// (optionally prefixed with `sqlite://`) as a SQLite file; `:memory:` is accepted.
+pub fn open(url: &str) -> anyhow::Result<Box<dyn Backend>> {
+    if url.starts_with("postgres://") || url.starts_with("postgresql://") {
+        return open_postgres(url);
+    }
+    let path = url.strip_prefix("sqlite://").unwrap_or(url);
+    Ok(Box::new(Sqlite::open(path)?))
+}
+
+#[cfg(feature = "postgres")]
+fn open_postgres(url: &str) -> anyhow::Result<Box<dyn Backend>> {
+    Ok(Box::new(Postgres::connect(url)?))
+}
+
+#[cfg(not(feature = "postgres"))]
+fn open_postgres(_url: &str) -> anyhow::Result<Box<dyn Backend>> {
+    bail!("this build has no Postgres support; rebuild with `--features postgres`")
+}
+
+/// SQLite through `rusqlite`.
+pub struct Sqlite {
+    conn: rusqlite::Connection,
+}
+
+impl Sqlite {
+    /// Opens (or creates) a database file.
+    pub fn open(path: &str) -> anyhow::Result<Self> {
+        let conn = if path == ":memory:" {
+            rusqlite::Connection::open_in_memory()?
+        } else {
+            rusqlite::Connection::open(path).with_context(|| format!("opening {path}"))?
+        };
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        Ok(Self { conn })
+    }
+}
+
+fn sqlite_params(params: &[Value]) -> Vec<rusqlite::types::Value> {
+    params
+        .iter()
+        .map(|value| match value {
+            Value::Null => rusqlite::types::Value::Null,
+            Value::Int(value) => rusqlite::types::Value::Integer(*value),
+            Value::Text(value) => rusqlite::types::Value::Text(value.clone()),
+        })
+        .collect()
+}
+
+impl Backend for Sqlite {
+    fn execute(&mut self, sql: &str, params: &[Value]) -> anyhow::Result<u64> {
+        let changed = self.conn.execute(sql, rusqlite::params_from_iter(sqlite_params(params)))?;
+        Ok(changed as u64)
+    }
+
+    fn query(&mut self, sql: &str, params: &[Value]) -> anyhow::Result<Vec<Row>> {
+        let mut statement = self.conn.prepare_cached(sql)?;
+        let columns = statement.column_count();
+        let rows = statement.query_map(rusqlite::params_from_iter(sqlite_params(params)), |row| {
+            (0..columns)
+                .map(|i| {
+                    Ok(match row.get_ref(i)? {
+                        rusqlite::types::ValueRef::Null => Value::Null,
+                        rusqlite::types::ValueRef::Integer(value) => Value::Int(value),
+                        rusqlite::types::ValueRef::Text(value) => Value::Text(String::from_utf8_lossy(value).into()),
+                        other => {
+                            return Err(rusqlite::Error::InvalidColumnType(i, format!("{other:?}"), other.data_type()))
+                        }
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(Row)
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    fn execute_batch(&mut self, sql: &str) -> anyhow::Result<()> {
+        Ok(self.conn.execute_batch(sql)?)
+    }
+}
+
+/// Rewrites `?` placeholders into Postgres' numbered `$1, $2, ...`.
+///
+/// Statements in this crate never contain a literal `?`, so no quoting rules are needed.
+pub fn numbered_placeholders(sql: &str) -> String {
+    let mut out = String::with_capacity(sql.len() + 8);
+    let mut next = 1;
+    for c in sql.chars() {
+        if c == '?' {
+            out.push('$');
+            out.push_str(&next.to_string());
+            next += 1;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Postgres through the blocking `postgres` client.
+///
+/// The client drives its own runtime, so it must not be called from inside a Tokio task; the
+/// indexer keeps all database work outside of `block_on` for that reason.
+#[cfg(feature = "postgres")]
+pub struct Postgres {
+    client: postgres::Client,
+}
+
+#[cfg(feature = "postgres")]
+impl Postgres {
+    /// Connects without TLS; put a TLS-terminating proxy in front of remote databases.
+    pub fn connect(url: &str) -> anyhow::Result<Self> {
+        let client = postgres::Client::connect(url, postgres::NoTls).context("connecting to Postgres")?;
+        Ok(Self { client })
+    }
+}
+
+#[cfg(feature = "postgres")]
+fn postgres_params(params: &[Value]) -> anyhow::Result<Vec<Box<dyn postgres::types::ToSql + Sync>>> {
+    params
+        .iter()
+        .map(|value| -> anyhow::Result<Box<dyn postgres::types::ToSql + Sync>> {
+            match value {
+                Value::Null => bail!("NULL parameters are not supported"),
+                Value::Int(value) => Ok(Box::new(*value)),
+                Value::Text(value) => Ok(Box::new(value.clone())),
+            }
+        })
+        .collect()
+}
+
+#[cfg(feature = "postgres")]
+impl Backend for Postgres {
+    fn execute(&mut self, sql: &str, params: &[Value]) -> anyhow::Result<u64> {
+        let params = postgres_params(params)?;
+        let refs: Vec<_> = params.iter().map(|param| param.as_ref() as &(dyn postgres::types::ToSql + Sync)).collect();
+        Ok(self.client.execute(numbered_placeholders(sql).as_str(), &refs)?)
+    }
+
+    fn query(&mut self, sql: &str, params: &[Value]) -> anyhow::Result<Vec<Row>> {
+        use postgres::types::Type;
+
+        let params = postgres_params(params)?;
+        let refs: Vec<_> = params.iter().map(|param| param.as_ref() as &(dyn postgres::types::ToSql + Sync)).collect();
+        self.client
+            .query(numbered_placeholders(sql).as_str(), &refs)?
+            .iter()
+            .map(|row| {
+                (0..row.len())
+                    .map(|i| {
+                        let ty = row.columns()[i].type_();
+                        Ok(if *ty == Type::INT8 {
+                            row.get::<_, Option<i64>>(i).map_or(Value::Null, Value::Int)
+                        } else if *ty == Type::INT4 {
+                            row.get::<_, Option<i32>>(i).map_or(Value::Null, |value| Value::Int(value.into()))
+                        } else if *ty == Type::TEXT || *ty == Type::VARCHAR {
+                            row.get::<_, Option<String>>(i).map_or(Value::Null, Value::Text)
+                        } else {
+                            bail!("unsupported column type {ty}")
+                        })
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()
+                    .map(Row)
+            })
+            .collect()
+    }
+
+    fn execute_batch(&mut self, sql: &str) -> anyhow::Result<()> {
+        Ok(self.client.batch_execute(sql)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrites_placeholders_in_order() {
+        assert_eq!(
+            numbered_placeholders("SELECT a FROM t WHERE b = ? AND c > ?"),
+            "SELECT a FROM t WHERE b = $1 AND c > $2"
+        );
+    }
+
+    #[test]
+    fn sqlite_round_trips_values() {
+        let mut db = open(":memory:").unwrap();
+        db.execute_batch("CREATE TABLE t (n BIGINT, s TEXT)").unwrap();
+        let amount = U256::from(10).pow(U256::from(30));
+        db.execute("INSERT INTO t (n, s) VALUES (?, ?)", &[Value::from(7u64), Value::from(amount)]).unwrap();
+
+        let rows = db.query("SELECT n, s, NULL FROM t", &[]).unwrap();
+        assert_eq!(rows[0].int(0).unwrap(), 7);
+        assert_eq!(rows[0].u256(1).unwrap(), amount);
+        assert_eq!(rows[0].opt_int(2).unwrap(), None);
+    }
+}
@@ -0,0 +1,170 @@
+//! Decoding raw logs and attributing them to epochs.
+
+use alloy::{
+    primitives::{Address, Bytes, B256},
+    sol_types::SolEventInterface,
+};
+
+use crate::bindings::{
+    EventsLib::{self, EventsLibEvents},
+    IOrionVault::IOrionVaultEvents,
+};
+
+/// A log decoded against `EventsLib` or `IOrionVault`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OrionEvent {
+    /// Emitted by the config, orchestrator, factory, price adapter registry or a vault.
+    Protocol(EventsLibEvents),
+    /// Emitted by a vault.
+    Vault(IOrionVaultEvents),
+}
+
+impl OrionEvent {
+    /// Decodes a log, or `None` for events outside both interfaces (ERC-20 transfers, upgrades).
+    pub fn decode(topics: &[B256], data: &[u8]) -> Option<(&'static str, Self)> {
+        let selector = topics.first()?.0;
+        if let Some(name) = EventsLibEvents::name_by_selector(selector) {
+            return EventsLibEvents::decode_raw_log(topics, data).ok().map(|event| (name, Self::Protocol(event)));
+        }
+        let name = IOrionVaultEvents::name_by_selector(selector)?;
+        IOrionVaultEvents::decode_raw_log(topics, data).ok().map(|event| (name, Self::Vault(event)))
+    }
+
+    /// Vault registered by this event, if any; its own logs must be indexed from here on.
+    pub fn registered_vault(&self) -> Option<Address> {
+        match self {
+            Self::Protocol(EventsLibEvents::OrionVaultAdded(EventsLib::OrionVaultAdded { vault }))
+            | Self::Protocol(EventsLibEvents::OrionVaultCreated(EventsLib::OrionVaultCreated { vault, .. })) => {
+                Some(*vault)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// One decoded log with its position in the chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IndexedLog {
+    /// Block the log was mined in.
+    pub block_number: u64,
+    /// Hash of that block.
+    pub block_hash: B256,
+    /// Block timestamp, when the node includes it in `eth_getLogs`.
+    pub block_timestamp: Option<u64>,
+    /// Position of the log in the block.
+    pub log_index: u64,
+    /// Emitting transaction.
+    pub tx_hash: B256,
+    /// Emitting contract.
+    pub address: Address,
+    /// Epoch the log belongs to (see [`EpochTracker`]).
+    pub epoch: u64,
+    /// Event name, e.g. `VaultStateUpdated`.
+    pub name: &'static str,
+    /// Raw topics, kept for events without a normalized table.
+    pub topics: Vec<B256>,
+    /// Raw data.
+    pub data: Bytes,
+    /// Decoded event.
+    pub event: OrionEvent,
+}
+
+/// Labels logs with the `epochCounter` they belong to.
+///
+/// Everything between `EpochStart(n)` and `EpochEnd(n)` (fees, redemptions, state updates) belongs
+/// to epoch `n`; requests made while idle belong to the epoch that will process them, which is
+/// the on-chain `epochCounter` at that point.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EpochTracker {
+    counter: u64,
+}
+
+impl EpochTracker {
+    /// Starts from the orchestrator's `epochCounter` before the first log.
+    pub fn new(counter: u64) -> Self {
+        Self { counter }
+    }
+
+    /// Returns the epoch of `event` and advances past `EpochEnd`.
+    pub fn observe(&mut self, event: &OrionEvent) -> u64 {
+        match event {
+            OrionEvent::Protocol(EventsLibEvents::EpochStart(start)) => {
+                self.counter = start.epochCounter.saturating_to();
+                self.counter
+            }
+            OrionEvent::Protocol(EventsLibEvents::EpochEnd(end)) => {
+                let epoch = end.epochCounter.saturating_to();
+                self.counter = epoch + 1;
+                epoch
+            }
+            _ => self.counter,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        primitives::{address, U256},
+        sol_types::SolEvent,
+    };
+
+    use super::*;
+    use crate::bindings::IOrionVault;
+
+    fn decode(event: &impl SolEvent) -> (&'static str, OrionEvent) {
+        let data = event.encode_log_data();
+        OrionEvent::decode(data.topics(), &data.data).unwrap()
+    }
+
+    #[test]
+    fn decodes_protocol_and_vault_events() {
+        let (name, event) = decode(&EventsLib::EpochEnd {
+            epochCounter: U256::from(3),
+            nettedRebalanceVolumeUnderlying: U256::from(10),
+        });
+        assert_eq!(name, "EpochEnd");
+        assert!(matches!(event, OrionEvent::Protocol(EventsLibEvents::EpochEnd(_))));
+
+        let (name, event) = decode(&IOrionVault::RedeemClaimable {
+            controller: address!("00000000000000000000000000000000000000c1"),
+            requestId: U256::ZERO,
+            assets: U256::from(5),
+            shares: U256::from(4),
+        });
+        assert_eq!(name, "RedeemClaimable");
+        assert!(matches!(event, OrionEvent::Vault(IOrionVaultEvents::RedeemClaimable(_))));
+    }
+
+    #[test]
+    fn ignores_foreign_events() {
+        let topic = alloy::primitives::keccak256("Transfer(address,address,uint256)");
+        assert_eq!(OrionEvent::decode(&[topic], &[]), None);
+        assert_eq!(OrionEvent::decode(&[], &[]), None);
+    }
+
+    #[test]
+    fn attributes_vault_activity_to_the_running_epoch() {
+        let mut tracker = EpochTracker::new(0);
+        let user = address!("00000000000000000000000000000000000000c1");
+        let request = decode(&IOrionVault::DepositRequest {
+            controller: user,
+            owner: user,
+            requestId: U256::ZERO,
+            sender: user,
+            assets: U256::from(1),
+        })
+        .1;
+        let start = decode(&EventsLib::EpochStart { epochCounter: U256::ZERO, assets: vec![], prices: vec![] }).1;
+        let fees =
+            decode(&IOrionVault::VaultFeesAccrued { managementFee: U256::from(1), performanceFee: U256::ZERO }).1;
+        let end =
+            decode(&EventsLib::EpochEnd { epochCounter: U256::ZERO, nettedRebalanceVolumeUnderlying: U256::ZERO }).1;
+
+        assert_eq!(tracker.observe(&request), 0);
+        assert_eq!(tracker.observe(&start), 0);
+        assert_eq!(tracker.observe(&fees), 0);
+        assert_eq!(tracker.observe(&end), 0);
+        assert_eq!(tracker.observe(&request), 1);
+    }
+}
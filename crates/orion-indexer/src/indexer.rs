@@ -0,0 +1,207 @@
+//! The indexing loop: reorg check, batch fetch, decode, store.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::Duration,
+};
+
+use alloy::{primitives::Address, rpc::types::Log};
+use anyhow::{bail, Context};
+use tokio::runtime::Runtime;
+use tracing::{info, warn};
+
+use crate::chain::{Chain, Contracts};
+use crate::events::{EpochTracker, IndexedLog, OrionEvent};
+use crate::store::{BlockRef, Store};
+
+/// How far back a reorg is searched for before giving up.
+const REORG_SEARCH_DEPTH: u64 = 256;
+
+/// Tuning knobs of the loop.
+#[derive(Clone, Copy, Debug)]
+pub struct IndexerOptions {
+    /// First block to index when the database is empty (the protocol deployment block).
+    pub from_block: u64,
+    /// Maximum blocks per `eth_getLogs` request.
+    pub batch_size: u64,
+    /// Blocks behind the head to stay; reorgs within this window are never seen.
+    pub confirmations: u64,
+    /// Sleep between polls once caught up.
+    pub poll_interval: Duration,
+}
+
+/// What one [`Indexer::step`] did.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Step {
+    /// Indexed `from..=to`.
+    Indexed {
+        /// First block of the batch.
+        from: u64,
+        /// Last block of the batch.
+        to: u64,
+        /// Decoded logs stored.
+        logs: usize,
+    },
+    /// The stored tip was orphaned; rows after `ancestor` were deleted.
+    Reorged {
+        /// Last block still on the canonical chain.
+        ancestor: u64,
+    },
+    /// Nothing new up to the confirmed head.
+    CaughtUp,
+}
+
+/// Follows one deployment into a [`Store`].
+///
+/// The store is blocking (SQLite, or the Postgres client which runs its own runtime), so the loop
+/// is synchronous and drives the async RPC calls on an owned runtime instead of the other way
+/// round.
+pub struct Indexer {
+    runtime: Runtime,
+    chain: Chain,
+    contracts: Contracts,
+    store: Store,
+    options: IndexerOptions,
+}
+
+impl Indexer {
+    /// Resolves the protocol contracts from `config`.
+    pub fn new(
+        runtime: Runtime,
+        chain: Chain,
+        config: Address,
+        store: Store,
+        options: IndexerOptions,
+    ) -> anyhow::Result<Self> {
+        let contracts = runtime.block_on(chain.contracts(config))?;
+        info!(?contracts, "following protocol contracts");
+        Ok(Self { runtime, chain, contracts, store, options })
+    }
+
+    /// Indexes until Ctrl-C.
+    pub fn run(&mut self) -> anyhow::Result<()> {
+        loop {
+            match self.step() {
+                Ok(Step::CaughtUp) => {}
+                Ok(step) => {
+                    info!(?step);
+                    continue;
+                }
+                Err(err) => warn!("indexing step failed: {err:#}"),
+            }
+            let stopped = self.runtime.block_on(async {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => true,
+                    _ = tokio::time::sleep(self.options.poll_interval) => false,
+                }
+            });
+            if stopped {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Handles a reorg or indexes the next batch.
+    pub fn step(&mut self) -> anyhow::Result<Step> {
+        let tip = self.store.tip()?;
+        if let Some(tip) = tip {
+            let canonical = self.runtime.block_on(self.chain.block(tip.number))?;
+            if canonical.hash != tip.hash {
+                let ancestor = self.find_ancestor()?;
+                warn!(tip = tip.number, ancestor, "reorg detected, rolling back");
+                self.store.rollback(ancestor)?;
+                return Ok(Step::Reorged { ancestor });
+            }
+        }
+
+        let head = self.runtime.block_on(self.chain.head())?.saturating_sub(self.options.confirmations);
+        let from = tip.map_or(self.options.from_block, |tip| tip.number + 1);
+        if from > head {
+            return Ok(Step::CaughtUp);
+        }
+        let to = head.min(from + self.options.batch_size.max(1) - 1);
+
+        // Read the end of the range before its logs: if the chain moves in between, the stored
+        // hash is the stale one and the next step rolls the batch back.
+        let end = self.runtime.block_on(self.chain.block(to))?;
+        let vaults = self.store.vaults()?;
+        let logs = self.fetch(vaults, from, to)?;
+
+        let counter =
+            self.runtime.block_on(self.chain.epoch_counter(self.contracts.orchestrator, from.saturating_sub(1)))?;
+        let mut tracker = EpochTracker::new(counter);
+        let indexed: Vec<IndexedLog> = logs
+            .into_iter()
+            .filter_map(|log| {
+                let (name, event) = OrionEvent::decode(log.topics(), &log.data().data)?;
+                Some((log, name, event))
+            })
+            .map(|(log, name, event)| {
+                Ok(IndexedLog {
+                    block_number: log.block_number.context("pending log")?,
+                    block_hash: log.block_hash.context("pending log")?,
+                    block_timestamp: log.block_timestamp,
+                    log_index: log.log_index.context("pending log")?,
+                    tx_hash: log.transaction_hash.context("pending log")?,
+                    address: log.address(),
+                    epoch: tracker.observe(&event),
+                    name,
+                    topics: log.topics().to_vec(),
+                    data: log.data().data.clone(),
+                    event,
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        let blocks = self.blocks(&indexed, end)?;
+        self.store.apply(&blocks, &indexed)?;
+        Ok(Step::Indexed { from, to, logs: indexed.len() })
+    }
+
+    /// Logs of the protocol contracts and every vault, including vaults registered in this range.
+    fn fetch(&self, known_vaults: Vec<Address>, from: u64, to: u64) -> anyhow::Result<Vec<Log>> {
+        let mut followed: BTreeSet<Address> = self.contracts.addresses().into_iter().chain(known_vaults).collect();
+        let mut pending: Vec<Address> = followed.iter().copied().collect();
+        let mut logs = Vec::new();
+        while !pending.is_empty() {
+            let batch = self.runtime.block_on(self.chain.logs(pending, from, to))?;
+            pending = batch
+                .iter()
+                .filter_map(|log| OrionEvent::decode(log.topics(), &log.data().data)?.1.registered_vault())
+                .filter(|vault| followed.insert(*vault))
+                .collect();
+            logs.extend(batch);
+        }
+        logs.sort_by_key(|log| (log.block_number, log.log_index));
+        Ok(logs)
+    }
+
+    /// Block refs for every block with an indexed log, plus the end of the range.
+    fn blocks(&self, logs: &[IndexedLog], end: BlockRef) -> anyhow::Result<Vec<BlockRef>> {
+        let mut blocks = BTreeMap::new();
+        for log in logs {
+            let (number, hash) = (log.block_number, log.block_hash);
+            if number == end.number && hash != end.hash {
+                bail!("block {number} changed while indexing, retrying");
+            }
+            let timestamp = match log.block_timestamp {
+                Some(timestamp) => timestamp,
+                None if blocks.contains_key(&number) => continue,
+                None => self.runtime.block_on(self.chain.block(number))?.timestamp,
+            };
+            blocks.insert(number, BlockRef { number, hash, timestamp });
+        }
+        blocks.insert(end.number, end);
+        Ok(blocks.into_values().collect())
+    }
+
+    /// Newest stored block that is still canonical.
+    fn find_ancestor(&mut self) -> anyhow::Result<u64> {
+        for block in self.store.recent_blocks(REORG_SEARCH_DEPTH)? {
+            if self.runtime.block_on(self.chain.block(block.number))?.hash == block.hash {
+                return Ok(block.number);
+            }
+        }
+        bail!("reorg deeper than the last {REORG_SEARCH_DEPTH} indexed blocks; re-index from scratch")
+    }
+}
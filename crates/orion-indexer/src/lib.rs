@@ -0,0 +1,31 @@
+//! Indexer for Orion protocol events.
+//!
+//! Follows `OrionConfig`, `LiquidityOrchestrator`, the price adapter registry, the vault factory
+//! and every registered vault, decodes each `EventsLib` / `IOrionVault` event and writes it to
+//! normalized tables in SQLite or Postgres:
+//!
+//! - epochs: `EpochStart` (with prices), `EpochStateCommitted`, `EpochSellExecuted` /
+//!   `EpochBuyExecuted`, `ProtocolFeesAccrued`, `EpochEnd`;
+//! - vaults: `OrionVaultCreated`, lifecycle (added, decommissioning, decommissioned),
+//!   `VaultStateUpdated`, requests, `DepositClaimable` / `RedeemClaimable`, `VaultFeesAccrued`;
+//! - assets: whitelisting and decommissioning.
+//!
+//! Every decoded log is also kept verbatim in `events`. Rows are keyed by the log position, so a
+//! reorg is undone by deleting everything after the last canonical block and indexing resumes
+//! from the stored tip. The [`query`] module exposes the per-epoch and per-vault views dashboards
+//! need without re-scanning the chain.
+
+pub mod bindings;
+pub mod chain;
+pub mod db;
+pub mod events;
+pub mod indexer;
+pub mod query;
+pub mod schema;
+pub mod store;
+
+pub use chain::{Chain, Contracts};
+pub use events::{EpochTracker, IndexedLog, OrionEvent};
+pub use indexer::{Indexer, IndexerOptions, Step};
+pub use query::{AssetSlippage, EpochSummary, SharePricePoint, VaultFeesPoint, VaultFeesReport};
+pub use store::{BlockRef, Store};
@@ -0,0 +1,133 @@
+//! `orion-indexer` follows a deployment into a database and answers dashboard queries from it.
+//!
+//! ```text
+//! orion-indexer --database orion.db run --rpc-url http://127.0.0.1:8545 --config 0x... --from-block <fromBlock>
+//! orion-indexer --database orion.db epochs
+//! orion-indexer --database orion.db share-prices 0x<vault>
+//! orion-indexer --database orion.db vault-fees 0x<vault>
+//! orion-indexer --database orion.db slippage [--epoch 3]
+//! ```
+//!
+//! `--database` takes a SQLite path or, when built with `--features postgres`, a `postgres://` URL.
+//! Query subcommands print JSON.
+
+use std::time::Duration;
+
+use alloy::{
+    primitives::Address,
+    providers::{Provider, ProviderBuilder},
+};
+use anyhow::Context;
+use clap::{Parser, Subcommand};
+use orion_indexer::{Chain, Indexer, IndexerOptions, Store};
+use serde::Serialize;
+use tracing_subscriber::EnvFilter;
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Index Orion protocol events and query per-epoch and per-vault history")]
+struct Cli {
+    /// SQLite file or `postgres://` URL.
+    #[arg(long, env = "ORION_INDEXER_DATABASE", default_value = "orion-indexer.db")]
+    database: String,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Follow the chain, resuming from the last indexed block.
+    Run {
+        /// JSON-RPC endpoint.
+        #[arg(long, env = "ORION_RPC_URL", default_value = "http://127.0.0.1:8545")]
+        rpc_url: String,
+
+        /// `OrionConfig` proxy address; the other contracts are resolved from it.
+        #[arg(long, env = "ORION_CONFIG")]
+        config: Address,
+
+        /// First block to index when the database is empty.
+        #[arg(long, default_value_t = 0)]
+        from_block: u64,
+
+        /// Maximum blocks per `eth_getLogs` request.
+        #[arg(long, default_value_t = 2_000)]
+        batch_size: u64,
+
+        /// Blocks to stay behind the head.
+        #[arg(long, default_value_t = 0)]
+        confirmations: u64,
+
+        /// Seconds between polls once caught up.
+        #[arg(long, default_value_t = 12)]
+        poll_interval: u64,
+
+        /// Index until caught up, then exit.
+        #[arg(long)]
+        once: bool,
+    },
+    /// Summary of every epoch.
+    Epochs,
+    /// Summary of one epoch.
+    Epoch {
+        /// `epochCounter`
+        epoch: u64,
+    },
+    /// Share price after each state update of a vault.
+    SharePrices {
+        /// Vault address.
+        vault: Address,
+    },
+    /// Fees accrued by a vault, per epoch.
+    VaultFees {
+        /// Vault address.
+        vault: Address,
+    },
+    /// Estimated versus executed underlying per asset.
+    Slippage {
+        /// Restrict to one epoch.
+        #[arg(long)]
+        epoch: Option<u64>,
+    },
+}
+
+fn print(value: &impl Serialize) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()))
+        .init();
+    let cli = Cli::parse();
+    let mut store = Store::open(&cli.database)?;
+
+    match cli.command {
+        Command::Run { rpc_url, config, from_block, batch_size, confirmations, poll_interval, once } => {
+            let runtime = tokio::runtime::Runtime::new()?;
+            let provider = {
+                let _guard = runtime.enter();
+                ProviderBuilder::new().connect_http(rpc_url.parse().context("invalid --rpc-url")?).erased()
+            };
+            let options = IndexerOptions {
+                from_block,
+                batch_size,
+                confirmations,
+                poll_interval: Duration::from_secs(poll_interval),
+            };
+            let mut indexer = Indexer::new(runtime, Chain::new(provider), config, store, options)?;
+            if once {
+                while indexer.step()? != orion_indexer::Step::CaughtUp {}
+                Ok(())
+            } else {
+                indexer.run()
+            }
+        }
+        Command::Epochs => print(&store.epochs()?),
+        Command::Epoch { epoch } => print(&store.epoch(epoch)?.with_context(|| format!("epoch {epoch} not indexed"))?),
+        Command::SharePrices { vault } => print(&store.share_prices(vault)?),
+        Command::VaultFees { vault } => print(&store.vault_fees(vault)?),
+        Command::Slippage { epoch } => print(&store.slippage(epoch)?),
+    }
+}
@@ -0,0 +1,330 @@
+//! Per-epoch and per-vault read models for dashboards.
+
+use std::collections::BTreeMap;
+
+use alloy::primitives::{Address, I256, U256};
+use serde::Serialize;
+
+use crate::db::{Row, Value};
+use crate::store::Store;
+
+/// One epoch, from `EpochStart` to `EpochEnd`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochSummary {
+    /// `epochCounter`
+    pub epoch: u64,
+    /// Block of `EpochStart`.
+    pub start_block: u64,
+    /// Timestamp of `EpochStart`.
+    pub start_timestamp: u64,
+    /// Block of `EpochEnd`, if the epoch has completed.
+    pub end_block: Option<u64>,
+    /// Timestamp of `EpochEnd`.
+    pub end_timestamp: Option<u64>,
+    /// `EpochStateCommitted` events; more than one means a leg failure rotated the commitment.
+    pub commitments: u64,
+    /// Executed sell and buy orders.
+    pub trades: u64,
+    /// `nettedRebalanceVolumeUnderlying` from `EpochEnd`.
+    pub netted_volume: Option<U256>,
+    /// `ProtocolFeesAccrued` during the epoch.
+    pub protocol_fees: U256,
+    /// Management plus performance fees accrued by all vaults.
+    pub vault_fees: U256,
+}
+
+/// A vault's share price after one `VaultStateUpdated`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharePricePoint {
+    /// Epoch that produced the update.
+    pub epoch: u64,
+    /// Block of the update.
+    pub block_number: u64,
+    /// Timestamp of that block.
+    pub timestamp: u64,
+    /// `newTotalAssets`
+    pub total_assets: U256,
+    /// `totalSupply`
+    pub total_supply: U256,
+    /// `convertToAssets(10 ** decimals())`
+    pub share_price: U256,
+    /// High-water mark after the update.
+    pub high_water_mark: U256,
+}
+
+/// Fees a vault accrued in one epoch.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultFeesPoint {
+    /// Epoch the fees were accrued in.
+    pub epoch: u64,
+    /// Management fee, in underlying units.
+    pub management_fee: U256,
+    /// Performance fee, in underlying units.
+    pub performance_fee: U256,
+}
+
+/// All fees a vault has accrued.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultFeesReport {
+    /// Sum of management fees.
+    pub total_management_fee: U256,
+    /// Sum of performance fees.
+    pub total_performance_fee: U256,
+    /// Per-epoch breakdown.
+    pub epochs: Vec<VaultFeesPoint>,
+}
+
+/// Execution quality of the orders routed for one asset.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetSlippage {
+    /// Whitelisted asset.
+    pub asset: Address,
+    /// Sell orders executed.
+    pub sells: u64,
+    /// Buy orders executed.
+    pub buys: u64,
+    /// Underlying the sell orders were expected to return.
+    pub sell_estimated_underlying: U256,
+    /// Underlying the sell orders actually returned.
+    pub sell_executed_underlying: U256,
+    /// Underlying the buy orders were expected to cost.
+    pub buy_estimated_underlying: U256,
+    /// Underlying the buy orders actually cost.
+    pub buy_executed_underlying: U256,
+    /// Net cost absorbed by the protocol buffer: shortfall on sells plus overspend on buys.
+    /// Negative when execution beat the estimates.
+    pub slippage: I256,
+}
+
+fn signed(value: U256) -> I256 {
+    I256::from_raw(value)
+}
+
+impl Store {
+    /// Every epoch seen so far, oldest first.
+    pub fn epochs(&mut self) -> anyhow::Result<Vec<EpochSummary>> {
+        self.epoch_summaries(None)
+    }
+
+    /// One epoch, if its `EpochStart` has been indexed.
+    pub fn epoch(&mut self, epoch: u64) -> anyhow::Result<Option<EpochSummary>> {
+        Ok(self.epoch_summaries(Some(epoch))?.pop())
+    }
+
+    fn epoch_summaries(&mut self, only: Option<u64>) -> anyhow::Result<Vec<EpochSummary>> {
+        let (filter, params): (&str, Vec<Value>) = match only {
+            Some(epoch) => (" WHERE epoch = ?", vec![epoch.into()]),
+            None => ("", vec![]),
+        };
+        let db = self.db();
+
+        let mut epochs = BTreeMap::new();
+        let starts = db.query(
+            &format!(
+                "SELECT s.epoch, s.block_number, b.timestamp FROM epoch_starts s
+                 JOIN blocks b ON b.block_number = s.block_number{}",
+                filter.replace("epoch", "s.epoch")
+            ),
+            &params,
+        )?;
+        for row in &starts {
+            let epoch = row.int(0)? as u64;
+            epochs.insert(
+                epoch,
+                EpochSummary {
+                    epoch,
+                    start_block: row.int(1)? as u64,
+                    start_timestamp: row.int(2)? as u64,
+                    ..Default::default()
+                },
+            );
+        }
+
+        let ends = db.query(
+            &format!(
+                "SELECT e.epoch, e.block_number, b.timestamp, e.netted_volume FROM epoch_ends e
+                 JOIN blocks b ON b.block_number = e.block_number{}",
+                filter.replace("epoch", "e.epoch")
+            ),
+            &params,
+        )?;
+        for row in &ends {
+            if let Some(summary) = epochs.get_mut(&(row.int(0)? as u64)) {
+                summary.end_block = Some(row.int(1)? as u64);
+                summary.end_timestamp = Some(row.int(2)? as u64);
+                summary.netted_volume = Some(row.u256(3)?);
+            }
+        }
+
+        let count_sql = |table: &str| format!("SELECT epoch, COUNT(*) FROM {table}{filter} GROUP BY epoch");
+        for row in &db.query(&count_sql("epoch_commitments"), &params)? {
+            if let Some(summary) = epochs.get_mut(&(row.int(0)? as u64)) {
+                summary.commitments = row.int(1)? as u64;
+            }
+        }
+        for row in &db.query(&count_sql("epoch_trades"), &params)? {
+            if let Some(summary) = epochs.get_mut(&(row.int(0)? as u64)) {
+                summary.trades = row.int(1)? as u64;
+            }
+        }
+
+        for row in &db.query(&format!("SELECT epoch, amount FROM protocol_fees{filter}"), &params)? {
+            if let Some(summary) = epochs.get_mut(&(row.int(0)? as u64)) {
+                summary.protocol_fees += row.u256(1)?;
+            }
+        }
+        for row in
+            &db.query(&format!("SELECT epoch, management_fee, performance_fee FROM vault_fees{filter}"), &params)?
+        {
+            if let Some(summary) = epochs.get_mut(&(row.int(0)? as u64)) {
+                summary.vault_fees += row.u256(1)? + row.u256(2)?;
+            }
+        }
+
+        Ok(epochs.into_values().collect())
+    }
+
+    /// Share price after every state update of `vault`, oldest first.
+    pub fn share_prices(&mut self, vault: Address) -> anyhow::Result<Vec<SharePricePoint>> {
+        self.db()
+            .query(
+                "SELECT v.epoch, v.block_number, b.timestamp, v.total_assets, v.total_supply, v.share_price,
+                 v.high_water_mark FROM vault_states v JOIN blocks b ON b.block_number = v.block_number
+                 WHERE v.vault = ? ORDER BY v.block_number, v.log_index",
+                &[vault.into()],
+            )?
+            .iter()
+            .map(|row: &Row| {
+                Ok(SharePricePoint {
+                    epoch: row.int(0)? as u64,
+                    block_number: row.int(1)? as u64,
+                    timestamp: row.int(2)? as u64,
+                    total_assets: row.u256(3)?,
+                    total_supply: row.u256(4)?,
+                    share_price: row.u256(5)?,
+                    high_water_mark: row.u256(6)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Fees accrued by `vault`, per epoch and in total.
+    pub fn vault_fees(&mut self, vault: Address) -> anyhow::Result<VaultFeesReport> {
+        let rows = self.db().query(
+            "SELECT epoch, management_fee, performance_fee FROM vault_fees WHERE vault = ?
+             ORDER BY block_number, log_index",
+            &[vault.into()],
+        )?;
+        let mut report = VaultFeesReport::default();
+        for row in &rows {
+            let point = VaultFeesPoint {
+                epoch: row.int(0)? as u64,
+                management_fee: row.u256(1)?,
+                performance_fee: row.u256(2)?,
+            };
+            report.total_management_fee += point.management_fee;
+            report.total_performance_fee += point.performance_fee;
+            match report.epochs.last_mut() {
+                Some(last) if last.epoch == point.epoch => {
+                    last.management_fee += point.management_fee;
+                    last.performance_fee += point.performance_fee;
+                }
+                _ => report.epochs.push(point),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Slippage per asset over all epochs, or a single one.
+    pub fn slippage(&mut self, epoch: Option<u64>) -> anyhow::Result<Vec<AssetSlippage>> {
+        let (filter, params): (&str, Vec<Value>) = match epoch {
+            Some(epoch) => (" WHERE epoch = ?", vec![epoch.into()]),
+            None => ("", vec![]),
+        };
+        let rows = self.db().query(
+            &format!("SELECT asset, side, execution_underlying, estimated_underlying FROM epoch_trades{filter}"),
+            &params,
+        )?;
+
+        let mut assets: BTreeMap<Address, AssetSlippage> = BTreeMap::new();
+        for row in &rows {
+            let asset = row.address(0)?;
+            let entry = assets.entry(asset).or_insert_with(|| AssetSlippage { asset, ..Default::default() });
+            let (executed, estimated) = (row.u256(2)?, row.u256(3)?);
+            if row.text(1)? == "sell" {
+                entry.sells += 1;
+                entry.sell_executed_underlying += executed;
+                entry.sell_estimated_underlying += estimated;
+                entry.slippage += signed(estimated) - signed(executed);
+            } else {
+                entry.buys += 1;
+                entry.buy_executed_underlying += executed;
+                entry.buy_estimated_underlying += estimated;
+                entry.slippage += signed(executed) - signed(estimated);
+            }
+        }
+        Ok(assets.into_values().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::address;
+
+    use super::*;
+    use crate::store::tests::{epoch_zero, VAULT};
+
+    #[test]
+    fn summarizes_epochs() {
+        let mut store = epoch_zero().store();
+        let epochs = store.epochs().unwrap();
+        assert_eq!(epochs.len(), 1);
+        assert_eq!(
+            epochs[0],
+            EpochSummary {
+                epoch: 0,
+                start_block: 3,
+                start_timestamp: 3_000,
+                end_block: Some(6),
+                end_timestamp: Some(6_000),
+                commitments: 1,
+                trades: 2,
+                netted_volume: Some(U256::from(110)),
+                protocol_fees: U256::from(2),
+                vault_fees: U256::from(3),
+            }
+        );
+        assert_eq!(store.epoch(0).unwrap(), Some(epochs[0].clone()));
+        assert_eq!(store.epoch(1).unwrap(), None);
+    }
+
+    #[test]
+    fn tracks_vault_share_price_and_fees() {
+        let mut store = epoch_zero().store();
+        let prices = store.share_prices(VAULT).unwrap();
+        assert_eq!(prices.len(), 1);
+        assert_eq!(prices[0].share_price, U256::from(970_000));
+        assert_eq!(prices[0].timestamp, 6_000);
+
+        let fees = store.vault_fees(VAULT).unwrap();
+        assert_eq!(fees.total_management_fee, U256::from(3));
+        assert_eq!(fees.epochs, vec![VaultFeesPoint { epoch: 0, management_fee: U256::from(3), ..Default::default() }]);
+    }
+
+    #[test]
+    fn nets_slippage_per_asset() {
+        let mut store = epoch_zero().store();
+        let slippage = store.slippage(None).unwrap();
+        assert_eq!(slippage.len(), 1);
+        assert_eq!(slippage[0].asset, address!("00000000000000000000000000000000000000b1"));
+        assert_eq!((slippage[0].sells, slippage[0].buys), (1, 1));
+        // Sold for 2 less and bought for 1 more than estimated.
+        assert_eq!(slippage[0].slippage, I256::try_from(3).unwrap());
+        assert!(store.slippage(Some(1)).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,191 @@
+//! Table layout.
+//!
+//! Every table is append-only and keyed by the `(block_number, log_index)` of the log a row came
+//! from, so rolling back a reorg is one `DELETE ... WHERE block_number > ?` per table and
+//! re-indexing is idempotent. Epoch and vault aggregates are derived at query time.
+
+/// Tables holding rows derived from logs, in the order they are rolled back.
+pub const TABLES: &[&str] = &[
+    "events",
+    "epoch_starts",
+    "epoch_prices",
+    "epoch_commitments",
+    "epoch_trades",
+    "epoch_ends",
+    "protocol_fees",
+    "vaults",
+    "vault_lifecycle",
+    "asset_lifecycle",
+    "vault_states",
+    "vault_requests",
+    "vault_deposits",
+    "vault_redeems",
+    "vault_fees",
+    "blocks",
+];
+
+/// Idempotent DDL for both backends.
+pub const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS blocks (
+    block_number BIGINT PRIMARY KEY,
+    block_hash TEXT NOT NULL,
+    timestamp BIGINT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS events (
+    block_number BIGINT NOT NULL,
+    log_index BIGINT NOT NULL,
+    tx_hash TEXT NOT NULL,
+    address TEXT NOT NULL,
+    epoch BIGINT NOT NULL,
+    name TEXT NOT NULL,
+    topics TEXT NOT NULL,
+    data TEXT NOT NULL,
+    PRIMARY KEY (block_number, log_index)
+);
+CREATE INDEX IF NOT EXISTS events_by_address ON events (address, name);
+
+CREATE TABLE IF NOT EXISTS epoch_starts (
+    block_number BIGINT NOT NULL,
+    log_index BIGINT NOT NULL,
+    epoch BIGINT NOT NULL,
+    PRIMARY KEY (block_number, log_index)
+);
+
+CREATE TABLE IF NOT EXISTS epoch_prices (
+    block_number BIGINT NOT NULL,
+    log_index BIGINT NOT NULL,
+    epoch BIGINT NOT NULL,
+    asset TEXT NOT NULL,
+    price TEXT NOT NULL,
+    PRIMARY KEY (block_number, log_index, asset)
+);
+
+CREATE TABLE IF NOT EXISTS epoch_commitments (
+    block_number BIGINT NOT NULL,
+    log_index BIGINT NOT NULL,
+    epoch BIGINT NOT NULL,
+    commitment TEXT NOT NULL,
+    PRIMARY KEY (block_number, log_index)
+);
+
+CREATE TABLE IF NOT EXISTS epoch_trades (
+    block_number BIGINT NOT NULL,
+    log_index BIGINT NOT NULL,
+    epoch BIGINT NOT NULL,
+    asset TEXT NOT NULL,
+    side TEXT NOT NULL,
+    shares_amount TEXT NOT NULL,
+    execution_underlying TEXT NOT NULL,
+    estimated_underlying TEXT NOT NULL,
+    PRIMARY KEY (block_number, log_index)
+);
+CREATE INDEX IF NOT EXISTS epoch_trades_by_asset ON epoch_trades (asset, epoch);
+
+CREATE TABLE IF NOT EXISTS epoch_ends (
+    block_number BIGINT NOT NULL,
+    log_index BIGINT NOT NULL,
+    epoch BIGINT NOT NULL,
+    netted_volume TEXT NOT NULL,
+    PRIMARY KEY (block_number, log_index)
+);
+
+CREATE TABLE IF NOT EXISTS protocol_fees (
+    block_number BIGINT NOT NULL,
+    log_index BIGINT NOT NULL,
+    epoch BIGINT NOT NULL,
+    amount TEXT NOT NULL,
+    PRIMARY KEY (block_number, log_index)
+);
+
+CREATE TABLE IF NOT EXISTS vaults (
+    block_number BIGINT NOT NULL,
+    log_index BIGINT NOT NULL,
+    vault TEXT NOT NULL,
+    manager TEXT NOT NULL,
+    strategist TEXT NOT NULL,
+    name TEXT NOT NULL,
+    symbol TEXT NOT NULL,
+    fee_type BIGINT NOT NULL,
+    performance_fee BIGINT NOT NULL,
+    management_fee BIGINT NOT NULL,
+    deposit_access_control TEXT NOT NULL,
+    vault_type TEXT NOT NULL,
+    PRIMARY KEY (block_number, log_index)
+);
+
+CREATE TABLE IF NOT EXISTS vault_lifecycle (
+    block_number BIGINT NOT NULL,
+    log_index BIGINT NOT NULL,
+    vault TEXT NOT NULL,
+    status TEXT NOT NULL,
+    PRIMARY KEY (block_number, log_index)
+);
+
+CREATE TABLE IF NOT EXISTS asset_lifecycle (
+    block_number BIGINT NOT NULL,
+    log_index BIGINT NOT NULL,
+    asset TEXT NOT NULL,
+    status TEXT NOT NULL,
+    PRIMARY KEY (block_number, log_index)
+);
+
+CREATE TABLE IF NOT EXISTS vault_states (
+    block_number BIGINT NOT NULL,
+    log_index BIGINT NOT NULL,
+    vault TEXT NOT NULL,
+    epoch BIGINT NOT NULL,
+    total_assets TEXT NOT NULL,
+    total_supply TEXT NOT NULL,
+    share_price TEXT NOT NULL,
+    high_water_mark TEXT NOT NULL,
+    tokens TEXT NOT NULL,
+    shares TEXT NOT NULL,
+    PRIMARY KEY (block_number, log_index)
+);
+CREATE INDEX IF NOT EXISTS vault_states_by_vault ON vault_states (vault, epoch);
+
+CREATE TABLE IF NOT EXISTS vault_requests (
+    block_number BIGINT NOT NULL,
+    log_index BIGINT NOT NULL,
+    vault TEXT NOT NULL,
+    epoch BIGINT NOT NULL,
+    account TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    PRIMARY KEY (block_number, log_index)
+);
+
+CREATE TABLE IF NOT EXISTS vault_deposits (
+    block_number BIGINT NOT NULL,
+    log_index BIGINT NOT NULL,
+    vault TEXT NOT NULL,
+    epoch BIGINT NOT NULL,
+    account TEXT NOT NULL,
+    assets TEXT NOT NULL,
+    shares TEXT NOT NULL,
+    PRIMARY KEY (block_number, log_index)
+);
+
+CREATE TABLE IF NOT EXISTS vault_redeems (
+    block_number BIGINT NOT NULL,
+    log_index BIGINT NOT NULL,
+    vault TEXT NOT NULL,
+    epoch BIGINT NOT NULL,
+    account TEXT NOT NULL,
+    assets TEXT NOT NULL,
+    shares TEXT NOT NULL,
+    PRIMARY KEY (block_number, log_index)
+);
+
+CREATE TABLE IF NOT EXISTS vault_fees (
+    block_number BIGINT NOT NULL,
+    log_index BIGINT NOT NULL,
+    vault TEXT NOT NULL,
+    epoch BIGINT NOT NULL,
+    management_fee TEXT NOT NULL,
+    performance_fee TEXT NOT NULL,
+    PRIMARY KEY (block_number, log_index)
+);
+CREATE INDEX IF NOT EXISTS vault_fees_by_vault ON vault_fees (vault, epoch);
+";
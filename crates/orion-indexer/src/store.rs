@@ -0,0 +1,522 @@
+//! Writes decoded logs into the normalized tables and rolls them back on reorgs.
+
+use alloy::primitives::{Address, B256};
+use anyhow::Context;
+
+use crate::bindings::{EventsLib::EventsLibEvents, IOrionVault::IOrionVaultEvents};
+use crate::db::{self, Backend, Value};
+use crate::events::{IndexedLog, OrionEvent};
+use crate::schema::{SCHEMA, TABLES};
+
+/// An indexed block, used to detect reorgs and to timestamp rows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlockRef {
+    /// Block number.
+    pub number: u64,
+    /// Block hash at indexing time.
+    pub hash: B256,
+    /// Block timestamp.
+    pub timestamp: u64,
+}
+
+/// The indexer database.
+pub struct Store {
+    db: Box<dyn Backend>,
+}
+
+impl Store {
+    /// Opens the database at `url` (see [`db::open`]) and creates missing tables.
+    pub fn open(url: &str) -> anyhow::Result<Self> {
+        Self::new(db::open(url)?)
+    }
+
+    /// Wraps an open connection and creates missing tables.
+    pub fn new(mut db: Box<dyn Backend>) -> anyhow::Result<Self> {
+        db.execute_batch(SCHEMA).context("creating schema")?;
+        Ok(Self { db })
+    }
+
+    /// Raw connection, for queries.
+    pub fn db(&mut self) -> &mut dyn Backend {
+        self.db.as_mut()
+    }
+
+    /// Last indexed block; indexing resumes right after it.
+    pub fn tip(&mut self) -> anyhow::Result<Option<BlockRef>> {
+        Ok(self.recent_blocks(1)?.into_iter().next())
+    }
+
+    /// Up to `limit` indexed blocks, newest first.
+    pub fn recent_blocks(&mut self, limit: u64) -> anyhow::Result<Vec<BlockRef>> {
+        self.db
+            .query(
+                "SELECT block_number, block_hash, timestamp FROM blocks ORDER BY block_number DESC LIMIT ?",
+                &[limit.into()],
+            )?
+            .iter()
+            .map(|row| {
+                Ok(BlockRef { number: row.int(0)? as u64, hash: row.text(1)?.parse()?, timestamp: row.int(2)? as u64 })
+            })
+            .collect()
+    }
+
+    /// Vaults whose logs are followed, in registration order.
+    pub fn vaults(&mut self) -> anyhow::Result<Vec<Address>> {
+        let rows = self.db.query(
+            "SELECT vault FROM vault_lifecycle WHERE status = 'added'
+             UNION SELECT vault FROM vaults",
+            &[],
+        )?;
+        rows.iter().map(|row| row.address(0)).collect()
+    }
+
+    /// Deletes everything derived from blocks after `ancestor`.
+    pub fn rollback(&mut self, ancestor: u64) -> anyhow::Result<()> {
+        self.transaction(|db| {
+            for table in TABLES {
+                db.execute(&format!("DELETE FROM {table} WHERE block_number > ?"), &[ancestor.into()])?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Atomically records a batch: the blocks it covers and every decoded log in them.
+    pub fn apply(&mut self, blocks: &[BlockRef], logs: &[IndexedLog]) -> anyhow::Result<()> {
+        self.transaction(|db| {
+            for block in blocks {
+                db.execute(
+                    "INSERT INTO blocks (block_number, block_hash, timestamp) VALUES (?, ?, ?) ON CONFLICT DO NOTHING",
+                    &[block.number.into(), block.hash.into(), block.timestamp.into()],
+                )?;
+            }
+            for log in logs {
+                insert(db, log)
+                    .with_context(|| format!("indexing {} at {}:{}", log.name, log.block_number, log.log_index))?;
+            }
+            Ok(())
+        })
+    }
+
+    fn transaction<T>(&mut self, f: impl FnOnce(&mut dyn Backend) -> anyhow::Result<T>) -> anyhow::Result<T> {
+        self.db.execute_batch("BEGIN")?;
+        match f(self.db.as_mut()) {
+            Ok(value) => {
+                self.db.execute_batch("COMMIT")?;
+                Ok(value)
+            }
+            Err(err) => {
+                // The original error is more useful than a failed rollback.
+                let _ = self.db.execute_batch("ROLLBACK");
+                Err(err)
+            }
+        }
+    }
+}
+
+fn json<T: serde::Serialize>(value: &T) -> anyhow::Result<Value> {
+    Ok(serde_json::to_string(value)?.into())
+}
+
+/// Inserts `log` into `events` and, for the events with a normalized table, into that table.
+fn insert(db: &mut dyn Backend, log: &IndexedLog) -> anyhow::Result<()> {
+    let at = [log.block_number.into(), log.log_index.into()];
+    let row = |values: Vec<Value>| -> Vec<Value> { at.iter().cloned().chain(values).collect() };
+    let epoch = Value::from(log.epoch);
+
+    db.execute(
+        "INSERT INTO events (block_number, log_index, tx_hash, address, epoch, name, topics, data)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?) ON CONFLICT DO NOTHING",
+        &row(vec![
+            log.tx_hash.into(),
+            log.address.into(),
+            epoch.clone(),
+            log.name.into(),
+            json(&log.topics)?,
+            log.data.to_string().into(),
+        ]),
+    )?;
+
+    let vault = Value::from(log.address);
+    match &log.event {
+        OrionEvent::Protocol(EventsLibEvents::EpochStart(event)) => {
+            db.execute(
+                "INSERT INTO epoch_starts (block_number, log_index, epoch) VALUES (?, ?, ?) ON CONFLICT DO NOTHING",
+                &row(vec![epoch.clone()]),
+            )?;
+            for (asset, price) in event.assets.iter().zip(&event.prices) {
+                db.execute(
+                    "INSERT INTO epoch_prices (block_number, log_index, epoch, asset, price)
+                     VALUES (?, ?, ?, ?, ?) ON CONFLICT DO NOTHING",
+                    &row(vec![epoch.clone(), (*asset).into(), (*price).into()]),
+                )?;
+            }
+        }
+        OrionEvent::Protocol(EventsLibEvents::EpochStateCommitted(event)) => {
+            db.execute(
+                "INSERT INTO epoch_commitments (block_number, log_index, epoch, commitment)
+                 VALUES (?, ?, ?, ?) ON CONFLICT DO NOTHING",
+                &row(vec![epoch, event.epochStateCommitment.into()]),
+            )?;
+        }
+        OrionEvent::Protocol(EventsLibEvents::EpochSellExecuted(event)) => {
+            insert_trade(
+                db,
+                row(vec![
+                    epoch,
+                    event.asset.into(),
+                    "sell".into(),
+                    event.sharesAmount.into(),
+                    event.executionUnderlyingAmount.into(),
+                    event.estimatedUnderlyingAmount.into(),
+                ]),
+            )?;
+        }
+        OrionEvent::Protocol(EventsLibEvents::EpochBuyExecuted(event)) => {
+            insert_trade(
+                db,
+                row(vec![
+                    epoch,
+                    event.asset.into(),
+                    "buy".into(),
+                    event.sharesAmount.into(),
+                    event.executionUnderlyingAmount.into(),
+                    event.estimatedUnderlyingAmount.into(),
+                ]),
+            )?;
+        }
+        OrionEvent::Protocol(EventsLibEvents::EpochEnd(event)) => {
+            db.execute(
+                "INSERT INTO epoch_ends (block_number, log_index, epoch, netted_volume)
+                 VALUES (?, ?, ?, ?) ON CONFLICT DO NOTHING",
+                &row(vec![epoch, event.nettedRebalanceVolumeUnderlying.into()]),
+            )?;
+        }
+        OrionEvent::Protocol(EventsLibEvents::ProtocolFeesAccrued(event)) => {
+            db.execute(
+                "INSERT INTO protocol_fees (block_number, log_index, epoch, amount) VALUES (?, ?, ?, ?)
+                 ON CONFLICT DO NOTHING",
+                &row(vec![epoch, event.epochProtocolFees.into()]),
+            )?;
+        }
+        OrionEvent::Protocol(EventsLibEvents::OrionVaultCreated(event)) => {
+            db.execute(
+                "INSERT INTO vaults (block_number, log_index, vault, manager, strategist, name, symbol, fee_type,
+                 performance_fee, management_fee, deposit_access_control, vault_type)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) ON CONFLICT DO NOTHING",
+                &row(vec![
+                    event.vault.into(),
+                    event.manager.into(),
+                    event.strategist.into(),
+                    event.name.as_str().into(),
+                    event.symbol.as_str().into(),
+                    u64::from(event.feeType).into(),
+                    u64::from(event.performanceFee).into(),
+                    u64::from(event.managementFee).into(),
+                    event.depositAccessControl.into(),
+                    format!("{:?}", event.vaultType).into(),
+                ]),
+            )?;
+        }
+        OrionEvent::Protocol(EventsLibEvents::OrionVaultAdded(event)) => {
+            insert_lifecycle(db, "vault_lifecycle", "vault", row(vec![event.vault.into(), "added".into()]))?;
+        }
+        OrionEvent::Protocol(EventsLibEvents::VaultDecommissioningInitiated(event)) => {
+            insert_lifecycle(db, "vault_lifecycle", "vault", row(vec![event.vault.into(), "decommissioning".into()]))?;
+        }
+        OrionEvent::Protocol(EventsLibEvents::OrionVaultDecommissioned(event)) => {
+            insert_lifecycle(db, "vault_lifecycle", "vault", row(vec![event.vault.into(), "decommissioned".into()]))?;
+        }
+        OrionEvent::Protocol(EventsLibEvents::WhitelistedAssetAdded(event)) => {
+            insert_lifecycle(db, "asset_lifecycle", "asset", row(vec![event.asset.into(), "whitelisted".into()]))?;
+        }
+        OrionEvent::Protocol(EventsLibEvents::AssetDecommissioningInitiated(event)) => {
+            insert_lifecycle(db, "asset_lifecycle", "asset", row(vec![event.asset.into(), "decommissioning".into()]))?;
+        }
+        OrionEvent::Protocol(EventsLibEvents::WhitelistedAssetRemoved(event)) => {
+            insert_lifecycle(db, "asset_lifecycle", "asset", row(vec![event.asset.into(), "removed".into()]))?;
+        }
+        OrionEvent::Protocol(EventsLibEvents::VaultStateUpdated(event)) => {
+            db.execute(
+                "INSERT INTO vault_states (block_number, log_index, vault, epoch, total_assets, total_supply,
+                 share_price, high_water_mark, tokens, shares)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?) ON CONFLICT DO NOTHING",
+                &row(vec![
+                    vault,
+                    epoch,
+                    event.newTotalAssets.into(),
+                    event.totalSupply.into(),
+                    event.currentSharePrice.into(),
+                    event.highWaterMark.into(),
+                    json(&event.tokens)?,
+                    json(&event.shares.iter().map(ToString::to_string).collect::<Vec<_>>())?,
+                ]),
+            )?;
+        }
+        OrionEvent::Vault(IOrionVaultEvents::DepositRequest(event)) => {
+            insert_request(
+                db,
+                row(vec![vault, epoch, event.controller.into(), "deposit".into(), event.assets.into()]),
+            )?;
+        }
+        OrionEvent::Vault(IOrionVaultEvents::DepositRequestCancelled(event)) => {
+            insert_request(
+                db,
+                row(vec![vault, epoch, event.user.into(), "deposit_cancelled".into(), event.amount.into()]),
+            )?;
+        }
+        OrionEvent::Vault(IOrionVaultEvents::RedeemRequest(event)) => {
+            insert_request(db, row(vec![vault, epoch, event.controller.into(), "redeem".into(), event.shares.into()]))?;
+        }
+        OrionEvent::Vault(IOrionVaultEvents::RedeemRequestCancelled(event)) => {
+            insert_request(
+                db,
+                row(vec![vault, epoch, event.user.into(), "redeem_cancelled".into(), event.shares.into()]),
+            )?;
+        }
+        OrionEvent::Vault(IOrionVaultEvents::DepositClaimable(event)) => {
+            db.execute(
+                "INSERT INTO vault_deposits (block_number, log_index, vault, epoch, account, assets, shares)
+                 VALUES (?, ?, ?, ?, ?, ?, ?) ON CONFLICT DO NOTHING",
+                &row(vec![vault, epoch, event.controller.into(), event.assets.into(), event.shares.into()]),
+            )?;
+        }
+        OrionEvent::Vault(IOrionVaultEvents::RedeemClaimable(event)) => {
+            db.execute(
+                "INSERT INTO vault_redeems (block_number, log_index, vault, epoch, account, assets, shares)
+                 VALUES (?, ?, ?, ?, ?, ?, ?) ON CONFLICT DO NOTHING",
+                &row(vec![vault, epoch, event.controller.into(), event.assets.into(), event.shares.into()]),
+            )?;
+        }
+        OrionEvent::Vault(IOrionVaultEvents::VaultFeesAccrued(event)) => {
+            db.execute(
+                "INSERT INTO vault_fees (block_number, log_index, vault, epoch, management_fee, performance_fee)
+                 VALUES (?, ?, ?, ?, ?, ?) ON CONFLICT DO NOTHING",
+                &row(vec![vault, epoch, event.managementFee.into(), event.performanceFee.into()]),
+            )?;
+        }
+        // Configuration changes only live in `events`.
+        _ => {}
+    }
+    Ok(())
+}
+
+fn insert_trade(db: &mut dyn Backend, row: Vec<Value>) -> anyhow::Result<u64> {
+    db.execute(
+        "INSERT INTO epoch_trades (block_number, log_index, epoch, asset, side, shares_amount, execution_underlying,
+         estimated_underlying) VALUES (?, ?, ?, ?, ?, ?, ?, ?) ON CONFLICT DO NOTHING",
+        &row,
+    )
+}
+
+fn insert_lifecycle(db: &mut dyn Backend, table: &str, subject: &str, row: Vec<Value>) -> anyhow::Result<u64> {
+    db.execute(
+        &format!(
+            "INSERT INTO {table} (block_number, log_index, {subject}, status) VALUES (?, ?, ?, ?) ON CONFLICT DO NOTHING"
+        ),
+        &row,
+    )
+}
+
+fn insert_request(db: &mut dyn Backend, row: Vec<Value>) -> anyhow::Result<u64> {
+    db.execute(
+        "INSERT INTO vault_requests (block_number, log_index, vault, epoch, account, kind, amount)
+         VALUES (?, ?, ?, ?, ?, ?, ?) ON CONFLICT DO NOTHING",
+        &row,
+    )
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use alloy::{
+        primitives::{address, b256, U256},
+        sol_types::SolEvent,
+    };
+
+    use super::*;
+    use crate::bindings::{EventsLib, IOrionVault};
+    use crate::events::EpochTracker;
+
+    pub(crate) const VAULT: Address = address!("00000000000000000000000000000000000000a1");
+    pub(crate) const ORCHESTRATOR: Address = address!("00000000000000000000000000000000000000f1");
+    pub(crate) const CONFIG: Address = address!("00000000000000000000000000000000000000f2");
+
+    /// Builds in-order logs with epochs labelled the way the indexer does.
+    #[derive(Default)]
+    pub(crate) struct LogBuilder {
+        tracker: EpochTracker,
+        pub(crate) logs: Vec<IndexedLog>,
+        pub(crate) blocks: Vec<BlockRef>,
+    }
+
+    impl LogBuilder {
+        pub(crate) fn push(&mut self, block_number: u64, address: Address, event: &impl SolEvent) -> &mut Self {
+            let data = event.encode_log_data();
+            let (name, decoded) = OrionEvent::decode(data.topics(), &data.data).unwrap();
+            let block_hash = B256::with_last_byte(block_number as u8);
+            if self.blocks.last().map(|block| block.number) != Some(block_number) {
+                self.blocks.push(BlockRef { number: block_number, hash: block_hash, timestamp: 1_000 * block_number });
+            }
+            self.logs.push(IndexedLog {
+                block_number,
+                block_hash,
+                block_timestamp: None,
+                log_index: self.logs.len() as u64,
+                tx_hash: B256::ZERO,
+                address,
+                epoch: self.tracker.observe(&decoded),
+                name,
+                topics: data.topics().to_vec(),
+                data: data.data,
+                event: decoded,
+            });
+            self
+        }
+
+        pub(crate) fn store(&self) -> Store {
+            let mut store = Store::open(":memory:").unwrap();
+            store.apply(&self.blocks, &self.logs).unwrap();
+            store
+        }
+    }
+
+    /// One epoch with a deposit, a sell and a buy, fees and a vault state update.
+    pub(crate) fn epoch_zero() -> LogBuilder {
+        let user = address!("00000000000000000000000000000000000000c1");
+        let asset = address!("00000000000000000000000000000000000000b1");
+        let mut builder = LogBuilder::default();
+        builder
+            .push(
+                1,
+                CONFIG,
+                &EventsLib::OrionVaultCreated {
+                    vault: VAULT,
+                    manager: user,
+                    strategist: user,
+                    name: "Vault".into(),
+                    symbol: "V".into(),
+                    feeType: 0,
+                    performanceFee: 0,
+                    managementFee: 100,
+                    depositAccessControl: Address::ZERO,
+                    vaultType: EventsLib::VaultType::Transparent,
+                },
+            )
+            .push(1, CONFIG, &EventsLib::OrionVaultAdded { vault: VAULT })
+            .push(
+                2,
+                VAULT,
+                &IOrionVault::DepositRequest {
+                    controller: user,
+                    owner: user,
+                    requestId: U256::ZERO,
+                    sender: user,
+                    assets: U256::from(100),
+                },
+            )
+            .push(
+                3,
+                ORCHESTRATOR,
+                &EventsLib::EpochStart { epochCounter: U256::ZERO, assets: vec![asset], prices: vec![U256::from(2)] },
+            )
+            .push(
+                3,
+                ORCHESTRATOR,
+                &EventsLib::EpochStateCommitted {
+                    epochCounter: U256::ZERO,
+                    epochStateCommitment: b256!("0000000000000000000000000000000000000000000000000000000000000001"),
+                },
+            )
+            .push(
+                4,
+                ORCHESTRATOR,
+                &EventsLib::EpochSellExecuted {
+                    epochCounter: U256::ZERO,
+                    asset,
+                    executionUnderlyingAmount: U256::from(48),
+                    sharesAmount: U256::from(25),
+                    estimatedUnderlyingAmount: U256::from(50),
+                },
+            )
+            .push(
+                5,
+                ORCHESTRATOR,
+                &EventsLib::EpochBuyExecuted {
+                    epochCounter: U256::ZERO,
+                    asset,
+                    executionUnderlyingAmount: U256::from(61),
+                    sharesAmount: U256::from(30),
+                    estimatedUnderlyingAmount: U256::from(60),
+                },
+            )
+            .push(5, ORCHESTRATOR, &EventsLib::ProtocolFeesAccrued { epochProtocolFees: U256::from(2) })
+            .push(6, VAULT, &IOrionVault::VaultFeesAccrued { managementFee: U256::from(3), performanceFee: U256::ZERO })
+            .push(
+                6,
+                VAULT,
+                &IOrionVault::DepositClaimable {
+                    controller: user,
+                    requestId: U256::ZERO,
+                    assets: U256::from(100),
+                    shares: U256::from(100),
+                },
+            )
+            .push(
+                6,
+                VAULT,
+                &EventsLib::VaultStateUpdated {
+                    newTotalAssets: U256::from(97),
+                    totalSupply: U256::from(100),
+                    currentSharePrice: U256::from(970_000),
+                    highWaterMark: U256::from(1_000_000),
+                    tokens: vec![asset],
+                    shares: vec![U256::from(5)],
+                },
+            )
+            .push(
+                6,
+                ORCHESTRATOR,
+                &EventsLib::EpochEnd { epochCounter: U256::ZERO, nettedRebalanceVolumeUnderlying: U256::from(110) },
+            );
+        builder
+    }
+
+    fn count(store: &mut Store, table: &str) -> i64 {
+        store.db().query(&format!("SELECT COUNT(*) FROM {table}"), &[]).unwrap()[0].int(0).unwrap()
+    }
+
+    #[test]
+    fn normalizes_events() {
+        let mut store = epoch_zero().store();
+        assert_eq!(count(&mut store, "events"), 12);
+        assert_eq!(count(&mut store, "epoch_trades"), 2);
+        assert_eq!(count(&mut store, "epoch_prices"), 1);
+        assert_eq!(store.vaults().unwrap(), vec![VAULT]);
+        assert_eq!(store.tip().unwrap().map(|tip| tip.number), Some(6));
+
+        let rows = store.db().query("SELECT epoch, kind, amount FROM vault_requests", &[]).unwrap();
+        assert_eq!(rows[0].int(0).unwrap(), 0);
+        assert_eq!(rows[0].text(1).unwrap(), "deposit");
+        assert_eq!(rows[0].u256(2).unwrap(), U256::from(100));
+    }
+
+    #[test]
+    fn reapplying_a_batch_is_idempotent() {
+        let builder = epoch_zero();
+        let mut store = builder.store();
+        store.apply(&builder.blocks, &builder.logs).unwrap();
+        assert_eq!(count(&mut store, "events"), 12);
+        assert_eq!(count(&mut store, "blocks"), 6);
+    }
+
+    #[test]
+    fn rollback_drops_orphaned_rows() {
+        let mut store = epoch_zero().store();
+        store.rollback(4).unwrap();
+
+        assert_eq!(store.tip().unwrap().map(|tip| tip.number), Some(4));
+        assert_eq!(count(&mut store, "epoch_trades"), 1);
+        assert_eq!(count(&mut store, "vault_fees"), 0);
+        assert_eq!(count(&mut store, "epoch_ends"), 0);
+        assert_eq!(count(&mut store, "events"), 6);
+    }
+}
@@ -0,0 +1,102 @@
+//! Minimal on-chain interfaces the keeper reads from and writes to.
+//!
+//! Only the getters the keeper needs are declared; names and return types mirror
+//! `ILiquidityOrchestrator`, `IOrionConfig` and `IOrionTransparentVault` exactly.
+
+#![allow(missing_docs)]
+
+use alloy::sol;
+
+sol! {
+    #[sol(rpc)]
+    interface ILiquidityOrchestrator {
+        struct FeeModel {
+            uint8 feeType;
+            uint16 performanceFee;
+            uint16 managementFee;
+            uint256 highWaterMark;
+        }
+
+        struct ManagementFeeSchedule {
+            uint256[3] breakpoints;
+            uint16[3] tierRates;
+            uint256 minimumFee;
+        }
+
+        struct FeeSettlement {
+            uint32 crystallizationPeriod;
+            bool mintFeeShares;
+            uint64 lastCrystallization;
+            uint256 crystallizationPrice;
+        }
+
+        struct Hurdle {
+            uint8 hurdleType;
+            uint16 rate;
+            address benchmark;
+            uint256 benchmarkPrice;
+        }
+
+        struct EpochStateView {
+            address[] vaultsEpoch;
+            FeeModel[] vaultFeeModels;
+            ManagementFeeSchedule[] vaultManagementFeeSchedules;
+            FeeSettlement[] vaultFeeSettlements;
+            Hurdle[] vaultHurdles;
+            uint16[] vaultVFeeCoefficients;
+            uint16[] vaultRsFeeCoefficients;
+            bytes32 epochStateCommitment;
+        }
+
+        event EpochStateCommitted(uint256 indexed epochCounter, bytes32 indexed epochStateCommitment);
+
+        function config() external view returns (address);
+        function underlyingAsset() external view returns (address);
+        function checkUpkeep() external view returns (bool upkeepNeeded);
+        function currentPhase() external view returns (uint8);
+        function currentMinibatchIndex() external view returns (uint8);
+        function completedInCurrentMinibatch() external view returns (uint16);
+        function epochCounter() external view returns (uint256);
+        function epochDuration() external view returns (uint32);
+        function targetBufferRatio() external view returns (uint256);
+        function bufferAmount() external view returns (uint256);
+        function initialEpochBufferAmount() external view returns (uint256);
+        function epochStartTime() external view returns (uint256);
+        function buyingLegEntryBuffer() external view returns (uint256);
+        function getEpochState() external view returns (EpochStateView memory);
+        function getFailedEpochTokens() external view returns (address[] memory);
+        function getAssetPrices(address[] memory assets) external view returns (uint256[] memory assetPrices);
+        function performUpkeep(bytes calldata _publicValues, bytes calldata proofBytes, bytes calldata statesBytes)
+            external;
+    }
+
+    #[sol(rpc)]
+    interface IOrionConfig {
+        function maxFulfillBatchSize() external view returns (uint256);
+        function priceAdapterDecimals() external view returns (uint8);
+        function strategistIntentDecimals() external view returns (uint8);
+        function riskFreeRate() external view returns (uint16);
+        function getAllWhitelistedAssets() external view returns (address[] memory);
+        function getAllTokenDecimals() external view returns (uint8[] memory decimals);
+        function decommissioningAssets() external view returns (address[] memory);
+    }
+
+    #[sol(rpc)]
+    interface IOrionTransparentVault {
+        function getPortfolio() external view returns (address[] memory tokens, uint256[] memory sharesPerAsset);
+        function getIntent() external view returns (address[] memory tokens, uint32[] memory weights);
+        function pendingDeposit(uint256 fulfillBatchSize) external view returns (uint256);
+        function pendingRedeem(uint256 fulfillBatchSize) external view returns (uint256);
+        function pendingRedeemBatch(uint256 fulfillBatchSize)
+            external
+            view
+            returns (address[] memory users, uint256[] memory shares);
+        function totalSupply() external view returns (uint256);
+        function totalAssets() external view returns (uint256);
+    }
+
+    #[sol(rpc)]
+    interface IERC20 {
+        function balanceOf(address account) external view returns (uint256);
+    }
+}
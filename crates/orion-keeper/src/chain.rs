@@ -0,0 +1,213 @@
+//! Reads the orchestrator state and submits `performUpkeep`.
+
+use alloy::{
+    eips::BlockId,
+    primitives::{Address, Bytes, B256, U256},
+    providers::{DynProvider, Provider},
+    rpc::types::{Filter, TransactionReceipt},
+    sol_types::SolEvent,
+};
+use anyhow::{bail, Context};
+use orion_commitment::{
+    hash, CommitmentReport, EpochSnapshot, FeeModelSnapshot, IntentSnapshot, PortfolioSnapshot, ProtocolSnapshot,
+    VaultSnapshot,
+};
+use orion_state_orchestrator::{EpochInputs, RedeemBatch};
+
+use crate::bindings::{
+    ILiquidityOrchestrator::{self, ILiquidityOrchestratorInstance},
+    IOrionConfig, IOrionTransparentVault, IERC20,
+};
+use crate::phase::{Phase, UpkeepStatus};
+
+/// Blocks bracketing the commitments of the current epoch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CommitmentBlocks {
+    /// Block that sealed the epoch; vault leaves were read in this state.
+    pub sealed: u64,
+    /// Block of the latest `EpochStateCommitted`, i.e. the last leg-failure rotation (or the seal).
+    pub latest: u64,
+    /// Commitment emitted at `latest`.
+    pub commitment: B256,
+}
+
+/// Connection to one `LiquidityOrchestrator` deployment.
+#[derive(Clone, Debug)]
+pub struct Chain {
+    provider: DynProvider,
+    orchestrator: ILiquidityOrchestratorInstance<DynProvider>,
+    from_block: u64,
+}
+
+impl Chain {
+    /// Wraps a signing provider; `from_block` bounds the commitment event scan.
+    pub fn new(provider: DynProvider, orchestrator: Address, from_block: u64) -> Self {
+        let orchestrator = ILiquidityOrchestrator::new(orchestrator, provider.clone());
+        Self { provider, orchestrator, from_block }
+    }
+
+    /// Provider the keeper signs with.
+    pub fn provider(&self) -> &DynProvider {
+        &self.provider
+    }
+
+    /// Polls the phase machine.
+    pub async fn status(&self) -> anyhow::Result<UpkeepStatus> {
+        let lo = &self.orchestrator;
+        let block = BlockId::number(self.provider.get_block_number().await?);
+        Ok(UpkeepStatus {
+            upkeep_needed: lo.checkUpkeep().block(block).call().await?,
+            phase: Phase::try_from(lo.currentPhase().block(block).call().await?)?,
+            epoch: lo.epochCounter().block(block).call().await?,
+            minibatch_index: lo.currentMinibatchIndex().block(block).call().await?,
+            completed_in_minibatch: lo.completedInCurrentMinibatch().block(block).call().await?,
+            commitment: lo.getEpochState().block(block).call().await?.epochStateCommitment,
+        })
+    }
+
+    /// Finds the blocks where the commitments of `epoch` were emitted.
+    pub async fn commitment_blocks(&self, epoch: U256) -> anyhow::Result<CommitmentBlocks> {
+        let filter = Filter::new()
+            .address(*self.orchestrator.address())
+            .event_signature(ILiquidityOrchestrator::EpochStateCommitted::SIGNATURE_HASH)
+            .topic1(B256::from(epoch))
+            .from_block(self.from_block);
+        let logs = self.provider.get_logs(&filter).await?;
+        let (Some(first), Some(last)) = (logs.first(), logs.last()) else {
+            bail!("no EpochStateCommitted event for epoch {epoch} since block {}", self.from_block);
+        };
+        Ok(CommitmentBlocks {
+            sealed: first.block_number.context("pending log")?,
+            latest: last.block_number.context("pending log")?,
+            commitment: *last.topics().get(2).context("missing commitment topic")?,
+        })
+    }
+
+    /// Reconstructs the epoch inputs behind the current commitment.
+    ///
+    /// Vault data is frozen while the system is not idle, so it is read once at the sealing block
+    /// (or taken from `cached_vaults`). Protocol fields that move during the legs (buffer,
+    /// underlying balance, failed tokens) are read at the block of the latest rotation. The result
+    /// is checked against the on-chain commitment before it is handed to the prover.
+    pub async fn read_inputs(
+        &self,
+        epoch: U256,
+        cached_vaults: Option<(Vec<VaultSnapshot>, Vec<RedeemBatch>)>,
+    ) -> anyhow::Result<EpochInputs> {
+        let blocks = self.commitment_blocks(epoch).await?;
+        let view = self.orchestrator.getEpochState().block(BlockId::number(blocks.latest)).call().await?;
+        let (protocol, asset_prices) = self.read_protocol(BlockId::number(blocks.latest)).await?;
+
+        let (vaults, redeem_batches) = match cached_vaults {
+            Some(cached) => cached,
+            None => self.read_vaults(&view, protocol.max_fulfill_batch_size, BlockId::number(blocks.sealed)).await?,
+        };
+
+        let inputs = EpochInputs { snapshot: EpochSnapshot { protocol, asset_prices, vaults }, redeem_batches };
+        let computed = CommitmentReport::compute(&inputs.snapshot).epoch_state_commitment;
+        if computed != blocks.commitment {
+            bail!("CommitmentMismatch: reconstructed {computed}, on-chain {}", blocks.commitment);
+        }
+        Ok(inputs)
+    }
+
+    async fn read_protocol(&self, block: BlockId) -> anyhow::Result<(ProtocolSnapshot, Vec<U256>)> {
+        let lo = &self.orchestrator;
+        let config = IOrionConfig::new(lo.config().block(block).call().await?, self.provider.clone());
+        let underlying = IERC20::new(lo.underlyingAsset().block(block).call().await?, self.provider.clone());
+
+        let whitelisted_assets = config.getAllWhitelistedAssets().block(block).call().await?;
+        let asset_prices = lo.getAssetPrices(whitelisted_assets.clone()).block(block).call().await?;
+        let protocol = ProtocolSnapshot {
+            max_fulfill_batch_size: config.maxFulfillBatchSize().block(block).call().await?,
+            target_buffer_ratio: lo.targetBufferRatio().block(block).call().await?,
+            price_adapter_decimals: config.priceAdapterDecimals().block(block).call().await?,
+            strategist_intent_decimals: config.strategistIntentDecimals().block(block).call().await?,
+            epoch_duration: lo.epochDuration().block(block).call().await?,
+            whitelisted_assets,
+            token_decimals: config.getAllTokenDecimals().block(block).call().await?,
+            risk_free_rate: config.riskFreeRate().block(block).call().await?,
+            decommissioning_assets: config.decommissioningAssets().block(block).call().await?,
+            failed_epoch_tokens: lo.getFailedEpochTokens().block(block).call().await?,
+            initial_epoch_buffer_amount: lo.initialEpochBufferAmount().block(block).call().await?,
+            buying_leg_entry_buffer: lo.buyingLegEntryBuffer().block(block).call().await?,
+            buffer_amount: lo.bufferAmount().block(block).call().await?,
+            underlying_balance: underlying.balanceOf(*lo.address()).block(block).call().await?,
+            epoch_start_time: lo.epochStartTime().block(block).call().await?,
+        };
+        Ok((protocol, asset_prices))
+    }
+
+    async fn read_vaults(
+        &self,
+        view: &ILiquidityOrchestrator::EpochStateView,
+        batch_size: U256,
+        block: BlockId,
+    ) -> anyhow::Result<(Vec<VaultSnapshot>, Vec<RedeemBatch>)> {
+        let mut vaults = Vec::with_capacity(view.vaultsEpoch.len());
+        let mut batches = Vec::with_capacity(view.vaultsEpoch.len());
+        for (i, (&address, fee)) in view.vaultsEpoch.iter().zip(&view.vaultFeeModels).enumerate() {
+            let schedule = &view.vaultManagementFeeSchedules[i];
+            let settlement = &view.vaultFeeSettlements[i];
+            let hurdle = &view.vaultHurdles[i];
+            let vault = IOrionTransparentVault::new(address, self.provider.clone());
+            let portfolio = vault.getPortfolio().block(block).call().await?;
+            let intent = vault.getIntent().block(block).call().await?;
+            let batch = vault.pendingRedeemBatch(batch_size).block(block).call().await?;
+            vaults.push(VaultSnapshot {
+                address,
+                fee_model: FeeModelSnapshot {
+                    fee_type: fee.feeType,
+                    performance_fee: fee.performanceFee,
+                    management_fee: fee.managementFee,
+                    management_fee_breakpoints: schedule.breakpoints,
+                    management_fee_tier_rates: schedule.tierRates,
+                    minimum_management_fee: schedule.minimumFee,
+                    crystallization_period: settlement.crystallizationPeriod,
+                    mint_fee_shares: settlement.mintFeeShares,
+                    last_crystallization: settlement.lastCrystallization,
+                    high_water_mark: fee.highWaterMark,
+                    crystallization_price: settlement.crystallizationPrice,
+                    hurdle_type: hurdle.hurdleType,
+                    hurdle_rate: hurdle.rate,
+                    hurdle_benchmark: hurdle.benchmark,
+                    hurdle_benchmark_price: hurdle.benchmarkPrice,
+                },
+                pending_redeem: vault.pendingRedeem(batch_size).block(block).call().await?,
+                pending_deposit: vault.pendingDeposit(batch_size).block(block).call().await?,
+                redeem_batch_hash: hash::redeem_batch_hash(&batch.users, &batch.shares),
+                total_supply: vault.totalSupply().block(block).call().await?,
+                total_assets: vault.totalAssets().block(block).call().await?,
+                v_fee_coefficient: view.vaultVFeeCoefficients[i],
+                rs_fee_coefficient: view.vaultRsFeeCoefficients[i],
+                portfolio: PortfolioSnapshot { tokens: portfolio.tokens, shares: portfolio.sharesPerAsset },
+                intent: IntentSnapshot { tokens: intent.tokens, weights: intent.weights },
+            });
+            batches.push(RedeemBatch { users: batch.users, shares: batch.shares });
+        }
+        Ok((vaults, batches))
+    }
+
+    /// Sends `performUpkeep` and returns the transaction hash without waiting for inclusion.
+    pub async fn send_upkeep(&self, public_values: Bytes, proof: Bytes, states: Bytes) -> anyhow::Result<B256> {
+        let pending = self.orchestrator.performUpkeep(public_values, proof, states).send().await?;
+        Ok(*pending.tx_hash())
+    }
+
+    /// Waits for a previously sent transaction; `None` if the node no longer knows about it.
+    pub async fn wait_for(
+        &self,
+        tx: B256,
+        poll_interval: std::time::Duration,
+    ) -> anyhow::Result<Option<TransactionReceipt>> {
+        loop {
+            if let Some(receipt) = self.provider.get_transaction_receipt(tx).await? {
+                return Ok(Some(receipt));
+            }
+            if self.provider.get_transaction_by_hash(tx).await?.is_none() {
+                return Ok(None);
+            }
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! The polling loop tying chain reads, proving and submission together.
+
+use std::time::Duration;
+
+use alloy::primitives::Bytes;
+use anyhow::{bail, Context};
+use orion_state_orchestrator::execute;
+use tracing::{info, warn};
+
+use crate::chain::Chain;
+use crate::phase::{next_action, Action, UpkeepStatus};
+use crate::prover::Prover;
+use crate::store::{KeeperState, ProvenTransition, StateFile};
+
+/// Drives `performUpkeep` until the process is stopped.
+pub struct Keeper {
+    chain: Chain,
+    prover: Box<dyn Prover>,
+    store: StateFile,
+    state: KeeperState,
+    poll_interval: Duration,
+}
+
+impl Keeper {
+    /// Loads the persisted state and prepares the loop.
+    pub fn new(
+        chain: Chain,
+        prover: Box<dyn Prover>,
+        store: StateFile,
+        poll_interval: Duration,
+    ) -> anyhow::Result<Self> {
+        let state = store.load()?;
+        Ok(Self { chain, prover, store, state, poll_interval })
+    }
+
+    /// Runs [`Keeper::tick`] every poll interval until Ctrl-C.
+    pub async fn run(&mut self) -> anyhow::Result<()> {
+        loop {
+            match self.tick().await {
+                Ok(Action::Wait) => {}
+                // More work is likely due right away (next minibatch or phase).
+                Ok(_) => continue,
+                Err(err) => warn!("upkeep step failed: {err:#}"),
+            }
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => return Ok(()),
+                _ = tokio::time::sleep(self.poll_interval) => {}
+            }
+        }
+    }
+
+    /// Performs at most one `performUpkeep` and returns what it did.
+    pub async fn tick(&mut self) -> anyhow::Result<Action> {
+        self.settle_pending().await?;
+
+        let status = self.chain.status().await?;
+        if self.state.epoch != status.epoch {
+            self.state.roll_over(status.epoch);
+            self.store.save(&self.state)?;
+        }
+
+        let action = next_action(&status, self.state.proof.as_ref());
+        match action {
+            Action::Wait => {}
+            Action::Advance => {
+                info!(phase = ?status.phase, epoch = %status.epoch, "advancing");
+                self.submit(Bytes::new(), Bytes::new(), Bytes::new()).await?;
+            }
+            Action::Prove => {
+                if self.state.proof.is_some() {
+                    info!(epoch = %status.epoch, commitment = %status.commitment, "commitment rotated, re-proving");
+                }
+                let proven = self.prove(&status).await?;
+                self.state.proof = Some(proven.clone());
+                self.store.save(&self.state)?;
+                self.submit_proven(&status, &proven).await?;
+            }
+            Action::Submit => {
+                let proven = self.state.proof.clone().context("no cached proof")?;
+                self.submit_proven(&status, &proven).await?;
+            }
+        }
+        Ok(action)
+    }
+
+    async fn prove(&mut self, status: &UpkeepStatus) -> anyhow::Result<ProvenTransition> {
+        let cached_vaults =
+            self.state.inputs.as_ref().map(|inputs| (inputs.snapshot.vaults.clone(), inputs.redeem_batches.clone()));
+        let inputs = self.chain.read_inputs(status.epoch, cached_vaults).await?;
+        self.state.inputs = Some(inputs.clone());
+        self.store.save(&self.state)?;
+
+        let execution = execute(&inputs)?;
+        if execution.public_values.inputCommitment != status.commitment {
+            bail!("commitment moved while reading inputs, retrying on next poll");
+        }
+        let vaults_epoch: Vec<_> = inputs.snapshot.vaults.iter().map(|vault| vault.address).collect();
+        execution.states.validate(&vaults_epoch).context("state transition produced malformed states")?;
+        let proof = self.prover.prove(&inputs, &execution)?;
+        Ok(ProvenTransition {
+            epoch: status.epoch,
+            input_commitment: status.commitment,
+            public_values: execution.public_values_bytes().into(),
+            proof,
+            states: execution.states_bytes().into(),
+        })
+    }
+
+    async fn submit_proven(&mut self, status: &UpkeepStatus, proven: &ProvenTransition) -> anyhow::Result<()> {
+        info!(
+            phase = ?status.phase,
+            epoch = %status.epoch,
+            minibatch = status.minibatch_index,
+            completed = status.completed_in_minibatch,
+            "submitting proven step"
+        );
+        self.submit(proven.public_values.clone(), proven.proof.clone(), proven.states.clone()).await
+    }
+
+    async fn submit(&mut self, public_values: Bytes, proof: Bytes, states: Bytes) -> anyhow::Result<()> {
+        let tx = self.chain.send_upkeep(public_values, proof, states).await?;
+        // Persist before waiting so a restart does not double-submit the same step.
+        self.state.pending_tx = Some(tx);
+        self.store.save(&self.state)?;
+        self.settle_pending().await
+    }
+
+    /// Waits for the transaction recorded in the state file, if any.
+    async fn settle_pending(&mut self) -> anyhow::Result<()> {
+        let Some(tx) = self.state.pending_tx else {
+            return Ok(());
+        };
+        let receipt = self.chain.wait_for(tx, self.poll_interval).await?;
+        self.state.pending_tx = None;
+        self.store.save(&self.state)?;
+        match receipt {
+            Some(receipt) if receipt.status() => {
+                info!(%tx, block = receipt.block_number, "performUpkeep confirmed");
+                Ok(())
+            }
+            Some(_) => bail!("performUpkeep {tx} reverted"),
+            None => {
+                warn!(%tx, "performUpkeep dropped from the mempool");
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,26 @@
+//! Off-chain keeper for the `LiquidityOrchestrator` upkeep phase machine.
+//!
+//! Each poll reads `checkUpkeep`, `currentPhase`, `currentMinibatchIndex` and
+//! `completedInCurrentMinibatch` and submits the matching `performUpkeep` call:
+//!
+//! - Idle and StateCommitment take an empty payload;
+//! - SellingLeg, BuyingLeg and ProcessVaultOperations take the proven `StatesStruct` for the
+//!   current `epochStateCommitment`.
+//!
+//! The keeper stays stateless with respect to progress, which lives on-chain. Its state file
+//! only caches the epoch inputs, the proof and the in-flight transaction, so a restart resumes
+//! mid-leg without re-proving. When `_handleMinibatchLegFailure` rotates the commitment, the
+//! cached proof no longer matches `epochStateCommitment` and a fresh one is generated.
+
+pub mod bindings;
+pub mod chain;
+pub mod keeper;
+pub mod phase;
+pub mod prover;
+pub mod store;
+
+pub use chain::Chain;
+pub use keeper::Keeper;
+pub use phase::{next_action, Action, Phase, UpkeepStatus};
+pub use prover::{CommandProver, MockProver, Prover};
+pub use store::{KeeperState, ProvenTransition, StateFile};
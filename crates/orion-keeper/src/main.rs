@@ -0,0 +1,90 @@
+//! `orion-keeper` drives `performUpkeep` through every phase of each epoch.
+//!
+//! ```text
+//! anvil &
+//! pnpm hardhat run scripts/deploy-local.ts --network localhost
+//! orion-keeper --rpc-url http://127.0.0.1:8545 --private-key $OWNER_KEY \
+//!     --orchestrator 0x... --from-block <fromBlock> --prover-cmd "./prove.sh"
+//! ```
+//!
+//! With `MOCK_VERIFIER=true` the deploy script wires `MockSP1Verifier` into the orchestrator, and
+//! `--mock-prover` replaces `--prover-cmd` to run whole epochs without an SP1 prover.
+//!
+//! The signer must be the orchestrator owner or its `automationRegistry`.
+
+use std::{path::PathBuf, time::Duration};
+
+use alloy::{
+    primitives::Address,
+    providers::{Provider, ProviderBuilder},
+    signers::local::PrivateKeySigner,
+};
+use anyhow::Context;
+use clap::Parser;
+use orion_keeper::{Chain, CommandProver, Keeper, MockProver, Prover, StateFile};
+use tracing_subscriber::EnvFilter;
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Drive the LiquidityOrchestrator upkeep phase machine")]
+struct Cli {
+    /// JSON-RPC endpoint.
+    #[arg(long, env = "ORION_RPC_URL", default_value = "http://127.0.0.1:8545")]
+    rpc_url: String,
+
+    /// Hex private key of the owner or automation registry.
+    #[arg(long, env = "ORION_KEEPER_PRIVATE_KEY", hide_env_values = true)]
+    private_key: PrivateKeySigner,
+
+    /// `LiquidityOrchestrator` proxy address.
+    #[arg(long, env = "ORION_LIQUIDITY_ORCHESTRATOR")]
+    orchestrator: Address,
+
+    /// Command that reads epoch inputs JSON on stdin and prints hex proof bytes.
+    #[arg(long, env = "ORION_PROVER_CMD", required_unless_present = "mock_prover")]
+    prover_cmd: Option<String>,
+
+    /// Submit native executions with empty proofs (requires `MockSP1Verifier`).
+    #[arg(long, conflicts_with = "prover_cmd")]
+    mock_prover: bool,
+
+    /// Where cached inputs, proofs and in-flight transactions are persisted.
+    #[arg(long, default_value = "orion-keeper.json")]
+    state_file: PathBuf,
+
+    /// Seconds between polls.
+    #[arg(long, default_value_t = 12)]
+    poll_interval: u64,
+
+    /// First block to scan for `EpochStateCommitted` events.
+    #[arg(long, default_value_t = 0)]
+    from_block: u64,
+
+    /// Perform at most one upkeep step and exit.
+    #[arg(long)]
+    once: bool,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()))
+        .init();
+    let cli = Cli::parse();
+
+    let provider =
+        ProviderBuilder::new().wallet(cli.private_key).connect_http(cli.rpc_url.parse().context("invalid --rpc-url")?);
+    let chain = Chain::new(provider.erased(), cli.orchestrator, cli.from_block);
+    let prover: Box<dyn Prover> = match cli.prover_cmd {
+        Some(command) => Box::new(CommandProver::new(&command)?),
+        None => Box::new(MockProver),
+    };
+    let mut keeper =
+        Keeper::new(chain, prover, StateFile::new(cli.state_file), Duration::from_secs(cli.poll_interval))?;
+
+    if cli.once {
+        let action = keeper.tick().await?;
+        tracing::info!(?action, "done");
+        return Ok(());
+    }
+    keeper.run().await
+}
@@ -0,0 +1,161 @@
+//! Upkeep phase machine: what the next `performUpkeep` call must carry.
+
+use alloy::primitives::{B256, U256};
+use anyhow::bail;
+
+use crate::store::ProvenTransition;
+
+/// `ILiquidityOrchestrator.LiquidityUpkeepPhase`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    /// Waiting for the next epoch.
+    Idle,
+    /// Folding vault leaves into the epoch state commitment.
+    StateCommitment,
+    /// Executing the sell leg.
+    SellingLeg,
+    /// Executing the buy leg.
+    BuyingLeg,
+    /// Fulfilling deposits and redemptions and writing vault states.
+    ProcessVaultOperations,
+}
+
+impl TryFrom<u8> for Phase {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0 => Self::Idle,
+            1 => Self::StateCommitment,
+            2 => Self::SellingLeg,
+            3 => Self::BuyingLeg,
+            4 => Self::ProcessVaultOperations,
+            other => bail!("unknown upkeep phase {other}"),
+        })
+    }
+}
+
+impl Phase {
+    /// Whether `performUpkeep` verifies a proof in this phase.
+    pub fn needs_proof(self) -> bool {
+        matches!(self, Self::SellingLeg | Self::BuyingLeg | Self::ProcessVaultOperations)
+    }
+}
+
+/// On-chain progress of the orchestrator at one block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpkeepStatus {
+    /// `checkUpkeep()`
+    pub upkeep_needed: bool,
+    /// `currentPhase()`
+    pub phase: Phase,
+    /// `epochCounter()`
+    pub epoch: U256,
+    /// `currentMinibatchIndex()`
+    pub minibatch_index: u8,
+    /// `completedInCurrentMinibatch()`
+    pub completed_in_minibatch: u16,
+    /// `getEpochState().epochStateCommitment`
+    pub commitment: B256,
+}
+
+/// Next thing the keeper has to do.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Nothing to do until the next epoch is due.
+    Wait,
+    /// Idle start or a StateCommitment minibatch: the payload is ignored on-chain.
+    Advance,
+    /// Resubmit the cached proof; it still matches the on-chain commitment.
+    Submit,
+    /// No proof for the current commitment yet, either because the epoch just sealed or because
+    /// `_handleMinibatchLegFailure` rotated the commitment.
+    Prove,
+}
+
+/// Decides the next action from the chain status and the cached proof.
+///
+/// Progress within a leg (`currentMinibatchIndex`, `completedInCurrentMinibatch`) lives entirely
+/// on-chain, so resuming after a restart only needs the proof for the current commitment.
+pub fn next_action(status: &UpkeepStatus, cached: Option<&ProvenTransition>) -> Action {
+    if !status.upkeep_needed {
+        return Action::Wait;
+    }
+    if !status.phase.needs_proof() {
+        return Action::Advance;
+    }
+    match cached {
+        Some(proof) if proof.epoch == status.epoch && proof.input_commitment == status.commitment => Action::Submit,
+        _ => Action::Prove,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{b256, Bytes};
+
+    const COMMITMENT: B256 = b256!("00000000000000000000000000000000000000000000000000000000000000c1");
+    const ROTATED: B256 = b256!("00000000000000000000000000000000000000000000000000000000000000c2");
+
+    fn status(phase: Phase, commitment: B256) -> UpkeepStatus {
+        UpkeepStatus {
+            upkeep_needed: true,
+            phase,
+            epoch: U256::from(3),
+            minibatch_index: 1,
+            completed_in_minibatch: 2,
+            commitment,
+        }
+    }
+
+    fn proven(epoch: u64, input_commitment: B256) -> ProvenTransition {
+        ProvenTransition {
+            epoch: U256::from(epoch),
+            input_commitment,
+            public_values: Bytes::new(),
+            proof: Bytes::new(),
+            states: Bytes::new(),
+        }
+    }
+
+    #[test]
+    fn waits_when_no_upkeep_is_needed() {
+        let mut idle = status(Phase::Idle, B256::ZERO);
+        idle.upkeep_needed = false;
+        assert_eq!(next_action(&idle, None), Action::Wait);
+    }
+
+    #[test]
+    fn advances_without_proof_before_the_commitment_is_sealed() {
+        assert_eq!(next_action(&status(Phase::Idle, B256::ZERO), None), Action::Advance);
+        assert_eq!(next_action(&status(Phase::StateCommitment, B256::ZERO), None), Action::Advance);
+    }
+
+    #[test]
+    fn reuses_a_proof_for_the_same_commitment_across_phases() {
+        let cached = proven(3, COMMITMENT);
+        for phase in [Phase::SellingLeg, Phase::BuyingLeg, Phase::ProcessVaultOperations] {
+            assert_eq!(next_action(&status(phase, COMMITMENT), Some(&cached)), Action::Submit);
+        }
+    }
+
+    #[test]
+    fn reproves_after_commitment_rotation() {
+        let cached = proven(3, COMMITMENT);
+        assert_eq!(next_action(&status(Phase::SellingLeg, ROTATED), Some(&cached)), Action::Prove);
+    }
+
+    #[test]
+    fn ignores_proofs_from_previous_epochs() {
+        let cached = proven(2, COMMITMENT);
+        assert_eq!(next_action(&status(Phase::SellingLeg, COMMITMENT), Some(&cached)), Action::Prove);
+        assert_eq!(next_action(&status(Phase::SellingLeg, COMMITMENT), None), Action::Prove);
+    }
+
+    #[test]
+    fn rejects_unknown_phases() {
+        assert_eq!(Phase::try_from(4).unwrap(), Phase::ProcessVaultOperations);
+        assert!(Phase::try_from(5).is_err());
+    }
+}
@@ -0,0 +1,68 @@
+//! Proof generation backends.
+
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use alloy::primitives::Bytes;
+use anyhow::{bail, Context};
+use orion_state_orchestrator::{EpochInputs, Execution};
+
+/// Produces `proofBytes` for one epoch transition.
+pub trait Prover: Send + Sync {
+    /// Proves that the guest program maps `inputs` to `execution.public_values`.
+    fn prove(&self, inputs: &EpochInputs, execution: &Execution) -> anyhow::Result<Bytes>;
+}
+
+/// Delegates proving to an external command, typically a script around the SP1 SDK.
+///
+/// The command receives the epoch inputs as JSON on stdin (the same value the guest reads with
+/// `sp1_zkvm::io::read`) and must print the hex-encoded proof bytes on stdout.
+#[derive(Clone, Debug)]
+pub struct CommandProver {
+    program: String,
+    args: Vec<String>,
+}
+
+impl CommandProver {
+    /// Parses a whitespace-separated command line.
+    pub fn new(command: &str) -> anyhow::Result<Self> {
+        let mut parts = command.split_whitespace().map(str::to_owned);
+        let program = parts.next().context("empty prover command")?;
+        Ok(Self { program, args: parts.collect() })
+    }
+}
+
+impl Prover for CommandProver {
+    fn prove(&self, inputs: &EpochInputs, _execution: &Execution) -> anyhow::Result<Bytes> {
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .with_context(|| format!("spawning prover {}", self.program))?;
+        child.stdin.take().context("prover stdin")?.write_all(&serde_json::to_vec(inputs)?)?;
+
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            bail!("prover exited with {}", output.status);
+        }
+        let hex = String::from_utf8(output.stdout).context("prover output is not UTF-8")?;
+        hex.trim().parse().context("prover output is not hex-encoded proof bytes")
+    }
+}
+
+/// Skips proving: the transition is executed natively and submitted with empty proof bytes.
+///
+/// Only `contracts/test/MockSP1Verifier.sol` accepts these submissions; use it for local
+/// development against anvil.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MockProver;
+
+impl Prover for MockProver {
+    fn prove(&self, _inputs: &EpochInputs, _execution: &Execution) -> anyhow::Result<Bytes> {
+        Ok(Bytes::new())
+    }
+}
@@ -0,0 +1,145 @@
+//! Crash-safe keeper state persisted between runs.
+
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use alloy::primitives::{Bytes, B256, U256};
+use anyhow::Context;
+use orion_state_orchestrator::EpochInputs;
+use serde::{Deserialize, Serialize};
+
+/// A proven transition for one epoch state commitment.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvenTransition {
+    /// Epoch the proof belongs to.
+    pub epoch: U256,
+    /// Commitment the proof was generated against.
+    pub input_commitment: B256,
+    /// `_publicValues` argument.
+    pub public_values: Bytes,
+    /// `proofBytes` argument.
+    pub proof: Bytes,
+    /// `statesBytes` argument.
+    pub states: Bytes,
+}
+
+/// Everything the keeper needs to resume mid-epoch.
+///
+/// The on-chain phase and minibatch cursors are the source of truth for progress; this file only
+/// caches what is expensive or impossible to recompute after the fact.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeeperState {
+    /// Epoch the cached data belongs to.
+    pub epoch: U256,
+    /// Inputs read when the epoch was sealed. Vault state is rewritten during
+    /// ProcessVaultOperations, so it must be captured before that phase starts.
+    pub inputs: Option<EpochInputs>,
+    /// Proof for the latest commitment of `epoch`.
+    pub proof: Option<ProvenTransition>,
+    /// Hash of a `performUpkeep` transaction sent but not yet confirmed.
+    pub pending_tx: Option<B256>,
+}
+
+impl KeeperState {
+    /// Drops everything cached for a previous epoch.
+    pub fn roll_over(&mut self, epoch: U256) {
+        if self.epoch != epoch {
+            *self = Self { epoch, pending_tx: self.pending_tx, ..Self::default() };
+        }
+    }
+}
+
+/// JSON file holding the [`KeeperState`].
+#[derive(Clone, Debug)]
+pub struct StateFile {
+    path: PathBuf,
+}
+
+impl StateFile {
+    /// State file at `path`; it is created on the first save.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Path of the state file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Loads the state, or an empty one if the file does not exist yet.
+    pub fn load(&self) -> anyhow::Result<KeeperState> {
+        match fs::read_to_string(&self.path) {
+            Ok(raw) => serde_json::from_str(&raw).with_context(|| format!("parsing {}", self.path.display())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(KeeperState::default()),
+            Err(err) => Err(err).with_context(|| format!("reading {}", self.path.display())),
+        }
+    }
+
+    /// Writes the state atomically: a crash leaves either the old or the new file, never a torn one.
+    pub fn save(&self, state: &KeeperState) -> anyhow::Result<()> {
+        let tmp = self.path.with_extension("tmp");
+        let mut file = File::create(&tmp).with_context(|| format!("creating {}", tmp.display()))?;
+        file.write_all(&serde_json::to_vec_pretty(state)?)?;
+        file.sync_all()?;
+        fs::rename(&tmp, &self.path).with_context(|| format!("replacing {}", self.path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::b256;
+
+    #[test]
+    fn missing_file_loads_empty_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = StateFile::new(dir.path().join("keeper.json"));
+        assert_eq!(store.load().unwrap(), KeeperState::default());
+    }
+
+    #[test]
+    fn round_trips_through_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = StateFile::new(dir.path().join("keeper.json"));
+        let state = KeeperState {
+            epoch: U256::from(7),
+            inputs: None,
+            proof: Some(ProvenTransition {
+                epoch: U256::from(7),
+                input_commitment: b256!("00000000000000000000000000000000000000000000000000000000000000aa"),
+                public_values: Bytes::from_static(&[1, 2]),
+                proof: Bytes::new(),
+                states: Bytes::from_static(&[3]),
+            }),
+            pending_tx: Some(B256::repeat_byte(0xbb)),
+        };
+        store.save(&state).unwrap();
+        assert_eq!(store.load().unwrap(), state);
+        assert!(!dir.path().join("keeper.tmp").exists());
+    }
+
+    #[test]
+    fn roll_over_keeps_only_the_pending_transaction() {
+        let mut state =
+            KeeperState { epoch: U256::from(1), pending_tx: Some(B256::repeat_byte(1)), ..Default::default() };
+        state.proof = Some(ProvenTransition {
+            epoch: U256::from(1),
+            input_commitment: B256::ZERO,
+            public_values: Bytes::new(),
+            proof: Bytes::new(),
+            states: Bytes::new(),
+        });
+        state.roll_over(U256::from(1));
+        assert!(state.proof.is_some());
+        state.roll_over(U256::from(2));
+        assert_eq!(
+            state,
+            KeeperState { epoch: U256::from(2), pending_tx: Some(B256::repeat_byte(1)), ..Default::default() }
+        );
+    }
+}
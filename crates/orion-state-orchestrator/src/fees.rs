@@ -0,0 +1,498 @@
+//! Vault and protocol fee computation for a single epoch.
+//!
+//! Rates are annualised basis points pro-rated by `epochDuration`, like `OrionVault.YEAR_IN_SECONDS`.
+//! Share prices are the value of one full share (`10 ** SHARE_DECIMALS`) using the same virtual
+//! share/asset offset as `ERC4626Upgradeable._convertToAssets`.
+//!
+//! The management fee follows the fee model's breakpoint schedule, marginally like tax brackets,
+//! and is raised to the pro-rated minimum annual fee when it falls below it.
+//!
+//! Fee models with a crystallization period only charge the performance fee on the first epoch
+//! starting a full period after the last crystallization, measured from the share price recorded
+//! then, as `OrionVault._crystallize` advances it.
+//!
+//! The hurdle grows from that same reference at the protocol risk-free rate, at the fee model's own
+//! rate, or with its benchmark asset's price since the last crystallization.
+
+use alloy_primitives::U256;
+use orion_commitment::FeeModelSnapshot;
+
+use crate::math::{mul_div, pow10, Rounding};
+use crate::TransitionError;
+
+/// `BASIS_POINTS_FACTOR`
+pub const BASIS_POINTS_FACTOR: u64 = 10_000;
+/// `OrionVault.YEAR_IN_SECONDS`
+pub const YEAR_IN_SECONDS: u64 = 365 * 24 * 60 * 60;
+/// `OrionVault.SHARE_DECIMALS`
+pub const SHARE_DECIMALS: u8 = 18;
+
+/// Mirror of `IOrionVault.FeeType`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeeType {
+    /// Fee on the latest return, no hurdle or high water mark.
+    Absolute,
+    /// Fee on the full return once the hurdle rate is reached.
+    SoftHurdle,
+    /// Fee only on the return above the hurdle rate.
+    HardHurdle,
+    /// Fee only on gains above the previous peak.
+    HighWaterMark,
+    /// Fee above the greater of the hurdle and the high water mark.
+    HurdleHwm,
+}
+
+impl TryFrom<u8> for FeeType {
+    type Error = TransitionError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0 => Self::Absolute,
+            1 => Self::SoftHurdle,
+            2 => Self::HardHurdle,
+            3 => Self::HighWaterMark,
+            4 => Self::HurdleHwm,
+            other => return Err(TransitionError::InvalidFeeType(other)),
+        })
+    }
+}
+
+/// Mirror of `IOrionVault.HurdleType`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HurdleType {
+    /// Protocol-wide `riskFreeRate`.
+    RiskFreeRate,
+    /// The fee model's own annual `hurdleRate`.
+    FixedRate,
+    /// Price change of the fee model's `hurdleBenchmark`.
+    Benchmark,
+}
+
+impl TryFrom<u8> for HurdleType {
+    type Error = TransitionError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0 => Self::RiskFreeRate,
+            1 => Self::FixedRate,
+            2 => Self::Benchmark,
+            other => return Err(TransitionError::InvalidHurdleType(other)),
+        })
+    }
+}
+
+/// ERC-4626 share/asset conversions with the vault's decimals offset.
+#[derive(Clone, Copy, Debug)]
+pub struct ShareMath {
+    virtual_shares: U256,
+}
+
+impl ShareMath {
+    /// Conversions for a vault whose underlying has `underlying_decimals` decimals.
+    pub fn new(underlying_decimals: u8) -> Self {
+        Self { virtual_shares: pow10(SHARE_DECIMALS - underlying_decimals) }
+    }
+
+    /// `_convertToAssetsWithPITTotalAssets`
+    pub fn to_assets(
+        &self,
+        shares: U256,
+        total_assets: U256,
+        total_supply: U256,
+        rounding: Rounding,
+    ) -> Result<U256, TransitionError> {
+        mul_div(shares, total_assets + U256::from(1), total_supply + self.virtual_shares, rounding)
+    }
+
+    /// `_convertToSharesWithPITTotalAssets`
+    pub fn to_shares(
+        &self,
+        assets: U256,
+        total_assets: U256,
+        total_supply: U256,
+        rounding: Rounding,
+    ) -> Result<U256, TransitionError> {
+        mul_div(assets, total_supply + self.virtual_shares, total_assets + U256::from(1), rounding)
+    }
+
+    /// Value of one full share, the unit the high water mark is expressed in.
+    pub fn share_price(&self, total_assets: U256, total_supply: U256) -> Result<U256, TransitionError> {
+        self.to_assets(pow10(SHARE_DECIMALS), total_assets, total_supply, Rounding::Floor)
+    }
+}
+
+/// Annualised `rate_bps` of `amount` pro-rated over `epoch_duration` seconds.
+pub fn pro_rata_fee(amount: U256, rate_bps: u16, epoch_duration: u32) -> Result<U256, TransitionError> {
+    mul_div(
+        amount,
+        U256::from(rate_bps) * U256::from(epoch_duration),
+        U256::from(BASIS_POINTS_FACTOR * YEAR_IN_SECONDS),
+        Rounding::Floor,
+    )
+}
+
+/// Management fee on `total_assets` over `epoch_duration` seconds under the fee model's schedule.
+///
+/// Tiers are marginal: `management_fee` applies up to the first breakpoint and each tier rate to
+/// the assets between its breakpoint and the next; a zero breakpoint ends the schedule. The result
+/// is raised to the pro-rated `minimum_management_fee`, without exceeding `total_assets`.
+pub fn management_fee(
+    fee_model: &FeeModelSnapshot,
+    total_assets: U256,
+    epoch_duration: u32,
+) -> Result<U256, TransitionError> {
+    let mut fee = U256::ZERO;
+    let mut lower = U256::ZERO;
+    let mut rate = fee_model.management_fee;
+    for (&breakpoint, &tier_rate) in
+        fee_model.management_fee_breakpoints.iter().zip(&fee_model.management_fee_tier_rates)
+    {
+        if breakpoint.is_zero() || total_assets <= breakpoint {
+            break;
+        }
+        fee += pro_rata_fee(breakpoint - lower, rate, epoch_duration)?;
+        lower = breakpoint;
+        rate = tier_rate;
+    }
+    fee += pro_rata_fee(total_assets.saturating_sub(lower), rate, epoch_duration)?;
+
+    let minimum = mul_div(
+        fee_model.minimum_management_fee,
+        U256::from(epoch_duration),
+        U256::from(YEAR_IN_SECONDS),
+        Rounding::Floor,
+    )?;
+    Ok(fee.max(minimum).min(total_assets))
+}
+
+/// Share price a vault must exceed to clear the risk-free hurdle over `duration` seconds.
+pub fn hurdle_price(previous_price: U256, risk_free_rate: u16, duration: u64) -> Result<U256, TransitionError> {
+    let year = U256::from(BASIS_POINTS_FACTOR * YEAR_IN_SECONDS);
+    let growth = U256::from(risk_free_rate) * U256::from(duration);
+    mul_div(previous_price, year + growth, year, Rounding::Ceil)
+}
+
+/// Share price a vault must exceed to keep up with a benchmark asset.
+///
+/// Without a recorded reference price, or without a price this epoch, the benchmark is taken to be flat.
+pub fn benchmark_hurdle_price(
+    previous_price: U256,
+    reference_benchmark_price: U256,
+    benchmark_price: U256,
+) -> Result<U256, TransitionError> {
+    if reference_benchmark_price.is_zero() || benchmark_price.is_zero() {
+        return Ok(previous_price);
+    }
+    mul_div(previous_price, benchmark_price, reference_benchmark_price, Rounding::Ceil)
+}
+
+/// Whether the performance fee crystallizes in the epoch starting at `epoch_start_time`.
+pub fn crystallizes(fee_model: &FeeModelSnapshot, epoch_start_time: u64) -> bool {
+    let period = u64::from(fee_model.crystallization_period);
+    period == 0 || epoch_start_time >= fee_model.last_crystallization.saturating_add(period)
+}
+
+/// Share price above which the performance fee is charged, or `None` when no fee is due.
+pub fn performance_fee_benchmark(
+    fee_type: FeeType,
+    current_price: U256,
+    previous_price: U256,
+    high_water_mark: U256,
+    hurdle_price: U256,
+) -> Option<U256> {
+    let benchmark = match fee_type {
+        FeeType::Absolute => previous_price,
+        FeeType::SoftHurdle if current_price >= hurdle_price => previous_price,
+        FeeType::SoftHurdle => return None,
+        FeeType::HardHurdle => hurdle_price,
+        FeeType::HighWaterMark => high_water_mark,
+        FeeType::HurdleHwm => high_water_mark.max(hurdle_price),
+    };
+    (current_price > benchmark).then_some(benchmark)
+}
+
+/// Inputs to [`vault_fees`] for one vault.
+#[derive(Clone, Copy, Debug)]
+pub struct FeeInputs<'a> {
+    /// Fee model snapshotted at epoch start.
+    pub fee_model: &'a FeeModelSnapshot,
+    /// Point-in-time total assets before any fee [assets].
+    pub gross_total_assets: U256,
+    /// `totalAssets()` recorded by the previous epoch [assets].
+    pub previous_total_assets: U256,
+    /// `totalSupply()` [shares].
+    pub total_supply: U256,
+    /// Epoch duration [s].
+    pub epoch_duration: u32,
+    /// `epochStartTime` [s].
+    pub epoch_start_time: u64,
+    /// `config.riskFreeRate()` [bps].
+    pub risk_free_rate: u16,
+    /// Epoch price of the fee model's hurdle benchmark [priceAdapterDecimals], zero when unpriced.
+    pub benchmark_price: U256,
+    /// Active volume fee coefficient [bps].
+    pub v_fee_coefficient: u16,
+    /// Active revenue share fee coefficient [bps].
+    pub rs_fee_coefficient: u16,
+}
+
+/// Fee amounts charged to one vault for the epoch [assets], whatever form they are paid in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VaultFees {
+    /// Management fee credited to the vault (net of revenue share).
+    pub management_fee: U256,
+    /// Performance fee credited to the vault (net of revenue share).
+    pub performance_fee: U256,
+    /// Volume fee plus revenue share owed to the protocol.
+    pub protocol_fee: U256,
+}
+
+impl VaultFees {
+    /// Total deducted from the vault's assets.
+    pub fn total(&self) -> U256 {
+        self.management_fee + self.performance_fee + self.protocol_fee
+    }
+}
+
+/// Computes the epoch's fees for one vault.
+///
+/// The protocol volume fee and the manager's management fee are charged on gross assets.
+/// The performance fee is measured on the share price net of those two, so managers are not
+/// paid performance on money that is leaving the vault as fees. The protocol's revenue share is
+/// then carved out of the manager's fees.
+///
+/// Outside a crystallization epoch no performance fee is charged. With a crystallization period
+/// the fee is measured from the share price of the last crystallization and the hurdle compounds
+/// over the time elapsed since then, instead of over the last epoch.
+pub fn vault_fees(shares: &ShareMath, inputs: FeeInputs<'_>) -> Result<VaultFees, TransitionError> {
+    let fee_model = inputs.fee_model;
+    let fee_type = FeeType::try_from(fee_model.fee_type)?;
+    if inputs.total_supply.is_zero() {
+        return Ok(VaultFees::default());
+    }
+
+    let volume_fee = pro_rata_fee(inputs.gross_total_assets, inputs.v_fee_coefficient, inputs.epoch_duration)?;
+    let management_fee = management_fee(fee_model, inputs.gross_total_assets, inputs.epoch_duration)?
+        .min(inputs.gross_total_assets.saturating_sub(volume_fee));
+    let net_total_assets = inputs.gross_total_assets.saturating_sub(volume_fee + management_fee);
+
+    let current_price = shares.share_price(net_total_assets, inputs.total_supply)?;
+    let (previous_price, elapsed) = if fee_model.crystallization_period == 0 {
+        (shares.share_price(inputs.previous_total_assets, inputs.total_supply)?, u64::from(inputs.epoch_duration))
+    } else {
+        (fee_model.crystallization_price, inputs.epoch_start_time.saturating_sub(fee_model.last_crystallization))
+    };
+    let hurdle = match HurdleType::try_from(fee_model.hurdle_type)? {
+        HurdleType::RiskFreeRate => hurdle_price(previous_price, inputs.risk_free_rate, elapsed)?,
+        HurdleType::FixedRate => hurdle_price(previous_price, fee_model.hurdle_rate, elapsed)?,
+        HurdleType::Benchmark => {
+            benchmark_hurdle_price(previous_price, fee_model.hurdle_benchmark_price, inputs.benchmark_price)?
+        }
+    };
+
+    let benchmark =
+        performance_fee_benchmark(fee_type, current_price, previous_price, fee_model.high_water_mark, hurdle)
+            .filter(|_| crystallizes(fee_model, inputs.epoch_start_time));
+    let performance_fee = match benchmark {
+        Some(benchmark) => mul_div(
+            current_price - benchmark,
+            inputs.total_supply * U256::from(fee_model.performance_fee),
+            pow10(SHARE_DECIMALS) * U256::from(BASIS_POINTS_FACTOR),
+            Rounding::Floor,
+        )?
+        .min(net_total_assets),
+        None => U256::ZERO,
+    };
+
+    let bps = U256::from(BASIS_POINTS_FACTOR);
+    let rs = U256::from(inputs.rs_fee_coefficient);
+    let management_share = mul_div(management_fee, rs, bps, Rounding::Floor)?;
+    let performance_share = mul_div(performance_fee, rs, bps, Rounding::Floor)?;
+
+    Ok(VaultFees {
+        management_fee: management_fee - management_share,
+        performance_fee: performance_fee - performance_share,
+        protocol_fee: volume_fee + management_share + performance_share,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Address;
+    use orion_commitment::MANAGEMENT_FEE_TIERS;
+
+    const USDC_DECIMALS: u8 = 6;
+
+    fn fee_model(fee_type: u8) -> FeeModelSnapshot {
+        FeeModelSnapshot {
+            fee_type,
+            performance_fee: 2_000,
+            management_fee: 0,
+            management_fee_breakpoints: [U256::ZERO; MANAGEMENT_FEE_TIERS],
+            management_fee_tier_rates: [0; MANAGEMENT_FEE_TIERS],
+            minimum_management_fee: U256::ZERO,
+            crystallization_period: 0,
+            mint_fee_shares: false,
+            last_crystallization: 0,
+            high_water_mark: U256::from(1_000_000),
+            crystallization_price: U256::from(1_000_000),
+            hurdle_type: 0,
+            hurdle_rate: 0,
+            hurdle_benchmark: Address::ZERO,
+            hurdle_benchmark_price: U256::ZERO,
+        }
+    }
+
+    fn inputs(fee_model: &FeeModelSnapshot, gross: u64, previous: u64) -> FeeInputs<'_> {
+        FeeInputs {
+            fee_model,
+            gross_total_assets: U256::from(gross),
+            previous_total_assets: U256::from(previous),
+            total_supply: U256::from(previous) * pow10(12),
+            epoch_duration: 86_400,
+            epoch_start_time: 86_400,
+            risk_free_rate: 0,
+            benchmark_price: U256::ZERO,
+            v_fee_coefficient: 0,
+            rs_fee_coefficient: 0,
+        }
+    }
+
+    #[test]
+    fn share_price_at_par_matches_initial_high_water_mark() {
+        let shares = ShareMath::new(USDC_DECIMALS);
+        assert_eq!(shares.share_price(U256::ZERO, U256::ZERO).unwrap(), U256::from(1_000_000));
+    }
+
+    #[test]
+    fn management_fee_is_pro_rated_over_the_year() {
+        let fee = pro_rata_fee(U256::from(365_000_000u64), 100, 86_400).unwrap();
+        assert_eq!(fee, U256::from(10_000));
+    }
+
+    #[test]
+    fn management_fee_tiers_are_marginal_with_a_minimum() {
+        let usdc = |amount: u64| U256::from(amount) * pow10(USDC_DECIMALS);
+        let mut model = fee_model(0);
+        // 1% up to 10M, 0.75% up to 50M and 0.5% above.
+        model.management_fee = 100;
+        model.management_fee_breakpoints = [usdc(10_000_000), usdc(50_000_000), U256::ZERO];
+        model.management_fee_tier_rates = [75, 50, 0];
+        let year = 31_536_000;
+
+        assert_eq!(management_fee(&model, usdc(5_000_000), year).unwrap(), usdc(50_000));
+        assert_eq!(management_fee(&model, usdc(30_000_000), year).unwrap(), usdc(250_000));
+        assert_eq!(management_fee(&model, usdc(100_000_000), year).unwrap(), usdc(650_000));
+
+        model.minimum_management_fee = usdc(100_000);
+        assert_eq!(management_fee(&model, usdc(5_000_000), year).unwrap(), usdc(100_000));
+        assert_eq!(management_fee(&model, usdc(5_000_000), year / 365).unwrap(), usdc(100_000) / U256::from(365));
+        assert_eq!(management_fee(&model, usdc(30_000_000), year).unwrap(), usdc(250_000));
+    }
+
+    #[test]
+    fn absolute_fee_charges_a_share_of_the_gain() {
+        let shares = ShareMath::new(USDC_DECIMALS);
+        let model = fee_model(0);
+        let fees = vault_fees(&shares, inputs(&model, 110_000_000, 100_000_000)).unwrap();
+        // 10 USDC gain at 20% (one unit lost to virtual-share rounding)
+        assert!(fees.performance_fee > U256::from(1_999_000) && fees.performance_fee <= U256::from(2_000_000));
+    }
+
+    #[test]
+    fn high_water_mark_suppresses_fee_below_peak() {
+        let shares = ShareMath::new(USDC_DECIMALS);
+        let mut model = fee_model(3);
+        model.high_water_mark = U256::from(1_200_000);
+        let fees = vault_fees(&shares, inputs(&model, 110_000_000, 100_000_000)).unwrap();
+        assert_eq!(fees.performance_fee, U256::ZERO);
+    }
+
+    #[test]
+    fn soft_hurdle_is_all_or_nothing() {
+        let shares = ShareMath::new(USDC_DECIMALS);
+        let model = fee_model(1);
+        let mut below = inputs(&model, 100_001_000, 100_000_000);
+        below.risk_free_rate = 10_000;
+        below.epoch_duration = 31_536_000;
+        assert_eq!(vault_fees(&shares, below).unwrap().performance_fee, U256::ZERO);
+
+        let mut above = inputs(&model, 300_000_000, 100_000_000);
+        above.risk_free_rate = 10_000;
+        above.epoch_duration = 31_536_000;
+        assert!(vault_fees(&shares, above).unwrap().performance_fee > U256::from(39_000_000));
+    }
+
+    #[test]
+    fn hurdle_can_be_a_fixed_rate_or_a_benchmark() {
+        let shares = ShareMath::new(USDC_DECIMALS);
+
+        // A 30% vault rate instead of the zero risk-free rate.
+        let mut fixed_rate = fee_model(2);
+        fixed_rate.hurdle_type = 1;
+        fixed_rate.hurdle_rate = 3_000;
+        let mut year = inputs(&fixed_rate, 120_000_000, 100_000_000);
+        year.epoch_duration = 31_536_000;
+        assert_eq!(vault_fees(&shares, year).unwrap().performance_fee, U256::ZERO);
+
+        // A benchmark up 10% since its recorded price: only the 10% outperformance is charged.
+        let mut benchmark = fee_model(2);
+        benchmark.hurdle_type = 2;
+        benchmark.hurdle_benchmark_price = U256::from(100);
+        let mut epoch = inputs(&benchmark, 120_000_000, 100_000_000);
+        epoch.benchmark_price = U256::from(110);
+        let fee = vault_fees(&shares, epoch).unwrap().performance_fee;
+        assert!(fee > U256::from(1_999_000) && fee <= U256::from(2_000_000));
+
+        // Without a recorded price the benchmark counts as flat.
+        let unrecorded = FeeModelSnapshot { hurdle_benchmark_price: U256::ZERO, ..benchmark.clone() };
+        epoch.fee_model = &unrecorded;
+        let fee = vault_fees(&shares, epoch).unwrap().performance_fee;
+        assert!(fee > U256::from(3_999_000) && fee <= U256::from(4_000_000));
+    }
+
+    #[test]
+    fn crystallization_period_defers_the_performance_fee() {
+        let shares = ShareMath::new(USDC_DECIMALS);
+        let mut model = fee_model(0);
+        model.crystallization_period = 7 * 86_400;
+        model.last_crystallization = 86_400;
+
+        // 100 shares priced 1.00 at the last crystallization, 1.10 last epoch and 1.20 now.
+        let mut epoch = inputs(&model, 120_000_000, 110_000_000);
+        epoch.total_supply = U256::from(100_000_000) * pow10(12);
+
+        // Mid-period: nothing is charged, even on a gain over the last epoch.
+        let mut mid_period = epoch;
+        mid_period.epoch_start_time = 4 * 86_400;
+        assert_eq!(vault_fees(&shares, mid_period).unwrap().performance_fee, U256::ZERO);
+
+        // Period end: the whole gain since the crystallization price is charged, not just the last epoch's.
+        let mut period_end = epoch;
+        period_end.epoch_start_time = 8 * 86_400;
+        let fee = vault_fees(&shares, period_end).unwrap().performance_fee;
+        assert!(fee > U256::from(3_999_000) && fee <= U256::from(4_000_000));
+    }
+
+    #[test]
+    fn revenue_share_moves_fees_to_the_protocol() {
+        let shares = ShareMath::new(USDC_DECIMALS);
+        let model = fee_model(0);
+        let mut with_rs = inputs(&model, 110_000_000, 100_000_000);
+        with_rs.rs_fee_coefficient = 1_000;
+        let without = vault_fees(&shares, inputs(&model, 110_000_000, 100_000_000)).unwrap();
+        let with = vault_fees(&shares, with_rs).unwrap();
+        assert_eq!(with.total(), without.total());
+        assert!(with.protocol_fee > U256::ZERO);
+    }
+
+    #[test]
+    fn empty_vault_pays_nothing() {
+        let shares = ShareMath::new(USDC_DECIMALS);
+        let model = fee_model(0);
+        let mut empty = inputs(&model, 0, 0);
+        empty.total_supply = U256::ZERO;
+        assert_eq!(vault_fees(&shares, empty).unwrap(), VaultFees::default());
+    }
+}
@@ -0,0 +1,30 @@
+//! Guest program inputs: the committed snapshot plus the per-request data the leaf only sums.
+
+use alloy_primitives::{Address, U256};
+use orion_commitment::EpochSnapshot;
+use serde::{Deserialize, Serialize};
+
+/// Everything the state orchestrator reads for one epoch.
+///
+/// `snapshot` is exactly what is folded into `epochStateCommitment`; the guest recomputes that
+/// commitment from it, so any tampering shows up as a `CommitmentMismatch` on-chain.
+/// `redeem_batches` is bound through each vault's committed `redeemBatchHash`, and its per-vault
+/// sum must equal the committed `pendingRedeem`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochInputs {
+    /// Snapshot of the on-chain reads folded into the input commitment.
+    pub snapshot: EpochSnapshot,
+    /// `pendingRedeemBatch(maxFulfillBatchSize)` per vault, parallel to `snapshot.vaults`.
+    pub redeem_batches: Vec<RedeemBatch>,
+}
+
+/// Redeem requests of one vault fulfilled this epoch, in queue order.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RedeemBatch {
+    /// Requesting users, once per request.
+    pub users: Vec<Address>,
+    /// Shares fulfilled per request, parallel to `users`; the last one may be a partial fill.
+    pub shares: Vec<U256>,
+}
@@ -0,0 +1,78 @@
+//! Netting of per-vault targets into the protocol-wide sell and buy legs.
+
+use std::collections::BTreeMap;
+
+use alloy_primitives::{Address, U256};
+use orion_commitment::VaultSnapshot;
+
+use crate::market::Market;
+use crate::math::Rounding;
+use crate::TransitionError;
+use orion_types::{BuyLegOrders, SellLegOrders};
+
+/// Netted orders for the epoch plus the total estimated underlying volume.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Legs {
+    /// Orders executed during `SellingLeg`.
+    pub sell: SellLegOrders,
+    /// Orders executed during `BuyingLeg`.
+    pub buy: BuyLegOrders,
+    /// Sum of every estimated underlying amount across both legs [assets].
+    pub netted_volume: U256,
+}
+
+/// Nets current holdings against targets across every vault.
+///
+/// Both legs list every non-underlying whitelisted asset in whitelist order, with a zero amount
+/// when the asset trades on the other side or not at all. `_processMinibatchLeg` skips zero
+/// amounts, and the fixed layout keeps `currentMinibatchIndex` pointing at the same token when a
+/// leg failure rotates the commitment and the keeper resubmits a fresh proof mid-leg.
+pub fn net_legs(
+    market: &Market,
+    vaults: &[VaultSnapshot],
+    targets: &[(Vec<Address>, Vec<U256>)],
+) -> Result<Legs, TransitionError> {
+    let mut current: BTreeMap<Address, U256> = BTreeMap::new();
+    for vault in vaults {
+        for (&token, &amount) in vault.portfolio.tokens.iter().zip(&vault.portfolio.shares) {
+            *current.entry(token).or_default() += amount;
+        }
+    }
+    let mut target: BTreeMap<Address, U256> = BTreeMap::new();
+    for (tokens, amounts) in targets {
+        for (&token, &amount) in tokens.iter().zip(amounts) {
+            *target.entry(token).or_default() += amount;
+        }
+    }
+
+    let mut legs = Legs::default();
+    for &token in market.assets() {
+        if token == market.underlying() {
+            continue;
+        }
+        let have = current.get(&token).copied().unwrap_or_default();
+        let want = target.get(&token).copied().unwrap_or_default();
+
+        let (sell_amount, sell_estimate) = if have > want {
+            let amount = have - want;
+            (amount, market.value(token, amount, Rounding::Floor)?)
+        } else {
+            (U256::ZERO, U256::ZERO)
+        };
+        let (buy_amount, buy_estimate) = if want > have {
+            let amount = want - have;
+            (amount, market.value(token, amount, Rounding::Ceil)?)
+        } else {
+            (U256::ZERO, U256::ZERO)
+        };
+
+        legs.sell.sellingTokens.push(token);
+        legs.sell.sellingAmounts.push(sell_amount);
+        legs.sell.sellingEstimatedUnderlyingAmounts.push(sell_estimate);
+        legs.buy.buyingTokens.push(token);
+        legs.buy.buyingAmounts.push(buy_amount);
+        legs.buy.buyingEstimatedUnderlyingAmounts.push(buy_estimate);
+        legs.netted_volume += sell_estimate + buy_estimate;
+    }
+    Ok(legs)
+}
@@ -0,0 +1,290 @@
+//! Reference state transition of the Orion Internal State Orchestrator.
+//!
+//! Given the exact inputs folded into `epochStateCommitment`, [`execute`] computes the
+//! `StatesStruct` that `LiquidityOrchestrator.performUpkeep` consumes during the SellingLeg,
+//! BuyingLeg and ProcessVaultOperations phases, together with the `PublicValuesStruct` binding it
+//! to the commitment:
+//!
+//! ```text
+//! inputCommitment  = epochStateCommitment(snapshot)
+//! outputCommitment = keccak256(abi.encode(states))
+//! ```
+//!
+//! The SP1 guest in `programs/internal-state-orchestrator` is a thin wrapper around this crate so
+//! the same logic can be unit tested, executed natively and proven.
+//!
+//! This is the reference model, not the only valid one: any program whose verification key is
+//! registered on the orchestrator can compute its own states. The rules implemented here are
+//! documented on each step.
+
+pub mod fees;
+pub mod inputs;
+pub mod legs;
+pub mod market;
+pub mod math;
+pub mod vault;
+
+use alloy_primitives::{Address, U256};
+use alloy_sol_types::SolValue;
+use orion_commitment::{hash, CommitmentError, CommitmentReport, EpochSnapshot};
+
+pub use inputs::{EpochInputs, RedeemBatch};
+pub use orion_types::{output_commitment, BuyLegOrders, PublicValuesStruct, SellLegOrders, StatesStruct, VaultState};
+
+/// Errors raised while computing an epoch transition.
+#[derive(Debug, thiserror::Error)]
+pub enum TransitionError {
+    /// The snapshot itself is malformed.
+    #[error(transparent)]
+    Commitment(#[from] CommitmentError),
+    /// A `mulDiv` with a zero denominator.
+    #[error("division by zero")]
+    DivisionByZero,
+    /// An intermediate value does not fit in `uint256`.
+    #[error("arithmetic overflow")]
+    Overflow,
+    /// The snapshot has no whitelisted assets, so there is no underlying.
+    #[error("whitelist is empty")]
+    EmptyWhitelist,
+    /// A whitelisted asset has no price for the epoch.
+    #[error("zero price for {0}")]
+    ZeroPrice(Address),
+    /// The underlying must be priced at exactly `10 ** priceAdapterDecimals`.
+    #[error("underlying price {0} is not one unit")]
+    InvalidUnderlyingPrice(U256),
+    /// A portfolio or intent references an asset outside the whitelist.
+    #[error("{0} is not whitelisted")]
+    UnknownAsset(Address),
+    /// `feeType` does not map to a `FeeType` variant.
+    #[error("invalid fee type {0}")]
+    InvalidFeeType(u8),
+    /// `hurdleType` does not map to a `HurdleType` variant.
+    #[error("invalid hurdle type {0}")]
+    InvalidHurdleType(u8),
+    /// The intent weights do not sum to `10 ** strategistIntentDecimals`.
+    #[error("invalid intent for vault {0}")]
+    InvalidIntent(Address),
+    /// The redeem batch does not match the committed vault data.
+    #[error("invalid redeem batch for vault {vault}: {reason}")]
+    InvalidRedeemBatch {
+        /// Vault the batch belongs to.
+        vault: Address,
+        /// What is wrong with it.
+        reason: &'static str,
+    },
+}
+
+/// Result of one epoch transition.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Execution {
+    /// States decoded on-chain from `statesBytes`.
+    pub states: StatesStruct,
+    /// Public values committed by the guest.
+    pub public_values: PublicValuesStruct,
+}
+
+impl Execution {
+    /// `statesBytes` argument of `performUpkeep`: `abi.encode(states)`.
+    pub fn states_bytes(&self) -> Vec<u8> {
+        self.states.abi_encode()
+    }
+
+    /// `_publicValues` argument of `performUpkeep`: `abi.encode(publicValues)`.
+    pub fn public_values_bytes(&self) -> Vec<u8> {
+        self.public_values.abi_encode()
+    }
+}
+
+/// Runs the reference state transition for one epoch.
+///
+/// Per vault, in `vaultsEpoch` order:
+/// 1. value the portfolio at epoch prices and charge volume, management and performance fees;
+/// 2. pay out the redeem batch at the post-fee share price, then add pending deposits; fees paid in
+///    shares are minted first and stay in the vault's assets;
+/// 3. take a pro-rata cut to top the protocol buffer up to `targetBufferRatio`;
+/// 4. split what is left across the intent (see [`vault::target_portfolio`]).
+///
+/// The per-vault targets are then netted into one sell and one buy leg.
+pub fn execute(inputs: &EpochInputs) -> Result<Execution, TransitionError> {
+    let snapshot = &inputs.snapshot;
+    validate(inputs)?;
+    let protocol = &snapshot.protocol;
+    let market = market::Market::new(protocol, &snapshot.asset_prices)?;
+
+    let settled = snapshot
+        .vaults
+        .iter()
+        .zip(&inputs.redeem_batches)
+        .map(|(vault, batch)| vault::settle(&market, protocol, vault, batch))
+        .collect::<Result<Vec<_>, _>>()?;
+    let contributions = vault::buffer_contributions(protocol, &settled)?;
+
+    let mut vaults = Vec::with_capacity(settled.len());
+    let mut targets = Vec::with_capacity(settled.len());
+    for ((snapshot_vault, settled), contribution) in snapshot.vaults.iter().zip(&settled).zip(&contributions) {
+        let final_total_assets = settled.total_assets_after_flows - contribution;
+        let (tokens, shares) = vault::target_portfolio(&market, protocol, snapshot_vault, final_total_assets)?;
+        vaults.push(VaultState {
+            processRedeem: settled.process_redeem,
+            totalAssetsForRedeem: settled.total_assets_for_redeem,
+            totalAssetsForDeposit: settled.total_assets_for_deposit,
+            finalTotalAssets: final_total_assets,
+            managementFee: settled.accrued_management_fee,
+            performanceFee: settled.accrued_performance_fee,
+            tokens: tokens.clone(),
+            shares: shares.clone(),
+        });
+        targets.push((tokens, shares));
+    }
+
+    let legs = legs::net_legs(&market, &snapshot.vaults, &targets)?;
+    let states = StatesStruct {
+        vaults,
+        sellLeg: legs.sell,
+        buyLeg: legs.buy,
+        bufferIncrease: contributions.iter().copied().sum(),
+        epochProtocolFees: settled.iter().map(|vault| vault.fees.protocol_fee).sum(),
+        nettedRebalanceVolumeUnderlying: legs.netted_volume,
+    };
+
+    let public_values = orion_types::public_values(CommitmentReport::compute(snapshot).epoch_state_commitment, &states);
+    Ok(Execution { states, public_values })
+}
+
+fn validate(inputs: &EpochInputs) -> Result<(), TransitionError> {
+    let snapshot: &EpochSnapshot = &inputs.snapshot;
+    snapshot.validate()?;
+    if inputs.redeem_batches.len() != snapshot.vaults.len() {
+        return Err(CommitmentError::LengthMismatch {
+            field: "redeemBatches".into(),
+            expected: snapshot.vaults.len(),
+            actual: inputs.redeem_batches.len(),
+        }
+        .into());
+    }
+
+    for (vault, batch) in snapshot.vaults.iter().zip(&inputs.redeem_batches) {
+        let invalid = |reason| TransitionError::InvalidRedeemBatch { vault: vault.address, reason };
+        if batch.users.len() != batch.shares.len() {
+            return Err(invalid("users and shares differ in length"));
+        }
+        if hash::redeem_batch_hash(&batch.users, &batch.shares) != vault.redeem_batch_hash {
+            return Err(invalid("batch does not match redeemBatchHash"));
+        }
+        if U256::from(batch.users.len()) > snapshot.protocol.max_fulfill_batch_size {
+            return Err(invalid("batch exceeds maxFulfillBatchSize"));
+        }
+        if batch.shares.iter().copied().sum::<U256>() != vault.pending_redeem {
+            return Err(invalid("shares do not sum to pendingRedeem"));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::keccak256;
+    use alloy_sol_types::SolType;
+    use orion_commitment::{EpochSnapshot, PortfolioSnapshot};
+
+    const FIXTURE: &str = include_str!("../../orion-commitment/fixtures/single-vault.json");
+
+    fn inputs() -> EpochInputs {
+        let snapshot = EpochSnapshot::from_json(FIXTURE).unwrap();
+        let redeem_batches = vec![RedeemBatch::default(); snapshot.vaults.len()];
+        EpochInputs { snapshot, redeem_batches }
+    }
+
+    #[test]
+    fn first_epoch_allocates_deposits_to_the_intent() {
+        let execution = execute(&inputs()).unwrap();
+        let states = &execution.states;
+        let vault = &states.vaults[0];
+
+        // 100 USDC deposited, 1% target buffer on top of the 1 USDC seed: no top-up needed.
+        assert!(!vault.processRedeem);
+        assert_eq!(vault.totalAssetsForDeposit, U256::ZERO);
+        assert_eq!(vault.finalTotalAssets, U256::from(100_000_000));
+        assert_eq!(states.bufferIncrease, U256::ZERO);
+
+        // 60 USDC into a2 at 1.05 USDC per 18-decimal token, the rest stays in the underlying.
+        let a2 = snapshot_asset(1);
+        assert_eq!(vault.tokens, vec![snapshot_asset(0), a2]);
+        assert_eq!(vault.shares[0], U256::from(40_000_000));
+        assert_eq!(states.buyLeg.buyingTokens, vec![a2]);
+        assert_eq!(states.buyLeg.buyingAmounts[0], vault.shares[1]);
+        assert_eq!(states.sellLeg.sellingAmounts, vec![U256::ZERO]);
+        assert_eq!(states.nettedRebalanceVolumeUnderlying, states.buyLeg.buyingEstimatedUnderlyingAmounts[0]);
+    }
+
+    fn snapshot_asset(index: usize) -> Address {
+        inputs().snapshot.protocol.whitelisted_assets[index]
+    }
+
+    #[test]
+    fn public_values_bind_input_and_output() {
+        let inputs = inputs();
+        let execution = execute(&inputs).unwrap();
+        assert_eq!(
+            execution.public_values.inputCommitment,
+            CommitmentReport::compute(&inputs.snapshot).epoch_state_commitment
+        );
+        assert_eq!(execution.public_values.outputCommitment, keccak256(execution.states_bytes()));
+
+        // abi.encode(struct) of a dynamic struct starts with the 0x20 offset word.
+        assert_eq!(execution.states_bytes()[..32], U256::from(0x20).to_be_bytes::<32>());
+        let decoded = <StatesStruct as SolType>::abi_decode(&execution.states_bytes()).unwrap();
+        assert_eq!(decoded, execution.states);
+        assert_eq!(execution.public_values_bytes().len(), 64);
+    }
+
+    #[test]
+    fn redeem_batch_must_match_pending_redeem() {
+        let mut inputs = inputs();
+        inputs.snapshot.vaults[0].pending_redeem = U256::from(5);
+        assert!(matches!(execute(&inputs), Err(TransitionError::InvalidRedeemBatch { .. })));
+    }
+
+    #[test]
+    fn redeem_batch_must_match_its_committed_hash() {
+        let mut inputs = inputs();
+        inputs.redeem_batches[0] = RedeemBatch { users: vec![Address::with_last_byte(1)], shares: vec![U256::ZERO] };
+        assert!(matches!(
+            execute(&inputs),
+            Err(TransitionError::InvalidRedeemBatch { reason: "batch does not match redeemBatchHash", .. })
+        ));
+    }
+
+    #[test]
+    fn failed_tokens_are_frozen() {
+        let mut inputs = inputs();
+        let a2 = snapshot_asset(1);
+        inputs.snapshot.protocol.failed_epoch_tokens = vec![a2];
+        let execution = execute(&inputs).unwrap();
+        let vault = &execution.states.vaults[0];
+        assert_eq!(vault.tokens, vec![snapshot_asset(0)]);
+        assert_eq!(vault.shares, vec![U256::from(100_000_000)]);
+        assert_eq!(execution.states.buyLeg.buyingAmounts, vec![U256::ZERO]);
+    }
+
+    #[test]
+    fn fee_shares_keep_the_fee_in_the_vault() {
+        let mut inputs = inputs();
+        let vault = &mut inputs.snapshot.vaults[0];
+        vault.portfolio = PortfolioSnapshot { tokens: vec![snapshot_asset(0)], shares: vec![U256::from(100_000_000)] };
+        vault.total_assets = U256::from(100_000_000);
+        vault.total_supply = U256::from(100_000_000) * U256::from(10).pow(U256::from(12));
+        vault.pending_deposit = U256::ZERO;
+        let in_assets = execute(&inputs).unwrap().states.vaults.remove(0);
+
+        inputs.snapshot.vaults[0].fee_model.mint_fee_shares = true;
+        let in_shares = execute(&inputs).unwrap().states.vaults.remove(0);
+
+        // The manager's cut of the management fee is no longer taken out of the vault's assets...
+        assert!(!in_assets.managementFee.is_zero());
+        assert_eq!(in_shares.finalTotalAssets - in_assets.finalTotalAssets, in_assets.managementFee);
+        // ...but minted as shares worth slightly more than the fee at the pre-dilution price.
+        assert!(in_shares.managementFee > in_assets.managementFee * U256::from(10).pow(U256::from(12)));
+    }
+}
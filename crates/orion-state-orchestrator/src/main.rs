@@ -0,0 +1,69 @@
+//! `orion-state-orchestrator` executes the epoch transition natively, without proving it.
+//!
+//! ```text
+//! orion-state-orchestrator inputs.json [--decoded]
+//! ```
+//!
+//! Prints the `performUpkeep` payload as JSON: `publicValues`, `statesBytes` and an empty
+//! `proofBytes`, which is what `contracts/test/MockSP1Verifier.sol` accepts.
+
+use std::{fs, path::PathBuf};
+
+use alloy_primitives::{Bytes, B256};
+use anyhow::Context;
+use clap::Parser;
+use orion_state_orchestrator::{execute, EpochInputs, PublicValuesStruct, StatesStruct};
+use serde::Serialize;
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Run the Internal State Orchestrator natively and print the performUpkeep payload")]
+struct Cli {
+    /// Path to the epoch inputs JSON (`-` for stdin).
+    inputs: PathBuf,
+
+    /// Also print the decoded `StatesStruct` and `PublicValuesStruct`.
+    #[arg(long)]
+    decoded: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Payload {
+    input_commitment: B256,
+    output_commitment: B256,
+    public_values: Bytes,
+    proof_bytes: Bytes,
+    states_bytes: Bytes,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    decoded: Option<Decoded>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Decoded {
+    public_values: PublicValuesStruct,
+    states: StatesStruct,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let raw = if cli.inputs.as_os_str() == "-" {
+        std::io::read_to_string(std::io::stdin()).context("reading inputs from stdin")?
+    } else {
+        fs::read_to_string(&cli.inputs).with_context(|| format!("reading {}", cli.inputs.display()))?
+    };
+    let inputs: EpochInputs = serde_json::from_str(&raw).context("invalid epoch inputs")?;
+    let execution = execute(&inputs)?;
+
+    let payload = Payload {
+        input_commitment: execution.public_values.inputCommitment,
+        output_commitment: execution.public_values.outputCommitment,
+        public_values: execution.public_values_bytes().into(),
+        proof_bytes: Bytes::new(),
+        states_bytes: execution.states_bytes().into(),
+        decoded: cli.decoded.then_some(Decoded { public_values: execution.public_values, states: execution.states }),
+    };
+    println!("{}", serde_json::to_string_pretty(&payload)?);
+    Ok(())
+}
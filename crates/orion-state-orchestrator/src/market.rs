@@ -0,0 +1,150 @@
+//! Epoch price book built from the whitelisted assets and their committed prices.
+
+use std::collections::BTreeMap;
+
+use alloy_primitives::{Address, U256};
+use orion_commitment::ProtocolSnapshot;
+
+use crate::math::{mul_div, pow10, Rounding};
+use crate::TransitionError;
+
+#[derive(Clone, Copy, Debug)]
+struct AssetInfo {
+    price: U256,
+    decimals: u8,
+}
+
+/// Prices and decimals of every whitelisted asset for the epoch.
+///
+/// Prices follow `PriceAdapterRegistry.getPrice`: underlying units per whole token, scaled by
+/// `priceAdapterDecimals`. The underlying asset is always the first whitelisted asset
+/// (`OrionConfig.initialize` adds it first and it can never be removed).
+#[derive(Clone, Debug)]
+pub struct Market {
+    underlying: Address,
+    underlying_decimals: u8,
+    price_decimals: u8,
+    assets: BTreeMap<Address, AssetInfo>,
+    order: Vec<Address>,
+}
+
+impl Market {
+    /// Builds the price book from the committed whitelist and epoch prices.
+    pub fn new(protocol: &ProtocolSnapshot, prices: &[U256]) -> Result<Self, TransitionError> {
+        let underlying = *protocol.whitelisted_assets.first().ok_or(TransitionError::EmptyWhitelist)?;
+        let underlying_decimals = protocol.token_decimals[0];
+        let price_decimals = protocol.price_adapter_decimals;
+
+        let mut assets = BTreeMap::new();
+        for ((&asset, &decimals), &price) in
+            protocol.whitelisted_assets.iter().zip(&protocol.token_decimals).zip(prices)
+        {
+            if price.is_zero() {
+                return Err(TransitionError::ZeroPrice(asset));
+            }
+            assets.insert(asset, AssetInfo { price, decimals });
+        }
+        if assets[&underlying].price != pow10(price_decimals) {
+            return Err(TransitionError::InvalidUnderlyingPrice(assets[&underlying].price));
+        }
+
+        Ok(Self { underlying, underlying_decimals, price_decimals, assets, order: protocol.whitelisted_assets.clone() })
+    }
+
+    /// Underlying asset address.
+    pub fn underlying(&self) -> Address {
+        self.underlying
+    }
+
+    /// Underlying asset decimals.
+    pub fn underlying_decimals(&self) -> u8 {
+        self.underlying_decimals
+    }
+
+    /// Whitelisted assets in `getAllWhitelistedAssets()` order.
+    pub fn assets(&self) -> &[Address] {
+        &self.order
+    }
+
+    /// Committed epoch price of `token`, `None` when it is not whitelisted.
+    pub fn price(&self, token: Address) -> Option<U256> {
+        self.assets.get(&token).map(|info| info.price)
+    }
+
+    fn info(&self, token: Address) -> Result<AssetInfo, TransitionError> {
+        self.assets.get(&token).copied().ok_or(TransitionError::UnknownAsset(token))
+    }
+
+    /// Underlying value of `shares` units of `token`.
+    pub fn value(&self, token: Address, shares: U256, rounding: Rounding) -> Result<U256, TransitionError> {
+        if token == self.underlying {
+            return Ok(shares);
+        }
+        let info = self.info(token)?;
+        let numerator = info.price.checked_mul(pow10(self.underlying_decimals)).ok_or(TransitionError::Overflow)?;
+        mul_div(shares, numerator, pow10(self.price_decimals + info.decimals), rounding)
+    }
+
+    /// Number of `token` units worth `value` underlying, rounded down.
+    pub fn shares_for_value(&self, token: Address, value: U256) -> Result<U256, TransitionError> {
+        if token == self.underlying {
+            return Ok(value);
+        }
+        let info = self.info(token)?;
+        let denominator = info.price.checked_mul(pow10(self.underlying_decimals)).ok_or(TransitionError::Overflow)?;
+        mul_div(value, pow10(self.price_decimals + info.decimals), denominator, Rounding::Floor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    const USDC: Address = address!("00000000000000000000000000000000000000a1");
+    const WETH: Address = address!("00000000000000000000000000000000000000a2");
+
+    fn market() -> Market {
+        let protocol = ProtocolSnapshot {
+            max_fulfill_batch_size: U256::from(150),
+            target_buffer_ratio: U256::ZERO,
+            price_adapter_decimals: 14,
+            strategist_intent_decimals: 9,
+            epoch_duration: 86_400,
+            whitelisted_assets: vec![USDC, WETH],
+            token_decimals: vec![6, 18],
+            risk_free_rate: 0,
+            decommissioning_assets: vec![],
+            failed_epoch_tokens: vec![],
+            initial_epoch_buffer_amount: U256::ZERO,
+            buying_leg_entry_buffer: U256::ZERO,
+            buffer_amount: U256::ZERO,
+            underlying_balance: U256::ZERO,
+            epoch_start_time: U256::ZERO,
+        };
+        // 1 WETH = 2500 USDC
+        Market::new(&protocol, &[pow10(14), U256::from(2_500u64) * pow10(14)]).unwrap()
+    }
+
+    #[test]
+    fn values_tokens_in_underlying_units() {
+        let one_weth = pow10(18);
+        assert_eq!(market().value(WETH, one_weth, Rounding::Floor).unwrap(), U256::from(2_500_000_000u64));
+        assert_eq!(market().value(USDC, U256::from(7), Rounding::Floor).unwrap(), U256::from(7));
+    }
+
+    #[test]
+    fn shares_for_value_inverts_value() {
+        let market = market();
+        let shares = market.shares_for_value(WETH, U256::from(5_000_000_000u64)).unwrap();
+        assert_eq!(shares, U256::from(2) * pow10(18));
+    }
+
+    #[test]
+    fn rejects_unknown_assets() {
+        let stranger = address!("00000000000000000000000000000000000000ff");
+        assert!(
+            matches!(market().value(stranger, U256::from(1), Rounding::Floor), Err(TransitionError::UnknownAsset(a)) if a == stranger)
+        );
+    }
+}
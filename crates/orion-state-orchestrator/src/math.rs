@@ -0,0 +1,51 @@
+//! Fixed-point helpers matching OpenZeppelin `Math.mulDiv`.
+
+use alloy_primitives::{U256, U512};
+
+use crate::TransitionError;
+
+/// Rounding direction for [`mul_div`], mirroring `Math.Rounding`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    /// Round towards zero.
+    Floor,
+    /// Round away from zero.
+    Ceil,
+}
+
+/// `x * y / denominator` with a 512-bit intermediate, like `Math.mulDiv`.
+pub fn mul_div(x: U256, y: U256, denominator: U256, rounding: Rounding) -> Result<U256, TransitionError> {
+    if denominator.is_zero() {
+        return Err(TransitionError::DivisionByZero);
+    }
+    let product = U512::from(x) * U512::from(y);
+    let denominator = U512::from(denominator);
+    let mut quotient = product / denominator;
+    if rounding == Rounding::Ceil && !(product % denominator).is_zero() {
+        quotient += U512::from(1);
+    }
+    U256::checked_from_limbs_slice(quotient.as_limbs()).ok_or(TransitionError::Overflow)
+}
+
+/// `10 ** exponent`
+pub fn pow10(exponent: u8) -> U256 {
+    U256::from(10).pow(U256::from(exponent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mul_div_survives_256_bit_intermediate_overflow() {
+        let x = U256::MAX;
+        assert_eq!(mul_div(x, U256::from(3), U256::from(3), Rounding::Floor).unwrap(), x);
+    }
+
+    #[test]
+    fn mul_div_rounds_up_only_with_remainder() {
+        assert_eq!(mul_div(U256::from(10), U256::from(1), U256::from(3), Rounding::Ceil).unwrap(), U256::from(4));
+        assert_eq!(mul_div(U256::from(9), U256::from(1), U256::from(3), Rounding::Ceil).unwrap(), U256::from(3));
+        assert_eq!(mul_div(U256::from(10), U256::from(1), U256::from(3), Rounding::Floor).unwrap(), U256::from(3));
+    }
+}
@@ -0,0 +1,215 @@
+//! Per-vault settlement: fees, redemptions, deposits and the target portfolio.
+
+use alloy_primitives::{Address, U256};
+use orion_commitment::{ProtocolSnapshot, VaultSnapshot};
+
+use crate::fees::{vault_fees, FeeInputs, ShareMath, VaultFees};
+use crate::inputs::RedeemBatch;
+use crate::market::Market;
+use crate::math::{mul_div, pow10, Rounding};
+use crate::TransitionError;
+
+/// A vault after fees and LP flows, before the buffer top-up and rebalancing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SettledVault {
+    /// Point-in-time value of the live portfolio at epoch prices [assets].
+    pub gross_total_assets: U256,
+    /// Fees charged this epoch.
+    pub fees: VaultFees,
+    /// Management fee passed to `accrueVaultFees`: [assets], or [shares] when the fee model mints fee shares.
+    pub accrued_management_fee: U256,
+    /// Performance fee passed to `accrueVaultFees`, in the same unit as `accrued_management_fee`.
+    pub accrued_performance_fee: U256,
+    /// Whether `fulfillRedeem` should run.
+    pub process_redeem: bool,
+    /// Total assets passed to `fulfillRedeem` [assets].
+    pub total_assets_for_redeem: U256,
+    /// Underlying paid out to redeemers, summed per request exactly as `fulfillRedeem` rounds [assets].
+    pub redeemed_assets: U256,
+    /// Total assets passed to `fulfillDeposit` [assets].
+    pub total_assets_for_deposit: U256,
+    /// Total assets once pending deposits join the vault [assets].
+    pub total_assets_after_flows: U256,
+}
+
+/// Applies fees, the redeem batch and the deposit batch to one vault.
+///
+/// Fees paid in shares stay in the vault's assets; the shares are minted before `fulfillRedeem`
+/// at the post-fee share price, so redeemers and depositors see the same price either way.
+pub fn settle(
+    market: &Market,
+    protocol: &ProtocolSnapshot,
+    vault: &VaultSnapshot,
+    redeem_batch: &RedeemBatch,
+) -> Result<SettledVault, TransitionError> {
+    let shares = ShareMath::new(market.underlying_decimals());
+
+    let mut gross_total_assets = U256::ZERO;
+    for (&token, &amount) in vault.portfolio.tokens.iter().zip(&vault.portfolio.shares) {
+        gross_total_assets += market.value(token, amount, Rounding::Floor)?;
+    }
+
+    let fees = vault_fees(
+        &shares,
+        FeeInputs {
+            fee_model: &vault.fee_model,
+            gross_total_assets,
+            previous_total_assets: vault.total_assets,
+            total_supply: vault.total_supply,
+            epoch_duration: protocol.epoch_duration,
+            epoch_start_time: protocol.epoch_start_time.saturating_to(),
+            risk_free_rate: protocol.risk_free_rate,
+            benchmark_price: market.price(vault.fee_model.hurdle_benchmark).unwrap_or_default(),
+            v_fee_coefficient: vault.v_fee_coefficient,
+            rs_fee_coefficient: vault.rs_fee_coefficient,
+        },
+    )?;
+    let net_total_assets = gross_total_assets.saturating_sub(fees.total());
+
+    let (accrued_management_fee, accrued_performance_fee, total_assets_for_redeem, total_supply) =
+        if vault.fee_model.mint_fee_shares {
+            let management =
+                shares.to_shares(fees.management_fee, net_total_assets, vault.total_supply, Rounding::Floor)?;
+            let performance =
+                shares.to_shares(fees.performance_fee, net_total_assets, vault.total_supply, Rounding::Floor)?;
+            let total_assets = gross_total_assets.saturating_sub(fees.protocol_fee);
+            (management, performance, total_assets, vault.total_supply + management + performance)
+        } else {
+            (fees.management_fee, fees.performance_fee, net_total_assets, vault.total_supply)
+        };
+
+    // fulfillRedeem converts each request separately against the pre-burn supply.
+    let mut redeemed_assets = U256::ZERO;
+    for &request in &redeem_batch.shares {
+        redeemed_assets += shares.to_assets(request, total_assets_for_redeem, total_supply, Rounding::Floor)?;
+    }
+    let process_redeem = !vault.pending_redeem.is_zero();
+
+    let total_assets_for_deposit = total_assets_for_redeem - redeemed_assets;
+    let total_assets_after_flows = total_assets_for_deposit + vault.pending_deposit;
+
+    Ok(SettledVault {
+        gross_total_assets,
+        fees,
+        accrued_management_fee,
+        accrued_performance_fee,
+        process_redeem,
+        total_assets_for_redeem,
+        redeemed_assets,
+        total_assets_for_deposit,
+        total_assets_after_flows,
+    })
+}
+
+/// Splits the buffer top-up across vaults pro rata to their post-flow assets.
+///
+/// The buffer is topped up to `targetBufferRatio` of protocol assets, measured against the
+/// epoch-start snapshot `initialEpochBufferAmount` so the amount is identical for every proof of
+/// the epoch, including those generated after a leg failure rotates the commitment.
+pub fn buffer_contributions(
+    protocol: &ProtocolSnapshot,
+    settled: &[SettledVault],
+) -> Result<Vec<U256>, TransitionError> {
+    let total: U256 = settled.iter().map(|vault| vault.total_assets_after_flows).sum();
+    if total.is_zero() {
+        return Ok(vec![U256::ZERO; settled.len()]);
+    }
+    let target =
+        mul_div(total, protocol.target_buffer_ratio, U256::from(crate::fees::BASIS_POINTS_FACTOR), Rounding::Floor)?;
+    let deficit = target.saturating_sub(protocol.initial_epoch_buffer_amount);
+    settled.iter().map(|vault| mul_div(deficit, vault.total_assets_after_flows, total, Rounding::Floor)).collect()
+}
+
+/// Target holdings for a vault worth `final_total_assets` after rebalancing.
+///
+/// Weight on assets that are being decommissioned is redirected to the underlying. Tokens that
+/// failed to trade earlier in the epoch are frozen at their current holding and their planned
+/// trade is absorbed by the underlying position, so orders for every other token stay identical
+/// to the ones already executed before the failure.
+pub fn target_portfolio(
+    market: &Market,
+    protocol: &ProtocolSnapshot,
+    vault: &VaultSnapshot,
+    final_total_assets: U256,
+) -> Result<(Vec<Address>, Vec<U256>), TransitionError> {
+    let underlying = market.underlying();
+    let one = pow10(protocol.strategist_intent_decimals);
+    let intent = &vault.intent;
+
+    let weight_sum: u64 = intent.weights.iter().map(|&w| u64::from(w)).sum();
+    if intent.tokens.is_empty() || U256::from(weight_sum) != one {
+        return Err(TransitionError::InvalidIntent(vault.address));
+    }
+
+    let held = |token: Address| {
+        vault.portfolio.tokens.iter().position(|&t| t == token).map_or(U256::ZERO, |i| vault.portfolio.shares[i])
+    };
+    let is_frozen = |token: Address| token != underlying && protocol.failed_epoch_tokens.contains(&token);
+    let is_traded =
+        |token: Address| token != underlying && !is_frozen(token) && !protocol.decommissioning_assets.contains(&token);
+
+    let mut frozen_value = U256::ZERO;
+    let mut frozen = Vec::new();
+    for &token in vault.portfolio.tokens.iter().chain(&intent.tokens) {
+        if is_frozen(token) && !frozen.iter().any(|&(t, _)| t == token) {
+            let amount = held(token);
+            frozen_value += market.value(token, amount, Rounding::Floor)?;
+            frozen.push((token, amount));
+        }
+    }
+    let budget = final_total_assets.saturating_sub(frozen_value);
+
+    let traded_weight: u64 =
+        intent.tokens.iter().zip(&intent.weights).filter(|(&t, _)| is_traded(t)).map(|(_, &w)| u64::from(w)).sum();
+    let mut target_values = Vec::with_capacity(intent.tokens.len());
+    for (&token, &weight) in intent.tokens.iter().zip(&intent.weights) {
+        let value = if is_traded(token) {
+            mul_div(final_total_assets, U256::from(weight), one, Rounding::Floor)?
+        } else {
+            U256::ZERO
+        };
+        target_values.push(value);
+    }
+    let mut allocated: U256 = target_values.iter().copied().sum();
+    if allocated > budget {
+        // Frozen holdings crowd out part of the intent: scale traded tokens down to what is left.
+        for (value, (&token, &weight)) in target_values.iter_mut().zip(intent.tokens.iter().zip(&intent.weights)) {
+            if is_traded(token) {
+                *value = mul_div(budget, U256::from(weight), U256::from(traded_weight), Rounding::Floor)?;
+            }
+        }
+        allocated = target_values.iter().copied().sum();
+    }
+    let underlying_value = budget - allocated;
+
+    let mut tokens = Vec::new();
+    let mut amounts = Vec::new();
+    let mut push = |token: Address, amount: U256| {
+        if !amount.is_zero() {
+            tokens.push(token);
+            amounts.push(amount);
+        }
+    };
+
+    let mut underlying_placed = false;
+    for (&token, &value) in intent.tokens.iter().zip(&target_values) {
+        if token == underlying {
+            push(token, underlying_value);
+            underlying_placed = true;
+        } else if let Some(&(_, amount)) = frozen.iter().find(|&&(t, _)| t == token) {
+            push(token, amount);
+        } else if is_traded(token) {
+            push(token, market.shares_for_value(token, value)?);
+        }
+    }
+    for &(token, amount) in &frozen {
+        if !intent.tokens.contains(&token) {
+            push(token, amount);
+        }
+    }
+    if !underlying_placed {
+        push(underlying, underlying_value);
+    }
+
+    Ok((tokens, amounts))
+}
@@ -0,0 +1,108 @@
+//! Rust mirrors of the `ILiquidityOrchestrator` structs exchanged with `performUpkeep` and
+//! `getEpochState`.
+//!
+//! Field names and order follow the Solidity declarations exactly, so [`SolValue::abi_encode`]
+//! produces the same bytes as `abi.encode` and [`SolValue::abi_decode`] accepts what
+//! `abi.decode` accepts.
+//!
+//! [`SolValue::abi_encode`]: alloy_sol_types::SolValue::abi_encode
+//! [`SolValue::abi_decode`]: alloy_sol_types::SolValue::abi_decode
+
+use alloy_sol_types::sol;
+
+sol! {
+    /// `ILiquidityOrchestrator.PublicValuesStruct`, committed by the guest program.
+    #[derive(Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    struct PublicValuesStruct {
+        bytes32 inputCommitment;
+        bytes32 outputCommitment;
+    }
+
+    /// `ILiquidityOrchestrator.VaultState`
+    #[derive(Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    struct VaultState {
+        bool processRedeem;
+        uint256 totalAssetsForRedeem;
+        uint256 totalAssetsForDeposit;
+        uint256 finalTotalAssets;
+        uint256 managementFee;
+        uint256 performanceFee;
+        address[] tokens;
+        uint256[] shares;
+    }
+
+    /// `ILiquidityOrchestrator.SellLegOrders`
+    #[derive(Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    struct SellLegOrders {
+        address[] sellingTokens;
+        uint256[] sellingAmounts;
+        uint256[] sellingEstimatedUnderlyingAmounts;
+    }
+
+    /// `ILiquidityOrchestrator.BuyLegOrders`
+    #[derive(Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    struct BuyLegOrders {
+        address[] buyingTokens;
+        uint256[] buyingAmounts;
+        uint256[] buyingEstimatedUnderlyingAmounts;
+    }
+
+    /// `ILiquidityOrchestrator.StatesStruct`, decoded on-chain from `statesBytes`.
+    #[derive(Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    struct StatesStruct {
+        VaultState[] vaults;
+        SellLegOrders sellLeg;
+        BuyLegOrders buyLeg;
+        uint256 bufferIncrease;
+        uint256 epochProtocolFees;
+        uint256 nettedRebalanceVolumeUnderlying;
+    }
+
+    /// `IOrionVault.FeeModel`
+    #[derive(Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    struct FeeModel {
+        uint8 feeType;
+        uint16 performanceFee;
+        uint16 managementFee;
+        uint256 highWaterMark;
+    }
+
+    /// `IOrionVault.ManagementFeeSchedule`
+    #[derive(Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    struct ManagementFeeSchedule {
+        uint256[3] breakpoints;
+        uint16[3] tierRates;
+        uint256 minimumFee;
+    }
+
+    /// `IOrionVault.FeeSettlement`
+    #[derive(Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    struct FeeSettlement {
+        uint32 crystallizationPeriod;
+        bool mintFeeShares;
+        uint64 lastCrystallization;
+        uint256 crystallizationPrice;
+    }
+
+    /// `IOrionVault.Hurdle`
+    #[derive(Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    struct Hurdle {
+        uint8 hurdleType;
+        uint16 rate;
+        address benchmark;
+        uint256 benchmarkPrice;
+    }
+
+    /// `ILiquidityOrchestrator.EpochStateView`, returned by `getEpochState()`.
+    #[derive(Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    struct EpochStateView {
+        address[] vaultsEpoch;
+        FeeModel[] vaultFeeModels;
+        ManagementFeeSchedule[] vaultManagementFeeSchedules;
+        FeeSettlement[] vaultFeeSettlements;
+        Hurdle[] vaultHurdles;
+        uint16[] vaultVFeeCoefficients;
+        uint16[] vaultRsFeeCoefficients;
+        bytes32 epochStateCommitment;
+    }
+}
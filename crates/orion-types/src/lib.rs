@@ -0,0 +1,109 @@
+//! Typed mirrors of the structs `LiquidityOrchestrator.performUpkeep` consumes, with the same ABI
+//! encoding as Solidity.
+//!
+//! ```text
+//! _publicValues = abi.encode(PublicValuesStruct)
+//! statesBytes   = abi.encode(StatesStruct)
+//! outputCommitment = keccak256(abi.encode(abi.decode(statesBytes, (StatesStruct))))
+//! ```
+//!
+//! Keepers, provers and auditors should build and decode these payloads through this crate rather
+//! than by hand: [`decode_states`] applies the same decoding as the contract plus the shape checks
+//! in [`validate`], and [`output_commitment`] is the hash `_verifyPerformData` recomputes.
+
+pub mod abi;
+pub mod validate;
+
+use alloy_primitives::{keccak256, Address, B256};
+use alloy_sol_types::SolValue;
+
+pub use abi::{
+    BuyLegOrders, EpochStateView, FeeModel, FeeSettlement, Hurdle, ManagementFeeSchedule, PublicValuesStruct,
+    SellLegOrders, StatesStruct, VaultState,
+};
+pub use validate::StatesError;
+
+/// `keccak256(abi.encode(states))`, as recomputed by `_verifyPerformData`.
+pub fn output_commitment(states: &StatesStruct) -> B256 {
+    keccak256(states.abi_encode())
+}
+
+/// Public values binding `states` to the epoch state commitment they were computed from.
+pub fn public_values(input_commitment: B256, states: &StatesStruct) -> PublicValuesStruct {
+    PublicValuesStruct { inputCommitment: input_commitment, outputCommitment: output_commitment(states) }
+}
+
+/// Decodes `statesBytes` and checks it against the epoch's `vaultsEpoch`.
+pub fn decode_states(states_bytes: &[u8], vaults_epoch: &[Address]) -> Result<StatesStruct, StatesError> {
+    let states = StatesStruct::abi_decode(states_bytes)?;
+    states.validate(vaults_epoch)?;
+    Ok(states)
+}
+
+/// Decodes `_publicValues`.
+pub fn decode_public_values(public_values: &[u8]) -> Result<PublicValuesStruct, StatesError> {
+    Ok(PublicValuesStruct::abi_decode(public_values)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{address, b256, U256};
+
+    use super::*;
+
+    const VAULT: Address = address!("00000000000000000000000000000000000000a1");
+    const TOKEN: Address = address!("00000000000000000000000000000000000000b1");
+
+    fn states() -> StatesStruct {
+        StatesStruct {
+            vaults: vec![VaultState {
+                processRedeem: true,
+                finalTotalAssets: U256::from(100),
+                tokens: vec![TOKEN],
+                shares: vec![U256::from(7)],
+                ..Default::default()
+            }],
+            sellLeg: SellLegOrders {
+                sellingTokens: vec![TOKEN],
+                sellingAmounts: vec![U256::ZERO],
+                sellingEstimatedUnderlyingAmounts: vec![U256::ZERO],
+            },
+            buyLeg: BuyLegOrders {
+                buyingTokens: vec![TOKEN],
+                buyingAmounts: vec![U256::from(7)],
+                buyingEstimatedUnderlyingAmounts: vec![U256::from(7)],
+            },
+            bufferIncrease: U256::from(1),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn states_round_trip_through_abi_encoding() {
+        let states = states();
+        let bytes = states.abi_encode();
+        // `abi.encode` of a struct with dynamic members starts with the offset of its tail.
+        assert_eq!(&bytes[..32], U256::from(32).to_be_bytes::<32>().as_slice());
+        assert_eq!(decode_states(&bytes, &[VAULT]), Ok(states));
+    }
+
+    #[test]
+    fn decode_rejects_states_for_another_epoch() {
+        let bytes = states().abi_encode();
+        assert!(matches!(
+            decode_states(&bytes, &[VAULT, TOKEN]),
+            Err(StatesError::LengthMismatch { expected: 2, actual: 1, .. })
+        ));
+        assert!(matches!(decode_states(&bytes[..bytes.len() - 1], &[VAULT]), Err(StatesError::Decode(_))));
+    }
+
+    #[test]
+    fn public_values_commit_to_the_encoded_states() {
+        let input = b256!("1111111111111111111111111111111111111111111111111111111111111111");
+        let states = states();
+        let values = public_values(input, &states);
+        assert_eq!(values.inputCommitment, input);
+        assert_eq!(values.outputCommitment, keccak256(states.abi_encode()));
+        assert_eq!(decode_public_values(&values.abi_encode()), Ok(values));
+    }
+}
@@ -0,0 +1,146 @@
+//! Shape checks `LiquidityOrchestrator` relies on but does not perform itself.
+//!
+//! `performUpkeep` indexes `states.vaults` by position in `vaultsEpoch` and walks the token and
+//! amount arrays of each vault and leg in lockstep. A malformed `StatesStruct` only surfaces as an
+//! out-of-bounds panic (or a silently ignored tail) on-chain; these checks catch it before
+//! submission.
+
+use alloy_primitives::Address;
+
+use crate::abi::{BuyLegOrders, SellLegOrders, StatesStruct, VaultState};
+
+/// A `StatesStruct` that `performUpkeep` would reject or misapply.
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum StatesError {
+    /// `statesBytes` or `_publicValues` is not a valid encoding of the struct.
+    #[error("invalid encoding: {0}")]
+    Decode(#[from] alloy_sol_types::Error),
+    /// Two arrays that are read in lockstep on-chain have different lengths.
+    #[error("{field} has {actual} entries, expected {expected}")]
+    LengthMismatch {
+        /// Offending field, as a Solidity access path (`vaults[2].shares`).
+        field: String,
+        /// Length of the array it must be parallel to.
+        expected: usize,
+        /// Actual length.
+        actual: usize,
+    },
+}
+
+fn parallel(field: impl Into<String>, expected: usize, actual: usize) -> Result<(), StatesError> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(StatesError::LengthMismatch { field: field.into(), expected, actual })
+    }
+}
+
+impl VaultState {
+    /// Checks that `tokens` and `shares` are parallel.
+    pub fn validate(&self) -> Result<(), StatesError> {
+        parallel("shares", self.tokens.len(), self.shares.len())
+    }
+}
+
+impl SellLegOrders {
+    /// Checks that the amount arrays are parallel to `sellingTokens`.
+    pub fn validate(&self) -> Result<(), StatesError> {
+        let tokens = self.sellingTokens.len();
+        parallel("sellingAmounts", tokens, self.sellingAmounts.len())?;
+        parallel("sellingEstimatedUnderlyingAmounts", tokens, self.sellingEstimatedUnderlyingAmounts.len())
+    }
+}
+
+impl BuyLegOrders {
+    /// Checks that the amount arrays are parallel to `buyingTokens`.
+    pub fn validate(&self) -> Result<(), StatesError> {
+        let tokens = self.buyingTokens.len();
+        parallel("buyingAmounts", tokens, self.buyingAmounts.len())?;
+        parallel("buyingEstimatedUnderlyingAmounts", tokens, self.buyingEstimatedUnderlyingAmounts.len())
+    }
+}
+
+impl StatesStruct {
+    /// Checks that there is one [`VaultState`] per entry of `vaults_epoch` and that every token
+    /// array has parallel amount arrays.
+    pub fn validate(&self, vaults_epoch: &[Address]) -> Result<(), StatesError> {
+        parallel("vaults", vaults_epoch.len(), self.vaults.len())?;
+        for (i, vault) in self.vaults.iter().enumerate() {
+            vault.validate().map_err(|err| prefixed(&format!("vaults[{i}]"), err))?;
+        }
+        self.sellLeg.validate().map_err(|err| prefixed("sellLeg", err))?;
+        self.buyLeg.validate().map_err(|err| prefixed("buyLeg", err))
+    }
+}
+
+fn prefixed(parent: &str, err: StatesError) -> StatesError {
+    match err {
+        StatesError::LengthMismatch { field, expected, actual } => {
+            StatesError::LengthMismatch { field: format!("{parent}.{field}"), expected, actual }
+        }
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{address, U256};
+
+    use super::*;
+
+    const VAULT_A: Address = address!("00000000000000000000000000000000000000a1");
+    const VAULT_B: Address = address!("00000000000000000000000000000000000000a2");
+    const TOKEN: Address = address!("00000000000000000000000000000000000000b1");
+
+    fn states() -> StatesStruct {
+        let vault = VaultState { tokens: vec![TOKEN], shares: vec![U256::from(1)], ..Default::default() };
+        StatesStruct {
+            vaults: vec![vault.clone(), vault],
+            sellLeg: SellLegOrders {
+                sellingTokens: vec![TOKEN],
+                sellingAmounts: vec![U256::ZERO],
+                sellingEstimatedUnderlyingAmounts: vec![U256::ZERO],
+            },
+            buyLeg: BuyLegOrders {
+                buyingTokens: vec![TOKEN],
+                buyingAmounts: vec![U256::from(5)],
+                buyingEstimatedUnderlyingAmounts: vec![U256::from(5)],
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn accepts_well_formed_states() {
+        assert_eq!(states().validate(&[VAULT_A, VAULT_B]), Ok(()));
+    }
+
+    #[test]
+    fn rejects_vault_count_mismatch() {
+        assert_eq!(
+            states().validate(&[VAULT_A]),
+            Err(StatesError::LengthMismatch { field: "vaults".into(), expected: 1, actual: 2 })
+        );
+    }
+
+    #[test]
+    fn reports_the_path_of_non_parallel_arrays() {
+        let mut short_shares = states();
+        short_shares.vaults[1].shares.clear();
+        assert_eq!(
+            short_shares.validate(&[VAULT_A, VAULT_B]),
+            Err(StatesError::LengthMismatch { field: "vaults[1].shares".into(), expected: 1, actual: 0 })
+        );
+
+        let mut long_estimates = states();
+        long_estimates.buyLeg.buyingEstimatedUnderlyingAmounts.push(U256::ZERO);
+        assert_eq!(
+            long_estimates.validate(&[VAULT_A, VAULT_B]),
+            Err(StatesError::LengthMismatch {
+                field: "buyLeg.buyingEstimatedUnderlyingAmounts".into(),
+                expected: 1,
+                actual: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,17 @@
+//! Orion Internal State Orchestrator, the program behind `LiquidityOrchestrator.vKey`.
+//!
+//! Reads the epoch inputs, runs the reference transition and commits `abi.encode(publicValues)`,
+//! which is exactly the `_publicValues` blob `_verifyPerformData` decodes. The matching
+//! `statesBytes` are produced by the host from the same inputs with
+//! `orion_state_orchestrator::execute`.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use orion_state_orchestrator::{execute, EpochInputs};
+
+pub fn main() {
+    let inputs = sp1_zkvm::io::read::<EpochInputs>();
+    let execution = execute(&inputs).expect("invalid epoch inputs");
+    sp1_zkvm::io::commit_slice(&execution.public_values_bytes());
+}
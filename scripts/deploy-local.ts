@@ -0,0 +1,39 @@
+/**
+ * Deploys the protocol to a local node for running `orion-keeper` end to end.
+ *
+ *   anvil
+ *   [MOCK_VERIFIER=true] pnpm hardhat run scripts/deploy-local.ts --network localhost
+ *
+ * The first node account becomes owner and automation registry; pass its private key to the keeper.
+ * With MOCK_VERIFIER=true the orchestrator verifies through MockSP1Verifier, so the keeper can run
+ * with --mock-prover.
+ */
+import { ethers } from "../test/helpers/hh";
+import { deployUpgradeableProtocol } from "../test/helpers/deployUpgradeable";
+
+async function main() {
+  const [owner] = await ethers.getSigners();
+  const protocol = await deployUpgradeableProtocol(owner);
+
+  if (process.env.MOCK_VERIFIER === "true") {
+    const MockSP1VerifierFactory = await ethers.getContractFactory("MockSP1Verifier");
+    const mockVerifier = await MockSP1VerifierFactory.deploy(await protocol.liquidityOrchestrator.vKey());
+    await mockVerifier.waitForDeployment();
+    await protocol.liquidityOrchestrator.updateVerifier(await mockVerifier.getAddress());
+  }
+
+  const addresses = {
+    owner: owner.address,
+    orionConfig: await protocol.orionConfig.getAddress(),
+    liquidityOrchestrator: await protocol.liquidityOrchestrator.getAddress(),
+    transparentVaultFactory: await protocol.transparentVaultFactory.getAddress(),
+    underlyingAsset: await protocol.underlyingAsset.getAddress(),
+    fromBlock: await ethers.provider.getBlockNumber(),
+  };
+  console.log(JSON.stringify(addresses, null, 2));
+}
+
+main().catch((error) => {
+  console.error(error);
+  process.exitCode = 1;
+});
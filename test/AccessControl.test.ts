@@ -1,7 +1,8 @@
 import { expect } from "chai";
-import { ethers } from "./helpers/hh";
+import { ethers, networkHelpers } from "./helpers/hh";
 import { SignerWithAddress } from "@nomicfoundation/hardhat-ethers/signers";
 import type {
+  LiquidityOrchestrator,
   MockUnderlyingAsset,
   TransparentVaultFactory,
   OrionTransparentVault,
@@ -23,6 +24,7 @@ describe("Access Control", function () {
 
   let mockAsset: MockUnderlyingAsset;
   let factory: TransparentVaultFactory;
+  let liquidityOrchestrator: LiquidityOrchestrator;
   let accessControl: WhitelistAccessControl;
 
   const DEPOSIT_AMOUNT = ethers.parseUnits("1000", 6);
@@ -34,6 +36,7 @@ describe("Access Control", function () {
 
     mockAsset = deployed.underlyingAsset;
     factory = deployed.transparentVaultFactory;
+    liquidityOrchestrator = deployed.liquidityOrchestrator;
 
     // Deploy WhitelistAccessControl
     const WhitelistAccessControlFactory = await ethers.getContractFactory("WhitelistAccessControl");
@@ -171,6 +174,26 @@ describe("Access Control", function () {
       );
     });
 
+    it("Should check the controller rather than the caller", async function () {
+      await accessControl.addToWhitelist([user1.address]);
+
+      for (const user of [user1, user2]) {
+        await mockAsset.mint(user.address, DEPOSIT_AMOUNT);
+        await mockAsset.connect(user).approve(await vault.getAddress(), DEPOSIT_AMOUNT);
+      }
+
+      // A whitelisted caller cannot open a request for a non-whitelisted controller...
+      await expect(
+        vault.connect(user1)["requestDeposit(uint256,address,address)"](DEPOSIT_AMOUNT, user2.address, user1.address),
+      ).to.be.revertedWithCustomError(vault, "DepositNotAllowed");
+
+      // ...while anyone can fund a request for a whitelisted controller.
+      await vault
+        .connect(user2)
+        ["requestDeposit(uint256,address,address)"](DEPOSIT_AMOUNT, user1.address, user2.address);
+      expect(await vault.pendingDepositRequest(0, user1.address)).to.equal(DEPOSIT_AMOUNT);
+    });
+
     it("Should support batch whitelisting", async function () {
       const addresses = [user1.address, user2.address, user3.address];
 
@@ -185,6 +208,142 @@ describe("Access Control", function () {
     });
   });
 
+  describe("Redemptions And Share Transfers", function () {
+    let vault: OrionTransparentVault;
+    let shares: bigint;
+
+    beforeEach(async function () {
+      const vaultAddress = await factory.createVault.staticCall(
+        strategist.address,
+        "Closed Vault",
+        "CV",
+        0,
+        0,
+        0,
+        await accessControl.getAddress(),
+      );
+      await factory.createVault(strategist.address, "Closed Vault", "CV", 0, 0, 0, await accessControl.getAddress());
+      vault = (await ethers.getContractAt("OrionTransparentVault", vaultAddress)) as unknown as OrionTransparentVault;
+
+      // user1 is whitelisted and has a fulfilled deposit
+      await accessControl.addToWhitelist([user1.address]);
+      await mockAsset.mint(user1.address, DEPOSIT_AMOUNT);
+      await mockAsset.connect(user1).approve(vaultAddress, DEPOSIT_AMOUNT);
+      await vault.connect(user1).requestDeposit(DEPOSIT_AMOUNT);
+
+      const loAddress = await liquidityOrchestrator.getAddress();
+      await networkHelpers.impersonateAccount(loAddress);
+      await networkHelpers.setBalance(loAddress, ethers.parseEther("1"));
+      const loSigner = await ethers.getSigner(loAddress);
+      await vault.connect(loSigner).fulfillDeposit(0);
+      await vault.connect(loSigner).updateVaultState([], [], DEPOSIT_AMOUNT);
+      shares = await vault.maxMint(user1.address);
+    });
+
+    it("Should only deliver claimed shares to whitelisted receivers", async function () {
+      await expect(
+        vault.connect(user1)["deposit(uint256,address)"](DEPOSIT_AMOUNT, user2.address),
+      ).to.be.revertedWithCustomError(vault, "TransferNotAllowed");
+
+      await vault.connect(user1)["deposit(uint256,address)"](DEPOSIT_AMOUNT, user1.address);
+      expect(await vault.balanceOf(user1.address)).to.equal(shares);
+    });
+
+    it("Should reject share transfers to non-whitelisted addresses", async function () {
+      await vault.connect(user1)["deposit(uint256,address)"](DEPOSIT_AMOUNT, user1.address);
+
+      await expect(vault.connect(user1).transfer(user2.address, shares)).to.be.revertedWithCustomError(
+        vault,
+        "TransferNotAllowed",
+      );
+
+      await accessControl.addToWhitelist([user2.address]);
+      await vault.connect(user1).transfer(user2.address, shares);
+      expect(await vault.balanceOf(user2.address)).to.equal(shares);
+    });
+
+    it("Should reject redemption requests for non-whitelisted controllers", async function () {
+      await vault.connect(user1)["deposit(uint256,address)"](DEPOSIT_AMOUNT, user1.address);
+
+      await expect(
+        vault.connect(user1)["requestRedeem(uint256,address,address)"](shares, user2.address, user1.address),
+      ).to.be.revertedWithCustomError(vault, "RedeemNotAllowed");
+
+      await accessControl.removeFromWhitelist([user1.address]);
+      await expect(vault.connect(user1).requestRedeem(shares)).to.be.revertedWithCustomError(vault, "RedeemNotAllowed");
+
+      // Escrowing the shares in the vault is not a transfer the access control has to allow.
+      await accessControl.addToWhitelist([user1.address]);
+      await vault.connect(user1).requestRedeem(shares);
+      expect(await vault.pendingRedeemRequest(0, user1.address)).to.equal(shares);
+    });
+  });
+
+  describe("Legacy Access Controls", function () {
+    it("Should keep redemptions and transfers open with controllers predating their hooks", async function () {
+      const legacyAccessControl = await (await ethers.getContractFactory("MockLegacyAccessControl")).deploy();
+      const legacyAddress = await legacyAccessControl.getAddress();
+      const vaultAddress = await factory.createVault.staticCall(
+        strategist.address,
+        "Legacy Vault",
+        "LV",
+        0,
+        0,
+        0,
+        legacyAddress,
+      );
+      await factory.createVault(strategist.address, "Legacy Vault", "LV", 0, 0, 0, legacyAddress);
+      const vault = (await ethers.getContractAt(
+        "OrionTransparentVault",
+        vaultAddress,
+      )) as unknown as OrionTransparentVault;
+
+      await legacyAccessControl.addToWhitelist(user1.address);
+      for (const user of [user1, user2]) {
+        await mockAsset.mint(user.address, DEPOSIT_AMOUNT);
+        await mockAsset.connect(user).approve(vaultAddress, DEPOSIT_AMOUNT);
+      }
+      // Deposit requests are still gated.
+      await expect(vault.connect(user2).requestDeposit(DEPOSIT_AMOUNT)).to.be.revertedWithCustomError(
+        vault,
+        "DepositNotAllowed",
+      );
+      await vault.connect(user1).requestDeposit(DEPOSIT_AMOUNT);
+
+      const loAddress = await liquidityOrchestrator.getAddress();
+      await networkHelpers.impersonateAccount(loAddress);
+      await networkHelpers.setBalance(loAddress, ethers.parseEther("1"));
+      const loSigner = await ethers.getSigner(loAddress);
+      await vault.connect(loSigner).fulfillDeposit(0);
+      await vault.connect(loSigner).updateVaultState([], [], DEPOSIT_AMOUNT);
+
+      // Claims, transfers, redemption requests and their cancellation do not call the missing hooks.
+      const shares = await vault.maxMint(user1.address);
+      await vault.connect(user1)["deposit(uint256,address)"](DEPOSIT_AMOUNT, user1.address);
+      await vault.connect(user1).transfer(user2.address, shares);
+      await vault.connect(user2).requestRedeem(shares);
+      await vault.connect(user2)["cancelRedeemRequest(uint256)"](shares);
+      expect(await vault.balanceOf(user2.address)).to.equal(shares);
+    });
+
+    it("Should report the access control interface from current controllers", async function () {
+      const iface = ethers.Interface.from([
+        "function canRequestDeposit(address,bytes) view returns (bool)",
+        "function canRequestRedeem(address,bytes) view returns (bool)",
+        "function canTransferShares(address,address,uint256) view returns (bool)",
+        "function isBlocked(address) view returns (bool)",
+      ]);
+      let interfaceId = 0n;
+      iface.forEachFunction((fragment) => {
+        interfaceId ^= BigInt(fragment.selector);
+      });
+
+      expect(await accessControl.supportsInterface(ethers.toBeHex(interfaceId, 4))).to.equal(true);
+      // The original interface, with only the deposit hook, is not reported.
+      expect(await accessControl.supportsInterface(iface.getFunction("canRequestDeposit")!.selector)).to.equal(false);
+    });
+  });
+
   describe("Manager Can Update Access Control", function () {
     let vault: OrionTransparentVault;
 
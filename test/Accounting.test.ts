@@ -9,6 +9,7 @@ import type {
   TransparentVaultFactory,
   OrionTransparentVault,
 } from "../typechain-types";
+import { claimDeposit, claimRedeem } from "./helpers/claims";
 import { deployUpgradeableProtocol } from "./helpers/deployUpgradeable";
 import { resetNetwork } from "./helpers/resetNetwork";
 
@@ -134,6 +135,7 @@ describe("OrionVault Accounting", function () {
       const depositAssets = parseUnderlying("100000");
       await vault.connect(user).requestDeposit(depositAssets);
       await setVaultStateWithFulfilledDeposit(vault, depositAssets, depositAssets);
+      await claimDeposit(vault, user);
 
       const userShares = await vault.balanceOf(user.address);
       expect(userShares).to.be.gt(0);
@@ -153,6 +155,11 @@ describe("OrionVault Accounting", function () {
       await vault.connect(loSigner).fulfillRedeem(redeemTotalAssets);
       await ethers.provider.send("hardhat_stopImpersonatingAccount", [loAddress]);
 
+      // Fulfilled redemptions wait in the orchestrator until claimed.
+      expect(await underlyingAsset.balanceOf(user.address)).to.equal(balanceBefore);
+      expect(await vault.claimableRedeemRequest(0, user.address)).to.equal(redeemShares);
+      await claimRedeem(vault, user);
+
       const balanceAfter = await underlyingAsset.balanceOf(user.address);
       expect(balanceAfter - balanceBefore).to.equal(expectedUnderlying);
     });
@@ -1,6 +1,7 @@
 import { expect } from "chai";
 import { ethers, networkHelpers } from "./helpers/hh";
 import type { OrionTransparentVault, LiquidityOrchestrator } from "../typechain-types";
+import { claimDeposit } from "./helpers/claims";
 import { deployUpgradeableProtocol } from "./helpers/deployUpgradeable";
 import { resetNetwork } from "./helpers/resetNetwork";
 
@@ -141,8 +142,9 @@ describe("Batch Limit Accounting Fix", function () {
       const loSigner = await ethers.getImpersonatedSigner(loAddress);
       await vault.connect(loSigner).fulfillDeposit(totalDeposit);
 
-      // Now request redeems
+      // Now claim the shares and request redeems
       for (let i = 0; i < numUsers; i++) {
+        await claimDeposit(vault, users[i]);
         const userShares = await vault.balanceOf(users[i].address);
         // Approve vault to transfer shares for redeem
         await vault.connect(users[i]).approve(await vault.getAddress(), userShares);
@@ -218,9 +220,9 @@ describe("Batch Limit Accounting Fix", function () {
       const pendingDepositAfter = await vault.pendingDeposit(await config.maxFulfillBatchSize());
       void expect(pendingDepositAfter).to.equal(0);
 
-      // Verify shares were minted
+      // Verify shares were minted for every user to claim
       for (let i = 0; i < numUsers; i++) {
-        const shares = await vault.balanceOf(users[i].address);
+        const shares = await vault.maxMint(users[i].address);
         void expect(shares).to.be.greaterThan(0);
       }
     });
@@ -8,6 +8,7 @@ import type {
   OrionTransparentVault,
   LiquidityOrchestrator,
 } from "../typechain-types";
+import { claimDeposit } from "./helpers/claims";
 import { deployUpgradeableProtocol } from "./helpers/deployUpgradeable";
 import { resetNetwork } from "./helpers/resetNetwork";
 
@@ -213,10 +214,13 @@ describe("Batch Limit Consistency - Critical Accounting Fix", function () {
       await underlyingAsset.connect(owner).approve(await liquidityOrchestrator.getAddress(), totalDeposit);
       await liquidityOrchestrator.connect(owner).depositLiquidity(totalDeposit);
 
-      // Impersonate LiquidityOrchestrator to fulfill deposits (this mints shares to users)
+      // Impersonate LiquidityOrchestrator to fulfill deposits (this mints claimable shares)
       const loAddress = await liquidityOrchestrator.getAddress();
       const loSigner = await impersonateLiquidityOrchestrator(loAddress);
       await vault.connect(loSigner).fulfillDeposit(totalDeposit);
+      for (let i = 0; i < numUsers; i++) {
+        await claimDeposit(vault, users[i]);
+      }
     });
 
     it("Should return exact shares when requests < maxFulfillBatchSize", async function () {
@@ -334,6 +338,9 @@ describe("Batch Limit Consistency - Critical Accounting Fix", function () {
       const loAddress = await liquidityOrchestrator.getAddress();
       const loSigner = await impersonateLiquidityOrchestrator(loAddress);
       await vault.connect(loSigner).fulfillDeposit(totalDeposit);
+      for (let i = 0; i < numUsers; i++) {
+        await claimDeposit(vault, users[i]);
+      }
 
       // Now create many redeem requests
       let totalSharesRequested = 0n;
@@ -0,0 +1,188 @@
+import { expect } from "chai";
+import { ethers, networkHelpers } from "./helpers/hh";
+import type { CompositeAccessControl, OrionTransparentVault, WhitelistAccessControl } from "../typechain-types";
+import { deployUpgradeableProtocol } from "./helpers/deployUpgradeable";
+import { resetNetwork } from "./helpers/resetNetwork";
+
+/**
+ * @title Composite Access Control Tests
+ * @notice AND/OR policies over child access controllers, managed by the vault manager
+ */
+describe("Composite Access Control", function () {
+  const DEPOSIT_AMOUNT = ethers.parseUnits("100", 6); // 100 USDC
+  const INITIAL_BALANCE = ethers.parseUnits("1000000", 6); // 1M USDC per user
+  const LEAF = 0;
+  const AND = 1;
+  const OR = 2;
+
+  before(async function () {
+    await resetNetwork();
+  });
+
+  async function deployFixture() {
+    const allSigners = await ethers.getSigners();
+    const owner = allSigners[0];
+    const strategist = allSigners[1];
+    const [alice, bob, carol] = allSigners.slice(2, 5);
+
+    const deployed = await deployUpgradeableProtocol(owner);
+    const usdc = deployed.underlyingAsset;
+    const vaultFactory = deployed.transparentVaultFactory;
+
+    const vaultAddress = await vaultFactory.createVault.staticCall(
+      strategist.address,
+      "Composite Vault",
+      "CPV",
+      0,
+      0,
+      0,
+      ethers.ZeroAddress,
+    );
+    await vaultFactory.createVault(strategist.address, "Composite Vault", "CPV", 0, 0, 0, ethers.ZeroAddress);
+    const vault = (await ethers.getContractAt(
+      "OrionTransparentVault",
+      vaultAddress,
+    )) as unknown as OrionTransparentVault;
+
+    // Two independent policies: an allowlist and a pass holders list
+    const WhitelistAccessControlFactory = await ethers.getContractFactory("WhitelistAccessControl");
+    const allowlist = (await WhitelistAccessControlFactory.deploy(owner.address)) as unknown as WhitelistAccessControl;
+    const passHolders = (await WhitelistAccessControlFactory.deploy(
+      owner.address,
+    )) as unknown as WhitelistAccessControl;
+    await allowlist.addToWhitelist([alice.address, bob.address]);
+    await passHolders.addToWhitelist([alice.address]);
+
+    const CompositeAccessControlFactory = await ethers.getContractFactory("CompositeAccessControl");
+    const composite = (await CompositeAccessControlFactory.deploy(
+      vaultAddress,
+      policy(AND, await allowlist.getAddress(), await passHolders.getAddress()),
+    )) as unknown as CompositeAccessControl;
+    await vault.connect(owner).setDepositAccessControl(await composite.getAddress());
+
+    for (const user of [alice, bob, carol]) {
+      await usdc.mint(user.address, INITIAL_BALANCE);
+      await usdc.connect(user).approve(vaultAddress, ethers.MaxUint256);
+    }
+
+    const liquidityOrchestrator = deployed.liquidityOrchestrator;
+    return { owner, alice, bob, carol, vault, allowlist, passHolders, composite, liquidityOrchestrator };
+  }
+
+  /** A root combining two leaves with `nodeType`. */
+  function policy(nodeType: number, first: string, second: string) {
+    return [
+      { nodeType, controller: ethers.ZeroAddress, children: [1, 2] },
+      { nodeType: LEAF, controller: first, children: [] },
+      { nodeType: LEAF, controller: second, children: [] },
+    ];
+  }
+
+  it("Should require every child of an AND node", async function () {
+    const { alice, bob, vault } = await networkHelpers.loadFixture(deployFixture);
+
+    await vault.connect(alice).requestDeposit(DEPOSIT_AMOUNT);
+    await expect(vault.connect(bob).requestDeposit(DEPOSIT_AMOUNT)).to.be.revertedWithCustomError(
+      vault,
+      "DepositNotAllowed",
+    );
+  });
+
+  it("Should let the vault manager switch to an OR policy", async function () {
+    const { owner, bob, carol, vault, allowlist, passHolders, composite } =
+      await networkHelpers.loadFixture(deployFixture);
+    const nodes = policy(OR, await allowlist.getAddress(), await passHolders.getAddress());
+
+    await expect(composite.connect(owner).setPolicy(nodes))
+      .to.emit(composite, "PolicyNodeSet")
+      .withArgs(0, OR, ethers.ZeroAddress, [1, 2])
+      .and.to.emit(composite, "PolicySet")
+      .withArgs(3);
+    void expect((await composite.getPolicy()).length).to.equal(3);
+
+    await vault.connect(bob).requestDeposit(DEPOSIT_AMOUNT);
+    await expect(vault.connect(carol).requestDeposit(DEPOSIT_AMOUNT)).to.be.revertedWithCustomError(
+      vault,
+      "DepositNotAllowed",
+    );
+  });
+
+  it("Should reject policy changes from others and malformed policies", async function () {
+    const { owner, alice, allowlist, passHolders, composite } = await networkHelpers.loadFixture(deployFixture);
+    const nodes = policy(OR, await allowlist.getAddress(), await passHolders.getAddress());
+
+    await expect(composite.connect(alice).setPolicy(nodes)).to.be.revertedWithCustomError(composite, "NotAuthorized");
+
+    // A child pointing back at its parent would make a cycle.
+    nodes[0].children = [0, 1];
+    await expect(composite.connect(owner).setPolicy(nodes)).to.be.revertedWithCustomError(
+      composite,
+      "InvalidArguments",
+    );
+    await expect(composite.connect(owner).setPolicy([])).to.be.revertedWithCustomError(composite, "InvalidArguments");
+  });
+
+  it("Should only accept policies that are trees", async function () {
+    const { owner, allowlist, passHolders, composite } = await networkHelpers.loadFixture(deployFixture);
+    const first = await allowlist.getAddress();
+    const second = await passHolders.getAddress();
+
+    // Node 3 shared by nodes 1 and 2: evaluation would grow exponentially with the depth of such chains.
+    const shared = [
+      { nodeType: AND, controller: ethers.ZeroAddress, children: [1, 2] },
+      { nodeType: OR, controller: ethers.ZeroAddress, children: [3] },
+      { nodeType: OR, controller: ethers.ZeroAddress, children: [3] },
+      { nodeType: LEAF, controller: first, children: [] },
+    ];
+    await expect(composite.connect(owner).setPolicy(shared)).to.be.revertedWithCustomError(
+      composite,
+      "InvalidArguments",
+    );
+
+    // Node 3 unreachable from the root.
+    const orphan = [...policy(AND, first, second), { nodeType: LEAF, controller: second, children: [] }];
+    await expect(composite.connect(owner).setPolicy(orphan)).to.be.revertedWithCustomError(
+      composite,
+      "InvalidArguments",
+    );
+  });
+
+  it("Should apply the policy to share transfers", async function () {
+    const { alice, bob, vault, passHolders, liquidityOrchestrator } = await networkHelpers.loadFixture(deployFixture);
+
+    const loAddress = await liquidityOrchestrator.getAddress();
+    await networkHelpers.impersonateAccount(loAddress);
+    await networkHelpers.setBalance(loAddress, ethers.parseEther("1"));
+    const loSigner = await ethers.getSigner(loAddress);
+
+    await vault.connect(alice).requestDeposit(DEPOSIT_AMOUNT);
+    await vault.connect(loSigner).fulfillDeposit(0);
+    await vault.connect(loSigner).updateVaultState([], [], DEPOSIT_AMOUNT);
+    await vault.connect(alice)["deposit(uint256,address)"](DEPOSIT_AMOUNT, alice.address);
+    const shares = await vault.balanceOf(alice.address);
+
+    // bob is on the allowlist but holds no pass
+    await expect(vault.connect(alice).transfer(bob.address, shares)).to.be.revertedWithCustomError(
+      vault,
+      "TransferNotAllowed",
+    );
+    await passHolders.addToWhitelist([bob.address]);
+    await vault.connect(alice).transfer(bob.address, shares);
+  });
+
+  it("Should block accounts any child of an AND blocks, or every child of an OR", async function () {
+    const { owner, alice, bob, allowlist, composite } = await networkHelpers.loadFixture(deployFixture);
+    const sanctionsList = await (await ethers.getContractFactory("MockSanctionsList")).deploy();
+    const SanctionsAccessControlFactory = await ethers.getContractFactory("SanctionsAccessControl");
+    const screening = await SanctionsAccessControlFactory.deploy(await sanctionsList.getAddress());
+    await sanctionsList.setSanctioned(alice.address, true);
+
+    await composite.connect(owner).setPolicy(policy(AND, await allowlist.getAddress(), await screening.getAddress()));
+    void expect(await composite.isBlocked(alice.address)).to.equal(true);
+    void expect(await composite.isBlocked(bob.address)).to.equal(false);
+
+    // The allowlist never blocks pending requests, so an OR with it does not either.
+    await composite.connect(owner).setPolicy(policy(OR, await allowlist.getAddress(), await screening.getAddress()));
+    void expect(await composite.isBlocked(alice.address)).to.equal(false);
+  });
+});
@@ -0,0 +1,168 @@
+import { expect } from "chai";
+import { ethers, networkHelpers } from "./helpers/hh";
+import type { OrionTransparentVault, LiquidityOrchestrator } from "../typechain-types";
+import { deployUpgradeableProtocol } from "./helpers/deployUpgradeable";
+import { resetNetwork } from "./helpers/resetNetwork";
+
+/**
+ * @title Deposit Caps Tests
+ * @notice Vault-level and per-controller caps on deposit requests
+ * @dev Caps are scheduled by the manager and take effect after the fee change cooldown.
+ */
+describe("Deposit Caps", function () {
+  const DEPOSIT_AMOUNT = ethers.parseUnits("100", 6); // 100 USDC
+  const INITIAL_BALANCE = ethers.parseUnits("1000000", 6); // 1M USDC per user
+
+  before(async function () {
+    await resetNetwork();
+  });
+
+  async function deployFixture() {
+    const allSigners = await ethers.getSigners();
+    const owner = allSigners[0];
+    const strategist = allSigners[1];
+    const [alice, bob] = allSigners.slice(2, 4);
+
+    const deployed = await deployUpgradeableProtocol(owner);
+
+    const usdc = deployed.underlyingAsset;
+    const config = deployed.orionConfig;
+    const liquidityOrchestrator: LiquidityOrchestrator = deployed.liquidityOrchestrator;
+    const vaultFactory = deployed.transparentVaultFactory;
+
+    const vaultTx = await vaultFactory
+      .connect(owner)
+      .createVault(strategist.address, "Capped Vault", "CAP", 0, 0, 0, ethers.ZeroAddress);
+    const receipt = await vaultTx.wait();
+    const vaultCreatedEvent = receipt?.logs.find((log) => {
+      try {
+        return vaultFactory.interface.parseLog(log)?.name === "OrionVaultCreated";
+      } catch {
+        return false;
+      }
+    });
+    const parsedLog = vaultCreatedEvent ? vaultFactory.interface.parseLog(vaultCreatedEvent) : null;
+    const vault = (await ethers.getContractAt(
+      "OrionTransparentVault",
+      parsedLog?.args[0],
+    )) as unknown as OrionTransparentVault;
+
+    for (const user of [alice, bob]) {
+      await usdc.mint(user.address, INITIAL_BALANCE);
+      await usdc.connect(user).approve(await vault.getAddress(), ethers.MaxUint256);
+    }
+
+    const loAddress = await liquidityOrchestrator.getAddress();
+    await networkHelpers.impersonateAccount(loAddress);
+    await networkHelpers.setBalance(loAddress, ethers.parseEther("1"));
+    const loSigner = await ethers.getSigner(loAddress);
+
+    return { owner, alice, bob, config, vault, loSigner };
+  }
+
+  async function scheduleCaps(vaultCap: bigint, userCap: bigint) {
+    const fixture = await networkHelpers.loadFixture(deployFixture);
+    await fixture.vault.connect(fixture.owner).updateDepositCaps(vaultCap, userCap);
+    await networkHelpers.time.increase((await fixture.config.feeChangeCooldownDuration()) + 1n);
+    return fixture;
+  }
+
+  it("Should be uncapped by default", async function () {
+    const { alice, vault } = await networkHelpers.loadFixture(deployFixture);
+
+    void expect(await vault.maxDepositRequest(alice.address)).to.equal(ethers.MaxUint256);
+  });
+
+  it("Should apply new caps only after the cooldown", async function () {
+    const { owner, alice, config, vault } = await networkHelpers.loadFixture(deployFixture);
+
+    await expect(vault.connect(alice).updateDepositCaps(DEPOSIT_AMOUNT, 0)).to.be.revertedWithCustomError(
+      vault,
+      "NotAuthorized",
+    );
+    await expect(vault.connect(owner).updateDepositCaps(DEPOSIT_AMOUNT, 0)).to.emit(
+      vault,
+      "DepositCapsChangeScheduled",
+    );
+
+    // The previous (uncapped) caps stay active during the cooldown.
+    void expect((await vault.activeDepositCaps()).vaultCap).to.equal(0);
+    await vault.connect(alice).requestDeposit(DEPOSIT_AMOUNT * 2n);
+
+    await networkHelpers.time.increase((await config.feeChangeCooldownDuration()) + 1n);
+    void expect((await vault.activeDepositCaps()).vaultCap).to.equal(DEPOSIT_AMOUNT);
+
+    // Already above the new cap: nothing more can be requested, existing requests are untouched.
+    void expect(await vault.maxDepositRequest(alice.address)).to.equal(0);
+    void expect(await vault.pendingDepositRequest(0, alice.address)).to.equal(DEPOSIT_AMOUNT * 2n);
+  });
+
+  it("Should cap total assets plus pending deposits", async function () {
+    const { alice, bob, vault } = await scheduleCaps((DEPOSIT_AMOUNT * 3n) / 2n, 0n);
+
+    await vault.connect(alice).requestDeposit(DEPOSIT_AMOUNT);
+    void expect(await vault.maxDepositRequest(bob.address)).to.equal(DEPOSIT_AMOUNT / 2n);
+    await expect(vault.connect(bob).requestDeposit(DEPOSIT_AMOUNT))
+      .to.be.revertedWithCustomError(vault, "DepositCapExceeded")
+      .withArgs(DEPOSIT_AMOUNT, DEPOSIT_AMOUNT / 2n);
+
+    // Cancelling frees the room again.
+    await vault.connect(alice).cancelDepositRequest(DEPOSIT_AMOUNT);
+    await vault.connect(bob).requestDeposit(DEPOSIT_AMOUNT);
+  });
+
+  it("Should count fulfilled deposits towards the vault cap", async function () {
+    const { alice, bob, vault, loSigner } = await scheduleCaps(DEPOSIT_AMOUNT * 2n, 0n);
+
+    await vault.connect(alice).requestDeposit(DEPOSIT_AMOUNT);
+    await vault.connect(loSigner).fulfillDeposit(0);
+    await vault.connect(loSigner).updateVaultState([], [], DEPOSIT_AMOUNT);
+
+    void expect(await vault.maxDepositRequest(bob.address)).to.equal(DEPOSIT_AMOUNT);
+  });
+
+  it("Should cap each controller's position", async function () {
+    const { alice, bob, vault, loSigner } = await scheduleCaps(0n, (DEPOSIT_AMOUNT * 3n) / 2n);
+
+    await vault.connect(alice).requestDeposit(DEPOSIT_AMOUNT);
+    await vault.connect(loSigner).fulfillDeposit(0);
+
+    // Claimable deposits still count for alice; bob is unaffected.
+    void expect(await vault.maxDepositRequest(alice.address)).to.equal(DEPOSIT_AMOUNT / 2n);
+    await expect(vault.connect(alice).requestDeposit(DEPOSIT_AMOUNT))
+      .to.be.revertedWithCustomError(vault, "DepositCapExceeded")
+      .withArgs(DEPOSIT_AMOUNT, DEPOSIT_AMOUNT / 2n);
+    await vault.connect(bob).requestDeposit(DEPOSIT_AMOUNT);
+
+    // Funding a request for alice from another account does not get around her cap.
+    await expect(
+      vault.connect(bob)["requestDeposit(uint256,address,address)"](DEPOSIT_AMOUNT, alice.address, bob.address),
+    ).to.be.revertedWithCustomError(vault, "DepositCapExceeded");
+  });
+
+  it("Should always let fulfilled deposits be claimed in full", async function () {
+    const { owner, alice, bob, config, vault, loSigner } = await scheduleCaps(0n, (DEPOSIT_AMOUNT * 3n) / 2n);
+
+    for (const user of [alice, bob]) {
+      await vault.connect(user).requestDeposit(DEPOSIT_AMOUNT);
+    }
+    await vault.connect(loSigner).fulfillDeposit(0);
+    await vault.connect(loSigner).updateVaultState([], [], DEPOSIT_AMOUNT * 2n);
+
+    // Caps only apply to requests: alice can claim to bob even though it takes him over the cap.
+    void expect(await vault.maxDeposit(alice.address)).to.equal(DEPOSIT_AMOUNT);
+    await vault.connect(alice)["deposit(uint256,address)"](DEPOSIT_AMOUNT, bob.address);
+    const aliceShares = await vault.balanceOf(bob.address);
+
+    // Nor does a lower cap strand what is left to claim.
+    await vault.connect(owner).updateDepositCaps(0n, DEPOSIT_AMOUNT / 2n);
+    await networkHelpers.time.increase((await config.feeChangeCooldownDuration()) + 1n);
+    const shares = await vault.maxMint(bob.address);
+    void expect(await vault.maxDeposit(bob.address)).to.equal(DEPOSIT_AMOUNT);
+    await vault.connect(bob)["mint(uint256,address)"](shares, bob.address);
+
+    void expect(await vault.balanceOf(bob.address)).to.equal(aliceShares + shares);
+    void expect(await vault.maxMint(bob.address)).to.equal(0);
+    void expect(await vault.maxDepositRequest(bob.address)).to.equal(0);
+  });
+});
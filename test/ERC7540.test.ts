@@ -0,0 +1,268 @@
+import { expect } from "chai";
+import { ethers, networkHelpers } from "./helpers/hh";
+import type { OrionTransparentVault, LiquidityOrchestrator } from "../typechain-types";
+import { deployUpgradeableProtocol } from "./helpers/deployUpgradeable";
+import { resetNetwork } from "./helpers/resetNetwork";
+
+/**
+ * @title ERC-7540 Tests
+ * @notice Requests with controller/owner separation, operators, and claims through the ERC-4626 functions
+ * @dev Requests are aggregated per controller under request id 0; fulfillment moves them from Pending to Claimable.
+ */
+describe("ERC-7540", function () {
+  const DEPOSIT_AMOUNT = ethers.parseUnits("100", 6); // 100 USDC
+  const INITIAL_BALANCE = ethers.parseUnits("1000000", 6); // 1M USDC per user
+  const REQUEST_ID = 0;
+
+  before(async function () {
+    await resetNetwork();
+  });
+
+  async function deployFixture() {
+    const allSigners = await ethers.getSigners();
+    const owner = allSigners[0];
+    const strategist = allSigners[1];
+    const [alice, bob, router] = allSigners.slice(2, 5);
+
+    const deployed = await deployUpgradeableProtocol(owner);
+
+    const usdc = deployed.underlyingAsset;
+    const liquidityOrchestrator: LiquidityOrchestrator = deployed.liquidityOrchestrator;
+    const vaultFactory = deployed.transparentVaultFactory;
+
+    const vaultTx = await vaultFactory
+      .connect(owner)
+      .createVault(strategist.address, "Async Vault", "AV", 0, 0, 0, ethers.ZeroAddress);
+    const receipt = await vaultTx.wait();
+    const vaultCreatedEvent = receipt?.logs.find((log) => {
+      try {
+        return vaultFactory.interface.parseLog(log)?.name === "OrionVaultCreated";
+      } catch {
+        return false;
+      }
+    });
+    const parsedLog = vaultCreatedEvent ? vaultFactory.interface.parseLog(vaultCreatedEvent) : null;
+    const vault = (await ethers.getContractAt(
+      "OrionTransparentVault",
+      parsedLog?.args[0],
+    )) as unknown as OrionTransparentVault;
+
+    for (const user of [alice, bob]) {
+      await usdc.mint(user.address, INITIAL_BALANCE);
+      await usdc.connect(user).approve(await vault.getAddress(), ethers.MaxUint256);
+    }
+
+    const loAddress = await liquidityOrchestrator.getAddress();
+    await networkHelpers.impersonateAccount(loAddress);
+    await networkHelpers.setBalance(loAddress, ethers.parseEther("1"));
+    const loSigner = await ethers.getSigner(loAddress);
+
+    return { alice, bob, router, usdc, vault, loAddress, loSigner };
+  }
+
+  it("Should advertise the ERC-7540 interfaces", async function () {
+    const { vault } = await networkHelpers.loadFixture(deployFixture);
+
+    void expect(await vault.supportsInterface("0x01ffc9a7")).to.be.true; // ERC-165
+    void expect(await vault.supportsInterface("0xe3bc4e65")).to.be.true; // operator
+    void expect(await vault.supportsInterface("0xce3bbe50")).to.be.true; // asynchronous deposit
+    void expect(await vault.supportsInterface("0x620ee8e4")).to.be.true; // asynchronous redemption
+    void expect(await vault.supportsInterface("0xffffffff")).to.be.false;
+  });
+
+  describe("Operators", function () {
+    it("Should set and revoke operators", async function () {
+      const { alice, router, vault } = await networkHelpers.loadFixture(deployFixture);
+
+      await expect(vault.connect(alice).setOperator(router.address, true))
+        .to.emit(vault, "OperatorSet")
+        .withArgs(alice.address, router.address, true);
+      void expect(await vault.isOperator(alice.address, router.address)).to.be.true;
+
+      await vault.connect(alice).setOperator(router.address, false);
+      void expect(await vault.isOperator(alice.address, router.address)).to.be.false;
+
+      await expect(vault.connect(alice).setOperator(alice.address, true)).to.be.revertedWithCustomError(
+        vault,
+        "InvalidAddress",
+      );
+    });
+
+    it("Should only let the owner or its operators request with the owner's assets", async function () {
+      const { alice, bob, router, vault } = await networkHelpers.loadFixture(deployFixture);
+
+      await expect(
+        vault.connect(router)["requestDeposit(uint256,address,address)"](DEPOSIT_AMOUNT, bob.address, alice.address),
+      ).to.be.revertedWithCustomError(vault, "NotAuthorized");
+
+      await vault.connect(alice).setOperator(router.address, true);
+      await expect(
+        vault.connect(router)["requestDeposit(uint256,address,address)"](DEPOSIT_AMOUNT, bob.address, alice.address),
+      )
+        .to.emit(vault, "DepositRequest")
+        .withArgs(bob.address, alice.address, REQUEST_ID, router.address, DEPOSIT_AMOUNT);
+
+      // The request belongs to the controller, not to the owner of the assets.
+      void expect(await vault.pendingDepositRequest(REQUEST_ID, bob.address)).to.equal(DEPOSIT_AMOUNT);
+      void expect(await vault.pendingDepositRequest(REQUEST_ID, alice.address)).to.equal(0);
+    });
+
+    it("Should let operators cancel requests for the controller", async function () {
+      const { alice, router, usdc, vault } = await networkHelpers.loadFixture(deployFixture);
+
+      await vault.connect(alice).requestDeposit(DEPOSIT_AMOUNT);
+      await expect(
+        vault
+          .connect(router)
+          ["cancelDepositRequest(uint256,address,address)"](DEPOSIT_AMOUNT, alice.address, router.address),
+      ).to.be.revertedWithCustomError(vault, "NotAuthorized");
+
+      await vault.connect(alice).setOperator(router.address, true);
+      await expect(
+        vault
+          .connect(router)
+          ["cancelDepositRequest(uint256,address,address)"](DEPOSIT_AMOUNT, alice.address, router.address),
+      )
+        .to.emit(vault, "DepositRequestCancelled")
+        .withArgs(alice.address, DEPOSIT_AMOUNT);
+
+      void expect(await vault.pendingDepositRequest(REQUEST_ID, alice.address)).to.equal(0);
+      void expect(await usdc.balanceOf(router.address)).to.equal(DEPOSIT_AMOUNT);
+    });
+  });
+
+  describe("Deposits", function () {
+    it("Should move fulfilled deposits from pending to claimable", async function () {
+      const { alice, vault, loSigner } = await networkHelpers.loadFixture(deployFixture);
+
+      await vault.connect(alice).requestDeposit(DEPOSIT_AMOUNT);
+      void expect(await vault.pendingDepositRequest(REQUEST_ID, alice.address)).to.equal(DEPOSIT_AMOUNT);
+      void expect(await vault.claimableDepositRequest(REQUEST_ID, alice.address)).to.equal(0);
+
+      await expect(vault.connect(loSigner).fulfillDeposit(0)).to.emit(vault, "DepositClaimable");
+
+      void expect(await vault.pendingDepositRequest(REQUEST_ID, alice.address)).to.equal(0);
+      void expect(await vault.claimableDepositRequest(REQUEST_ID, alice.address)).to.equal(DEPOSIT_AMOUNT);
+      void expect(await vault.maxDeposit(alice.address)).to.equal(DEPOSIT_AMOUNT);
+      void expect(await vault.balanceOf(alice.address)).to.equal(0);
+      void expect(await vault.balanceOf(await vault.getAddress())).to.equal(await vault.maxMint(alice.address));
+    });
+
+    it("Should claim with deposit and mint at the fulfillment price", async function () {
+      const { alice, bob, vault, loSigner } = await networkHelpers.loadFixture(deployFixture);
+
+      await vault.connect(alice).requestDeposit(DEPOSIT_AMOUNT);
+      await vault.connect(loSigner).fulfillDeposit(0);
+      const claimableShares = await vault.maxMint(alice.address);
+
+      // Half the assets, to a different receiver.
+      await expect(vault.connect(alice)["deposit(uint256,address)"](DEPOSIT_AMOUNT / 2n, bob.address))
+        .to.emit(vault, "Deposit")
+        .withArgs(alice.address, bob.address, DEPOSIT_AMOUNT / 2n, claimableShares / 2n);
+      void expect(await vault.balanceOf(bob.address)).to.equal(claimableShares / 2n);
+
+      // The remaining shares.
+      const remainingShares = await vault.maxMint(alice.address);
+      await vault.connect(alice)["mint(uint256,address)"](remainingShares, alice.address);
+      void expect(await vault.balanceOf(alice.address)).to.equal(remainingShares);
+      void expect(await vault.maxDeposit(alice.address)).to.equal(0);
+      void expect(await vault.maxMint(alice.address)).to.equal(0);
+    });
+
+    it("Should only let the controller or its operators claim", async function () {
+      const { alice, router, vault, loSigner } = await networkHelpers.loadFixture(deployFixture);
+
+      await vault.connect(alice).requestDeposit(DEPOSIT_AMOUNT);
+      await vault.connect(loSigner).fulfillDeposit(0);
+
+      await expect(
+        vault.connect(router)["deposit(uint256,address,address)"](DEPOSIT_AMOUNT, router.address, alice.address),
+      ).to.be.revertedWithCustomError(vault, "NotAuthorized");
+
+      await vault.connect(alice).setOperator(router.address, true);
+      await vault.connect(router)["deposit(uint256,address,address)"](DEPOSIT_AMOUNT, alice.address, alice.address);
+      void expect(await vault.balanceOf(alice.address)).to.be.greaterThan(0);
+    });
+  });
+
+  describe("Redemptions", function () {
+    async function fundedFixture() {
+      const fixture = await deployFixture();
+      const { alice, vault, loSigner } = fixture;
+      await vault.connect(alice).requestDeposit(DEPOSIT_AMOUNT);
+      await vault.connect(loSigner).fulfillDeposit(0);
+      await vault.connect(loSigner).updateVaultState([], [], DEPOSIT_AMOUNT);
+      await vault.connect(alice)["deposit(uint256,address)"](DEPOSIT_AMOUNT, alice.address);
+      return fixture;
+    }
+
+    it("Should let an approved spender request with the owner's shares", async function () {
+      const { alice, bob, router, vault } = await networkHelpers.loadFixture(fundedFixture);
+      const shares = await vault.balanceOf(alice.address);
+
+      await expect(
+        vault.connect(router)["requestRedeem(uint256,address,address)"](shares, bob.address, alice.address),
+      ).to.be.revertedWithCustomError(vault, "ERC20InsufficientAllowance");
+
+      await vault.connect(alice).approve(router.address, shares);
+      await expect(
+        vault.connect(router)["requestRedeem(uint256,address,address)"](shares, bob.address, alice.address),
+      )
+        .to.emit(vault, "RedeemRequest")
+        .withArgs(bob.address, alice.address, REQUEST_ID, router.address, shares);
+
+      void expect(await vault.allowance(alice.address, router.address)).to.equal(0);
+      void expect(await vault.pendingRedeemRequest(REQUEST_ID, bob.address)).to.equal(shares);
+    });
+
+    it("Should keep fulfilled redemptions in the orchestrator until claimed", async function () {
+      const { alice, bob, usdc, vault, loAddress, loSigner } = await networkHelpers.loadFixture(fundedFixture);
+      const shares = await vault.balanceOf(alice.address);
+
+      await vault.connect(alice).requestRedeem(shares);
+      const orchestratorBalance = await usdc.balanceOf(loAddress);
+      const aliceBalance = await usdc.balanceOf(alice.address);
+
+      await expect(vault.connect(loSigner).fulfillRedeem(DEPOSIT_AMOUNT)).to.emit(vault, "RedeemClaimable");
+      void expect(await usdc.balanceOf(loAddress)).to.equal(orchestratorBalance);
+      void expect(await usdc.balanceOf(alice.address)).to.equal(aliceBalance);
+      void expect(await vault.pendingRedeemRequest(REQUEST_ID, alice.address)).to.equal(0);
+      void expect(await vault.claimableRedeemRequest(REQUEST_ID, alice.address)).to.equal(shares);
+
+      const claimableAssets = await vault.maxWithdraw(alice.address);
+      void expect(claimableAssets).to.be.greaterThan(0);
+
+      // Withdraw part of the assets to another receiver, then redeem the remaining shares.
+      await expect(vault.connect(alice).withdraw(claimableAssets / 2n, bob.address, alice.address))
+        .to.emit(vault, "Withdraw")
+        .withArgs(alice.address, bob.address, alice.address, claimableAssets / 2n, shares / 2n);
+      await vault.connect(alice).redeem(await vault.maxRedeem(alice.address), alice.address, alice.address);
+
+      void expect(await usdc.balanceOf(bob.address)).to.equal(INITIAL_BALANCE + claimableAssets / 2n);
+      void expect(await usdc.balanceOf(alice.address)).to.equal(aliceBalance + claimableAssets - claimableAssets / 2n);
+      void expect(await vault.maxRedeem(alice.address)).to.equal(0);
+      void expect(await vault.maxWithdraw(alice.address)).to.equal(0);
+    });
+
+    it("Should only let the controller or its operators claim redemptions", async function () {
+      const { alice, router, vault, loSigner } = await networkHelpers.loadFixture(fundedFixture);
+      const shares = await vault.balanceOf(alice.address);
+
+      await vault.connect(alice).requestRedeem(shares);
+      await vault.connect(loSigner).fulfillRedeem(DEPOSIT_AMOUNT);
+
+      await expect(vault.connect(router).redeem(shares, router.address, alice.address)).to.be.revertedWithCustomError(
+        vault,
+        "NotAuthorized",
+      );
+      await expect(vault.connect(router).withdraw(1, router.address, alice.address)).to.be.revertedWithCustomError(
+        vault,
+        "NotAuthorized",
+      );
+
+      await vault.connect(alice).setOperator(router.address, true);
+      await vault.connect(router).redeem(shares, alice.address, alice.address);
+      void expect(await vault.maxRedeem(alice.address)).to.equal(0);
+    });
+  });
+});
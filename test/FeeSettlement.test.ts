@@ -0,0 +1,131 @@
+import { expect } from "chai";
+import { ethers, networkHelpers } from "./helpers/hh";
+import type { OrionTransparentVault, LiquidityOrchestrator } from "../typechain-types";
+import { deployUpgradeableProtocol } from "./helpers/deployUpgradeable";
+import { resetNetwork } from "./helpers/resetNetwork";
+
+/**
+ * @title Fee Settlement Tests
+ * @notice Performance fee crystallization periods and fees paid in minted vault shares
+ * @dev Settlement changes are scheduled by the manager and take effect after the fee change cooldown.
+ */
+describe("Fee Settlement", function () {
+  const DEPOSIT_AMOUNT = ethers.parseUnits("100", 6); // 100 USDC
+  const INITIAL_BALANCE = ethers.parseUnits("1000000", 6); // 1M USDC per user
+  const QUARTER = 90n * 24n * 60n * 60n;
+
+  before(async function () {
+    await resetNetwork();
+  });
+
+  async function deployFixture() {
+    const allSigners = await ethers.getSigners();
+    const owner = allSigners[0];
+    const strategist = allSigners[1];
+    const alice = allSigners[2];
+
+    const deployed = await deployUpgradeableProtocol(owner);
+
+    const usdc = deployed.underlyingAsset;
+    const config = deployed.orionConfig;
+    const liquidityOrchestrator: LiquidityOrchestrator = deployed.liquidityOrchestrator;
+    const vaultFactory = deployed.transparentVaultFactory;
+
+    const vaultTx = await vaultFactory
+      .connect(owner)
+      .createVault(strategist.address, "Fee Vault", "FV", 3, 2000, 100, ethers.ZeroAddress);
+    const receipt = await vaultTx.wait();
+    const vaultCreatedEvent = receipt?.logs.find((log) => {
+      try {
+        return vaultFactory.interface.parseLog(log)?.name === "OrionVaultCreated";
+      } catch {
+        return false;
+      }
+    });
+    const parsedLog = vaultCreatedEvent ? vaultFactory.interface.parseLog(vaultCreatedEvent) : null;
+    const vault = (await ethers.getContractAt(
+      "OrionTransparentVault",
+      parsedLog?.args[0],
+    )) as unknown as OrionTransparentVault;
+
+    await usdc.mint(alice.address, INITIAL_BALANCE);
+    await usdc.connect(alice).approve(await vault.getAddress(), ethers.MaxUint256);
+
+    const loAddress = await liquidityOrchestrator.getAddress();
+    await networkHelpers.impersonateAccount(loAddress);
+    await networkHelpers.setBalance(loAddress, ethers.parseEther("1"));
+    const loSigner = await ethers.getSigner(loAddress);
+
+    return { owner, alice, config, vault, loSigner };
+  }
+
+  async function scheduleSettlement(crystallizationPeriod: bigint, mintFeeShares: boolean) {
+    const fixture = await networkHelpers.loadFixture(deployFixture);
+    await fixture.vault.connect(fixture.owner).updateFeeSettlement(crystallizationPeriod, mintFeeShares);
+    await networkHelpers.time.increase((await fixture.config.feeChangeCooldownDuration()) + 1n);
+    return fixture;
+  }
+
+  it("Should crystallize every epoch and pay in underlying by default", async function () {
+    const { vault } = await networkHelpers.loadFixture(deployFixture);
+
+    const settlement = await vault.activeFeeSettlement();
+    void expect(settlement.crystallizationPeriod).to.equal(0);
+    void expect(settlement.mintFeeShares).to.equal(false);
+    void expect(settlement.crystallizationPrice).to.equal((await vault.activeFeeModel()).highWaterMark);
+  });
+
+  it("Should apply settlement changes only after the cooldown", async function () {
+    const { owner, alice, config, vault } = await networkHelpers.loadFixture(deployFixture);
+
+    await expect(vault.connect(alice).updateFeeSettlement(QUARTER, true)).to.be.revertedWithCustomError(
+      vault,
+      "NotAuthorized",
+    );
+    await expect(vault.connect(owner).updateFeeSettlement(366n * 24n * 60n * 60n, true)).to.be.revertedWithCustomError(
+      vault,
+      "InvalidArguments",
+    );
+    await expect(vault.connect(owner).updateFeeSettlement(QUARTER, true)).to.emit(
+      vault,
+      "VaultFeeSettlementChangeScheduled",
+    );
+
+    void expect((await vault.activeFeeSettlement()).mintFeeShares).to.equal(false);
+    await networkHelpers.time.increase((await config.feeChangeCooldownDuration()) + 1n);
+    const settlement = await vault.activeFeeSettlement();
+    void expect(settlement.crystallizationPeriod).to.equal(QUARTER);
+    void expect(settlement.mintFeeShares).to.equal(true);
+    // Rates are untouched.
+    void expect((await vault.activeFeeModel()).performanceFee).to.equal(2000);
+  });
+
+  it("Should mint fee shares to the manager instead of accruing underlying", async function () {
+    const { owner, vault, loSigner } = await scheduleSettlement(0n, true);
+    const managementFeeShares = ethers.parseUnits("1", 18);
+    const performanceFeeShares = ethers.parseUnits("2", 18);
+
+    await expect(vault.connect(loSigner).accrueVaultFees(managementFeeShares, performanceFeeShares, true))
+      .to.emit(vault, "VaultFeeSharesMinted")
+      .withArgs(owner.address, managementFeeShares, performanceFeeShares);
+
+    void expect(await vault.balanceOf(owner.address)).to.equal(managementFeeShares + performanceFeeShares);
+    void expect(await vault.pendingVaultFees()).to.equal(0);
+  });
+
+  it("Should keep the performance fee reference until the period has elapsed", async function () {
+    const { alice, vault, loSigner } = await scheduleSettlement(QUARTER, false);
+    const before = await vault.activeFeeModel();
+    const settlementBefore = await vault.activeFeeSettlement();
+
+    await vault.connect(alice).requestDeposit(DEPOSIT_AMOUNT);
+    await vault.connect(loSigner).fulfillDeposit(0);
+    // Share price doubles, but the period started at deployment has not elapsed.
+    await vault.connect(loSigner).updateVaultState([], [], DEPOSIT_AMOUNT * 2n);
+
+    void expect((await vault.activeFeeModel()).highWaterMark).to.equal(before.highWaterMark);
+    const settlementAfter = await vault.activeFeeSettlement();
+    void expect(settlementAfter.crystallizationPrice).to.equal(settlementBefore.crystallizationPrice);
+    void expect(settlementAfter.lastCrystallization).to.equal(settlementBefore.lastCrystallization);
+  });
+});
@@ -0,0 +1,205 @@
+import { expect } from "chai";
+import { ethers, networkHelpers } from "./helpers/hh";
+import type { OrionTransparentVault, LiquidityOrchestrator } from "../typechain-types";
+import { deployUpgradeableProtocol } from "./helpers/deployUpgradeable";
+import { resetNetwork } from "./helpers/resetNetwork";
+
+/**
+ * @title Fee Splits Tests
+ * @notice Vault fees split between the manager, the strategist and distribution partners
+ * @dev Splits are scheduled by the manager and take effect after the fee change cooldown.
+ */
+describe("Fee Splits", function () {
+  const MANAGEMENT_FEE = ethers.parseUnits("100", 6); // 100 USDC
+  const PERFORMANCE_FEE = ethers.parseUnits("300", 6); // 300 USDC
+
+  before(async function () {
+    await resetNetwork();
+  });
+
+  async function deployFixture() {
+    const allSigners = await ethers.getSigners();
+    const owner = allSigners[0];
+    const strategist = allSigners[1];
+    const [partner, alice] = allSigners.slice(2, 4);
+
+    const deployed = await deployUpgradeableProtocol(owner);
+
+    const usdc = deployed.underlyingAsset;
+    const config = deployed.orionConfig;
+    const liquidityOrchestrator: LiquidityOrchestrator = deployed.liquidityOrchestrator;
+    const vaultFactory = deployed.transparentVaultFactory;
+
+    const vaultTx = await vaultFactory
+      .connect(owner)
+      .createVault(strategist.address, "Split Vault", "SV", 3, 2000, 100, ethers.ZeroAddress);
+    const receipt = await vaultTx.wait();
+    const vaultCreatedEvent = receipt?.logs.find((log) => {
+      try {
+        return vaultFactory.interface.parseLog(log)?.name === "OrionVaultCreated";
+      } catch {
+        return false;
+      }
+    });
+    const parsedLog = vaultCreatedEvent ? vaultFactory.interface.parseLog(vaultCreatedEvent) : null;
+    const vault = (await ethers.getContractAt(
+      "OrionTransparentVault",
+      parsedLog?.args[0],
+    )) as unknown as OrionTransparentVault;
+
+    const loAddress = await liquidityOrchestrator.getAddress();
+    await usdc.mint(loAddress, MANAGEMENT_FEE + PERFORMANCE_FEE);
+    await networkHelpers.impersonateAccount(loAddress);
+    await networkHelpers.setBalance(loAddress, ethers.parseEther("1"));
+    const loSigner = await ethers.getSigner(loAddress);
+
+    return { owner, strategist, partner, alice, usdc, config, vault, loSigner };
+  }
+
+  // 30% to the strategist and 20% to the partner, the remaining 50% to the manager.
+  async function scheduleSplits(mintFeeShares: boolean) {
+    const fixture = await networkHelpers.loadFixture(deployFixture);
+    const { owner, strategist, partner, config, vault } = fixture;
+    if (mintFeeShares) await vault.connect(owner).updateFeeSettlement(0, true);
+    await vault.connect(owner).updateFeeSplits([
+      { recipient: strategist.address, share: 3000 },
+      { recipient: partner.address, share: 2000 },
+    ]);
+    await networkHelpers.time.increase((await config.feeChangeCooldownDuration()) + 1n);
+    return fixture;
+  }
+
+  it("Should pay everything to the manager by default", async function () {
+    const { owner, vault, loSigner } = await networkHelpers.loadFixture(deployFixture);
+
+    void expect(await vault.activeFeeSplits()).to.deep.equal([]);
+    await vault.connect(loSigner).accrueVaultFees(MANAGEMENT_FEE, PERFORMANCE_FEE, false);
+    void expect(await vault.claimableVaultFees(owner.address)).to.equal(MANAGEMENT_FEE + PERFORMANCE_FEE);
+  });
+
+  it("Should validate fee splits", async function () {
+    const { owner, strategist, partner, vault } = await networkHelpers.loadFixture(deployFixture);
+
+    await expect(
+      vault.connect(strategist).updateFeeSplits([{ recipient: strategist.address, share: 3000 }]),
+    ).to.be.revertedWithCustomError(vault, "NotAuthorized");
+    await expect(
+      vault.connect(owner).updateFeeSplits([{ recipient: ethers.ZeroAddress, share: 3000 }]),
+    ).to.be.revertedWithCustomError(vault, "ZeroAddress");
+    await expect(
+      vault.connect(owner).updateFeeSplits([{ recipient: strategist.address, share: 0 }]),
+    ).to.be.revertedWithCustomError(vault, "InvalidArguments");
+    await expect(
+      vault.connect(owner).updateFeeSplits([
+        { recipient: strategist.address, share: 6000 },
+        { recipient: partner.address, share: 4001 },
+      ]),
+    ).to.be.revertedWithCustomError(vault, "InvalidArguments");
+    await expect(
+      vault.connect(owner).updateFeeSplits(Array(9).fill({ recipient: partner.address, share: 100 })),
+    ).to.be.revertedWithCustomError(vault, "InvalidArguments");
+  });
+
+  it("Should apply new fee splits only after the cooldown", async function () {
+    const { owner, strategist, config, vault, loSigner } = await networkHelpers.loadFixture(deployFixture);
+
+    await expect(vault.connect(owner).updateFeeSplits([{ recipient: strategist.address, share: 3000 }])).to.emit(
+      vault,
+      "FeeSplitsChangeScheduled",
+    );
+
+    // Fees accrued during the cooldown still go to the manager only.
+    void expect(await vault.activeFeeSplits()).to.deep.equal([]);
+    await vault.connect(loSigner).accrueVaultFees(MANAGEMENT_FEE, 0, false);
+    void expect(await vault.claimableVaultFees(strategist.address)).to.equal(0);
+
+    await networkHelpers.time.increase((await config.feeChangeCooldownDuration()) + 1n);
+    void expect(await vault.activeFeeSplits()).to.deep.equal([[strategist.address, 3000n]]);
+  });
+
+  it("Should let each recipient claim their share in underlying", async function () {
+    const { owner, strategist, partner, alice, usdc, vault, loSigner } = await scheduleSplits(false);
+    const totalFee = MANAGEMENT_FEE + PERFORMANCE_FEE;
+
+    await vault.connect(loSigner).accrueVaultFees(MANAGEMENT_FEE, PERFORMANCE_FEE, false);
+    void expect(await vault.pendingVaultFees()).to.equal(totalFee);
+    void expect(await vault.claimableVaultFees(strategist.address)).to.equal((totalFee * 3n) / 10n);
+    void expect(await vault.claimableVaultFees(partner.address)).to.equal((totalFee * 2n) / 10n);
+    void expect(await vault.claimableVaultFees(owner.address)).to.equal(totalFee / 2n);
+
+    await expect(vault.connect(strategist).claimVaultFees((totalFee * 3n) / 10n))
+      .to.emit(vault, "VaultFeesClaimed")
+      .withArgs(strategist.address, (totalFee * 3n) / 10n);
+    void expect(await usdc.balanceOf(strategist.address)).to.equal((totalFee * 3n) / 10n);
+    void expect(await vault.pendingVaultFees()).to.equal((totalFee * 7n) / 10n);
+
+    // Nobody can claim beyond their own balance.
+    await expect(vault.connect(partner).claimVaultFees(totalFee / 2n)).to.be.revertedWithCustomError(
+      vault,
+      "InsufficientAmount",
+    );
+    await expect(vault.connect(alice).claimVaultFees(1n)).to.be.revertedWithCustomError(vault, "InsufficientAmount");
+    await vault.connect(owner).claimVaultFees(totalFee / 2n);
+    void expect(await vault.claimableVaultFees(owner.address)).to.equal(0);
+  });
+
+  it("Should mint each recipient their share of the fee shares", async function () {
+    const { owner, strategist, partner, vault, loSigner } = await scheduleSplits(true);
+    const managementFeeShares = ethers.parseUnits("1", 18) + 1n;
+    const performanceFeeShares = ethers.parseUnits("3", 18);
+
+    await expect(vault.connect(loSigner).accrueVaultFees(managementFeeShares, performanceFeeShares, true))
+      .to.emit(vault, "VaultFeeSharesMinted")
+      .withArgs(strategist.address, (managementFeeShares * 3n) / 10n, (performanceFeeShares * 3n) / 10n);
+
+    const strategistShares = (managementFeeShares * 3n) / 10n + (performanceFeeShares * 3n) / 10n;
+    const partnerShares = (managementFeeShares * 2n) / 10n + (performanceFeeShares * 2n) / 10n;
+    void expect(await vault.balanceOf(strategist.address)).to.equal(strategistShares);
+    void expect(await vault.balanceOf(partner.address)).to.equal(partnerShares);
+    // The manager receives the remainder, rounding dust included.
+    void expect(await vault.balanceOf(owner.address)).to.equal(
+      managementFeeShares + performanceFeeShares - strategistShares - partnerShares,
+    );
+    void expect(await vault.pendingVaultFees()).to.equal(0);
+  });
+
+  it("Should credit the fees accrued before fee splits to the manager once", async function () {
+    const { owner, strategist, usdc, vault, loSigner } = await scheduleSplits(false);
+    const legacyFee = MANAGEMENT_FEE;
+
+    // Fees accrued by the previous implementation are in pendingVaultFees only (slot 12).
+    await networkHelpers.setStorageAt(await vault.getAddress(), 12, legacyFee);
+    await vault.connect(loSigner).accrueVaultFees(0, PERFORMANCE_FEE, false);
+    void expect(await vault.pendingVaultFees()).to.equal(legacyFee + PERFORMANCE_FEE);
+    void expect(await vault.claimableVaultFees(owner.address)).to.equal(PERFORMANCE_FEE / 2n);
+
+    await expect(vault.connect(strategist).migrateVaultFeeClaims()).to.be.revertedWithCustomError(
+      vault,
+      "NotAuthorized",
+    );
+    await vault.connect(owner).migrateVaultFeeClaims();
+    void expect(await vault.claimableVaultFees(owner.address)).to.equal(legacyFee + PERFORMANCE_FEE / 2n);
+    void expect(await vault.claimableVaultFees(strategist.address)).to.equal((PERFORMANCE_FEE * 3n) / 10n);
+    await expect(vault.connect(owner).migrateVaultFeeClaims()).to.be.revertedWithCustomError(
+      vault,
+      "InvalidInitialization",
+    );
+
+    const balanceBefore = await usdc.balanceOf(owner.address);
+    await vault.connect(owner).claimVaultFees(legacyFee + PERFORMANCE_FEE / 2n);
+    void expect(await usdc.balanceOf(owner.address)).to.equal(balanceBefore + legacyFee + PERFORMANCE_FEE / 2n);
+  });
+
+  it("Should let the manager claim the fees accrued before fee splits right after the upgrade", async function () {
+    const { owner, usdc, vault } = await scheduleSplits(false);
+    const legacyFee = MANAGEMENT_FEE;
+
+    // Fees accrued by the previous implementation are in pendingVaultFees only (slot 12).
+    await networkHelpers.setStorageAt(await vault.getAddress(), 12, legacyFee);
+
+    const balanceBefore = await usdc.balanceOf(owner.address);
+    await vault.connect(owner).claimVaultFees(legacyFee);
+    void expect(await usdc.balanceOf(owner.address)).to.equal(balanceBefore + legacyFee);
+    void expect(await vault.pendingVaultFees()).to.equal(0);
+  });
+});
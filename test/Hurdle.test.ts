@@ -0,0 +1,109 @@
+import { expect } from "chai";
+import { ethers, networkHelpers } from "./helpers/hh";
+import type { OrionTransparentVault } from "../typechain-types";
+import { deployUpgradeableProtocol } from "./helpers/deployUpgradeable";
+import { resetNetwork } from "./helpers/resetNetwork";
+
+/**
+ * @title Hurdle Tests
+ * @notice Per-vault hurdles: the protocol risk-free rate, a fixed rate or a benchmark asset
+ * @dev Hurdle changes are scheduled by the manager and take effect after the fee change cooldown.
+ */
+describe("Hurdle", function () {
+  const RISK_FREE_RATE = 0;
+  const FIXED_RATE = 1;
+  const BENCHMARK = 2;
+
+  before(async function () {
+    await resetNetwork();
+  });
+
+  async function deployFixture() {
+    const allSigners = await ethers.getSigners();
+    const owner = allSigners[0];
+    const strategist = allSigners[1];
+
+    const deployed = await deployUpgradeableProtocol(owner);
+
+    const usdc = deployed.underlyingAsset;
+    const config = deployed.orionConfig;
+    const vaultFactory = deployed.transparentVaultFactory;
+
+    const vaultTx = await vaultFactory
+      .connect(owner)
+      .createVault(strategist.address, "Hurdle Vault", "HV", 4, 2000, 100, ethers.ZeroAddress);
+    const receipt = await vaultTx.wait();
+    const vaultCreatedEvent = receipt?.logs.find((log) => {
+      try {
+        return vaultFactory.interface.parseLog(log)?.name === "OrionVaultCreated";
+      } catch {
+        return false;
+      }
+    });
+    const parsedLog = vaultCreatedEvent ? vaultFactory.interface.parseLog(vaultCreatedEvent) : null;
+    const vault = (await ethers.getContractAt(
+      "OrionTransparentVault",
+      parsedLog?.args[0],
+    )) as unknown as OrionTransparentVault;
+
+    return { owner, strategist, config, vault, benchmark: await usdc.getAddress() };
+  }
+
+  it("Should use the protocol risk-free rate by default", async function () {
+    const { vault } = await networkHelpers.loadFixture(deployFixture);
+
+    const hurdle = await vault.activeHurdle();
+    void expect(hurdle.hurdleType).to.equal(RISK_FREE_RATE);
+    void expect(hurdle.rate).to.equal(0);
+    void expect(hurdle.benchmark).to.equal(ethers.ZeroAddress);
+  });
+
+  it("Should apply a fixed hurdle rate only after the cooldown", async function () {
+    const { owner, strategist, config, vault } = await networkHelpers.loadFixture(deployFixture);
+
+    await expect(
+      vault.connect(strategist).updateHurdle(FIXED_RATE, 500, ethers.ZeroAddress),
+    ).to.be.revertedWithCustomError(vault, "NotAuthorized");
+    await expect(vault.connect(owner).updateHurdle(FIXED_RATE, 500, ethers.ZeroAddress)).to.emit(
+      vault,
+      "VaultHurdleChangeScheduled",
+    );
+    void expect((await vault.activeHurdle()).hurdleType).to.equal(RISK_FREE_RATE);
+
+    await networkHelpers.time.increase((await config.feeChangeCooldownDuration()) + 1n);
+    const hurdle = await vault.activeHurdle();
+    void expect(hurdle.hurdleType).to.equal(FIXED_RATE);
+    void expect(hurdle.rate).to.equal(500);
+  });
+
+  it("Should only accept a whitelisted benchmark", async function () {
+    const { owner, strategist, config, vault, benchmark } = await networkHelpers.loadFixture(deployFixture);
+
+    await expect(vault.connect(owner).updateHurdle(BENCHMARK, 0, strategist.address))
+      .to.be.revertedWithCustomError(vault, "TokenNotWhitelisted")
+      .withArgs(strategist.address);
+    await vault.connect(owner).updateHurdle(BENCHMARK, 0, benchmark);
+
+    await networkHelpers.time.increase((await config.feeChangeCooldownDuration()) + 1n);
+    const hurdle = await vault.activeHurdle();
+    void expect(hurdle.benchmark).to.equal(benchmark);
+    // Recorded at the first crystallization.
+    void expect(hurdle.benchmarkPrice).to.equal(0);
+  });
+
+  it("Should reject parameters that do not belong to the hurdle type", async function () {
+    const { owner, vault, benchmark } = await networkHelpers.loadFixture(deployFixture);
+
+    for (const [hurdleType, hurdleRate, hurdleBenchmark] of [
+      [3, 0, ethers.ZeroAddress],
+      [RISK_FREE_RATE, 500, ethers.ZeroAddress],
+      [RISK_FREE_RATE, 0, benchmark],
+      [FIXED_RATE, 500, benchmark],
+      [BENCHMARK, 500, benchmark],
+    ] as [number, number, string][]) {
+      await expect(
+        vault.connect(owner).updateHurdle(hurdleType, hurdleRate, hurdleBenchmark),
+      ).to.be.revertedWithCustomError(vault, "InvalidArguments");
+    }
+  });
+});
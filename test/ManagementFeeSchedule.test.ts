@@ -0,0 +1,106 @@
+import { expect } from "chai";
+import { ethers, networkHelpers } from "./helpers/hh";
+import type { OrionTransparentVault } from "../typechain-types";
+import { deployUpgradeableProtocol } from "./helpers/deployUpgradeable";
+import { resetNetwork } from "./helpers/resetNetwork";
+
+/**
+ * @title Management Fee Schedule Tests
+ * @notice Tiered management fee breakpoints and the minimum annual management fee
+ * @dev The fees themselves are computed by the state orchestrator; these tests cover scheduling.
+ */
+describe("Management Fee Schedule", function () {
+  const usdc = (amount: number) => ethers.parseUnits(amount.toString(), 6);
+  // 1% up to 10M, 0.75% up to 50M and 0.5% above.
+  const BREAKPOINTS: [bigint, bigint, bigint] = [usdc(10_000_000), usdc(50_000_000), 0n];
+  const TIER_RATES: [number, number, number] = [75, 50, 0];
+  const MINIMUM_FEE = usdc(100_000);
+
+  before(async function () {
+    await resetNetwork();
+  });
+
+  async function deployFixture() {
+    const allSigners = await ethers.getSigners();
+    const owner = allSigners[0];
+    const strategist = allSigners[1];
+
+    const deployed = await deployUpgradeableProtocol(owner);
+
+    const config = deployed.orionConfig;
+    const vaultFactory = deployed.transparentVaultFactory;
+
+    const vaultTx = await vaultFactory
+      .connect(owner)
+      .createVault(strategist.address, "Tiered Vault", "TV", 3, 2000, 100, ethers.ZeroAddress);
+    const receipt = await vaultTx.wait();
+    const vaultCreatedEvent = receipt?.logs.find((log) => {
+      try {
+        return vaultFactory.interface.parseLog(log)?.name === "OrionVaultCreated";
+      } catch {
+        return false;
+      }
+    });
+    const parsedLog = vaultCreatedEvent ? vaultFactory.interface.parseLog(vaultCreatedEvent) : null;
+    const vault = (await ethers.getContractAt(
+      "OrionTransparentVault",
+      parsedLog?.args[0],
+    )) as unknown as OrionTransparentVault;
+
+    return { owner, strategist, config, vault };
+  }
+
+  it("Should charge a flat management fee by default", async function () {
+    const { vault } = await networkHelpers.loadFixture(deployFixture);
+
+    void expect((await vault.activeFeeModel()).managementFee).to.equal(100);
+    const schedule = await vault.activeManagementFeeSchedule();
+    void expect(schedule.breakpoints).to.deep.equal([0n, 0n, 0n]);
+    void expect(schedule.tierRates).to.deep.equal([0n, 0n, 0n]);
+    void expect(schedule.minimumFee).to.equal(0);
+  });
+
+  it("Should apply a schedule only after the cooldown", async function () {
+    const { owner, strategist, config, vault } = await networkHelpers.loadFixture(deployFixture);
+
+    await expect(
+      vault.connect(strategist).updateManagementFeeSchedule(BREAKPOINTS, TIER_RATES, MINIMUM_FEE),
+    ).to.be.revertedWithCustomError(vault, "NotAuthorized");
+    await expect(vault.connect(owner).updateManagementFeeSchedule(BREAKPOINTS, TIER_RATES, MINIMUM_FEE)).to.emit(
+      vault,
+      "VaultManagementFeeScheduleChangeScheduled",
+    );
+    void expect((await vault.activeManagementFeeSchedule()).minimumFee).to.equal(0);
+
+    await networkHelpers.time.increase((await config.feeChangeCooldownDuration()) + 1n);
+    const schedule = await vault.activeManagementFeeSchedule();
+    void expect(schedule.breakpoints).to.deep.equal(BREAKPOINTS);
+    void expect(schedule.tierRates).to.deep.equal(TIER_RATES.map(BigInt));
+    void expect(schedule.minimumFee).to.equal(MINIMUM_FEE);
+    const feeModel = await vault.activeFeeModel();
+    // The base rate and the performance fee are untouched.
+    void expect(feeModel.managementFee).to.equal(100);
+    void expect(feeModel.performanceFee).to.equal(2000);
+  });
+
+  it("Should require strictly ascending breakpoints before the unused ones", async function () {
+    const { owner, vault } = await networkHelpers.loadFixture(deployFixture);
+
+    for (const [breakpoints, tierRates] of [
+      [[usdc(50_000_000), usdc(10_000_000), 0n], TIER_RATES],
+      [[usdc(10_000_000), usdc(10_000_000), 0n], TIER_RATES],
+      [[0n, usdc(10_000_000), 0n], [0, 75, 0]],
+      [[usdc(10_000_000), 0n, 0n], [75, 50, 0]],
+    ] as [bigint[], number[]][]) {
+      await expect(
+        vault
+          .connect(owner)
+          .updateManagementFeeSchedule(
+            breakpoints as [bigint, bigint, bigint],
+            tierRates as [number, number, number],
+            MINIMUM_FEE,
+          ),
+      ).to.be.revertedWithCustomError(vault, "InvalidArguments");
+    }
+  });
+});
@@ -156,7 +156,7 @@ describe("Minimum Amount DOS Prevention", function () {
 
       await expect(vault.connect(user1).requestDeposit(MIN_DEPOSIT))
         .to.emit(vault, "DepositRequest")
-        .withArgs(user1.address, MIN_DEPOSIT);
+        .withArgs(user1.address, user1.address, 0, user1.address, MIN_DEPOSIT);
     });
 
     it("should accept deposit requests above minimum", async function () {
@@ -169,7 +169,7 @@ describe("Minimum Amount DOS Prevention", function () {
 
       await expect(vault.connect(user1).requestDeposit(aboveMin))
         .to.emit(vault, "DepositRequest")
-        .withArgs(user1.address, aboveMin);
+        .withArgs(user1.address, user1.address, 0, user1.address, aboveMin);
     });
 
     it("should prevent spam attack with 150+ tiny deposits", async function () {
@@ -12,6 +12,7 @@ import type {
   TransparentVaultFactory,
   OrionTransparentVault,
 } from "../typechain-types";
+import { claimDeposit } from "./helpers/claims";
 import { deployUpgradeableProtocol } from "./helpers/deployUpgradeable";
 import { resetNetwork } from "./helpers/resetNetwork";
 
@@ -469,41 +470,44 @@ describe("Config", function () {
 });
 
 describe("OrionVault - Base Functionality", function () {
-  describe("Synchronous ERC4626 Functions", function () {
-    it("Should revert deposit function with SynchronousCallDisabled error", async function () {
+  describe("ERC-4626 Claim Functions", function () {
+    it("Should revert deposit when nothing is claimable", async function () {
       const depositAmount = ethers.parseUnits("100", 6);
 
-      await expect(vault.deposit(depositAmount, user.address)).to.be.revertedWithCustomError(
-        vault,
-        "SynchronousCallDisabled",
-      );
+      await expect(vault.connect(user)["deposit(uint256,address)"](depositAmount, user.address))
+        .to.be.revertedWithCustomError(vault, "ERC4626ExceededMaxDeposit")
+        .withArgs(user.address, depositAmount, 0);
     });
 
-    it("Should revert mint function with SynchronousCallDisabled error", async function () {
+    it("Should revert mint when nothing is claimable", async function () {
       const mintAmount = ethers.parseUnits("100", 18);
 
-      await expect(vault.mint(mintAmount, user.address)).to.be.revertedWithCustomError(
-        vault,
-        "SynchronousCallDisabled",
-      );
+      await expect(vault.connect(user)["mint(uint256,address)"](mintAmount, user.address))
+        .to.be.revertedWithCustomError(vault, "ERC4626ExceededMaxMint")
+        .withArgs(user.address, mintAmount, 0);
     });
 
-    it("Should revert withdraw function with SynchronousCallDisabled error", async function () {
+    it("Should revert withdraw when nothing is claimable", async function () {
       const withdrawAmount = ethers.parseUnits("100", 6);
 
-      await expect(vault.withdraw(withdrawAmount, user.address, user.address)).to.be.revertedWithCustomError(
-        vault,
-        "SynchronousCallDisabled",
-      );
+      await expect(vault.connect(user).withdraw(withdrawAmount, user.address, user.address))
+        .to.be.revertedWithCustomError(vault, "ERC4626ExceededMaxWithdraw")
+        .withArgs(user.address, withdrawAmount, 0);
     });
 
-    it("Should revert redeem function with SynchronousCallDisabled error", async function () {
+    it("Should revert redeem when nothing is claimable", async function () {
       const redeemAmount = ethers.parseUnits("100", 18);
 
-      await expect(vault.redeem(redeemAmount, user.address, user.address)).to.be.revertedWithCustomError(
-        vault,
-        "SynchronousCallDisabled",
-      );
+      await expect(vault.connect(user).redeem(redeemAmount, user.address, user.address))
+        .to.be.revertedWithCustomError(vault, "ERC4626ExceededMaxRedeem")
+        .withArgs(user.address, redeemAmount, 0);
+    });
+
+    it("Should revert previews with SynchronousCallDisabled error", async function () {
+      await expect(vault.previewDeposit(1)).to.be.revertedWithCustomError(vault, "SynchronousCallDisabled");
+      await expect(vault.previewMint(1)).to.be.revertedWithCustomError(vault, "SynchronousCallDisabled");
+      await expect(vault.previewWithdraw(1)).to.be.revertedWithCustomError(vault, "SynchronousCallDisabled");
+      await expect(vault.previewRedeem(1)).to.be.revertedWithCustomError(vault, "SynchronousCallDisabled");
     });
   });
 
@@ -614,6 +618,7 @@ describe("OrionVault - Base Functionality", function () {
       const loSigner = await ethers.getSigner(loAddress);
 
       await vault.connect(loSigner).fulfillDeposit(depositAmount);
+      await claimDeposit(vault, user);
 
       // Stop impersonation
       await ethers.provider.send("hardhat_stopImpersonatingAccount", [loAddress]);
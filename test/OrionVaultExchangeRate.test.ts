@@ -1,5 +1,6 @@
 import { expect } from "chai";
 import { ethers, networkHelpers } from "./helpers/hh";
+import { claimDeposit } from "./helpers/claims";
 import { deployUpgradeableProtocol } from "./helpers/deployUpgradeable";
 import { resetNetwork } from "./helpers/resetNetwork";
 
@@ -128,6 +129,7 @@ describe("OrionVault Exchange Rate Tests", function () {
       await vault.connect(impersonatedLiquidityOrchestrator).updateVaultState([], [], depositAmount);
 
       // Check exchange rate
+      await claimDeposit(vault, lp1);
       const shares = await vault.balanceOf(lp1.address);
       const assets = await vault.convertToAssets(shares);
 
@@ -157,7 +159,9 @@ describe("OrionVault Exchange Rate Tests", function () {
       await vault.connect(impersonatedLiquidityOrchestrator).updateVaultState([], [], deposit1 + deposit2);
 
       // Check exchange rates
+      await claimDeposit(vault, lp1);
       const shares1 = await vault.balanceOf(lp1.address);
+      await claimDeposit(vault, lp2);
       const shares2 = await vault.balanceOf(lp2.address);
       const assets1 = await vault.convertToAssets(shares1);
       const assets2 = await vault.convertToAssets(shares2);
@@ -181,6 +185,7 @@ describe("OrionVault Exchange Rate Tests", function () {
       await vault.connect(impersonatedLiquidityOrchestrator).fulfillDeposit(0);
       await vault.connect(impersonatedLiquidityOrchestrator).updateVaultState([], [], depositAmount);
 
+      await claimDeposit(vault, lp1);
       const shares = await vault.balanceOf(lp1.address);
 
       // Test round-trip conversions
@@ -237,6 +242,7 @@ describe("OrionVault Exchange Rate Tests", function () {
       await vault.connect(impersonatedLiquidityOrchestrator).fulfillDeposit(0);
       await vault.connect(impersonatedLiquidityOrchestrator).updateVaultState([], [], initialDeposit);
 
+      await claimDeposit(vault, lp1);
       const initialShares = await vault.balanceOf(lp1.address);
       const initialAssets = await vault.convertToAssets(initialShares);
 
@@ -286,7 +292,9 @@ describe("OrionVault Exchange Rate Tests", function () {
       await vault.connect(impersonatedLiquidityOrchestrator).fulfillDeposit(0);
       await vault.connect(impersonatedLiquidityOrchestrator).updateVaultState([], [], deposit1 + deposit2);
 
+      await claimDeposit(vault, lp1);
       const shares1Before = await vault.balanceOf(lp1.address);
+      await claimDeposit(vault, lp2);
       const shares2Before = await vault.balanceOf(lp2.address);
       const assets1Before = await vault.convertToAssets(shares1Before);
       const assets2Before = await vault.convertToAssets(shares2Before);
@@ -328,6 +336,7 @@ describe("OrionVault Exchange Rate Tests", function () {
       await vault.connect(impersonatedLiquidityOrchestrator).fulfillDeposit(0);
       await vault.connect(impersonatedLiquidityOrchestrator).updateVaultState([], [], tinyDeposit);
 
+      await claimDeposit(vault, lp1);
       const shares = await vault.balanceOf(lp1.address);
       const assets = await vault.convertToAssets(shares);
 
@@ -384,6 +393,7 @@ describe("OrionVault Exchange Rate Tests", function () {
       await vault.connect(impersonatedLiquidityOrchestrator).fulfillDeposit(0);
       await vault.connect(impersonatedLiquidityOrchestrator).updateVaultState([], [], deposit1);
 
+      await claimDeposit(vault, lp1);
       const shares1 = await vault.balanceOf(lp1.address);
       const assets1 = await vault.convertToAssets(shares1);
 
@@ -393,6 +403,7 @@ describe("OrionVault Exchange Rate Tests", function () {
       await vault.connect(impersonatedLiquidityOrchestrator).fulfillDeposit(deposit1);
       await vault.connect(impersonatedLiquidityOrchestrator).updateVaultState([], [], deposit1 + deposit2);
 
+      await claimDeposit(vault, lp2);
       const shares2 = await vault.balanceOf(lp2.address);
       const assets2 = await vault.convertToAssets(shares2);
 
@@ -402,6 +413,7 @@ describe("OrionVault Exchange Rate Tests", function () {
       await vault.connect(impersonatedLiquidityOrchestrator).fulfillDeposit(deposit1 + deposit2);
       await vault.connect(impersonatedLiquidityOrchestrator).updateVaultState([], [], deposit1 + deposit2 + deposit3);
 
+      await claimDeposit(vault, lp3);
       const shares3 = await vault.balanceOf(lp3.address);
       const assets3 = await vault.convertToAssets(shares3);
 
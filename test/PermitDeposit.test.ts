@@ -0,0 +1,164 @@
+import { expect } from "chai";
+import { ethers, networkHelpers } from "./helpers/hh";
+import type { SignerWithAddress } from "@nomicfoundation/hardhat-ethers/signers";
+import type { LiquidityOrchestrator, MockPermit2, MockUnderlyingAsset, OrionTransparentVault } from "../typechain-types";
+import { deployUpgradeableProtocol } from "./helpers/deployUpgradeable";
+import { resetNetwork } from "./helpers/resetNetwork";
+
+/**
+ * @title Permit Deposit Tests
+ * @notice Single-transaction deposit requests with EIP-2612 and Permit2 signatures
+ */
+describe("Permit Deposits", function () {
+  const DEPOSIT_AMOUNT = ethers.parseUnits("100", 6); // 100 USDC
+  const INITIAL_BALANCE = ethers.parseUnits("1000000", 6); // 1M USDC per user
+  const PERMIT2_ADDRESS = "0x000000000022D473030F116dDEE9F6B43aC78BA3";
+
+  before(async function () {
+    await resetNetwork();
+  });
+
+  async function deployFixture() {
+    const allSigners = await ethers.getSigners();
+    const owner = allSigners[0];
+    const strategist = allSigners[1];
+    const alice = allSigners[2];
+
+    const deployed = await deployUpgradeableProtocol(owner);
+
+    const usdc: MockUnderlyingAsset = deployed.underlyingAsset;
+    const liquidityOrchestrator: LiquidityOrchestrator = deployed.liquidityOrchestrator;
+    const vaultFactory = deployed.transparentVaultFactory;
+
+    const vaultTx = await vaultFactory
+      .connect(owner)
+      .createVault(strategist.address, "Permit Vault", "PV", 0, 0, 0, ethers.ZeroAddress);
+    const receipt = await vaultTx.wait();
+    const vaultCreatedEvent = receipt?.logs.find((log) => {
+      try {
+        return vaultFactory.interface.parseLog(log)?.name === "OrionVaultCreated";
+      } catch {
+        return false;
+      }
+    });
+    const parsedLog = vaultCreatedEvent ? vaultFactory.interface.parseLog(vaultCreatedEvent) : null;
+    const vault = (await ethers.getContractAt(
+      "OrionTransparentVault",
+      parsedLog?.args[0],
+    )) as unknown as OrionTransparentVault;
+
+    // No approval: every request below goes through a signature.
+    await usdc.mint(alice.address, INITIAL_BALANCE);
+
+    // Install the Permit2 mock at the canonical address.
+    const MockPermit2Factory = await ethers.getContractFactory("MockPermit2");
+    const permit2Implementation = await MockPermit2Factory.deploy();
+    await networkHelpers.setCode(
+      PERMIT2_ADDRESS,
+      await ethers.provider.getCode(await permit2Implementation.getAddress()),
+    );
+    const permit2 = (await ethers.getContractAt("MockPermit2", PERMIT2_ADDRESS)) as unknown as MockPermit2;
+
+    return { alice, usdc, vault, permit2, loAddress: await liquidityOrchestrator.getAddress() };
+  }
+
+  async function signPermit(
+    usdc: MockUnderlyingAsset,
+    owner: SignerWithAddress,
+    spender: string,
+    value: bigint,
+    deadline: bigint,
+  ) {
+    const { chainId } = await ethers.provider.getNetwork();
+    const signature = await owner.signTypedData(
+      { name: "USD Coin", version: "1", chainId, verifyingContract: await usdc.getAddress() },
+      {
+        Permit: [
+          { name: "owner", type: "address" },
+          { name: "spender", type: "address" },
+          { name: "value", type: "uint256" },
+          { name: "nonce", type: "uint256" },
+          { name: "deadline", type: "uint256" },
+        ],
+      },
+      { owner: owner.address, spender, value, nonce: await usdc.nonces(owner.address), deadline },
+    );
+    return ethers.Signature.from(signature);
+  }
+
+  describe("EIP-2612", function () {
+    it("Should request a deposit without a prior approval", async function () {
+      const { alice, usdc, vault, loAddress } = await networkHelpers.loadFixture(deployFixture);
+      const deadline = BigInt(await networkHelpers.time.latest()) + 3600n;
+      const { v, r, s } = await signPermit(usdc, alice, await vault.getAddress(), DEPOSIT_AMOUNT, deadline);
+
+      await expect(vault.connect(alice).requestDepositWithPermit(DEPOSIT_AMOUNT, deadline, v, r, s))
+        .to.emit(vault, "DepositRequest")
+        .withArgs(alice.address, alice.address, 0, alice.address, DEPOSIT_AMOUNT);
+
+      void expect(await vault.pendingDepositRequest(0, alice.address)).to.equal(DEPOSIT_AMOUNT);
+      void expect(await usdc.balanceOf(loAddress)).to.equal(DEPOSIT_AMOUNT);
+      void expect(await usdc.allowance(alice.address, await vault.getAddress())).to.equal(0);
+    });
+
+    it("Should still request when the permit was front-run", async function () {
+      const { alice, usdc, vault } = await networkHelpers.loadFixture(deployFixture);
+      const vaultAddress = await vault.getAddress();
+      const deadline = BigInt(await networkHelpers.time.latest()) + 3600n;
+      const { v, r, s } = await signPermit(usdc, alice, vaultAddress, DEPOSIT_AMOUNT, deadline);
+
+      await usdc.permit(alice.address, vaultAddress, DEPOSIT_AMOUNT, deadline, v, r, s);
+      await vault.connect(alice).requestDepositWithPermit(DEPOSIT_AMOUNT, deadline, v, r, s);
+      void expect(await vault.pendingDepositRequest(0, alice.address)).to.equal(DEPOSIT_AMOUNT);
+    });
+
+    it("Should revert when the permit is invalid and there is no allowance", async function () {
+      const { alice, usdc, vault } = await networkHelpers.loadFixture(deployFixture);
+      const deadline = BigInt(await networkHelpers.time.latest()) + 3600n;
+      // Signed for a smaller amount than requested.
+      const { v, r, s } = await signPermit(usdc, alice, await vault.getAddress(), DEPOSIT_AMOUNT / 2n, deadline);
+
+      await expect(
+        vault.connect(alice).requestDepositWithPermit(DEPOSIT_AMOUNT, deadline, v, r, s),
+      ).to.be.revertedWithCustomError(usdc, "ERC20InsufficientAllowance");
+    });
+  });
+
+  describe("Permit2", function () {
+    it("Should move the assets straight to the liquidity orchestrator", async function () {
+      const { alice, usdc, vault, loAddress } = await networkHelpers.loadFixture(deployFixture);
+      await usdc.connect(alice).approve(PERMIT2_ADDRESS, ethers.MaxUint256);
+      const deadline = BigInt(await networkHelpers.time.latest()) + 3600n;
+
+      await expect(vault.connect(alice).requestDepositWithPermit2(DEPOSIT_AMOUNT, 0, deadline, "0x"))
+        .to.emit(vault, "DepositRequest")
+        .withArgs(alice.address, alice.address, 0, alice.address, DEPOSIT_AMOUNT);
+
+      void expect(await vault.pendingDepositRequest(0, alice.address)).to.equal(DEPOSIT_AMOUNT);
+      void expect(await usdc.balanceOf(loAddress)).to.equal(DEPOSIT_AMOUNT);
+    });
+
+    it("Should not queue a request when Permit2 rejects the transfer", async function () {
+      const { alice, usdc, vault, permit2 } = await networkHelpers.loadFixture(deployFixture);
+      await usdc.connect(alice).approve(PERMIT2_ADDRESS, ethers.MaxUint256);
+      const deadline = BigInt(await networkHelpers.time.latest()) + 3600n;
+
+      await vault.connect(alice).requestDepositWithPermit2(DEPOSIT_AMOUNT, 7, deadline, "0x");
+      await expect(
+        vault.connect(alice).requestDepositWithPermit2(DEPOSIT_AMOUNT, 7, deadline, "0x"),
+      ).to.be.revertedWithCustomError(permit2, "InvalidNonce");
+      void expect(await vault.pendingDepositRequest(0, alice.address)).to.equal(DEPOSIT_AMOUNT);
+    });
+
+    it("Should apply the usual request checks before transferring", async function () {
+      const { alice, usdc, vault } = await networkHelpers.loadFixture(deployFixture);
+      await usdc.connect(alice).approve(PERMIT2_ADDRESS, ethers.MaxUint256);
+      const deadline = BigInt(await networkHelpers.time.latest()) + 3600n;
+
+      await expect(vault.connect(alice).requestDepositWithPermit2(0, 0, deadline, "0x")).to.be.revertedWithCustomError(
+        vault,
+        "AmountMustBeGreaterThanZero",
+      );
+    });
+  });
+});
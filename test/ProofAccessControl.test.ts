@@ -0,0 +1,258 @@
+import { expect } from "chai";
+import { ethers, networkHelpers } from "./helpers/hh";
+import type { SignerWithAddress } from "@nomicfoundation/hardhat-ethers/signers";
+import type {
+  CompositeAccessControl,
+  MerkleAccessControl,
+  OrionTransparentVault,
+  SignatureAccessControl,
+} from "../typechain-types";
+import { deployUpgradeableProtocol } from "./helpers/deployUpgradeable";
+import { resetNetwork } from "./helpers/resetNetwork";
+
+/**
+ * @title Proof-Based Access Control Tests
+ * @notice Merkle and EIP-712 attestation access controllers reading their proofs from the vault calldata
+ * @dev Proofs are appended to the call followed by their length, see AccessDataLib. Composite controllers
+ *      take one proof per policy node.
+ */
+describe("Proof-Based Access Control", function () {
+  const DEPOSIT_AMOUNT = ethers.parseUnits("100", 6); // 100 USDC
+  const INITIAL_BALANCE = ethers.parseUnits("1000000", 6); // 1M USDC per user
+  const coder = ethers.AbiCoder.defaultAbiCoder();
+
+  before(async function () {
+    await resetNetwork();
+  });
+
+  async function deployFixture() {
+    const allSigners = await ethers.getSigners();
+    const owner = allSigners[0];
+    const strategist = allSigners[1];
+    const [alice, bob, carol, complianceSigner] = allSigners.slice(2, 6);
+
+    const deployed = await deployUpgradeableProtocol(owner);
+    const usdc = deployed.underlyingAsset;
+    const vaultFactory = deployed.transparentVaultFactory;
+
+    const root = merkleRoot([alice.address, bob.address]);
+    const MerkleAccessControlFactory = await ethers.getContractFactory("MerkleAccessControl");
+    const merkleAccessControl = (await MerkleAccessControlFactory.deploy(
+      owner.address,
+      root,
+    )) as unknown as MerkleAccessControl;
+    const SignatureAccessControlFactory = await ethers.getContractFactory("SignatureAccessControl");
+    const signatureAccessControl = (await SignatureAccessControlFactory.deploy(
+      owner.address,
+      complianceSigner.address,
+    )) as unknown as SignatureAccessControl;
+
+    async function createVault(accessControl: string) {
+      const vaultAddress = await vaultFactory.createVault.staticCall(
+        strategist.address,
+        "Gated Vault",
+        "GV",
+        0,
+        0,
+        0,
+        accessControl,
+      );
+      await vaultFactory.createVault(strategist.address, "Gated Vault", "GV", 0, 0, 0, accessControl);
+      return (await ethers.getContractAt("OrionTransparentVault", vaultAddress)) as unknown as OrionTransparentVault;
+    }
+    const merkleVault = await createVault(await merkleAccessControl.getAddress());
+    const signatureVault = await createVault(await signatureAccessControl.getAddress());
+
+    for (const user of [alice, bob, carol]) {
+      await usdc.mint(user.address, INITIAL_BALANCE);
+      await usdc.connect(user).approve(await merkleVault.getAddress(), ethers.MaxUint256);
+      await usdc.connect(user).approve(await signatureVault.getAddress(), ethers.MaxUint256);
+    }
+
+    return {
+      owner,
+      alice,
+      bob,
+      carol,
+      complianceSigner,
+      merkleAccessControl,
+      signatureAccessControl,
+      merkleVault,
+      signatureVault,
+    };
+  }
+
+  function leaf(account: string) {
+    return ethers.keccak256(ethers.keccak256(coder.encode(["address"], [account])));
+  }
+
+  function hashPair(a: string, b: string) {
+    return ethers.keccak256(BigInt(a) < BigInt(b) ? ethers.concat([a, b]) : ethers.concat([b, a]));
+  }
+
+  // Two-leaf tree: the proof of each leaf is the other one.
+  function merkleRoot([first, second]: string[]) {
+    return hashPair(leaf(first), leaf(second));
+  }
+
+  /** EIP-712 attestation of `account` by `complianceSigner`, encoded as SignatureAccessControl reads it. */
+  async function attest(
+    accessControl: SignatureAccessControl,
+    complianceSigner: SignerWithAddress,
+    account: string,
+    expiry: bigint,
+  ) {
+    const { chainId } = await ethers.provider.getNetwork();
+    const nonce = await accessControl.nonces(account);
+    const signature = await complianceSigner.signTypedData(
+      { name: "OrionAccessControl", version: "1", chainId, verifyingContract: await accessControl.getAddress() },
+      {
+        Attestation: [
+          { name: "account", type: "address" },
+          { name: "nonce", type: "uint256" },
+          { name: "expiry", type: "uint256" },
+        ],
+      },
+      { account, nonce, expiry },
+    );
+    return coder.encode(["uint256", "uint256", "bytes"], [nonce, expiry, signature]);
+  }
+
+  /** Sends requestDeposit(assets) with `payload` appended, as AccessDataLib expects it. */
+  async function requestDepositWithPayload(vault: OrionTransparentVault, user: SignerWithAddress, payload: string) {
+    const callData = vault.interface.encodeFunctionData("requestDeposit(uint256)", [DEPOSIT_AMOUNT]);
+    const length = coder.encode(["uint256"], [ethers.dataLength(payload)]);
+    return user.sendTransaction({ to: await vault.getAddress(), data: ethers.concat([callData, payload, length]) });
+  }
+
+  describe("MerkleAccessControl", function () {
+    it("Should accept a deposit request with a valid proof", async function () {
+      const { alice, bob, merkleVault } = await networkHelpers.loadFixture(deployFixture);
+      const proof = coder.encode(["bytes32[]"], [[leaf(bob.address)]]);
+
+      await requestDepositWithPayload(merkleVault, alice, proof);
+      void expect(await merkleVault.pendingDepositRequest(0, alice.address)).to.equal(DEPOSIT_AMOUNT);
+    });
+
+    it("Should reject missing proofs and proofs of other accounts", async function () {
+      const { bob, carol, merkleVault } = await networkHelpers.loadFixture(deployFixture);
+      const proof = coder.encode(["bytes32[]"], [[leaf(bob.address)]]);
+
+      await expect(merkleVault.connect(carol).requestDeposit(DEPOSIT_AMOUNT)).to.be.revertedWithCustomError(
+        merkleVault,
+        "DepositNotAllowed",
+      );
+      await expect(requestDepositWithPayload(merkleVault, carol, proof)).to.be.revertedWithCustomError(
+        merkleVault,
+        "DepositNotAllowed",
+      );
+    });
+
+    it("Should let the owner replace the root", async function () {
+      const { owner, alice, bob, carol, merkleAccessControl, merkleVault } =
+        await networkHelpers.loadFixture(deployFixture);
+      const newRoot = merkleRoot([bob.address, carol.address]);
+
+      await expect(merkleAccessControl.connect(alice).setMerkleRoot(newRoot)).to.be.revertedWithCustomError(
+        merkleAccessControl,
+        "OwnableUnauthorizedAccount",
+      );
+      await expect(merkleAccessControl.connect(owner).setMerkleRoot(newRoot))
+        .to.emit(merkleAccessControl, "MerkleRootUpdated")
+        .withArgs(newRoot);
+
+      await requestDepositWithPayload(merkleVault, carol, coder.encode(["bytes32[]"], [[leaf(bob.address)]]));
+      await expect(
+        requestDepositWithPayload(merkleVault, alice, coder.encode(["bytes32[]"], [[leaf(bob.address)]])),
+      ).to.be.revertedWithCustomError(merkleVault, "DepositNotAllowed");
+    });
+  });
+
+  describe("SignatureAccessControl", function () {
+    it("Should accept a deposit request with a valid attestation", async function () {
+      const { alice, complianceSigner, signatureAccessControl, signatureVault } =
+        await networkHelpers.loadFixture(deployFixture);
+      const expiry = BigInt(await networkHelpers.time.latest()) + 3600n;
+      const attestation = await attest(signatureAccessControl, complianceSigner, alice.address, expiry);
+
+      await requestDepositWithPayload(signatureVault, alice, attestation);
+      void expect(await signatureVault.pendingDepositRequest(0, alice.address)).to.equal(DEPOSIT_AMOUNT);
+    });
+
+    it("Should reject attestations for other accounts or from other signers", async function () {
+      const { alice, bob, complianceSigner, signatureAccessControl, signatureVault } =
+        await networkHelpers.loadFixture(deployFixture);
+      const expiry = BigInt(await networkHelpers.time.latest()) + 3600n;
+
+      const aliceAttestation = await attest(signatureAccessControl, complianceSigner, alice.address, expiry);
+      await expect(requestDepositWithPayload(signatureVault, bob, aliceAttestation)).to.be.revertedWithCustomError(
+        signatureVault,
+        "DepositNotAllowed",
+      );
+
+      const selfAttestation = await attest(signatureAccessControl, bob, bob.address, expiry);
+      await expect(requestDepositWithPayload(signatureVault, bob, selfAttestation)).to.be.revertedWithCustomError(
+        signatureVault,
+        "DepositNotAllowed",
+      );
+    });
+
+    it("Should reject expired and revoked attestations", async function () {
+      const { owner, alice, bob, complianceSigner, signatureAccessControl, signatureVault } =
+        await networkHelpers.loadFixture(deployFixture);
+      const expiry = BigInt(await networkHelpers.time.latest()) + 3600n;
+      const aliceAttestation = await attest(signatureAccessControl, complianceSigner, alice.address, expiry);
+      const bobAttestation = await attest(signatureAccessControl, complianceSigner, bob.address, expiry);
+
+      await expect(signatureAccessControl.connect(owner).revoke([bob.address]))
+        .to.emit(signatureAccessControl, "AttestationsRevoked")
+        .withArgs(bob.address, 1);
+      await expect(requestDepositWithPayload(signatureVault, bob, bobAttestation)).to.be.revertedWithCustomError(
+        signatureVault,
+        "DepositNotAllowed",
+      );
+
+      await networkHelpers.time.increase(3600);
+      await expect(requestDepositWithPayload(signatureVault, alice, aliceAttestation)).to.be.revertedWithCustomError(
+        signatureVault,
+        "DepositNotAllowed",
+      );
+    });
+  });
+
+  describe("CompositeAccessControl", function () {
+    it("Should pass each child its own proof", async function () {
+      const { owner, alice, bob, complianceSigner, merkleAccessControl, signatureAccessControl, merkleVault } =
+        await networkHelpers.loadFixture(deployFixture);
+      const CompositeAccessControlFactory = await ethers.getContractFactory("CompositeAccessControl");
+      const composite = (await CompositeAccessControlFactory.deploy(await merkleVault.getAddress(), [
+        { nodeType: 1, controller: ethers.ZeroAddress, children: [1, 2] }, // AND
+        { nodeType: 0, controller: await merkleAccessControl.getAddress(), children: [] },
+        { nodeType: 0, controller: await signatureAccessControl.getAddress(), children: [] },
+      ])) as unknown as CompositeAccessControl;
+      await merkleVault.connect(owner).setDepositAccessControl(await composite.getAddress());
+
+      const expiry = BigInt(await networkHelpers.time.latest()) + 3600n;
+      const aliceAttestation = await attest(signatureAccessControl, complianceSigner, alice.address, expiry);
+      const aliceProof = coder.encode(["bytes32[]"], [[leaf(bob.address)]]);
+      const segments = (payloads: string[]) => coder.encode(["bytes[]"], [payloads]);
+
+      await requestDepositWithPayload(merkleVault, alice, segments(["0x", aliceProof, aliceAttestation]));
+      void expect(await merkleVault.pendingDepositRequest(0, alice.address)).to.equal(DEPOSIT_AMOUNT);
+
+      // Either proof alone fails the AND.
+      const bobAttestation = await attest(signatureAccessControl, complianceSigner, bob.address, expiry);
+      const bobProof = coder.encode(["bytes32[]"], [[leaf(alice.address)]]);
+      const proofOnly = segments(["0x", bobProof]);
+      const attestationOnly = segments(["0x", "0x", bobAttestation]);
+      await expect(requestDepositWithPayload(merkleVault, bob, proofOnly)).to.be.revertedWithCustomError(
+        merkleVault,
+        "DepositNotAllowed",
+      );
+      await expect(requestDepositWithPayload(merkleVault, bob, attestationOnly)).to.be.revertedWithCustomError(
+        merkleVault,
+        "DepositNotAllowed",
+      );
+    });
+  });
+});
@@ -0,0 +1,180 @@
+import { expect } from "chai";
+import { ethers, networkHelpers } from "./helpers/hh";
+import type { OrionTransparentVault } from "../typechain-types";
+import { deployUpgradeableProtocol } from "./helpers/deployUpgradeable";
+import { resetNetwork } from "./helpers/resetNetwork";
+
+/**
+ * @title Protocol Fee Overrides Tests
+ * @notice Protocol fee tiers by vault epoch volume and per-vault protocol fee overrides
+ * @dev Both are scheduled by the config owner and take effect after the fee change cooldown.
+ */
+describe("Protocol Fee Overrides", function () {
+  const usdc = (amount: number) => ethers.parseUnits(amount.toString(), 6);
+  // 0.1% / 10% by default, 0.08% / 8% from 10M and 0.05% / 5% from 50M.
+  const TIERS = [
+    { minVolume: usdc(10_000_000), vFeeCoefficient: 8, rsFeeCoefficient: 800 },
+    { minVolume: usdc(50_000_000), vFeeCoefficient: 5, rsFeeCoefficient: 500 },
+  ];
+
+  before(async function () {
+    await resetNetwork();
+  });
+
+  async function deployFixture() {
+    const allSigners = await ethers.getSigners();
+    const owner = allSigners[0];
+    const strategist = allSigners[1];
+
+    const deployed = await deployUpgradeableProtocol(owner);
+
+    const config = deployed.orionConfig;
+    const liquidityOrchestrator = deployed.liquidityOrchestrator;
+    const vaultFactory = deployed.transparentVaultFactory;
+
+    const vaultTx = await vaultFactory
+      .connect(owner)
+      .createVault(strategist.address, "Partner Vault", "PV", 3, 2000, 100, ethers.ZeroAddress);
+    const receipt = await vaultTx.wait();
+    const vaultCreatedEvent = receipt?.logs.find((log) => {
+      try {
+        return vaultFactory.interface.parseLog(log)?.name === "OrionVaultCreated";
+      } catch {
+        return false;
+      }
+    });
+    const parsedLog = vaultCreatedEvent ? vaultFactory.interface.parseLog(vaultCreatedEvent) : null;
+    const vault = (await ethers.getContractAt(
+      "OrionTransparentVault",
+      parsedLog?.args[0],
+    )) as unknown as OrionTransparentVault;
+
+    await config.connect(owner).updateProtocolFees(10, 1000);
+    await networkHelpers.time.increase((await config.feeChangeCooldownDuration()) + 1n);
+
+    return { owner, strategist, config, liquidityOrchestrator, vault };
+  }
+
+  it("Should apply the protocol fees by default", async function () {
+    const { config, vault } = await networkHelpers.loadFixture(deployFixture);
+
+    void expect(await config.activeProtocolFeeTiers()).to.deep.equal([]);
+    void expect((await config.activeVaultProtocolFeeOverride(vault)).enabled).to.equal(false);
+    void expect(await config.activeVaultProtocolFees(vault, usdc(100_000_000))).to.deep.equal([10n, 1000n]);
+  });
+
+  it("Should validate protocol fee tiers", async function () {
+    const { owner, strategist, config } = await networkHelpers.loadFixture(deployFixture);
+
+    await expect(config.connect(strategist).updateProtocolFeeTiers(TIERS)).to.be.revertedWithCustomError(
+      config,
+      "OwnableUnauthorizedAccount",
+    );
+    for (const tiers of [
+      [TIERS[1], TIERS[0]],
+      [TIERS[0], TIERS[0]],
+      [{ ...TIERS[0], minVolume: 0n }],
+      [{ ...TIERS[0], vFeeCoefficient: 51 }],
+      [{ ...TIERS[0], rsFeeCoefficient: 2001 }],
+      Array.from({ length: 9 }, (_, i) => ({ ...TIERS[0], minVolume: usdc(i + 1) })),
+    ]) {
+      await expect(config.connect(owner).updateProtocolFeeTiers(tiers)).to.be.revertedWithCustomError(
+        config,
+        "InvalidArguments",
+      );
+    }
+  });
+
+  it("Should select the highest tier reached only after the cooldown", async function () {
+    const { owner, config, vault } = await networkHelpers.loadFixture(deployFixture);
+
+    await expect(config.connect(owner).updateProtocolFeeTiers(TIERS)).to.emit(
+      config,
+      "ProtocolFeeTiersChangeScheduled",
+    );
+    void expect(await config.activeVaultProtocolFees(vault, usdc(50_000_000))).to.deep.equal([10n, 1000n]);
+
+    await networkHelpers.time.increase((await config.feeChangeCooldownDuration()) + 1n);
+    void expect(await config.activeVaultProtocolFees(vault, usdc(9_999_999))).to.deep.equal([10n, 1000n]);
+    void expect(await config.activeVaultProtocolFees(vault, usdc(10_000_000))).to.deep.equal([8n, 800n]);
+    void expect(await config.activeVaultProtocolFees(vault, usdc(50_000_000))).to.deep.equal([5n, 500n]);
+  });
+
+  it("Should apply the protocol fees in a vault's first epoch", async function () {
+    const { owner, config, liquidityOrchestrator, vault } = await networkHelpers.loadFixture(deployFixture);
+
+    await config.connect(owner).updateProtocolFeeTiers(TIERS);
+    await networkHelpers.time.increase((await config.feeChangeCooldownDuration()) + 1n);
+
+    // No epoch has been processed yet, and every tier starts above zero volume.
+    const volume = await liquidityOrchestrator.vaultEpochVolume(vault);
+    void expect(volume).to.equal(0);
+    void expect(await config.activeVaultProtocolFees(vault, volume)).to.deep.equal([10n, 1000n]);
+  });
+
+  it("Should count credited redemptions towards the vault epoch volume", async function () {
+    const { owner, config, liquidityOrchestrator, vault } = await networkHelpers.loadFixture(deployFixture);
+
+    await config.connect(owner).updateProtocolFeeTiers(TIERS);
+    await networkHelpers.time.increase((await config.feeChangeCooldownDuration()) + 1n);
+
+    const vaultAddress = await vault.getAddress();
+    await networkHelpers.impersonateAccount(vaultAddress);
+    await networkHelpers.setBalance(vaultAddress, ethers.parseEther("1"));
+    const vaultSigner = await ethers.getSigner(vaultAddress);
+
+    await liquidityOrchestrator.connect(vaultSigner).creditRedemptionFunds(owner.address, usdc(6_000_000));
+    await liquidityOrchestrator.connect(vaultSigner).creditRedemptionFunds(owner.address, usdc(4_000_000));
+    const volume = await liquidityOrchestrator.vaultEpochVolume(vault);
+    void expect(volume).to.equal(usdc(10_000_000));
+    void expect(await config.activeVaultProtocolFees(vault, volume)).to.deep.equal([8n, 800n]);
+  });
+
+  it("Should validate vault protocol fee overrides", async function () {
+    const { owner, strategist, config, vault } = await networkHelpers.loadFixture(deployFixture);
+    const feeOverride = { enabled: true, vFeeCoefficient: 0, rsFeeCoefficient: 0 };
+
+    await expect(
+      config.connect(strategist).updateVaultProtocolFeeOverride(vault, feeOverride),
+    ).to.be.revertedWithCustomError(config, "OwnableUnauthorizedAccount");
+    await expect(
+      config.connect(owner).updateVaultProtocolFeeOverride(strategist.address, feeOverride),
+    ).to.be.revertedWithCustomError(config, "InvalidAddress");
+    for (const invalidOverride of [
+      { ...feeOverride, vFeeCoefficient: 51 },
+      { ...feeOverride, rsFeeCoefficient: 2001 },
+      { enabled: false, vFeeCoefficient: 5, rsFeeCoefficient: 0 },
+    ]) {
+      await expect(
+        config.connect(owner).updateVaultProtocolFeeOverride(vault, invalidOverride),
+      ).to.be.revertedWithCustomError(config, "InvalidArguments");
+    }
+  });
+
+  it("Should let an override take precedence over the tiers only after the cooldown", async function () {
+    const { owner, config, vault } = await networkHelpers.loadFixture(deployFixture);
+    const cooldown = (await config.feeChangeCooldownDuration()) + 1n;
+
+    await config.connect(owner).updateProtocolFeeTiers(TIERS);
+    await networkHelpers.time.increase(cooldown);
+
+    // Launch incentive: no protocol fees for the vault.
+    await expect(
+      config
+        .connect(owner)
+        .updateVaultProtocolFeeOverride(vault, { enabled: true, vFeeCoefficient: 0, rsFeeCoefficient: 0 }),
+    ).to.emit(config, "VaultProtocolFeeOverrideChangeScheduled");
+    void expect(await config.activeVaultProtocolFees(vault, usdc(50_000_000))).to.deep.equal([5n, 500n]);
+
+    await networkHelpers.time.increase(cooldown);
+    void expect(await config.activeVaultProtocolFees(vault, usdc(50_000_000))).to.deep.equal([0n, 0n]);
+
+    // Disabling the override falls back to the tiers, again after the cooldown.
+    await config
+      .connect(owner)
+      .updateVaultProtocolFeeOverride(vault, { enabled: false, vFeeCoefficient: 0, rsFeeCoefficient: 0 });
+    void expect(await config.activeVaultProtocolFees(vault, usdc(50_000_000))).to.deep.equal([0n, 0n]);
+    await networkHelpers.time.increase(cooldown);
+    void expect(await config.activeVaultProtocolFees(vault, usdc(50_000_000))).to.deep.equal([5n, 500n]);
+  });
+});
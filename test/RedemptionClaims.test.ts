@@ -0,0 +1,134 @@
+import { expect } from "chai";
+import { ethers, networkHelpers } from "./helpers/hh";
+import type { OrionTransparentVault, LiquidityOrchestrator } from "../typechain-types";
+import { deployUpgradeableProtocol } from "./helpers/deployUpgradeable";
+import { resetNetwork } from "./helpers/resetNetwork";
+
+/**
+ * @title Redemption Claims Tests
+ * @notice Fulfilled redemptions are credited in the liquidity orchestrator and pulled by their controllers
+ * @dev fulfillRedeem transfers nothing, so no individual recipient can make the epoch revert.
+ */
+describe("Redemption Claims", function () {
+  const DEPOSIT_AMOUNT = ethers.parseUnits("100", 6); // 100 USDC
+  const INITIAL_BALANCE = ethers.parseUnits("1000000", 6); // 1M USDC per user
+
+  before(async function () {
+    await resetNetwork();
+  });
+
+  async function deployFixture() {
+    const allSigners = await ethers.getSigners();
+    const owner = allSigners[0];
+    const strategist = allSigners[1];
+    const [alice, bob] = allSigners.slice(2, 4);
+
+    const deployed = await deployUpgradeableProtocol(owner);
+
+    const usdc = deployed.underlyingAsset;
+    const liquidityOrchestrator: LiquidityOrchestrator = deployed.liquidityOrchestrator;
+    const vaultFactory = deployed.transparentVaultFactory;
+
+    const vaultTx = await vaultFactory
+      .connect(owner)
+      .createVault(strategist.address, "Claim Vault", "CV", 0, 0, 0, ethers.ZeroAddress);
+    const receipt = await vaultTx.wait();
+    const vaultCreatedEvent = receipt?.logs.find((log) => {
+      try {
+        return vaultFactory.interface.parseLog(log)?.name === "OrionVaultCreated";
+      } catch {
+        return false;
+      }
+    });
+    const parsedLog = vaultCreatedEvent ? vaultFactory.interface.parseLog(vaultCreatedEvent) : null;
+    const vault = (await ethers.getContractAt(
+      "OrionTransparentVault",
+      parsedLog?.args[0],
+    )) as unknown as OrionTransparentVault;
+
+    for (const user of [alice, bob]) {
+      await usdc.mint(user.address, INITIAL_BALANCE);
+      await usdc.connect(user).approve(await vault.getAddress(), ethers.MaxUint256);
+    }
+
+    const loAddress = await liquidityOrchestrator.getAddress();
+    await networkHelpers.impersonateAccount(loAddress);
+    await networkHelpers.setBalance(loAddress, ethers.parseEther("1"));
+    const loSigner = await ethers.getSigner(loAddress);
+
+    // Alice and bob hold shares and have both requested to redeem all of them.
+    for (const user of [alice, bob]) {
+      await vault.connect(user).requestDeposit(DEPOSIT_AMOUNT);
+    }
+    await vault.connect(loSigner).fulfillDeposit(0);
+    await vault.connect(loSigner).updateVaultState([], [], DEPOSIT_AMOUNT * 2n);
+    for (const user of [alice, bob]) {
+      await vault.connect(user)["deposit(uint256,address)"](DEPOSIT_AMOUNT, user.address);
+      await vault.connect(user).requestRedeem(await vault.balanceOf(user.address));
+    }
+
+    return { alice, bob, usdc, vault, liquidityOrchestrator, loAddress, loSigner };
+  }
+
+  it("Should credit every controller in the orchestrator without transferring", async function () {
+    const { alice, bob, usdc, vault, liquidityOrchestrator, loAddress, loSigner } =
+      await networkHelpers.loadFixture(deployFixture);
+    const vaultAddress = await vault.getAddress();
+    const orchestratorBalance = await usdc.balanceOf(loAddress);
+
+    await expect(vault.connect(loSigner).fulfillRedeem(DEPOSIT_AMOUNT * 2n))
+      .to.emit(liquidityOrchestrator, "RedemptionFundsCredited")
+      .withArgs(vaultAddress, alice.address, DEPOSIT_AMOUNT);
+
+    void expect(await usdc.balanceOf(loAddress)).to.equal(orchestratorBalance);
+    void expect(await liquidityOrchestrator.claimableRedemptions(vaultAddress, alice.address)).to.equal(DEPOSIT_AMOUNT);
+    void expect(await liquidityOrchestrator.claimableRedemptions(vaultAddress, bob.address)).to.equal(DEPOSIT_AMOUNT);
+    void expect(await vault.maxWithdraw(alice.address)).to.equal(DEPOSIT_AMOUNT);
+  });
+
+  it("Should pay out the whole claimable balance with claimRedemption", async function () {
+    const { alice, bob, usdc, vault, liquidityOrchestrator, loSigner } =
+      await networkHelpers.loadFixture(deployFixture);
+    const vaultAddress = await vault.getAddress();
+    await vault.connect(loSigner).fulfillRedeem(DEPOSIT_AMOUNT * 2n);
+    const shares = await vault.claimableRedeemRequest(0, alice.address);
+
+    await expect(vault.connect(alice).claimRedemption(bob.address))
+      .to.emit(vault, "Withdraw")
+      .withArgs(alice.address, bob.address, alice.address, DEPOSIT_AMOUNT, shares);
+
+    void expect(await usdc.balanceOf(bob.address)).to.equal(INITIAL_BALANCE);
+    void expect(await liquidityOrchestrator.claimableRedemptions(vaultAddress, alice.address)).to.equal(0);
+    void expect(await vault.claimableRedeemRequest(0, alice.address)).to.equal(0);
+
+    // Bob's balance is untouched by alice's claim.
+    void expect(await liquidityOrchestrator.claimableRedemptions(vaultAddress, bob.address)).to.equal(DEPOSIT_AMOUNT);
+
+    await expect(vault.connect(alice).claimRedemption(alice.address)).to.be.revertedWithCustomError(
+      vault,
+      "AmountMustBeGreaterThanZero",
+    );
+  });
+
+  it("Should only pay vaults out of the balances they credited", async function () {
+    const { alice, vault, liquidityOrchestrator, loSigner } = await networkHelpers.loadFixture(deployFixture);
+    const vaultAddress = await vault.getAddress();
+    await vault.connect(loSigner).fulfillRedeem(DEPOSIT_AMOUNT * 2n);
+
+    await expect(
+      liquidityOrchestrator.connect(alice).transferRedemptionFunds(alice.address, alice.address, 1),
+    ).to.be.revertedWithCustomError(liquidityOrchestrator, "NotAuthorized");
+    await expect(
+      liquidityOrchestrator.connect(alice).creditRedemptionFunds(alice.address, DEPOSIT_AMOUNT),
+    ).to.be.revertedWithCustomError(liquidityOrchestrator, "NotAuthorized");
+
+    await networkHelpers.impersonateAccount(vaultAddress);
+    await networkHelpers.setBalance(vaultAddress, ethers.parseEther("1"));
+    const vaultSigner = await ethers.getSigner(vaultAddress);
+    await expect(
+      liquidityOrchestrator
+        .connect(vaultSigner)
+        .transferRedemptionFunds(alice.address, alice.address, DEPOSIT_AMOUNT + 1n),
+    ).to.be.revertedWithCustomError(liquidityOrchestrator, "InsufficientAmount");
+  });
+});
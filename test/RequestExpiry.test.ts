@@ -0,0 +1,159 @@
+import { expect } from "chai";
+import { ethers, networkHelpers } from "./helpers/hh";
+import type { OrionTransparentVault, LiquidityOrchestrator } from "../typechain-types";
+import { deployUpgradeableProtocol } from "./helpers/deployUpgradeable";
+import { resetNetwork } from "./helpers/resetNetwork";
+
+/**
+ * @title Request Expiry Tests
+ * @notice Expired requests are left out of the next batch and cleared by the following fulfill call
+ * @dev Outside an epoch, expiry is checked against the block timestamp.
+ */
+describe("Request Expiry", function () {
+  const DEPOSIT_AMOUNT = ethers.parseUnits("100", 6); // 100 USDC
+  const INITIAL_BALANCE = ethers.parseUnits("1000000", 6); // 1M USDC per user
+  const TTL = 3600n;
+
+  before(async function () {
+    await resetNetwork();
+  });
+
+  async function deployFixture() {
+    const allSigners = await ethers.getSigners();
+    const owner = allSigners[0];
+    const strategist = allSigners[1];
+    const [alice, bob] = allSigners.slice(2, 4);
+
+    const deployed = await deployUpgradeableProtocol(owner);
+
+    const usdc = deployed.underlyingAsset;
+    const liquidityOrchestrator: LiquidityOrchestrator = deployed.liquidityOrchestrator;
+    const vaultFactory = deployed.transparentVaultFactory;
+
+    const vaultTx = await vaultFactory
+      .connect(owner)
+      .createVault(strategist.address, "Expiry Vault", "EV", 0, 0, 0, ethers.ZeroAddress);
+    const receipt = await vaultTx.wait();
+    const vaultCreatedEvent = receipt?.logs.find((log) => {
+      try {
+        return vaultFactory.interface.parseLog(log)?.name === "OrionVaultCreated";
+      } catch {
+        return false;
+      }
+    });
+    const parsedLog = vaultCreatedEvent ? vaultFactory.interface.parseLog(vaultCreatedEvent) : null;
+    const vault = (await ethers.getContractAt(
+      "OrionTransparentVault",
+      parsedLog?.args[0],
+    )) as unknown as OrionTransparentVault;
+
+    for (const user of [alice, bob]) {
+      await usdc.mint(user.address, INITIAL_BALANCE);
+      await usdc.connect(user).approve(await vault.getAddress(), ethers.MaxUint256);
+    }
+
+    const loAddress = await liquidityOrchestrator.getAddress();
+    await networkHelpers.impersonateAccount(loAddress);
+    await networkHelpers.setBalance(loAddress, ethers.parseEther("1"));
+    const loSigner = await ethers.getSigner(loAddress);
+
+    return { alice, bob, usdc, vault, liquidityOrchestrator, loSigner };
+  }
+
+  async function expiryIn(seconds: bigint) {
+    return BigInt(await networkHelpers.time.latest()) + seconds;
+  }
+
+  it("Should reject an expiry that is not in the future", async function () {
+    const { alice, vault } = await networkHelpers.loadFixture(deployFixture);
+    const now = BigInt(await networkHelpers.time.latest());
+
+    await expect(vault.connect(alice).requestDepositWithExpiry(DEPOSIT_AMOUNT, alice.address, alice.address, now))
+      .to.be.revertedWithCustomError(vault, "InvalidRequestExpiry")
+      .withArgs(now);
+  });
+
+  it("Should keep a request in the batch until it expires", async function () {
+    const { alice, vault } = await networkHelpers.loadFixture(deployFixture);
+    const batchSize = 10;
+
+    await vault
+      .connect(alice)
+      .requestDepositWithExpiry(DEPOSIT_AMOUNT, alice.address, alice.address, await expiryIn(TTL));
+    void expect(await vault.pendingDeposit(batchSize)).to.equal(DEPOSIT_AMOUNT);
+
+    await networkHelpers.time.increase(TTL);
+    void expect(await vault.pendingDeposit(batchSize)).to.equal(0);
+    // Still queued, and still cancellable, until a fulfill call refunds it.
+    void expect(await vault.pendingDepositRequest(0, alice.address)).to.equal(DEPOSIT_AMOUNT);
+    void expect(await vault.pendingDepositCount()).to.equal(1);
+  });
+
+  it("Should refund expired deposits and fulfill the others", async function () {
+    const { alice, bob, usdc, vault, liquidityOrchestrator, loSigner } =
+      await networkHelpers.loadFixture(deployFixture);
+
+    await vault
+      .connect(alice)
+      .requestDepositWithExpiry(DEPOSIT_AMOUNT, alice.address, alice.address, await expiryIn(TTL));
+    await vault.connect(bob).requestDeposit(DEPOSIT_AMOUNT);
+    await networkHelpers.time.increase(TTL);
+
+    await expect(vault.connect(loSigner).fulfillDeposit(0))
+      .to.emit(vault, "DepositRequestExpired")
+      .withArgs(alice.address, DEPOSIT_AMOUNT)
+      .and.to.emit(liquidityOrchestrator, "DepositRefundCredited")
+      .and.to.emit(vault, "DepositClaimable");
+
+    void expect(await vault.pendingDepositRequest(0, alice.address)).to.equal(0);
+    void expect(await vault.claimableDepositRequest(0, alice.address)).to.equal(0);
+    void expect(await vault.claimableDepositRequest(0, bob.address)).to.equal(DEPOSIT_AMOUNT);
+    void expect(await vault.pendingDepositCount()).to.equal(0);
+
+    // The refund is credited, not pushed: alice claims it herself.
+    void expect(await usdc.balanceOf(alice.address)).to.equal(INITIAL_BALANCE - DEPOSIT_AMOUNT);
+    void expect(await liquidityOrchestrator.claimableDepositRefunds(vault, alice.address)).to.equal(DEPOSIT_AMOUNT);
+    await expect(vault.connect(bob).claimDepositRefund(bob.address)).to.be.revertedWithCustomError(
+      vault,
+      "AmountMustBeGreaterThanZero",
+    );
+    await expect(vault.connect(alice).claimDepositRefund(alice.address))
+      .to.emit(liquidityOrchestrator, "DepositRefundClaimed")
+      .withArgs(await vault.getAddress(), alice.address, alice.address, DEPOSIT_AMOUNT);
+    void expect(await usdc.balanceOf(alice.address)).to.equal(INITIAL_BALANCE);
+    void expect(await liquidityOrchestrator.claimableDepositRefunds(vault, alice.address)).to.equal(0);
+  });
+
+  it("Should drop expired redemptions and return their shares", async function () {
+    const { alice, bob, vault, loSigner } = await networkHelpers.loadFixture(deployFixture);
+    const vaultAddress = await vault.getAddress();
+
+    for (const user of [alice, bob]) {
+      await vault.connect(user).requestDeposit(DEPOSIT_AMOUNT);
+    }
+    await vault.connect(loSigner).fulfillDeposit(0);
+    await vault.connect(loSigner).updateVaultState([], [], DEPOSIT_AMOUNT * 2n);
+    for (const user of [alice, bob]) {
+      await vault.connect(user)["deposit(uint256,address)"](DEPOSIT_AMOUNT, user.address);
+    }
+    const shares = await vault.balanceOf(alice.address);
+
+    await vault.connect(alice).requestRedeemWithExpiry(shares, alice.address, alice.address, await expiryIn(TTL));
+    await vault.connect(bob).requestRedeem(shares);
+    await networkHelpers.time.increase(TTL);
+
+    const [users, batchShares] = await vault.pendingRedeemBatch(10);
+    void expect(users).to.deep.equal([bob.address]);
+    void expect(batchShares).to.deep.equal([shares]);
+    void expect(await vault.pendingRedeem(10)).to.equal(shares);
+
+    await expect(vault.connect(loSigner).fulfillRedeem(DEPOSIT_AMOUNT))
+      .to.emit(vault, "RedeemRequestExpired")
+      .withArgs(alice.address, shares);
+
+    void expect(await vault.balanceOf(alice.address)).to.equal(shares);
+    void expect(await vault.balanceOf(vaultAddress)).to.equal(0);
+    void expect(await vault.claimableRedeemRequest(0, bob.address)).to.equal(shares);
+    void expect(await vault.pendingRedeemCount()).to.equal(0);
+  });
+});
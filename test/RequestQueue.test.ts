@@ -0,0 +1,224 @@
+import { expect } from "chai";
+import { ethers, networkHelpers } from "./helpers/hh";
+import type { OrionTransparentVault, LiquidityOrchestrator } from "../typechain-types";
+import { claimDeposit } from "./helpers/claims";
+import { deployUpgradeableProtocol } from "./helpers/deployUpgradeable";
+import { resetNetwork } from "./helpers/resetNetwork";
+
+/**
+ * @title Request Queue Tests
+ * @notice Deposit and redeem requests are fulfilled first in, first out
+ * @dev Every request call appends a new entry, cancellations never reorder the queue,
+ *      and the per-epoch limits partially fill the request at the head.
+ */
+describe("Request Queue", function () {
+  const DEPOSIT_AMOUNT = ethers.parseUnits("100", 6); // 100 USDC
+  const INITIAL_BALANCE = ethers.parseUnits("1000000", 6); // 1M USDC per user
+
+  before(async function () {
+    await resetNetwork();
+  });
+
+  async function deployFixture() {
+    const allSigners = await ethers.getSigners();
+    const owner = allSigners[0];
+    const strategist = allSigners[1];
+    const users = allSigners.slice(2, 6);
+
+    const deployed = await deployUpgradeableProtocol(owner);
+
+    const usdc = deployed.underlyingAsset;
+    const config = deployed.orionConfig;
+    const liquidityOrchestrator: LiquidityOrchestrator = deployed.liquidityOrchestrator;
+    const vaultFactory = deployed.transparentVaultFactory;
+
+    const vaultTx = await vaultFactory
+      .connect(owner)
+      .createVault(strategist.address, "Queue Vault", "QV", 0, 0, 0, ethers.ZeroAddress);
+    const receipt = await vaultTx.wait();
+    const vaultCreatedEvent = receipt?.logs.find((log) => {
+      try {
+        return vaultFactory.interface.parseLog(log)?.name === "OrionVaultCreated";
+      } catch {
+        return false;
+      }
+    });
+    const parsedLog = vaultCreatedEvent ? vaultFactory.interface.parseLog(vaultCreatedEvent) : null;
+    const vault = (await ethers.getContractAt(
+      "OrionTransparentVault",
+      parsedLog?.args[0],
+    )) as unknown as OrionTransparentVault;
+
+    for (const user of users) {
+      await usdc.mint(user.address, INITIAL_BALANCE);
+      await usdc.connect(user).approve(await vault.getAddress(), ethers.MaxUint256);
+    }
+
+    const loAddress = await liquidityOrchestrator.getAddress();
+    await networkHelpers.impersonateAccount(loAddress);
+    await networkHelpers.setBalance(loAddress, ethers.parseEther("1"));
+    const loSigner = await ethers.getSigner(loAddress);
+
+    return { owner, users, config, vault, loSigner };
+  }
+
+  describe("Ordering", function () {
+    it("Should queue a top-up behind later requests", async function () {
+      const { users, config, vault } = await networkHelpers.loadFixture(deployFixture);
+      const [alice, bob] = users;
+
+      await vault.connect(alice).requestDeposit(DEPOSIT_AMOUNT);
+      await vault.connect(bob).requestDeposit(DEPOSIT_AMOUNT * 2n);
+      await vault.connect(alice).requestDeposit(DEPOSIT_AMOUNT * 3n);
+
+      void expect(await vault.pendingDepositCount()).to.equal(3);
+      void expect(await vault.pendingDepositOf(alice.address)).to.equal(DEPOSIT_AMOUNT * 4n);
+      void expect(await vault.pendingDeposit(2)).to.equal(DEPOSIT_AMOUNT * 3n);
+      void expect(await vault.pendingDeposit(await config.maxFulfillBatchSize())).to.equal(DEPOSIT_AMOUNT * 6n);
+    });
+
+    it("Should cancel the most recent requests first without reordering the rest", async function () {
+      const { users, vault } = await networkHelpers.loadFixture(deployFixture);
+      const [alice, bob, carol] = users;
+
+      await vault.connect(alice).requestDeposit(DEPOSIT_AMOUNT);
+      await vault.connect(bob).requestDeposit(DEPOSIT_AMOUNT * 2n);
+      await vault.connect(alice).requestDeposit(DEPOSIT_AMOUNT * 3n);
+      await vault.connect(carol).requestDeposit(DEPOSIT_AMOUNT * 4n);
+
+      // Drops alice's second request entirely and nothing else.
+      await vault.connect(alice).cancelDepositRequest(DEPOSIT_AMOUNT * 3n);
+      void expect(await vault.pendingDepositCount()).to.equal(3);
+      void expect(await vault.pendingDeposit(2)).to.equal(DEPOSIT_AMOUNT * 3n);
+
+      // Removing bob from the middle keeps alice ahead of carol.
+      await vault.connect(bob).cancelDepositRequest(DEPOSIT_AMOUNT * 2n);
+      void expect(await vault.pendingDeposit(1)).to.equal(DEPOSIT_AMOUNT);
+      void expect(await vault.pendingDeposit(2)).to.equal(DEPOSIT_AMOUNT * 5n);
+      void expect(await vault.pendingDepositOf(bob.address)).to.equal(0);
+    });
+
+    it("Should list one redeem batch entry per request in queue order", async function () {
+      const { users, vault, loSigner } = await networkHelpers.loadFixture(deployFixture);
+      const [alice, bob] = users;
+
+      await vault.connect(alice).requestDeposit(DEPOSIT_AMOUNT);
+      await vault.connect(bob).requestDeposit(DEPOSIT_AMOUNT);
+      await vault.connect(loSigner).fulfillDeposit(0);
+      await claimDeposit(vault, alice);
+      await claimDeposit(vault, bob);
+
+      const shares = await vault.balanceOf(alice.address);
+      await vault.connect(alice).approve(await vault.getAddress(), ethers.MaxUint256);
+      await vault.connect(bob).approve(await vault.getAddress(), ethers.MaxUint256);
+      await vault.connect(alice).requestRedeem(shares / 2n);
+      await vault.connect(bob).requestRedeem(shares);
+      await vault.connect(alice).requestRedeem(shares / 2n);
+
+      const [batchUsers, batchShares] = await vault.pendingRedeemBatch(10);
+      void expect(batchUsers).to.deep.equal([alice.address, bob.address, alice.address]);
+      void expect(batchShares).to.deep.equal([shares / 2n, shares, shares / 2n]);
+    });
+  });
+
+  describe("Per-epoch limits", function () {
+    it("Should only let the manager set the limits while idle", async function () {
+      const { owner, users, vault } = await networkHelpers.loadFixture(deployFixture);
+
+      await expect(vault.connect(users[0]).setFulfillLimits(1, 1)).to.be.revertedWithCustomError(
+        vault,
+        "NotAuthorized",
+      );
+      await expect(vault.connect(owner).setFulfillLimits(DEPOSIT_AMOUNT, 0))
+        .to.emit(vault, "FulfillLimitsUpdated")
+        .withArgs(DEPOSIT_AMOUNT, 0);
+      void expect(await vault.maxDepositPerEpoch()).to.equal(DEPOSIT_AMOUNT);
+    });
+
+    it("Should partially fill the head request and keep it first", async function () {
+      const { owner, users, config, vault, loSigner } = await networkHelpers.loadFixture(deployFixture);
+      const [alice, bob] = users;
+      const batchSize = await config.maxFulfillBatchSize();
+
+      await vault.connect(owner).setFulfillLimits(DEPOSIT_AMOUNT * 2n, 0);
+      await vault.connect(alice).requestDeposit(DEPOSIT_AMOUNT * 5n);
+      await vault.connect(bob).requestDeposit(DEPOSIT_AMOUNT);
+
+      void expect(await vault.pendingDeposit(batchSize)).to.equal(DEPOSIT_AMOUNT * 2n);
+      await vault.connect(loSigner).fulfillDeposit(0);
+      void expect(await vault.claimableDepositRequest(0, alice.address)).to.equal(DEPOSIT_AMOUNT * 2n);
+      void expect(await vault.claimableDepositRequest(0, bob.address)).to.equal(0);
+      void expect(await vault.pendingDepositOf(alice.address)).to.equal(DEPOSIT_AMOUNT * 3n);
+      void expect(await vault.pendingDepositCount()).to.equal(2);
+
+      await vault.connect(loSigner).fulfillDeposit(DEPOSIT_AMOUNT * 2n);
+      void expect(await vault.pendingDepositOf(alice.address)).to.equal(DEPOSIT_AMOUNT);
+
+      // Alice's remainder and bob's request fit in the third epoch.
+      void expect(await vault.pendingDeposit(batchSize)).to.equal(DEPOSIT_AMOUNT * 2n);
+      await vault.connect(loSigner).fulfillDeposit(DEPOSIT_AMOUNT * 4n);
+      void expect(await vault.pendingDepositCount()).to.equal(0);
+      void expect(await vault.claimableDepositRequest(0, bob.address)).to.equal(DEPOSIT_AMOUNT);
+    });
+  });
+
+  describe("Upgrade from per-user maps", function () {
+    // Storage slot of the deposit requests map the queues replaced (EnumerableMap.AddressToUintMap)
+    const LEGACY_DEPOSIT_REQUESTS_SLOT = 6n;
+
+    async function setLegacyDepositRequest(vault: OrionTransparentVault, index: bigint, user: string, amount: bigint) {
+      const coder = ethers.AbiCoder.defaultAbiCoder();
+      const vaultAddress = await vault.getAddress();
+      const slot = LEGACY_DEPOSIT_REQUESTS_SLOT;
+      const keysSlot = BigInt(ethers.keccak256(coder.encode(["uint256"], [slot])));
+
+      await networkHelpers.setStorageAt(vaultAddress, slot, index + 1n);
+      await networkHelpers.setStorageAt(vaultAddress, keysSlot + index, user);
+      await networkHelpers.setStorageAt(
+        vaultAddress,
+        ethers.keccak256(coder.encode(["address", "uint256"], [user, slot + 1n])),
+        index + 1n,
+      );
+      await networkHelpers.setStorageAt(
+        vaultAddress,
+        ethers.keccak256(coder.encode(["address", "uint256"], [user, slot + 2n])),
+        amount,
+      );
+    }
+
+    it("Should move requests made before the upgrade ahead of new ones", async function () {
+      const { users, config, vault, loSigner } = await networkHelpers.loadFixture(deployFixture);
+      const [alice, bob, carol] = users;
+      const batchSize = await config.maxFulfillBatchSize();
+
+      await setLegacyDepositRequest(vault, 0n, alice.address, DEPOSIT_AMOUNT);
+      await setLegacyDepositRequest(vault, 1n, bob.address, DEPOSIT_AMOUNT * 2n);
+
+      await expect(vault.connect(carol).requestDeposit(DEPOSIT_AMOUNT)).to.be.revertedWithCustomError(
+        vault,
+        "RequestQueuesNotMigrated",
+      );
+
+      // Long maps can be moved over several calls.
+      await vault.connect(carol).migrateRequestQueues(1);
+      void expect(await vault.pendingDepositCount()).to.equal(1);
+      await expect(vault.connect(carol).requestDeposit(DEPOSIT_AMOUNT)).to.be.revertedWithCustomError(
+        vault,
+        "RequestQueuesNotMigrated",
+      );
+      await vault.connect(carol).migrateRequestQueues(1);
+
+      await vault.connect(carol).requestDeposit(DEPOSIT_AMOUNT);
+      void expect(await vault.pendingDepositOf(alice.address)).to.equal(DEPOSIT_AMOUNT);
+      void expect(await vault.pendingDepositOf(bob.address)).to.equal(DEPOSIT_AMOUNT * 2n);
+      void expect(await vault.pendingDeposit(2)).to.equal(DEPOSIT_AMOUNT * 3n);
+      void expect(await vault.pendingDeposit(batchSize)).to.equal(DEPOSIT_AMOUNT * 4n);
+
+      await vault.connect(loSigner).fulfillDeposit(0);
+      for (const user of [alice, bob, carol]) {
+        void expect(await vault.pendingDepositOf(user.address)).to.equal(0);
+      }
+      void expect(await vault.claimableDepositRequest(0, bob.address)).to.equal(DEPOSIT_AMOUNT * 2n);
+    });
+  });
+});
@@ -0,0 +1,86 @@
+import { expect } from "chai";
+import { ethers } from "./helpers/hh";
+
+import type { RequestQueueLibTest } from "../typechain-types/contracts/test";
+import { resetNetwork } from "./helpers/resetNetwork";
+
+describe("RequestQueueLib", function () {
+  let queue: RequestQueueLibTest;
+
+  before(async function () {
+    await resetNetwork();
+  });
+
+  beforeEach(async function () {
+    const RequestQueueLibTestFactory = await ethers.getContractFactory("RequestQueueLibTest");
+    const deployedContract = await RequestQueueLibTestFactory.deploy();
+    await deployedContract.waitForDeployment();
+    queue = deployedContract as unknown as RequestQueueLibTest;
+  });
+
+  describe("cancel", function () {
+    it("should skip requests removed out of order", async function () {
+      const [alice, bob] = await ethers.getSigners();
+
+      // Request ids 1-4: alice, bob, alice, alice.
+      await queue.push(alice.address, 100);
+      await queue.push(bob.address, 200);
+      await queue.push(alice.address, 300);
+      await queue.push(alice.address, 400);
+      // e.g. expired and refunded while alice's other requests stay queued
+      await queue.remove(3);
+
+      await queue.cancel(alice.address, 450);
+
+      const [users, amounts] = await queue.queued();
+      expect(users).to.deep.equal([alice.address, bob.address]);
+      expect(amounts).to.deep.equal([50n, 200n]);
+      expect(await queue.length()).to.equal(2);
+      expect(await queue.pendingOf(alice.address)).to.equal(50);
+      expect(await queue.pendingOf(bob.address)).to.equal(200);
+    });
+
+    it("should keep the queue consistent when cancelling everything after an out of order removal", async function () {
+      const [alice, bob] = await ethers.getSigners();
+
+      await queue.push(alice.address, 100);
+      await queue.push(alice.address, 300);
+      await queue.push(bob.address, 200);
+      await queue.push(alice.address, 400);
+      await queue.remove(2);
+
+      await queue.cancel(alice.address, 500);
+      await queue.push(alice.address, 50);
+
+      const [users, amounts] = await queue.queued();
+      expect(users).to.deep.equal([bob.address, alice.address]);
+      expect(amounts).to.deep.equal([200n, 50n]);
+      expect(await queue.length()).to.equal(2);
+    });
+
+    it("should only visit the user's queued requests", async function () {
+      const [alice, bob] = await ethers.getSigners();
+
+      await queue.push(alice.address, 100);
+      await queue.push(bob.address, 100);
+      const baseline = await queue.cancel.estimateGas(alice.address, 100);
+
+      // Request ids 3-42: many of alice's requests, fulfilled out of order behind her queued one.
+      for (let i = 0; i < 40; ++i) {
+        await queue.push(alice.address, 100);
+      }
+      for (let id = 3; id <= 42; ++id) {
+        await queue.remove(id);
+      }
+
+      const gas = await queue.cancel.estimateGas(alice.address, 100);
+      expect(gas).to.be.lessThan((baseline * 11n) / 10n);
+
+      await queue.cancel(alice.address, 100);
+      const [users, amounts] = await queue.queued();
+      expect(users).to.deep.equal([bob.address]);
+      expect(amounts).to.deep.equal([100n]);
+      expect(await queue.pendingOf(alice.address)).to.equal(0);
+    });
+  });
+});
@@ -0,0 +1,269 @@
+import { expect } from "chai";
+import { ethers, networkHelpers } from "./helpers/hh";
+import type {
+  LiquidityOrchestrator,
+  MockSanctionsList,
+  OrionTransparentVault,
+  SanctionsAccessControl,
+} from "../typechain-types";
+import { deployUpgradeableProtocol } from "./helpers/deployUpgradeable";
+import { resetNetwork } from "./helpers/resetNetwork";
+
+/**
+ * @title Sanctions Tests
+ * @notice Sanctions-oracle access control and freezing of pending requests
+ * @dev Requests made before an address was flagged are frozen when they come up for fulfillment,
+ *      and the manager can freeze any controller's requests by hand.
+ */
+describe("Sanctions", function () {
+  const DEPOSIT_AMOUNT = ethers.parseUnits("100", 6); // 100 USDC
+  const INITIAL_BALANCE = ethers.parseUnits("1000000", 6); // 1M USDC per user
+
+  before(async function () {
+    await resetNetwork();
+  });
+
+  async function deployFixture() {
+    const allSigners = await ethers.getSigners();
+    const owner = allSigners[0];
+    const strategist = allSigners[1];
+    const [alice, bob] = allSigners.slice(2, 4);
+
+    const deployed = await deployUpgradeableProtocol(owner);
+
+    const usdc = deployed.underlyingAsset;
+    const liquidityOrchestrator: LiquidityOrchestrator = deployed.liquidityOrchestrator;
+    const vaultFactory = deployed.transparentVaultFactory;
+
+    const MockSanctionsListFactory = await ethers.getContractFactory("MockSanctionsList");
+    const sanctionsList = (await MockSanctionsListFactory.deploy()) as unknown as MockSanctionsList;
+    const SanctionsAccessControlFactory = await ethers.getContractFactory("SanctionsAccessControl");
+    const accessControl = (await SanctionsAccessControlFactory.deploy(
+      await sanctionsList.getAddress(),
+    )) as unknown as SanctionsAccessControl;
+
+    const vaultTx = await vaultFactory
+      .connect(owner)
+      .createVault(strategist.address, "Screened Vault", "SV", 0, 0, 0, await accessControl.getAddress());
+    const receipt = await vaultTx.wait();
+    const vaultCreatedEvent = receipt?.logs.find((log) => {
+      try {
+        return vaultFactory.interface.parseLog(log)?.name === "OrionVaultCreated";
+      } catch {
+        return false;
+      }
+    });
+    const parsedLog = vaultCreatedEvent ? vaultFactory.interface.parseLog(vaultCreatedEvent) : null;
+    const vault = (await ethers.getContractAt(
+      "OrionTransparentVault",
+      parsedLog?.args[0],
+    )) as unknown as OrionTransparentVault;
+
+    for (const user of [alice, bob]) {
+      await usdc.mint(user.address, INITIAL_BALANCE);
+      await usdc.connect(user).approve(await vault.getAddress(), ethers.MaxUint256);
+    }
+
+    const loAddress = await liquidityOrchestrator.getAddress();
+    await networkHelpers.impersonateAccount(loAddress);
+    await networkHelpers.setBalance(loAddress, ethers.parseEther("1"));
+    const loSigner = await ethers.getSigner(loAddress);
+
+    return { owner, alice, bob, sanctionsList, vault, loSigner };
+  }
+
+  async function depositedFixture() {
+    const fixture = await deployFixture();
+    const { alice, bob, vault, loSigner } = fixture;
+    for (const user of [alice, bob]) {
+      await vault.connect(user).requestDeposit(DEPOSIT_AMOUNT);
+    }
+    await vault.connect(loSigner).fulfillDeposit(0);
+    await vault.connect(loSigner).updateVaultState([], [], DEPOSIT_AMOUNT * 2n);
+    for (const user of [alice, bob]) {
+      await vault.connect(user)["deposit(uint256,address)"](DEPOSIT_AMOUNT, user.address);
+    }
+    return fixture;
+  }
+
+  describe("SanctionsAccessControl", function () {
+    it("Should reject zero address sanctions list", async function () {
+      const SanctionsAccessControlFactory = await ethers.getContractFactory("SanctionsAccessControl");
+      await expect(SanctionsAccessControlFactory.deploy(ethers.ZeroAddress)).to.be.revertedWithCustomError(
+        SanctionsAccessControlFactory,
+        "ZeroAddress",
+      );
+    });
+
+    it("Should block deposit requests from sanctioned addresses", async function () {
+      const { alice, bob, sanctionsList, vault } = await networkHelpers.loadFixture(deployFixture);
+      await sanctionsList.setSanctioned(alice.address, true);
+
+      await expect(vault.connect(alice).requestDeposit(DEPOSIT_AMOUNT)).to.be.revertedWithCustomError(
+        vault,
+        "DepositNotAllowed",
+      );
+      await vault.connect(bob).requestDeposit(DEPOSIT_AMOUNT);
+      void expect(await vault.pendingDepositRequest(0, bob.address)).to.equal(DEPOSIT_AMOUNT);
+    });
+
+    it("Should block redemptions and transfers involving sanctioned addresses", async function () {
+      const { alice, bob, sanctionsList, vault } = await networkHelpers.loadFixture(depositedFixture);
+      const shares = await vault.balanceOf(alice.address);
+      await sanctionsList.setSanctioned(alice.address, true);
+
+      await expect(vault.connect(alice).requestRedeem(shares)).to.be.revertedWithCustomError(vault, "RedeemNotAllowed");
+      await expect(vault.connect(alice).transfer(bob.address, shares)).to.be.revertedWithCustomError(
+        vault,
+        "TransferNotAllowed",
+      );
+      await expect(vault.connect(bob).transfer(alice.address, 1n)).to.be.revertedWithCustomError(
+        vault,
+        "TransferNotAllowed",
+      );
+    });
+  });
+
+  describe("Frozen Requests", function () {
+    it("Should only let the manager freeze requests", async function () {
+      const { alice, bob, vault } = await networkHelpers.loadFixture(deployFixture);
+      await vault.connect(alice).requestDeposit(DEPOSIT_AMOUNT);
+
+      await expect(vault.connect(bob).freezeRequests(alice.address)).to.be.revertedWithCustomError(
+        vault,
+        "NotAuthorized",
+      );
+      await expect(vault.connect(bob).unfreezeRequests(alice.address)).to.be.revertedWithCustomError(
+        vault,
+        "NotAuthorized",
+      );
+    });
+
+    it("Should revert when there is nothing to freeze or unfreeze", async function () {
+      const { owner, alice, vault } = await networkHelpers.loadFixture(deployFixture);
+
+      await expect(vault.connect(owner).freezeRequests(alice.address)).to.be.revertedWithCustomError(
+        vault,
+        "InsufficientAmount",
+      );
+      await expect(vault.connect(owner).unfreezeRequests(alice.address)).to.be.revertedWithCustomError(
+        vault,
+        "InsufficientAmount",
+      );
+    });
+
+    it("Should skip frozen deposits when fulfilling", async function () {
+      const { owner, alice, bob, sanctionsList, vault, loSigner } = await networkHelpers.loadFixture(deployFixture);
+      for (const user of [alice, bob]) {
+        await vault.connect(user).requestDeposit(DEPOSIT_AMOUNT);
+      }
+      await sanctionsList.setSanctioned(alice.address, true);
+
+      await expect(vault.connect(owner).freezeRequests(alice.address))
+        .to.emit(vault, "RequestsFrozen")
+        .withArgs(alice.address, DEPOSIT_AMOUNT, 0);
+      void expect(await vault.pendingDepositRequest(0, alice.address)).to.equal(0);
+      void expect(await vault.pendingDeposit(10)).to.equal(DEPOSIT_AMOUNT);
+      void expect((await vault.frozenRequests(alice.address)).assets).to.equal(DEPOSIT_AMOUNT);
+
+      // Frozen requests cannot be cancelled by their controller.
+      await expect(vault.connect(alice).cancelDepositRequest(DEPOSIT_AMOUNT)).to.be.revertedWithCustomError(
+        vault,
+        "InsufficientAmount",
+      );
+
+      await vault.connect(loSigner).fulfillDeposit(0);
+      void expect(await vault.claimableDepositRequest(0, alice.address)).to.equal(0);
+      void expect(await vault.claimableDepositRequest(0, bob.address)).to.equal(DEPOSIT_AMOUNT);
+      void expect((await vault.frozenRequests(alice.address)).assets).to.equal(DEPOSIT_AMOUNT);
+    });
+
+    it("Should skip frozen redemptions and keep their shares in escrow", async function () {
+      const { owner, alice, bob, vault, loSigner } = await networkHelpers.loadFixture(depositedFixture);
+      const vaultAddress = await vault.getAddress();
+      const shares = await vault.balanceOf(alice.address);
+      for (const user of [alice, bob]) {
+        await vault.connect(user).requestRedeem(shares);
+      }
+
+      await expect(vault.connect(owner).freezeRequests(alice.address))
+        .to.emit(vault, "RequestsFrozen")
+        .withArgs(alice.address, 0, shares);
+      void expect(await vault.pendingRedeem(10)).to.equal(shares);
+
+      await vault.connect(loSigner).fulfillRedeem(DEPOSIT_AMOUNT);
+      void expect(await vault.claimableRedeemRequest(0, alice.address)).to.equal(0);
+      void expect(await vault.claimableRedeemRequest(0, bob.address)).to.equal(shares);
+      void expect(await vault.balanceOf(vaultAddress)).to.equal(shares);
+    });
+
+    it("Should queue unfrozen requests again", async function () {
+      const { owner, alice, bob, vault } = await networkHelpers.loadFixture(deployFixture);
+      for (const user of [alice, bob]) {
+        await vault.connect(user).requestDeposit(DEPOSIT_AMOUNT);
+      }
+      await vault.connect(owner).freezeRequests(alice.address);
+
+      await expect(vault.connect(owner).unfreezeRequests(alice.address))
+        .to.emit(vault, "RequestsUnfrozen")
+        .withArgs(alice.address, DEPOSIT_AMOUNT, 0);
+      void expect(await vault.pendingDepositRequest(0, alice.address)).to.equal(DEPOSIT_AMOUNT);
+      void expect(await vault.pendingDeposit(10)).to.equal(DEPOSIT_AMOUNT * 2n);
+      void expect((await vault.frozenRequests(alice.address)).assets).to.equal(0);
+    });
+  });
+
+  describe("Blocked Controllers", function () {
+    it("Should freeze deposits of sanctioned controllers at fulfillment", async function () {
+      const { alice, bob, sanctionsList, vault, loSigner } = await networkHelpers.loadFixture(deployFixture);
+      for (const user of [alice, bob]) {
+        await vault.connect(user).requestDeposit(DEPOSIT_AMOUNT);
+      }
+      await sanctionsList.setSanctioned(alice.address, true);
+
+      // The committed amounts already leave alice out, without the manager stepping in.
+      void expect(await vault.pendingDeposit(10)).to.equal(DEPOSIT_AMOUNT);
+
+      await expect(vault.connect(loSigner).fulfillDeposit(0))
+        .to.emit(vault, "RequestsFrozen")
+        .withArgs(alice.address, DEPOSIT_AMOUNT, 0);
+      void expect(await vault.pendingDepositCount()).to.equal(0);
+      void expect(await vault.claimableDepositRequest(0, alice.address)).to.equal(0);
+      void expect(await vault.claimableDepositRequest(0, bob.address)).to.equal(DEPOSIT_AMOUNT);
+      void expect((await vault.frozenRequests(alice.address)).assets).to.equal(DEPOSIT_AMOUNT);
+    });
+
+    it("Should freeze redemptions of sanctioned controllers at fulfillment", async function () {
+      const { alice, bob, sanctionsList, vault, loSigner } = await networkHelpers.loadFixture(depositedFixture);
+      const vaultAddress = await vault.getAddress();
+      const shares = await vault.balanceOf(alice.address);
+      for (const user of [alice, bob]) {
+        await vault.connect(user).requestRedeem(shares);
+      }
+      await sanctionsList.setSanctioned(alice.address, true);
+
+      const [users] = await vault.pendingRedeemBatch(10);
+      void expect(users).to.deep.equal([bob.address]);
+
+      await expect(vault.connect(loSigner).fulfillRedeem(DEPOSIT_AMOUNT))
+        .to.emit(vault, "RequestsFrozen")
+        .withArgs(alice.address, 0, shares);
+      void expect(await vault.pendingRedeemCount()).to.equal(0);
+      void expect(await vault.claimableRedeemRequest(0, bob.address)).to.equal(shares);
+      void expect((await vault.frozenRequests(alice.address)).shares).to.equal(shares);
+      void expect(await vault.balanceOf(vaultAddress)).to.equal(shares);
+    });
+
+    it("Should fulfill unfrozen requests once the controller is no longer blocked", async function () {
+      const { owner, alice, sanctionsList, vault, loSigner } = await networkHelpers.loadFixture(deployFixture);
+      await vault.connect(alice).requestDeposit(DEPOSIT_AMOUNT);
+      await sanctionsList.setSanctioned(alice.address, true);
+      await vault.connect(loSigner).fulfillDeposit(0);
+
+      await sanctionsList.setSanctioned(alice.address, false);
+      await vault.connect(owner).unfreezeRequests(alice.address);
+      await vault.connect(loSigner).fulfillDeposit(0);
+      void expect(await vault.claimableDepositRequest(0, alice.address)).to.equal(DEPOSIT_AMOUNT);
+    });
+  });
+});
@@ -0,0 +1,30 @@
+import type { SignerWithAddress } from "@nomicfoundation/hardhat-ethers/signers";
+import type { OrionTransparentVault } from "../../typechain-types";
+
+type Vault = OrionTransparentVault;
+
+/**
+ * Claims every share of the user's fulfilled deposit requests (ERC-7540 `deposit`).
+ * Returns the shares received.
+ */
+export async function claimDeposit(vault: Vault, user: SignerWithAddress): Promise<bigint> {
+  const shares = await vault.maxMint(user.address);
+  const assets = await vault.maxDeposit(user.address);
+  if (assets > 0n) {
+    await vault.connect(user)["deposit(uint256,address)"](assets, user.address);
+  }
+  return shares;
+}
+
+/**
+ * Claims every asset of the user's fulfilled redemption requests (ERC-7540 `redeem`).
+ * Returns the assets received.
+ */
+export async function claimRedeem(vault: Vault, user: SignerWithAddress): Promise<bigint> {
+  const shares = await vault.maxRedeem(user.address);
+  const assets = await vault.maxWithdraw(user.address);
+  if (shares > 0n) {
+    await vault.connect(user).redeem(shares, user.address, user.address);
+  }
+  return assets;
+}
@@ -0,0 +1,124 @@
+import { execFileSync, spawnSync } from "node:child_process";
+import path from "node:path";
+import { fileURLToPath } from "node:url";
+import { ethers } from "./hh";
+import type { LiquidityOrchestrator, OrionConfig } from "../typechain-types";
+
+/**
+ * `performUpkeep` payload produced by running the state orchestrator natively.
+ * Only accepted by `MockSP1Verifier` (the proof is empty).
+ */
+export interface MockUpkeepPayload {
+  inputCommitment: string;
+  outputCommitment: string;
+  publicValues: string;
+  proofBytes: string;
+  statesBytes: string;
+}
+
+const REPO_ROOT = path.resolve(path.dirname(fileURLToPath(import.meta.url)), "../..");
+
+/**
+ * Whether the Rust toolchain needed by {@link mockProveEpoch} is available.
+ */
+export function hasCargo(): boolean {
+  return spawnSync("cargo", ["--version"]).status === 0;
+}
+
+/**
+ * Reads the epoch inputs behind the current `epochStateCommitment`, in the JSON layout of
+ * `orion_state_orchestrator::EpochInputs`.
+ *
+ * Call this during SellingLeg or BuyingLeg: vault state is rewritten during ProcessVaultOperations,
+ * so reuse the payload computed earlier for that phase.
+ */
+export async function readEpochInputs(liquidityOrchestrator: LiquidityOrchestrator, orionConfig: OrionConfig) {
+  const epoch = await liquidityOrchestrator.getEpochState();
+  const maxFulfillBatchSize = await orionConfig.maxFulfillBatchSize();
+  const whitelistedAssets = await orionConfig.getAllWhitelistedAssets();
+  const underlying = await ethers.getContractAt("MockUnderlyingAsset", await liquidityOrchestrator.underlyingAsset());
+
+  const protocol = {
+    maxFulfillBatchSize: maxFulfillBatchSize.toString(),
+    targetBufferRatio: (await liquidityOrchestrator.targetBufferRatio()).toString(),
+    priceAdapterDecimals: Number(await orionConfig.priceAdapterDecimals()),
+    strategistIntentDecimals: Number(await orionConfig.strategistIntentDecimals()),
+    epochDuration: Number(await liquidityOrchestrator.epochDuration()),
+    whitelistedAssets: [...whitelistedAssets],
+    tokenDecimals: (await orionConfig.getAllTokenDecimals()).map(Number),
+    riskFreeRate: Number(await orionConfig.riskFreeRate()),
+    decommissioningAssets: [...(await orionConfig.decommissioningAssets())],
+    failedEpochTokens: [...(await liquidityOrchestrator.getFailedEpochTokens())],
+    initialEpochBufferAmount: (await liquidityOrchestrator.initialEpochBufferAmount()).toString(),
+    buyingLegEntryBuffer: (await liquidityOrchestrator.buyingLegEntryBuffer()).toString(),
+    bufferAmount: (await liquidityOrchestrator.bufferAmount()).toString(),
+    underlyingBalance: (await underlying.balanceOf(await liquidityOrchestrator.getAddress())).toString(),
+    epochStartTime: (await liquidityOrchestrator.epochStartTime()).toString(),
+  };
+  const assetPrices = (await liquidityOrchestrator.getAssetPrices([...whitelistedAssets])).map(String);
+
+  const vaults = [];
+  const redeemBatches = [];
+  for (let i = 0; i < epoch.vaultsEpoch.length; i++) {
+    const vault = await ethers.getContractAt("OrionTransparentVault", epoch.vaultsEpoch[i]);
+    const feeModel = epoch.vaultFeeModels[i];
+    const schedule = epoch.vaultManagementFeeSchedules[i];
+    const settlement = epoch.vaultFeeSettlements[i];
+    const hurdle = epoch.vaultHurdles[i];
+    const [portfolioTokens, portfolioShares] = await vault.getPortfolio();
+    const [intentTokens, intentWeights] = await vault.getIntent();
+    const [users, shares] = await vault.pendingRedeemBatch(maxFulfillBatchSize);
+
+    vaults.push({
+      address: epoch.vaultsEpoch[i],
+      feeModel: {
+        feeType: Number(feeModel.feeType),
+        performanceFee: Number(feeModel.performanceFee),
+        managementFee: Number(feeModel.managementFee),
+        managementFeeBreakpoints: schedule.breakpoints.map((breakpoint) => breakpoint.toString()),
+        managementFeeTierRates: schedule.tierRates.map(Number),
+        minimumManagementFee: schedule.minimumFee.toString(),
+        crystallizationPeriod: Number(settlement.crystallizationPeriod),
+        mintFeeShares: settlement.mintFeeShares,
+        lastCrystallization: Number(settlement.lastCrystallization),
+        highWaterMark: feeModel.highWaterMark.toString(),
+        crystallizationPrice: settlement.crystallizationPrice.toString(),
+        hurdleType: Number(hurdle.hurdleType),
+        hurdleRate: Number(hurdle.rate),
+        hurdleBenchmark: hurdle.benchmark,
+        hurdleBenchmarkPrice: hurdle.benchmarkPrice.toString(),
+      },
+      pendingRedeem: (await vault.pendingRedeem(maxFulfillBatchSize)).toString(),
+      pendingDeposit: (await vault.pendingDeposit(maxFulfillBatchSize)).toString(),
+      redeemBatchHash: ethers.keccak256(
+        ethers.AbiCoder.defaultAbiCoder().encode(["address[]", "uint256[]"], [users, shares]),
+      ),
+      totalSupply: (await vault.totalSupply()).toString(),
+      totalAssets: (await vault.totalAssets()).toString(),
+      vFeeCoefficient: Number(epoch.vaultVFeeCoefficients[i]),
+      rsFeeCoefficient: Number(epoch.vaultRsFeeCoefficients[i]),
+      portfolio: { tokens: [...portfolioTokens], shares: portfolioShares.map(String) },
+      intent: { tokens: [...intentTokens], weights: intentWeights.map(Number) },
+    });
+    redeemBatches.push({ users: [...users], shares: shares.map(String) });
+  }
+
+  return { snapshot: { protocol, assetPrices, vaults }, redeemBatches };
+}
+
+/**
+ * Runs the reference state transition natively (`cargo run -p orion-state-orchestrator`) on the
+ * current epoch inputs and returns the payload for `performUpkeep`.
+ */
+export async function mockProveEpoch(
+  liquidityOrchestrator: LiquidityOrchestrator,
+  orionConfig: OrionConfig,
+): Promise<MockUpkeepPayload> {
+  const inputs = await readEpochInputs(liquidityOrchestrator, orionConfig);
+  const output = execFileSync(
+    "cargo",
+    ["run", "--quiet", "--package", "orion-state-orchestrator", "--bin", "orion-state-orchestrator", "--", "-"],
+    { cwd: REPO_ROOT, input: JSON.stringify(inputs), encoding: "utf8" },
+  );
+  return JSON.parse(output) as MockUpkeepPayload;
+}
@@ -0,0 +1,160 @@
+import type { SignerWithAddress } from "@nomicfoundation/hardhat-ethers/signers";
+import { expect } from "chai";
+import { ethers, networkHelpers } from "../helpers/hh";
+
+import type {
+  MockUnderlyingAsset,
+  MockERC4626Asset,
+  ERC4626ExecutionAdapter,
+  OrionConfig,
+  LiquidityOrchestrator,
+  OrionTransparentVault,
+  MockSP1Verifier,
+} from "../typechain-types";
+import { deployUpgradeableProtocol } from "../helpers/deployUpgradeable";
+import { resetNetwork } from "../helpers/resetNetwork";
+import { hasCargo, mockProveEpoch, type MockUpkeepPayload } from "../helpers/mockProver";
+
+/**
+ * Full epoch driven by the natively executed state orchestrator and MockSP1Verifier.
+ * Requires the Rust toolchain; skipped otherwise.
+ */
+describe("Mock prover epoch", function () {
+  const DEPOSIT_AMOUNT = ethers.parseUnits("100", 6);
+
+  let orionConfig: OrionConfig;
+  let liquidityOrchestrator: LiquidityOrchestrator;
+  let underlyingAsset: MockUnderlyingAsset;
+  let mockAsset: MockERC4626Asset;
+  let mockVerifier: MockSP1Verifier;
+  let vault: OrionTransparentVault;
+
+  let owner: SignerWithAddress;
+  let strategist: SignerWithAddress;
+  let automationRegistry: SignerWithAddress;
+  let user: SignerWithAddress;
+
+  let payload: MockUpkeepPayload;
+
+  before(async function () {
+    if (!hasCargo()) this.skip();
+    this.timeout(300_000);
+
+    await resetNetwork();
+
+    [owner, strategist, automationRegistry, user] = await ethers.getSigners();
+
+    const MockUnderlyingAssetFactory = await ethers.getContractFactory("MockUnderlyingAsset");
+    underlyingAsset = (await MockUnderlyingAssetFactory.deploy(6)) as unknown as MockUnderlyingAsset;
+    await underlyingAsset.waitForDeployment();
+
+    const MockERC4626AssetFactory = await ethers.getContractFactory("MockERC4626Asset");
+    mockAsset = (await MockERC4626AssetFactory.deploy(
+      await underlyingAsset.getAddress(),
+      "Mock Asset",
+      "MA",
+    )) as unknown as MockERC4626Asset;
+    await mockAsset.waitForDeployment();
+
+    const deployed = await deployUpgradeableProtocol(owner, underlyingAsset, automationRegistry);
+    orionConfig = deployed.orionConfig;
+    liquidityOrchestrator = deployed.liquidityOrchestrator;
+
+    const MockSP1VerifierFactory = await ethers.getContractFactory("MockSP1Verifier");
+    mockVerifier = (await MockSP1VerifierFactory.deploy(
+      await liquidityOrchestrator.vKey(),
+    )) as unknown as MockSP1Verifier;
+    await mockVerifier.waitForDeployment();
+    await liquidityOrchestrator.updateVerifier(await mockVerifier.getAddress());
+
+    const MockPriceAdapterFactory = await ethers.getContractFactory("MockPriceAdapter");
+    const priceAdapter = await MockPriceAdapterFactory.deploy();
+    await priceAdapter.waitForDeployment();
+
+    const ERC4626ExecutionAdapterFactory = await ethers.getContractFactory("ERC4626ExecutionAdapter");
+    const executionAdapter = (await ERC4626ExecutionAdapterFactory.deploy(
+      await orionConfig.getAddress(),
+    )) as unknown as ERC4626ExecutionAdapter;
+    await executionAdapter.waitForDeployment();
+
+    await orionConfig.addWhitelistedAsset(
+      await mockAsset.getAddress(),
+      await priceAdapter.getAddress(),
+      await executionAdapter.getAddress(),
+    );
+
+    const vaultTx = await deployed.transparentVaultFactory
+      .connect(owner)
+      .createVault(strategist.address, "Mock Prover Vault", "MPV", 0, 0, 0, ethers.ZeroAddress);
+    const vaultReceipt = await vaultTx.wait();
+    const vaultEvent = vaultReceipt?.logs.find((log) => {
+      try {
+        return deployed.transparentVaultFactory.interface.parseLog(log)?.name === "OrionVaultCreated";
+      } catch {
+        return false;
+      }
+    });
+    const vaultAddress = deployed.transparentVaultFactory.interface.parseLog(vaultEvent!)?.args[0];
+    vault = (await ethers.getContractAt("OrionTransparentVault", vaultAddress)) as unknown as OrionTransparentVault;
+
+    await vault.connect(strategist).submitIntent([
+      { token: await mockAsset.getAddress(), weight: 600000000 },
+      { token: await underlyingAsset.getAddress(), weight: 400000000 },
+    ]);
+
+    await underlyingAsset.mint(user.address, DEPOSIT_AMOUNT);
+    await underlyingAsset.connect(user).approve(await vault.getAddress(), DEPOSIT_AMOUNT);
+    await vault.connect(user).requestDeposit(DEPOSIT_AMOUNT);
+
+    await networkHelpers.time.increase((await liquidityOrchestrator.epochDuration()) + 1n);
+    while ((await liquidityOrchestrator.currentPhase()) < 2n) {
+      await liquidityOrchestrator.connect(automationRegistry).performUpkeep("0x", "0x", "0x");
+    }
+    expect(await liquidityOrchestrator.currentPhase()).to.equal(2); // SellingLeg
+
+    payload = await mockProveEpoch(liquidityOrchestrator, orionConfig);
+  });
+
+  it("matches the on-chain epoch state commitment", async function () {
+    const epoch = await liquidityOrchestrator.getEpochState();
+    expect(payload.inputCommitment).to.equal(epoch.epochStateCommitment);
+    expect(payload.proofBytes).to.equal("0x");
+  });
+
+  it("rejects tampered states", async function () {
+    const tampered = payload.statesBytes.slice(0, -2) + (payload.statesBytes.endsWith("00") ? "01" : "00");
+    await expect(
+      liquidityOrchestrator.connect(automationRegistry).performUpkeep(payload.publicValues, payload.proofBytes, tampered),
+    ).to.be.revertedWithCustomError(liquidityOrchestrator, "CommitmentMismatch");
+  });
+
+  it("rejects the payload for another program key", async function () {
+    await mockVerifier.setAcceptedVKey(ethers.ZeroHash);
+    await expect(
+      liquidityOrchestrator
+        .connect(automationRegistry)
+        .performUpkeep(payload.publicValues, payload.proofBytes, payload.statesBytes),
+    ).to.be.revertedWith("MockSP1Verifier: unexpected vKey");
+    await mockVerifier.setAcceptedVKey(await liquidityOrchestrator.vKey());
+  });
+
+  it("completes the epoch with the natively executed transition", async function () {
+    while ((await liquidityOrchestrator.currentPhase()) !== 0n) {
+      await liquidityOrchestrator
+        .connect(automationRegistry)
+        .performUpkeep(payload.publicValues, payload.proofBytes, payload.statesBytes);
+    }
+
+    expect(await liquidityOrchestrator.epochCounter()).to.equal(1);
+    void expect(await orionConfig.isSystemIdle()).to.be.true;
+    expect(await vault.pendingDeposit(await orionConfig.maxFulfillBatchSize())).to.equal(0);
+    expect(await vault.claimableDepositRequest(0, user.address)).to.equal(DEPOSIT_AMOUNT);
+    expect(await vault.maxMint(user.address)).to.be.gt(0);
+    expect(await vault.totalAssets()).to.be.gt(0);
+    expect(await vault.totalAssets()).to.be.lte(DEPOSIT_AMOUNT);
+
+    const [tokens, shares] = await vault.getPortfolio();
+    expect(tokens).to.deep.equal([await mockAsset.getAddress(), await underlyingAsset.getAddress()]);
+    expect(await mockAsset.balanceOf(await liquidityOrchestrator.getAddress())).to.equal(shares[0]);
+  });
+});